@@ -0,0 +1,397 @@
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use clap::Args;
+use keygen_rs::component::Component;
+use keygen_rs::config::{set_config, KeygenConfig};
+use keygen_rs::entitlement::Entitlement;
+use keygen_rs::errors::Error;
+use keygen_rs::group::Group;
+use keygen_rs::license::License;
+use keygen_rs::license_file::{IncludedResources, LicenseFile};
+use keygen_rs::machine::Machine;
+use keygen_rs::machine_file::MachineFile;
+use serde::Serialize;
+
+use crate::exit::CliError;
+use crate::output::{print_json, write_stdout, OutputFormat};
+
+/// Verify and decode an offline license file (`.lic`) or machine file.
+#[derive(Debug, Args)]
+pub struct InspectArgs {
+    /// License or machine file to inspect
+    file: PathBuf,
+    /// Hex-encoded Ed25519 public key of the account
+    #[arg(long, env = "KEYGEN_PUBLIC_KEY")]
+    public_key: String,
+    /// License key, used to decrypt the file contents
+    #[arg(long, env = "KEYGEN_LICENSE_KEY")]
+    license_key: Option<String>,
+    /// Machine fingerprint; machine files are decrypted with the license key plus fingerprint
+    #[arg(long)]
+    fingerprint: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum FileKind {
+    License,
+    Machine,
+}
+
+impl FileKind {
+    fn detect(contents: &str) -> Result<Self, Error> {
+        match contents.trim_start() {
+            c if c.starts_with("-----BEGIN LICENSE FILE-----") => Ok(FileKind::License),
+            c if c.starts_with("-----BEGIN MACHINE FILE-----") => Ok(FileKind::Machine),
+            _ => Err(Error::LicenseFileInvalid(
+                "Expected a license file or machine file".to_string(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Contents {
+    issued: DateTime<Utc>,
+    expiry: DateTime<Utc>,
+    ttl: i32,
+    expired: bool,
+    license: License,
+    machine: Option<Machine>,
+    entitlements: Vec<Entitlement>,
+    components: Vec<Component>,
+    groups: Vec<Group>,
+}
+
+impl Contents {
+    fn new(
+        (issued, expiry, ttl): (DateTime<Utc>, DateTime<Utc>, i32),
+        expired: bool,
+        license: License,
+        machine: Option<Machine>,
+        included: Option<IncludedResources>,
+    ) -> Self {
+        let (entitlements, components, groups) = match included {
+            Some(included) => (included.entitlements, included.components, included.groups),
+            None => Default::default(),
+        };
+        Contents {
+            issued,
+            expiry,
+            ttl,
+            expired,
+            license,
+            machine,
+            entitlements,
+            components,
+            groups,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+    kind: FileKind,
+    algorithm: String,
+    /// Signature verification always passes for a printed report
+    signature: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    contents: Option<Contents>,
+}
+
+/// Verify the file's signature, then decode its contents. Encrypted files are
+/// only decrypted when a license key is given.
+///
+/// Contents of an expired file are still printed before failing, since the
+/// expiry is usually what a support ticket is about.
+pub fn run(args: InspectArgs, format: OutputFormat) -> Result<(), CliError> {
+    let certificate = fs::read_to_string(&args.file)
+        .map_err(|e| CliError::Io(format!("Failed to read {}: {e}", args.file.display())))?;
+    let (report, expired) = inspect(certificate, args)?;
+
+    match format {
+        OutputFormat::Json => print_json(&report)?,
+        OutputFormat::Table => write_stdout(&render(&report))?,
+    }
+    if expired {
+        return Err(Error::CertificateFileExpired.into());
+    }
+    Ok(())
+}
+
+fn inspect(certificate: String, args: InspectArgs) -> Result<(Report, bool), CliError> {
+    let kind = FileKind::detect(&certificate)?;
+    set_config(KeygenConfig {
+        public_key: Some(args.public_key),
+        ..Default::default()
+    })?;
+
+    let report = match kind {
+        FileKind::License => {
+            let file = LicenseFile {
+                certificate,
+                ..Default::default()
+            };
+            file.verify()?;
+            let cert = file.certificate()?;
+            let decoded = match args.license_key {
+                _ if !cert.is_encrypted() => Some(file.decode()),
+                Some(key) => Some(file.decrypt(&key)),
+                None => None,
+            };
+            let decrypted = match decoded {
+                Some(Ok(dataset)) => Some((dataset, false)),
+                Some(Err(Error::LicenseFileExpired(dataset))) => Some((*dataset, true)),
+                Some(Err(error)) => return Err(error.into()),
+                None => None,
+            };
+            let contents = decrypted.map(|(dataset, expired)| {
+                Contents::new(
+                    (dataset.issued, dataset.expiry, dataset.ttl),
+                    expired,
+                    dataset.license,
+                    None,
+                    dataset.included,
+                )
+            });
+            report(kind, cert.alg, contents)
+        }
+        FileKind::Machine => {
+            let file = MachineFile {
+                certificate,
+                ..Default::default()
+            };
+            file.verify_signature()?;
+            let cert = file.certificate()?;
+            let decoded = match (args.license_key, args.fingerprint) {
+                _ if !cert.is_encrypted() => Some(file.decode()),
+                (Some(license_key), Some(fingerprint)) => {
+                    Some(file.decrypt(&format!("{license_key}{fingerprint}")))
+                }
+                (Some(_), None) => {
+                    return Err(CliError::Config(
+                        "Machine files are decrypted with the license key and --fingerprint"
+                            .to_string(),
+                    ))
+                }
+                (None, _) => None,
+            };
+            let decrypted = match decoded {
+                Some(Ok(dataset)) => Some((dataset, false)),
+                Some(Err(Error::MachineFileExpired(dataset))) => Some((*dataset, true)),
+                Some(Err(error)) => return Err(error.into()),
+                None => None,
+            };
+            let contents = decrypted.map(|(dataset, expired)| {
+                Contents::new(
+                    (dataset.issued, dataset.expiry, dataset.ttl),
+                    expired,
+                    dataset.license,
+                    Some(dataset.machine),
+                    dataset.included,
+                )
+            });
+            report(kind, cert.alg, contents)
+        }
+    };
+    Ok(report)
+}
+
+fn report(kind: FileKind, algorithm: String, contents: Option<Contents>) -> (Report, bool) {
+    let expired = contents.as_ref().is_some_and(|c| c.expired);
+    let report = Report {
+        kind,
+        algorithm,
+        signature: "valid",
+        contents,
+    };
+    (report, expired)
+}
+
+fn render(report: &Report) -> String {
+    let mut lines = vec![
+        ("File", format!("{:?}", report.kind).to_lowercase()),
+        ("Algorithm", report.algorithm.clone()),
+        ("Signature", report.signature.to_string()),
+    ];
+    let Some(contents) = &report.contents else {
+        lines.push((
+            "Contents",
+            "encrypted (pass --license-key to decrypt)".to_string(),
+        ));
+        return format_lines(&lines);
+    };
+
+    let expiry = contents.expiry.to_rfc3339();
+    let license = &contents.license;
+    lines.extend([
+        ("Issued", contents.issued.to_rfc3339()),
+        (
+            "Expiry",
+            if contents.expired {
+                format!("{expiry} (expired)")
+            } else {
+                expiry
+            },
+        ),
+        ("TTL", format!("{}s", contents.ttl)),
+        ("License", license.id.clone()),
+        ("  Name", license.name.clone().unwrap_or_default()),
+        ("  Key", license.key.clone()),
+        ("  Status", license.status.clone().unwrap_or_default()),
+        (
+            "  Expiry",
+            license.expiry.map(|e| e.to_rfc3339()).unwrap_or_default(),
+        ),
+        ("  Policy", license.policy.clone().unwrap_or_default()),
+    ]);
+    if let Some(machine) = &contents.machine {
+        lines.push(("Machine", machine.id.clone()));
+        lines.push(("  Fingerprint", machine.fingerprint.clone()));
+        lines.push(("  Name", machine.name.clone().unwrap_or_default()));
+    }
+    let codes: Vec<&str> = contents
+        .entitlements
+        .iter()
+        .map(|e| e.code.as_str())
+        .collect();
+    lines.push(("Entitlements", codes.join(", ")));
+    if report.kind == FileKind::Machine {
+        let fingerprints: Vec<&str> = contents
+            .components
+            .iter()
+            .map(|c| c.fingerprint.as_str())
+            .collect();
+        lines.push(("Components", fingerprints.join(", ")));
+    }
+    let groups: Vec<&str> = contents.groups.iter().map(|g| g.name.as_str()).collect();
+    lines.push(("Groups", groups.join(", ")));
+    format_lines(&lines)
+}
+
+fn format_lines(lines: &[(&str, String)]) -> String {
+    let width = lines
+        .iter()
+        .map(|(label, _)| label.len())
+        .max()
+        .unwrap_or(0)
+        + 1;
+    lines
+        .iter()
+        .map(|(label, value)| {
+            format!("{:<width$} {value}", format!("{label}:"))
+                .trim_end()
+                .to_string()
+                + "\n"
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_file_kind() {
+        assert_eq!(
+            FileKind::detect("-----BEGIN MACHINE FILE-----\nabc\n-----END MACHINE FILE-----\n")
+                .unwrap(),
+            FileKind::Machine
+        );
+        assert_eq!(
+            FileKind::detect("\n-----BEGIN LICENSE FILE-----\nabc").unwrap(),
+            FileKind::License
+        );
+        assert!(FileKind::detect("not a license file").is_err());
+    }
+
+    #[test]
+    fn test_render_without_key() {
+        let report = Report {
+            kind: FileKind::License,
+            algorithm: "aes-256-gcm+ed25519".to_string(),
+            signature: "valid",
+            contents: None,
+        };
+        assert_eq!(
+            render(&report),
+            "File:      license\n\
+             Algorithm: aes-256-gcm+ed25519\n\
+             Signature: valid\n\
+             Contents:  encrypted (pass --license-key to decrypt)\n"
+        );
+    }
+
+    /// An unencrypted license file, as Keygen checks out by default, signed
+    /// with a fresh key whose hex public key is returned alongside it.
+    fn unencrypted_license_file() -> (String, String) {
+        use base64::{engine::general_purpose, Engine};
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let signing_key = SigningKey::generate(&mut rand::rngs::OsRng);
+        let dataset = serde_json::json!({
+            "meta": {
+                "issued": "2024-01-01T00:00:00Z",
+                "expiry": "2099-01-01T00:00:00Z",
+                "ttl": 0
+            },
+            "data": {
+                "id": "license-1",
+                "type": "licenses",
+                "attributes": {
+                    "name": "ACME Corp",
+                    "key": "LICENSE-KEY",
+                    "expiry": null,
+                    "status": "ACTIVE",
+                    "metadata": {}
+                },
+                "relationships": {}
+            },
+            "included": []
+        });
+        let enc = general_purpose::STANDARD.encode(dataset.to_string());
+        let sig = general_purpose::STANDARD.encode(
+            signing_key
+                .sign(format!("license/{enc}").as_bytes())
+                .to_bytes(),
+        );
+        let cert = serde_json::json!({"enc": enc, "sig": sig, "alg": "base64+ed25519"});
+        let certificate = format!(
+            "-----BEGIN LICENSE FILE-----\n{}\n-----END LICENSE FILE-----\n",
+            general_purpose::STANDARD.encode(cert.to_string())
+        );
+        (
+            certificate,
+            hex::encode(signing_key.verifying_key().to_bytes()),
+        )
+    }
+
+    #[test]
+    fn test_inspect_unencrypted_license_file_without_key() {
+        let (certificate, public_key) = unencrypted_license_file();
+        let args = InspectArgs {
+            file: PathBuf::from("customer.lic"),
+            public_key: public_key.clone(),
+            license_key: None,
+            fingerprint: None,
+        };
+        let (report, expired) = inspect(certificate.clone(), args).unwrap();
+
+        assert!(!expired);
+        assert_eq!(report.algorithm, "base64+ed25519");
+        let contents = report.contents.unwrap();
+        assert_eq!(contents.license.id, "license-1");
+        assert_eq!(contents.license.key, "LICENSE-KEY");
+
+        let args = InspectArgs {
+            file: PathBuf::from("customer.lic"),
+            public_key,
+            license_key: Some("LICENSE-KEY".to_string()),
+            fingerprint: None,
+        };
+        let (report, _) = inspect(certificate, args).unwrap();
+        assert!(report.contents.is_some());
+    }
+}
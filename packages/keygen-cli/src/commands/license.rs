@@ -0,0 +1,144 @@
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use clap::Subcommand;
+use keygen_rs::license::{License, LicenseCheckoutOpts, LicenseCreateRequest, LicenseListOptions};
+
+use super::{signed, ListArgs};
+use crate::exit::CliError;
+use crate::output::{print_json, print_list, print_one, write_stdout, OutputFormat};
+
+#[derive(Debug, Subcommand)]
+pub enum LicenseCommand {
+    /// List licenses
+    List {
+        #[arg(long)]
+        policy: Option<String>,
+        #[arg(long)]
+        product: Option<String>,
+        /// Filter by status, e.g. ACTIVE, EXPIRING, EXPIRED, SUSPENDED
+        #[arg(long)]
+        status: Option<String>,
+        /// Filter by owner ID or email
+        #[arg(long)]
+        owner: Option<String>,
+        #[command(flatten)]
+        list: ListArgs,
+    },
+    /// Show a license
+    Get { id: String },
+    /// Create a license for a policy
+    Create {
+        #[arg(long)]
+        policy: String,
+        #[arg(long)]
+        name: Option<String>,
+        /// Custom license key; generated by Keygen when omitted
+        #[arg(long)]
+        key: Option<String>,
+        /// Expiry as an RFC 3339 timestamp, overriding the policy duration
+        #[arg(long)]
+        expiry: Option<DateTime<Utc>>,
+        #[arg(long)]
+        max_machines: Option<i32>,
+        /// Owner user ID
+        #[arg(long)]
+        owner: Option<String>,
+    },
+    /// Suspend a license
+    Suspend { id: String },
+    /// Reinstate a suspended license
+    Reinstate { id: String },
+    /// Extend a license's expiry by its policy duration
+    Renew { id: String },
+    /// Revoke a license
+    Revoke { id: String },
+    /// Delete a license
+    Delete { id: String },
+    /// Check out an encrypted offline license file
+    Checkout {
+        id: String,
+        /// Time-to-live of the license file, in seconds
+        #[arg(long)]
+        ttl: Option<i64>,
+        /// Relationships to embed, e.g. entitlements,product
+        #[arg(long, value_delimiter = ',')]
+        include: Option<Vec<String>>,
+        /// Write the certificate to this file instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+pub async fn run(command: LicenseCommand, format: OutputFormat) -> Result<(), CliError> {
+    match command {
+        LicenseCommand::List {
+            policy,
+            product,
+            status,
+            owner,
+            list,
+        } => {
+            let options = LicenseListOptions {
+                limit: signed(list.limit()),
+                page_size: signed(list.page_size()),
+                page_number: signed(list.page_number()),
+                policy,
+                product,
+                status,
+                owner,
+                ..Default::default()
+            };
+            print_list(&License::list(Some(&options)).await?, format)
+        }
+        LicenseCommand::Get { id } => print_one(&License::get(&id).await?, format),
+        LicenseCommand::Create {
+            policy,
+            name,
+            key,
+            expiry,
+            max_machines,
+            owner,
+        } => {
+            let request = LicenseCreateRequest {
+                policy_id: policy,
+                name,
+                key,
+                expiry,
+                max_machines,
+                owner_id: owner,
+                ..Default::default()
+            };
+            print_one(&License::create(request).await?, format)
+        }
+        LicenseCommand::Suspend { id } => {
+            print_one(&License::get(&id).await?.suspend().await?, format)
+        }
+        LicenseCommand::Reinstate { id } => {
+            print_one(&License::get(&id).await?.reinstate().await?, format)
+        }
+        LicenseCommand::Renew { id } => print_one(&License::get(&id).await?.renew().await?, format),
+        LicenseCommand::Revoke { id } => Ok(License::get(&id).await?.revoke().await?),
+        LicenseCommand::Delete { id } => Ok(License::get(&id).await?.delete().await?),
+        LicenseCommand::Checkout {
+            id,
+            ttl,
+            include,
+            out,
+        } => {
+            let options = LicenseCheckoutOpts { ttl, include };
+            let license_file = License::get(&id).await?.checkout(&options).await?;
+            match (out, format) {
+                (Some(path), _) => {
+                    fs::write(&path, &license_file.certificate).map_err(|e| {
+                        CliError::Io(format!("Failed to write {}: {e}", path.display()))
+                    })?;
+                    print_one(&license_file, format)
+                }
+                (None, OutputFormat::Json) => print_json(&license_file),
+                (None, OutputFormat::Table) => write_stdout(&license_file.certificate),
+            }
+        }
+    }
+}
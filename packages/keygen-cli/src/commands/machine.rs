@@ -0,0 +1,58 @@
+use clap::Subcommand;
+use keygen_rs::machine::{Machine, MachineListFilters};
+
+use super::{signed, ListArgs};
+use crate::exit::CliError;
+use crate::output::{print_list, print_one, OutputFormat};
+
+#[derive(Debug, Subcommand)]
+pub enum MachineCommand {
+    /// List machines
+    List {
+        /// Filter by license ID or key
+        #[arg(long)]
+        license: Option<String>,
+        /// Filter by user ID or email
+        #[arg(long)]
+        user: Option<String>,
+        #[arg(long)]
+        product: Option<String>,
+        #[arg(long)]
+        fingerprint: Option<String>,
+        #[command(flatten)]
+        list: ListArgs,
+    },
+    /// Show a machine
+    Get { id: String },
+    /// Deactivate a machine, freeing its activation slot
+    Deactivate { id: String },
+    /// Reset a machine's heartbeat monitor
+    Reset { id: String },
+}
+
+pub async fn run(command: MachineCommand, format: OutputFormat) -> Result<(), CliError> {
+    match command {
+        MachineCommand::List {
+            license,
+            user,
+            product,
+            fingerprint,
+            list,
+        } => {
+            let filters = MachineListFilters {
+                license,
+                user,
+                product,
+                fingerprint,
+                limit: signed(list.limit()),
+                page_size: signed(list.page_size()),
+                page_number: signed(list.page_number()),
+                ..Default::default()
+            };
+            print_list(&Machine::list(Some(filters)).await?, format)
+        }
+        MachineCommand::Get { id } => print_one(&Machine::get(&id).await?, format),
+        MachineCommand::Deactivate { id } => Ok(Machine::get(&id).await?.deactivate().await?),
+        MachineCommand::Reset { id } => print_one(&Machine::get(&id).await?.reset().await?, format),
+    }
+}
@@ -0,0 +1,40 @@
+use clap::Args;
+
+pub mod inspect;
+pub mod license;
+pub mod machine;
+pub mod policy;
+pub mod release;
+pub mod token;
+pub mod user;
+pub mod webhook;
+
+/// Paging flags shared by every `list` subcommand.
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    /// Number of results to return (1-100)
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=100))]
+    limit: Option<u32>,
+    /// Page number; `--limit` becomes the page size (default 100)
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    page: Option<u32>,
+}
+
+impl ListArgs {
+    pub fn limit(&self) -> Option<u32> {
+        self.limit.filter(|_| self.page.is_none())
+    }
+
+    pub fn page_size(&self) -> Option<u32> {
+        self.page.map(|_| self.limit.unwrap_or(100))
+    }
+
+    pub fn page_number(&self) -> Option<u32> {
+        self.page
+    }
+}
+
+/// Convert a validated 1-100 paging value for option structs that use `i32`
+pub fn signed(value: Option<u32>) -> Option<i32> {
+    value.map(|v| v as i32)
+}
@@ -0,0 +1,37 @@
+use clap::Subcommand;
+use keygen_rs::policy::{ListPoliciesOptions, Policy};
+
+use super::ListArgs;
+use crate::exit::CliError;
+use crate::output::{print_list, print_one, OutputFormat};
+
+#[derive(Debug, Subcommand)]
+pub enum PolicyCommand {
+    /// List policies
+    List {
+        #[arg(long)]
+        product: Option<String>,
+        #[command(flatten)]
+        list: ListArgs,
+    },
+    /// Show a policy
+    Get { id: String },
+    /// Delete a policy and all of its licenses
+    Delete { id: String },
+}
+
+pub async fn run(command: PolicyCommand, format: OutputFormat) -> Result<(), CliError> {
+    match command {
+        PolicyCommand::List { product, list } => {
+            let options = ListPoliciesOptions {
+                limit: list.limit(),
+                page_size: list.page_size(),
+                page_number: list.page_number(),
+                product,
+            };
+            print_list(&Policy::list(Some(options)).await?, format)
+        }
+        PolicyCommand::Get { id } => print_one(&Policy::get(&id).await?, format),
+        PolicyCommand::Delete { id } => Ok(Policy::get(&id).await?.delete().await?),
+    }
+}
@@ -0,0 +1,158 @@
+use std::path::PathBuf;
+
+use clap::{Subcommand, ValueEnum};
+use keygen_rs::artifact::{Artifact, CreateArtifactRequest};
+use keygen_rs::release::{CreateReleaseRequest, ListReleasesOptions, Release, ReleaseChannel};
+use tokio::fs::File;
+
+use super::ListArgs;
+use crate::exit::CliError;
+use crate::output::{print_list, print_one, OutputFormat};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Channel {
+    Stable,
+    Rc,
+    Beta,
+    Alpha,
+    Dev,
+}
+
+impl From<Channel> for ReleaseChannel {
+    fn from(channel: Channel) -> Self {
+        match channel {
+            Channel::Stable => ReleaseChannel::Stable,
+            Channel::Rc => ReleaseChannel::Rc,
+            Channel::Beta => ReleaseChannel::Beta,
+            Channel::Alpha => ReleaseChannel::Alpha,
+            Channel::Dev => ReleaseChannel::Dev,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ReleaseCommand {
+    /// List releases
+    List {
+        #[arg(long)]
+        product: Option<String>,
+        #[arg(long, value_enum)]
+        channel: Option<Channel>,
+        #[command(flatten)]
+        list: ListArgs,
+    },
+    /// Show a release
+    Get { id: String },
+    /// Create a draft release
+    Create {
+        /// Semantic version, e.g. 1.2.0
+        #[arg(long)]
+        version: String,
+        #[arg(long, env = "KEYGEN_PRODUCT")]
+        product: String,
+        #[arg(long, value_enum, default_value_t = Channel::Stable)]
+        channel: Channel,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// Create an artifact for a release and upload the file
+    Upload {
+        /// Release ID
+        release: String,
+        /// File to upload
+        file: PathBuf,
+        /// Artifact filename; defaults to the file's name
+        #[arg(long)]
+        filename: Option<String>,
+        /// File type; defaults to the file's extension
+        #[arg(long)]
+        filetype: Option<String>,
+        /// Target platform, e.g. darwin, linux or win32
+        #[arg(long)]
+        platform: Option<String>,
+        /// Target architecture, e.g. amd64 or arm64
+        #[arg(long)]
+        arch: Option<String>,
+    },
+    /// Publish a release
+    Publish { id: String },
+    /// Yank a release, making it unavailable for download
+    Yank { id: String },
+}
+
+pub async fn run(command: ReleaseCommand, format: OutputFormat) -> Result<(), CliError> {
+    match command {
+        ReleaseCommand::List {
+            product,
+            channel,
+            list,
+        } => {
+            let options = ListReleasesOptions {
+                limit: list.limit(),
+                page_size: list.page_size(),
+                page_number: list.page_number(),
+                product,
+                channel: channel.map(Into::into),
+                ..Default::default()
+            };
+            print_list(&Release::list(Some(options)).await?, format)
+        }
+        ReleaseCommand::Get { id } => print_one(&Release::get(&id).await?, format),
+        ReleaseCommand::Create {
+            version,
+            product,
+            channel,
+            name,
+            tag,
+        } => {
+            let request = CreateReleaseRequest {
+                version,
+                channel: channel.into(),
+                product_id: product,
+                name,
+                description: None,
+                status: None,
+                tag,
+                metadata: None,
+            };
+            print_one(&Release::create(request).await?, format)
+        }
+        ReleaseCommand::Upload {
+            release,
+            file,
+            filename,
+            filetype,
+            platform,
+            arch,
+        } => {
+            let read_error =
+                |e: std::io::Error| CliError::Io(format!("Failed to read {}: {e}", file.display()));
+            // Streamed rather than read into memory, since release binaries can be large
+            let contents = File::open(&file).await.map_err(read_error)?;
+            let filesize = contents.metadata().await.map_err(read_error)?.len();
+            let filename = filename
+                .or_else(|| file.file_name().map(|n| n.to_string_lossy().into_owned()))
+                .ok_or_else(|| CliError::Io(format!("{} is not a file", file.display())))?;
+            let filetype =
+                filetype.or_else(|| file.extension().map(|e| e.to_string_lossy().into_owned()));
+            let request = CreateArtifactRequest {
+                filename,
+                release_id: release,
+                filetype,
+                filesize: Some(filesize),
+                platform,
+                arch,
+                signature: None,
+                checksum: None,
+                metadata: None,
+            };
+            print_one(&Artifact::upload(request, contents).await?, format)
+        }
+        ReleaseCommand::Publish { id } => {
+            print_one(&Release::get(&id).await?.publish().await?, format)
+        }
+        ReleaseCommand::Yank { id } => print_one(&Release::get(&id).await?.yank().await?, format),
+    }
+}
@@ -0,0 +1,45 @@
+use clap::Subcommand;
+use keygen_rs::token::{ListTokensOptions, Token};
+
+use super::ListArgs;
+use crate::exit::CliError;
+use crate::output::{print_list, print_one, OutputFormat};
+
+#[derive(Debug, Subcommand)]
+pub enum TokenCommand {
+    /// List tokens
+    List {
+        /// Filter by bearer type, e.g. user, product or license
+        #[arg(long)]
+        bearer_type: Option<String>,
+        #[arg(long)]
+        bearer_id: Option<String>,
+        #[command(flatten)]
+        list: ListArgs,
+    },
+    /// Show a token
+    Get { id: String },
+    /// Revoke a token
+    Revoke { id: String },
+}
+
+pub async fn run(command: TokenCommand, format: OutputFormat) -> Result<(), CliError> {
+    match command {
+        TokenCommand::List {
+            bearer_type,
+            bearer_id,
+            list,
+        } => {
+            let options = ListTokensOptions {
+                limit: list.limit(),
+                page_size: list.page_size(),
+                page_number: list.page_number(),
+                bearer_type,
+                bearer_id,
+            };
+            print_list(&Token::list(Some(options)).await?, format)
+        }
+        TokenCommand::Get { id } => print_one(&Token::get(&id).await?, format),
+        TokenCommand::Revoke { id } => Ok(Token::get(&id).await?.revoke().await?),
+    }
+}
@@ -0,0 +1,41 @@
+use clap::Subcommand;
+use keygen_rs::user::{ListUsersOptions, User};
+
+use super::ListArgs;
+use crate::exit::CliError;
+use crate::output::{print_list, print_one, OutputFormat};
+
+#[derive(Debug, Subcommand)]
+pub enum UserCommand {
+    /// List users
+    List {
+        #[arg(long)]
+        product: Option<String>,
+        #[command(flatten)]
+        list: ListArgs,
+    },
+    /// Show a user by ID or email
+    Get { id: String },
+    /// Ban a user, blocking authentication and license validation
+    Ban { id: String },
+    /// Lift a user's ban
+    Unban { id: String },
+}
+
+pub async fn run(command: UserCommand, format: OutputFormat) -> Result<(), CliError> {
+    match command {
+        UserCommand::List { product, list } => {
+            let options = ListUsersOptions {
+                limit: list.limit(),
+                page_size: list.page_size(),
+                page_number: list.page_number(),
+                product,
+                ..Default::default()
+            };
+            print_list(&User::list(Some(options)).await?.users, format)
+        }
+        UserCommand::Get { id } => print_one(&User::get(&id).await?, format),
+        UserCommand::Ban { id } => print_one(&User::get(&id).await?.ban().await?, format),
+        UserCommand::Unban { id } => print_one(&User::get(&id).await?.unban().await?, format),
+    }
+}
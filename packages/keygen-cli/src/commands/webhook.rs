@@ -0,0 +1,72 @@
+use clap::Subcommand;
+use keygen_rs::webhook::endpoint::{
+    WebhookEndpoint, WebhookEndpointCreateRequest, WebhookEndpointListOptions,
+};
+use keygen_rs::webhook::event::{WebhookEventListOptions, WebhookEventRecord};
+use keygen_rs::webhook::event_types::WebhookEvent;
+
+use super::{signed, ListArgs};
+use crate::exit::CliError;
+use crate::output::{print_list, print_one, OutputFormat};
+
+#[derive(Debug, Subcommand)]
+pub enum WebhookCommand {
+    /// List webhook endpoints
+    List {
+        #[command(flatten)]
+        list: ListArgs,
+    },
+    /// Create a webhook endpoint
+    Create {
+        url: String,
+        /// Events to subscribe to, e.g. license.created,machine.deleted; all when omitted
+        #[arg(long, value_delimiter = ',')]
+        subscribe: Option<Vec<WebhookEvent>>,
+    },
+    /// Delete a webhook endpoint
+    Delete { id: String },
+    /// List webhook delivery events
+    Events {
+        /// Filter by event name, e.g. license.created
+        #[arg(long)]
+        event: Option<String>,
+        #[command(flatten)]
+        list: ListArgs,
+    },
+    /// Retry delivery of a webhook event
+    Retry { id: String },
+}
+
+pub async fn run(command: WebhookCommand, format: OutputFormat) -> Result<(), CliError> {
+    match command {
+        WebhookCommand::List { list } => {
+            let options = WebhookEndpointListOptions {
+                limit: signed(list.limit()),
+                page_size: signed(list.page_size()),
+                page_number: signed(list.page_number()),
+            };
+            print_list(&WebhookEndpoint::list(Some(&options)).await?, format)
+        }
+        WebhookCommand::Create { url, subscribe } => {
+            let mut request = WebhookEndpointCreateRequest::new(url);
+            if let Some(subscriptions) = subscribe {
+                request = request.with_subscriptions(subscriptions);
+            }
+            print_one(&WebhookEndpoint::create(request).await?, format)
+        }
+        WebhookCommand::Delete { id } => Ok(WebhookEndpoint::get(&id).await?.delete().await?),
+        WebhookCommand::Events { event, list } => {
+            let options = WebhookEventListOptions {
+                limit: signed(list.limit()),
+                page_size: signed(list.page_size()),
+                page_number: signed(list.page_number()),
+                event_type: event,
+                ..Default::default()
+            };
+            print_list(&WebhookEventRecord::list(Some(&options)).await?, format)
+        }
+        WebhookCommand::Retry { id } => {
+            print_one(&WebhookEventRecord::get(&id).await?.retry().await?, format)
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::path::Path;
+
+use keygen_rs::config::{ConfigLayer, KeygenConfig};
+
+/// Build the SDK config from the defaults, an optional TOML or JSON config
+/// file, then the `KEYGEN_*` environment variables, each overriding the last.
+pub fn load(path: Option<&Path>) -> Result<KeygenConfig, String> {
+    let file = match path {
+        Some(path) => ConfigLayer::from_file(path).map_err(|e| e.to_string())?,
+        None => ConfigLayer::default(),
+    };
+    let env = ConfigLayer::from_env().map_err(|e| e.to_string())?;
+    resolve(file.merge(env))
+}
+
+fn resolve(mut layer: ConfigLayer) -> Result<KeygenConfig, String> {
+    // The CLI only authenticates with a token. A license key exported for
+    // some app would otherwise be sent in its place.
+    layer.license_key = None;
+
+    if layer.account.is_none() {
+        return Err("Missing account: set KEYGEN_ACCOUNT or `account` in the config file".into());
+    }
+    if layer.token.is_none() {
+        return Err("Missing token: set KEYGEN_TOKEN or `token` in the config file".into());
+    }
+
+    let defaults = ConfigLayer {
+        user_agent: Some(concat!("keygen-cli/", env!("CARGO_PKG_VERSION")).to_string()),
+        ..Default::default()
+    };
+    defaults
+        .merge(layer)
+        .into_config()
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_overrides_file() {
+        let file: ConfigLayer = toml::from_str(
+            r#"
+            account = "file-account"
+            token = "file-token"
+            environment = "staging"
+            "#,
+        )
+        .unwrap();
+        let env = ConfigLayer {
+            account: Some("env-account".to_string()),
+            ..Default::default()
+        };
+        let config = resolve(file.merge(env)).unwrap();
+
+        assert_eq!(config.account, "env-account");
+        assert_eq!(config.token.as_deref(), Some("file-token"));
+        assert_eq!(config.environment.as_deref(), Some("staging"));
+        assert_eq!(config.api_url, KeygenConfig::default().api_url);
+        assert!(config.user_agent.unwrap().starts_with("keygen-cli/"));
+    }
+
+    #[test]
+    fn test_missing_token_is_an_error() {
+        let layer = ConfigLayer {
+            account: Some("account".to_string()),
+            ..Default::default()
+        };
+        assert!(resolve(layer).is_err());
+    }
+
+    #[test]
+    fn test_license_key_does_not_replace_token() {
+        let layer = ConfigLayer {
+            account: Some("account".to_string()),
+            token: Some("admin-token".to_string()),
+            license_key: Some("APP-LICENSE-KEY".to_string()),
+            ..Default::default()
+        };
+        let config = resolve(layer).unwrap();
+
+        assert_eq!(config.token.as_deref(), Some("admin-token"));
+        assert!(config.license_key.is_none());
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt;
+
+use keygen_rs::errors::{Error, ErrorMeta};
+
+/// Exit codes, so scripts can branch on the kind of failure without parsing
+/// error messages. `2` is reserved for usage errors reported by clap.
+pub const FAILURE: u8 = 1;
+pub const CONFIG: u8 = 3;
+pub const AUTH: u8 = 4;
+pub const NOT_FOUND: u8 = 5;
+pub const CONFLICT: u8 = 6;
+pub const LICENSE_STATE: u8 = 7;
+pub const RATE_LIMITED: u8 = 8;
+pub const NOT_GENUINE: u8 = 9;
+pub const EXPIRED: u8 = 10;
+pub const UNDECRYPTABLE: u8 = 11;
+
+/// A command failure, carrying enough to pick the exit code.
+#[derive(Debug)]
+pub enum CliError {
+    Config(String),
+    Api(Error),
+    Io(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Config(_) => CONFIG,
+            CliError::Api(error) => code_for(error),
+            CliError::Io(_) => FAILURE,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Config(message) | CliError::Io(message) => f.write_str(message),
+            CliError::Api(error) => match error.code().as_str() {
+                "ERROR" => write!(f, "{error}"),
+                code => write!(f, "{}: {}", code, error.detail()),
+            },
+        }
+    }
+}
+
+impl From<Error> for CliError {
+    fn from(error: Error) -> Self {
+        CliError::Api(error)
+    }
+}
+
+/// Map an SDK error to an exit code, mostly by its Keygen error code.
+pub fn code_for(error: &Error) -> u8 {
+    match error {
+        Error::RateLimitExceeded { .. } => RATE_LIMITED,
+        Error::MissingConfiguration
+        | Error::InvalidConfiguration(_)
+        | Error::FeatureNotSupported { .. }
+        | Error::PublicKeyMissing
+        | Error::PublicKeyInvalid => CONFIG,
+        Error::LicenseFileNotGenuine(_)
+        | Error::MachineFileNotGenuine(_)
+        | Error::CertificateFileNotGenuine(_) => NOT_GENUINE,
+        Error::LicenseFileExpired(_)
+        | Error::MachineFileExpired(_)
+        | Error::CertificateFileExpired => EXPIRED,
+        Error::DecryptionError(_) | Error::LicenseFileNotEncrypted => UNDECRYPTABLE,
+        _ => code_for_keygen_code(&error.code()),
+    }
+}
+
+fn code_for_keygen_code(code: &str) -> u8 {
+    match code {
+        "NOT_FOUND" => NOT_FOUND,
+        "FORBIDDEN" | "UNAUTHORIZED" => AUTH,
+        c if c.starts_with("TOKEN_") || c == "LICENSE_TOKEN_INVALID" => AUTH,
+        c if c.ends_with("_TAKEN")
+            || c.ends_with("_CONFLICT")
+            || c.ends_with("_LIMIT_EXCEEDED")
+            || c.ends_with("_ALREADY_ACTIVATED")
+            || c.starts_with("LICENSE_TOO_MANY_") =>
+        {
+            CONFLICT
+        }
+        c if c.starts_with("LICENSE_") => LICENSE_STATE,
+        _ => FAILURE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_for_keygen_code() {
+        assert_eq!(code_for_keygen_code("NOT_FOUND"), NOT_FOUND);
+        assert_eq!(code_for_keygen_code("TOKEN_INVALID"), AUTH);
+        assert_eq!(code_for_keygen_code("LICENSE_TOKEN_INVALID"), AUTH);
+        assert_eq!(code_for_keygen_code("FINGERPRINT_TAKEN"), CONFLICT);
+        assert_eq!(code_for_keygen_code("LICENSE_TOO_MANY_MACHINES"), CONFLICT);
+        assert_eq!(code_for_keygen_code("LICENSE_SUSPENDED"), LICENSE_STATE);
+        assert_eq!(code_for_keygen_code("ERROR"), FAILURE);
+    }
+
+    #[test]
+    fn test_rate_limit_uses_variant() {
+        let error = Error::RateLimitExceeded {
+            window: "30s".to_string(),
+            count: 60,
+            limit: 60,
+            remaining: 0,
+            reset: 0,
+            retry_after: 30,
+        };
+        assert_eq!(code_for(&error), RATE_LIMITED);
+    }
+
+    #[test]
+    fn test_license_file_errors_use_variant() {
+        assert_eq!(
+            code_for(&Error::LicenseFileNotGenuine("bad signature".into())),
+            NOT_GENUINE
+        );
+        assert_eq!(code_for(&Error::CertificateFileExpired), EXPIRED);
+        assert_eq!(
+            code_for(&Error::DecryptionError("wrong key".into())),
+            UNDECRYPTABLE
+        );
+    }
+
+    #[test]
+    fn test_config_errors_use_variant() {
+        assert_eq!(code_for(&Error::MissingConfiguration), CONFIG);
+        assert_eq!(
+            code_for(&Error::InvalidConfiguration(
+                vec!["account is empty".into()]
+            )),
+            CONFIG
+        );
+        assert_eq!(
+            code_for(&Error::FeatureNotSupported {
+                feature: "environments".into(),
+                required: "1.1".into(),
+                api_version: "1.0".into(),
+            }),
+            CONFIG
+        );
+    }
+}
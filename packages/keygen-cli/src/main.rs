@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+mod commands;
+mod config;
+mod exit;
+mod output;
+
+use commands::{inspect, license, machine, policy, release, token, user, webhook};
+use exit::CliError;
+use output::OutputFormat;
+
+/// Manage a Keygen account from the command line.
+///
+/// Credentials are read from `KEYGEN_ACCOUNT` and `KEYGEN_TOKEN` (plus the
+/// optional `KEYGEN_API_URL`, `KEYGEN_ENVIRONMENT` and `KEYGEN_PRODUCT`), or
+/// from a TOML or JSON file passed with `--config`. Environment variables win.
+#[derive(Debug, Parser)]
+#[command(name = "keygen", version)]
+struct Cli {
+    /// Path to a TOML or JSON config file
+    #[arg(long, global = true, env = "KEYGEN_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Output format
+    #[arg(long, short, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    output: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Create, inspect and manage licenses
+    #[command(subcommand)]
+    License(license::LicenseCommand),
+    /// Inspect and manage machines
+    #[command(subcommand)]
+    Machine(machine::MachineCommand),
+    /// Create, upload, publish and yank releases
+    #[command(subcommand)]
+    Release(release::ReleaseCommand),
+    /// Inspect and delete policies
+    #[command(subcommand)]
+    Policy(policy::PolicyCommand),
+    /// Inspect, ban and unban users
+    #[command(subcommand)]
+    User(user::UserCommand),
+    /// Inspect and revoke tokens
+    #[command(subcommand)]
+    Token(token::TokenCommand),
+    /// Manage webhook endpoints and delivery events
+    #[command(subcommand)]
+    Webhook(webhook::WebhookCommand),
+    /// Verify and decode an offline license or machine file
+    Inspect(inspect::InspectArgs),
+}
+
+async fn run(cli: Cli) -> Result<(), CliError> {
+    // Inspecting a file works offline and doesn't need account credentials
+    if let Command::Inspect(args) = cli.command {
+        return inspect::run(args, cli.output);
+    }
+
+    let config = config::load(cli.config.as_deref()).map_err(CliError::Config)?;
+    keygen_rs::config::set_config(config)
+        .map_err(|e| CliError::Config(format!("Failed to apply config: {e}")))?;
+
+    let format = cli.output;
+    match cli.command {
+        Command::License(command) => license::run(command, format).await,
+        Command::Machine(command) => machine::run(command, format).await,
+        Command::Release(command) => release::run(command, format).await,
+        Command::Policy(command) => policy::run(command, format).await,
+        Command::User(command) => user::run(command, format).await,
+        Command::Token(command) => token::run(command, format).await,
+        Command::Webhook(command) => webhook::run(command, format).await,
+        Command::Inspect(_) => unreachable!("handled before loading config"),
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use keygen_rs::artifact::Artifact;
+use keygen_rs::license::License;
+use keygen_rs::license_file::LicenseFile;
+use keygen_rs::machine::Machine;
+use keygen_rs::policy::Policy;
+use keygen_rs::release::Release;
+use keygen_rs::token::Token;
+use keygen_rs::user::User;
+use keygen_rs::webhook::endpoint::WebhookEndpoint;
+use keygen_rs::webhook::event::WebhookEventRecord;
+use serde::Serialize;
+
+use crate::exit::CliError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+}
+
+/// A resource that can be printed as one row of a table.
+pub trait Tabular: Serialize {
+    const HEADERS: &'static [&'static str];
+    fn row(&self) -> Vec<String>;
+}
+
+pub fn print_list<T: Tabular>(items: &[T], format: OutputFormat) -> Result<(), CliError> {
+    match format {
+        OutputFormat::Json => print_json(&items),
+        OutputFormat::Table => {
+            let rows: Vec<Vec<String>> = items.iter().map(Tabular::row).collect();
+            write_stdout(&render_table(T::HEADERS, &rows))
+        }
+    }
+}
+
+pub fn print_one<T: Tabular>(item: &T, format: OutputFormat) -> Result<(), CliError> {
+    print_list(std::slice::from_ref(item), format)
+}
+
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<(), CliError> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| CliError::Io(e.to_string()))?;
+    write_stdout(&format!("{json}\n"))
+}
+
+/// Write to stdout, treating a closed pipe (e.g. `| head`) as success.
+pub fn write_stdout(text: &str) -> Result<(), CliError> {
+    match io::stdout().lock().write_all(text.as_bytes()) {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(CliError::Io(e.to_string())),
+        _ => Ok(()),
+    }
+}
+
+fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    let mut push_line = |cells: Vec<&str>| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    };
+    push_line(headers.to_vec());
+    for row in rows {
+        push_line(row.iter().map(String::as_str).collect());
+    }
+    out
+}
+
+fn opt<T: Display>(value: &Option<T>) -> String {
+    value.as_ref().map(ToString::to_string).unwrap_or_default()
+}
+
+/// Render a serde enum the way the API spells it, e.g. `ACTIVE` or `stable`
+fn api_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+impl Tabular for License {
+    const HEADERS: &'static [&'static str] = &["ID", "NAME", "KEY", "STATUS", "EXPIRY", "POLICY"];
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            opt(&self.name),
+            self.key.clone(),
+            opt(&self.status),
+            self.expiry.map(|e| e.to_rfc3339()).unwrap_or_default(),
+            opt(&self.policy),
+        ]
+    }
+}
+
+impl Tabular for LicenseFile {
+    const HEADERS: &'static [&'static str] = &["ID", "ISSUED", "EXPIRY", "TTL"];
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.issued.to_rfc3339(),
+            self.expiry.to_rfc3339(),
+            self.ttl.to_string(),
+        ]
+    }
+}
+
+impl Tabular for Machine {
+    const HEADERS: &'static [&'static str] = &[
+        "ID",
+        "NAME",
+        "FINGERPRINT",
+        "PLATFORM",
+        "HEARTBEAT",
+        "LICENSE",
+    ];
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            opt(&self.name),
+            self.fingerprint.clone(),
+            opt(&self.platform),
+            self.heartbeat_status.clone(),
+            opt(&self.license_id),
+        ]
+    }
+}
+
+impl Tabular for Release {
+    const HEADERS: &'static [&'static str] = &["ID", "VERSION", "CHANNEL", "STATUS", "CREATED"];
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.version.clone(),
+            api_name(&self.channel),
+            api_name(&self.status),
+            self.created.clone(),
+        ]
+    }
+}
+
+impl Tabular for Artifact {
+    const HEADERS: &'static [&'static str] =
+        &["ID", "FILENAME", "PLATFORM", "ARCH", "SIZE", "STATUS"];
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.filename.clone(),
+            opt(&self.platform),
+            opt(&self.arch),
+            opt(&self.filesize),
+            api_name(&self.status),
+        ]
+    }
+}
+
+impl Tabular for Policy {
+    const HEADERS: &'static [&'static str] = &[
+        "ID",
+        "NAME",
+        "DURATION",
+        "MAX_MACHINES",
+        "FLOATING",
+        "PRODUCT",
+    ];
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.name.clone(),
+            opt(&self.duration),
+            opt(&self.max_machines),
+            self.floating.to_string(),
+            opt(&self.product_id),
+        ]
+    }
+}
+
+impl Tabular for User {
+    const HEADERS: &'static [&'static str] = &["ID", "EMAIL", "NAME", "ROLE", "STATUS"];
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.email.clone(),
+            opt(&self.full_name),
+            api_name(&self.role),
+            api_name(&self.status),
+        ]
+    }
+}
+
+impl Tabular for Token {
+    const HEADERS: &'static [&'static str] = &["ID", "KIND", "NAME", "EXPIRY", "CREATED"];
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            api_name(&self.kind),
+            opt(&self.name),
+            opt(&self.expiry),
+            self.created.clone(),
+        ]
+    }
+}
+
+impl Tabular for WebhookEndpoint {
+    const HEADERS: &'static [&'static str] = &["ID", "URL", "SUBSCRIPTIONS", "CREATED"];
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.url.clone(),
+            self.subscriptions.join(","),
+            self.created.to_rfc3339(),
+        ]
+    }
+}
+
+impl Tabular for WebhookEventRecord {
+    const HEADERS: &'static [&'static str] = &["ID", "EVENT", "STATUS", "RESPONSE", "CREATED"];
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.event.clone(),
+            api_name(&self.status),
+            opt(&self.last_response_code),
+            self.created.to_rfc3339(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_table_aligns_columns() {
+        let rows = vec![
+            vec!["lic-1".to_string(), "ACTIVE".to_string()],
+            vec!["license-22".to_string(), String::new()],
+        ];
+        assert_eq!(
+            render_table(&["ID", "STATUS"], &rows),
+            "ID          STATUS\nlic-1       ACTIVE\nlicense-22\n"
+        );
+    }
+}
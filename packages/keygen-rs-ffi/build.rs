@@ -0,0 +1,22 @@
+use std::env;
+use std::path::PathBuf;
+
+/// Generate the C header into `OUT_DIR`. The copy shipped in `include/` is
+/// kept in sync by `tests/c_harness.rs`; run the tests with
+/// `KEYGEN_FFI_UPDATE_HEADER=1` to refresh it after changing the API.
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("Failed to read cbindgen.toml");
+
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=src");
+
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("Failed to generate the C header")
+        .write_to_file(out_dir.join("keygen.h"));
+}
@@ -0,0 +1,196 @@
+/* Generated by cbindgen from packages/keygen-rs-ffi. Do not edit. */
+
+#ifndef KEYGEN_RS_H
+#define KEYGEN_RS_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result of a call into the library.
+typedef enum KeygenStatus {
+  KEYGEN_STATUS_OK = 0,
+  // Any error without a more specific status
+  KEYGEN_STATUS_ERROR = 1,
+  // A NULL pointer, invalid UTF-8 or unknown setting was passed in
+  KEYGEN_STATUS_INVALID_ARGUMENT = 2,
+  KEYGEN_STATUS_INVALID_CONFIGURATION = 3,
+  KEYGEN_STATUS_MISSING_CONFIGURATION = 4,
+  // The request could not be sent or its response could not be read
+  KEYGEN_STATUS_NETWORK = 5,
+  KEYGEN_STATUS_RATE_LIMITED = 6,
+  KEYGEN_STATUS_PUBLIC_KEY_MISSING = 7,
+  KEYGEN_STATUS_PUBLIC_KEY_INVALID = 8,
+  KEYGEN_STATUS_SIGNATURE_INVALID = 9,
+  KEYGEN_STATUS_LICENSE_KEY_NOT_GENUINE = 10,
+  KEYGEN_STATUS_LICENSE_KEY_INVALID = 11,
+  KEYGEN_STATUS_LICENSE_NOT_ACTIVATED = 12,
+  KEYGEN_STATUS_LICENSE_EXPIRED = 13,
+  KEYGEN_STATUS_LICENSE_SUSPENDED = 14,
+  KEYGEN_STATUS_LICENSE_NOT_ALLOWED = 15,
+  KEYGEN_STATUS_LICENSE_TOO_MANY_MACHINES = 16,
+  KEYGEN_STATUS_MACHINE_NOT_FOUND = 17,
+  KEYGEN_STATUS_HEARTBEAT_DEAD = 18,
+  KEYGEN_STATUS_FILE_INVALID = 19,
+  KEYGEN_STATUS_FILE_NOT_GENUINE = 20,
+  KEYGEN_STATUS_FILE_EXPIRED = 21,
+  KEYGEN_STATUS_NOT_FOUND = 22,
+  KEYGEN_STATUS_FEATURE_NOT_SUPPORTED = 23,
+} KeygenStatus;
+
+// Opaque SDK configuration.
+typedef struct KeygenConfig KeygenConfig;
+
+// Opaque running heartbeat monitor.
+typedef struct KeygenHeartbeat KeygenHeartbeat;
+
+// Opaque license returned by `keygen_validate`.
+typedef struct KeygenLicense KeygenLicense;
+
+// Opaque machine returned by `keygen_license_activate`.
+typedef struct KeygenMachine KeygenMachine;
+
+// Called after every heartbeat with its status and the `user_data` passed to
+// `keygen_heartbeat_start`. It runs on the heartbeat's thread, where
+// `keygen_last_error_message` describes a failed heartbeat.
+typedef void (*KeygenHeartbeatCallback)(enum KeygenStatus, void*);
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Keygen error code of the thread's last error, e.g. `LICENSE_EXPIRED`, or
+// NULL if no call has failed. Valid until the next failing call on the
+// thread.
+const char *keygen_last_error_code(void);
+
+// Human readable detail of the thread's last error, or NULL if no call has
+// failed. Valid until the next failing call on the thread.
+const char *keygen_last_error_message(void);
+
+// Release a string returned by this library. NULL is ignored.
+void keygen_string_free(char *s);
+
+// Verify a signed license key against the configured public keys and write
+// its decoded payload to `out_payload`. `scheme` is e.g. `ED25519_SIGN`.
+enum KeygenStatus keygen_verify_key(const char *scheme, const char *signed_key, char **out_payload);
+
+// Create a configuration with the SDK defaults. Release it with
+// `keygen_config_free`.
+struct KeygenConfig *keygen_config_new(void);
+
+// Create a configuration from `KEYGEN_*` environment variables.
+enum KeygenStatus keygen_config_from_env(struct KeygenConfig **out_config);
+
+// Set the setting `name` to `value`; a NULL `value` clears optional
+// settings. `name` is one of `api_url`, `api_version`, `api_prefix`,
+// `account`, `environment`, `user_agent`, `deployment`, `signature_host`,
+// `product`, `package`, `license_key`, `public_key`, `platform`,
+// `max_clock_drift` and `signature_mode`.
+enum KeygenStatus keygen_config_set(struct KeygenConfig *config,
+                                    const char *name,
+                                    const char *value);
+
+// Check the configuration for mistakes, see `KeygenConfig::validate`.
+enum KeygenStatus keygen_config_validate(const struct KeygenConfig *config);
+
+// Install the configuration for every following call. The handle stays
+// owned by the caller.
+enum KeygenStatus keygen_config_apply(const struct KeygenConfig *config);
+
+// Release a configuration. NULL is ignored.
+void keygen_config_free(struct KeygenConfig *config);
+
+// Validate the configured license key for the given machine fingerprints
+// and entitlement codes.
+//
+// On success, and on `KEYGEN_STATUS_LICENSE_NOT_ACTIVATED` so that the
+// machine can be activated next, the license is written to `out_license`.
+enum KeygenStatus keygen_validate(const char *const *fingerprints,
+                                  size_t fingerprints_len,
+                                  const char *const *entitlements,
+                                  size_t entitlements_len,
+                                  struct KeygenLicense **out_license);
+
+// Write the license ID to `out_id`.
+enum KeygenStatus keygen_license_id(const struct KeygenLicense *license, char **out_id);
+
+// Write the license as a JSON object to `out_json`.
+enum KeygenStatus keygen_license_to_json(const struct KeygenLicense *license, char **out_json);
+
+// Activate the machine `fingerprint` for the license.
+enum KeygenStatus keygen_license_activate(const struct KeygenLicense *license,
+                                          const char *fingerprint,
+                                          struct KeygenMachine **out_machine);
+
+// Deactivate the license's machine with ID `machine_id`.
+enum KeygenStatus keygen_license_deactivate(const struct KeygenLicense *license,
+                                            const char *machine_id);
+
+// Check out a license file and write its certificate to `out_certificate`.
+// A `ttl` of 0 uses the policy's default.
+enum KeygenStatus keygen_license_checkout(const struct KeygenLicense *license,
+                                          int64_t ttl,
+                                          char **out_certificate);
+
+// Release a license. NULL is ignored.
+void keygen_license_free(struct KeygenLicense *license);
+
+// Verify a license file's signature against the configured public keys,
+// decrypt it with the license key and write its dataset as JSON to
+// `out_dataset`.
+//
+// An expired file fails with `KEYGEN_STATUS_FILE_EXPIRED`, but its dataset is
+// still written so the caller can decide how to handle it.
+enum KeygenStatus keygen_license_file_verify(const char *certificate,
+                                             const char *license_key,
+                                             char **out_dataset);
+
+// Write the machine ID to `out_id`.
+enum KeygenStatus keygen_machine_id(const struct KeygenMachine *machine, char **out_id);
+
+// Write the machine as a JSON object to `out_json`.
+enum KeygenStatus keygen_machine_to_json(const struct KeygenMachine *machine, char **out_json);
+
+// Deactivate the machine.
+enum KeygenStatus keygen_machine_deactivate(const struct KeygenMachine *machine);
+
+// Check out a machine file and write its certificate to `out_certificate`.
+// A `ttl` of 0 uses the policy's default.
+enum KeygenStatus keygen_machine_checkout(const struct KeygenMachine *machine,
+                                          int64_t ttl,
+                                          char **out_certificate);
+
+// Release a machine. NULL is ignored.
+void keygen_machine_free(struct KeygenMachine *machine);
+
+// Ping the machine now and then every `interval_secs` seconds on a
+// background thread until `keygen_heartbeat_stop` is called. `callback`
+// may be NULL.
+enum KeygenStatus keygen_heartbeat_start(const struct KeygenMachine *machine,
+                                         uint64_t interval_secs,
+                                         KeygenHeartbeatCallback callback,
+                                         void *user_data,
+                                         struct KeygenHeartbeat **out_heartbeat);
+
+// Stop a heartbeat monitor, wait for its thread to finish and release it.
+// NULL is ignored.
+void keygen_heartbeat_stop(struct KeygenHeartbeat *heartbeat);
+
+// Verify a machine file's signature against the configured public keys,
+// decrypt it and write its dataset as JSON to `out_dataset`. The decryption
+// key is the license key followed by the machine fingerprint.
+//
+// An expired file fails with `KEYGEN_STATUS_FILE_EXPIRED`, but its dataset is
+// still written so the caller can decide how to handle it.
+enum KeygenStatus keygen_machine_file_verify(const char *certificate,
+                                             const char *decryption_key,
+                                             char **out_dataset);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* KEYGEN_RS_H */
@@ -0,0 +1,114 @@
+use std::ffi::c_char;
+
+use keygen_rs::config::{Deployment, SignatureMode};
+
+use crate::{
+    fail, guard, handle_arg, invalid_argument, opt_str_arg, str_arg, write_out, KeygenStatus,
+};
+
+/// Opaque SDK configuration.
+pub struct KeygenConfig(pub(crate) keygen_rs::config::KeygenConfig);
+
+/// Create a configuration with the SDK defaults. Release it with
+/// `keygen_config_free`.
+#[no_mangle]
+pub extern "C" fn keygen_config_new() -> *mut KeygenConfig {
+    Box::into_raw(Box::new(KeygenConfig(Default::default())))
+}
+
+/// Create a configuration from `KEYGEN_*` environment variables.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_config_from_env(
+    out_config: *mut *mut KeygenConfig,
+) -> KeygenStatus {
+    guard(|| {
+        let config = keygen_rs::config::KeygenConfig::from_env().map_err(|e| fail(&e))?;
+        write_out(out_config, Box::into_raw(Box::new(KeygenConfig(config))))
+    })
+}
+
+/// Set the setting `name` to `value`; a NULL `value` clears optional
+/// settings. `name` is one of `api_url`, `api_version`, `api_prefix`,
+/// `account`, `environment`, `user_agent`, `deployment`, `signature_host`,
+/// `product`, `package`, `license_key`, `public_key`, `platform`,
+/// `max_clock_drift` and `signature_mode`.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_config_set(
+    config: *mut KeygenConfig,
+    name: *const c_char,
+    value: *const c_char,
+) -> KeygenStatus {
+    guard(|| {
+        let config = &mut config
+            .as_mut()
+            .ok_or_else(|| invalid_argument("config must not be NULL"))?
+            .0;
+        let name = str_arg(name, "name")?;
+        let value = opt_str_arg(value, "value")?;
+        let required = || value.ok_or_else(|| invalid_argument(&format!("{name} is required")));
+        let optional = || value.map(str::to_string);
+
+        match name {
+            "api_url" => config.api_url = required()?.to_string(),
+            "api_version" => config.api_version = required()?.to_string(),
+            "api_prefix" => config.api_prefix = required()?.to_string(),
+            "account" => config.account = required()?.to_string(),
+            "environment" => config.environment = optional(),
+            "user_agent" => config.user_agent = optional(),
+            "deployment" => {
+                config.deployment = value
+                    .map(str::parse::<Deployment>)
+                    .transpose()
+                    .map_err(|e| fail(&e))?
+            }
+            "signature_host" => config.signature_host = optional(),
+            "product" => config.product = required()?.to_string(),
+            "package" => config.package = value.unwrap_or_default().to_string(),
+            "license_key" => config.license_key = optional(),
+            "public_key" => config.public_key = optional(),
+            "platform" => config.platform = optional(),
+            "max_clock_drift" => {
+                config.max_clock_drift = value
+                    .map(|v| {
+                        v.parse::<i64>().map_err(|_| {
+                            invalid_argument(&format!("max_clock_drift {v:?} is not a number"))
+                        })
+                    })
+                    .transpose()?
+            }
+            "signature_mode" => {
+                config.signature_mode =
+                    required()?.parse::<SignatureMode>().map_err(|e| fail(&e))?
+            }
+            _ => return Err(invalid_argument(&format!("unknown setting {name:?}"))),
+        }
+        Ok(())
+    })
+}
+
+/// Check the configuration for mistakes, see `KeygenConfig::validate`.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_config_validate(config: *const KeygenConfig) -> KeygenStatus {
+    guard(|| {
+        let config = handle_arg(config, "config")?;
+        config.0.validate().map_err(|e| fail(&e))
+    })
+}
+
+/// Install the configuration for every following call. The handle stays
+/// owned by the caller.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_config_apply(config: *const KeygenConfig) -> KeygenStatus {
+    guard(|| {
+        let config = handle_arg(config, "config")?;
+        keygen_rs::config::set_config(config.0.clone()).map_err(|e| fail(&e))
+    })
+}
+
+/// Release a configuration. NULL is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_config_free(config: *mut KeygenConfig) {
+    if !config.is_null() {
+        drop(Box::from_raw(config));
+    }
+}
@@ -0,0 +1,276 @@
+//! C bindings for keygen-rs.
+//!
+//! Every fallible function returns a `KeygenStatus` and writes its result
+//! through an out-pointer. On failure, `keygen_last_error_code` and
+//! `keygen_last_error_message` describe the error until the next failing
+//! call on the same thread.
+//!
+//! Resources are opaque handles owned by the caller and released with their
+//! `*_free` function. Strings returned through `char **` out-pointers are
+//! owned by the caller and released with `keygen_string_free`. Pointer
+//! arguments must be valid for the duration of the call, and string arguments
+//! must be NUL-terminated UTF-8.
+//!
+//! Network calls block the calling thread; they run on the SDK's internal
+//! runtime (see `keygen_rs::blocking`) and use the configuration installed
+//! with `keygen_config_apply`.
+#![deny(clippy::all)]
+#![allow(clippy::missing_safety_doc)]
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use keygen_rs::errors::{Error, ErrorMeta};
+
+pub mod config;
+pub mod license;
+pub mod license_file;
+pub mod machine;
+pub mod machine_file;
+
+/// Result of a call into the library.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeygenStatus {
+    Ok = 0,
+    /// Any error without a more specific status
+    Error = 1,
+    /// A NULL pointer, invalid UTF-8 or unknown setting was passed in
+    InvalidArgument = 2,
+    InvalidConfiguration = 3,
+    MissingConfiguration = 4,
+    /// The request could not be sent or its response could not be read
+    Network = 5,
+    RateLimited = 6,
+    PublicKeyMissing = 7,
+    PublicKeyInvalid = 8,
+    SignatureInvalid = 9,
+    LicenseKeyNotGenuine = 10,
+    LicenseKeyInvalid = 11,
+    LicenseNotActivated = 12,
+    LicenseExpired = 13,
+    LicenseSuspended = 14,
+    LicenseNotAllowed = 15,
+    LicenseTooManyMachines = 16,
+    MachineNotFound = 17,
+    HeartbeatDead = 18,
+    FileInvalid = 19,
+    FileNotGenuine = 20,
+    FileExpired = 21,
+    NotFound = 22,
+    FeatureNotSupported = 23,
+}
+
+impl From<&Error> for KeygenStatus {
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::InvalidConfiguration(_) => KeygenStatus::InvalidConfiguration,
+            Error::MissingConfiguration => KeygenStatus::MissingConfiguration,
+            Error::HttpClient(_) => KeygenStatus::Network,
+            Error::RateLimitExceeded { .. } => KeygenStatus::RateLimited,
+            Error::PublicKeyMissing => KeygenStatus::PublicKeyMissing,
+            Error::PublicKeyInvalid => KeygenStatus::PublicKeyInvalid,
+            Error::KeygenSignatureInvalid { .. } | Error::KeygenSignatureMissing => {
+                KeygenStatus::SignatureInvalid
+            }
+            Error::LicenseKeyNotGenuine => KeygenStatus::LicenseKeyNotGenuine,
+            Error::LicenseKeyMissing
+            | Error::LicenseKeyInvalid { .. }
+            | Error::LicenseTokenInvalid { .. } => KeygenStatus::LicenseKeyInvalid,
+            Error::LicenseNotActivated { .. } => KeygenStatus::LicenseNotActivated,
+            Error::LicenseExpired { .. } => KeygenStatus::LicenseExpired,
+            Error::LicenseSuspended { .. } => KeygenStatus::LicenseSuspended,
+            Error::LicenseNotAllowed { .. } => KeygenStatus::LicenseNotAllowed,
+            Error::LicenseTooManyMachines { .. } | Error::MachineLimitExceeded { .. } => {
+                KeygenStatus::LicenseTooManyMachines
+            }
+            Error::MachineNotFound => KeygenStatus::MachineNotFound,
+            Error::HeartbeatDead { .. } => KeygenStatus::HeartbeatDead,
+            Error::CertificateFileInvalid(_)
+            | Error::CertificateFileNotSupported(_)
+            | Error::LicenseFileInvalid(_)
+            | Error::LicenseFileNotSupported(_)
+            | Error::LicenseFileNotEncrypted
+            | Error::MachineFileInvalid(_)
+            | Error::MachineFileNotSupported(_)
+            | Error::DecryptionError(_) => KeygenStatus::FileInvalid,
+            Error::CertificateFileNotGenuine(_)
+            | Error::LicenseFileNotGenuine(_)
+            | Error::MachineFileNotGenuine(_) => KeygenStatus::FileNotGenuine,
+            Error::CertificateFileExpired
+            | Error::LicenseFileExpired(_)
+            | Error::MachineFileExpired(_) => KeygenStatus::FileExpired,
+            Error::NotFound { .. } => KeygenStatus::NotFound,
+            Error::FeatureNotSupported { .. } => KeygenStatus::FeatureNotSupported,
+            _ => KeygenStatus::Error,
+        }
+    }
+}
+
+struct LastError {
+    code: CString,
+    message: CString,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<LastError>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(code: &str, message: &str) {
+    LAST_ERROR.with(|last| {
+        *last.borrow_mut() = Some(LastError {
+            code: c_string(code),
+            message: c_string(message),
+        });
+    });
+}
+
+/// Record `error` as the thread's last error and return its status.
+pub(crate) fn fail(error: &Error) -> KeygenStatus {
+    set_last_error(&error.code(), &error.detail());
+    KeygenStatus::from(error)
+}
+
+pub(crate) fn invalid_argument(message: &str) -> KeygenStatus {
+    set_last_error("INVALID_ARGUMENT", message);
+    KeygenStatus::InvalidArgument
+}
+
+/// Run an FFI body, turning a panic into `KeygenStatus::Error` instead of
+/// unwinding into C.
+pub(crate) fn guard(f: impl FnOnce() -> Result<(), KeygenStatus>) -> KeygenStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => KeygenStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => {
+            set_last_error("PANIC", "keygen-rs panicked");
+            KeygenStatus::Error
+        }
+    }
+}
+
+fn c_string(s: &str) -> CString {
+    CString::new(s.replace('\0', "")).unwrap_or_default()
+}
+
+/// Borrow a required string argument.
+pub(crate) unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, KeygenStatus> {
+    if ptr.is_null() {
+        return Err(invalid_argument(&format!("{name} must not be NULL")));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| invalid_argument(&format!("{name} is not valid UTF-8")))
+}
+
+/// Borrow an optional string argument, where NULL means unset.
+pub(crate) unsafe fn opt_str_arg<'a>(
+    ptr: *const c_char,
+    name: &str,
+) -> Result<Option<&'a str>, KeygenStatus> {
+    if ptr.is_null() {
+        Ok(None)
+    } else {
+        str_arg(ptr, name).map(Some)
+    }
+}
+
+/// Copy an array of `len` strings.
+pub(crate) unsafe fn str_array_arg(
+    ptr: *const *const c_char,
+    len: usize,
+    name: &str,
+) -> Result<Vec<String>, KeygenStatus> {
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+    if ptr.is_null() {
+        return Err(invalid_argument(&format!("{name} must not be NULL")));
+    }
+    std::slice::from_raw_parts(ptr, len)
+        .iter()
+        .map(|&s| str_arg(s, name).map(str::to_string))
+        .collect()
+}
+
+/// Borrow a handle argument.
+pub(crate) unsafe fn handle_arg<'a, T>(ptr: *const T, name: &str) -> Result<&'a T, KeygenStatus> {
+    ptr.as_ref()
+        .ok_or_else(|| invalid_argument(&format!("{name} must not be NULL")))
+}
+
+/// Write `value` through an out-pointer.
+pub(crate) unsafe fn write_out<T>(out: *mut T, value: T) -> Result<(), KeygenStatus> {
+    if out.is_null() {
+        return Err(invalid_argument("out pointer must not be NULL"));
+    }
+    out.write(value);
+    Ok(())
+}
+
+/// Hand a string to C; release it with `keygen_string_free`.
+pub(crate) fn into_c_string(s: &str) -> *mut c_char {
+    c_string(s).into_raw()
+}
+
+pub(crate) fn to_json<T: serde::Serialize>(value: &T) -> Result<*mut c_char, KeygenStatus> {
+    serde_json::to_string(value)
+        .map(|json| into_c_string(&json))
+        .map_err(|e| fail(&Error::from(e)))
+}
+
+/// Keygen error code of the thread's last error, e.g. `LICENSE_EXPIRED`, or
+/// NULL if no call has failed. Valid until the next failing call on the
+/// thread.
+#[no_mangle]
+pub extern "C" fn keygen_last_error_code() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |error| error.code.as_ptr())
+    })
+}
+
+/// Human readable detail of the thread's last error, or NULL if no call has
+/// failed. Valid until the next failing call on the thread.
+#[no_mangle]
+pub extern "C" fn keygen_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |error| error.message.as_ptr())
+    })
+}
+
+/// Release a string returned by this library. NULL is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Verify a signed license key against the configured public keys and write
+/// its decoded payload to `out_payload`. `scheme` is e.g. `ED25519_SIGN`.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_verify_key(
+    scheme: *const c_char,
+    signed_key: *const c_char,
+    out_payload: *mut *mut c_char,
+) -> KeygenStatus {
+    guard(|| {
+        let scheme = str_arg(scheme, "scheme")?;
+        let signed_key = str_arg(signed_key, "signed_key")?;
+        let scheme: keygen_rs::license::SchemeCode =
+            serde_json::from_value(serde_json::Value::String(scheme.to_string()))
+                .map_err(|_| invalid_argument(&format!("unknown scheme {scheme:?}")))?;
+
+        let payload = keygen_rs::verify(scheme, signed_key).map_err(|e| fail(&e))?;
+        write_out(
+            out_payload,
+            into_c_string(&String::from_utf8_lossy(&payload)),
+        )
+    })
+}
@@ -0,0 +1,134 @@
+use std::ffi::c_char;
+
+use keygen_rs::blocking;
+use keygen_rs::errors::Error;
+use keygen_rs::license::LicenseCheckoutOpts;
+
+use crate::machine::KeygenMachine;
+use crate::{
+    fail, guard, handle_arg, into_c_string, str_arg, str_array_arg, to_json, write_out,
+    KeygenStatus,
+};
+
+/// Opaque license returned by `keygen_validate`.
+pub struct KeygenLicense(pub(crate) keygen_rs::license::License);
+
+/// Validate the configured license key for the given machine fingerprints
+/// and entitlement codes.
+///
+/// On success, and on `KEYGEN_STATUS_LICENSE_NOT_ACTIVATED` so that the
+/// machine can be activated next, the license is written to `out_license`.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_validate(
+    fingerprints: *const *const c_char,
+    fingerprints_len: usize,
+    entitlements: *const *const c_char,
+    entitlements_len: usize,
+    out_license: *mut *mut KeygenLicense,
+) -> KeygenStatus {
+    guard(|| {
+        let fingerprints = str_array_arg(fingerprints, fingerprints_len, "fingerprints")?;
+        let entitlements = str_array_arg(entitlements, entitlements_len, "entitlements")?;
+
+        match blocking::validate(&fingerprints, &entitlements) {
+            Ok(license) => write_out(out_license, Box::into_raw(Box::new(KeygenLicense(license)))),
+            Err(Error::LicenseNotActivated {
+                code,
+                detail,
+                license,
+            }) => {
+                write_out(
+                    out_license,
+                    Box::into_raw(Box::new(KeygenLicense(*license.clone()))),
+                )?;
+                Err(fail(&Error::LicenseNotActivated {
+                    code,
+                    detail,
+                    license,
+                }))
+            }
+            Err(e) => Err(fail(&e)),
+        }
+    })
+}
+
+/// Write the license ID to `out_id`.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_license_id(
+    license: *const KeygenLicense,
+    out_id: *mut *mut c_char,
+) -> KeygenStatus {
+    guard(|| {
+        let license = handle_arg(license, "license")?;
+        write_out(out_id, into_c_string(&license.0.id))
+    })
+}
+
+/// Write the license as a JSON object to `out_json`.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_license_to_json(
+    license: *const KeygenLicense,
+    out_json: *mut *mut c_char,
+) -> KeygenStatus {
+    guard(|| {
+        let license = handle_arg(license, "license")?;
+        write_out(out_json, to_json(&license.0)?)
+    })
+}
+
+/// Activate the machine `fingerprint` for the license.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_license_activate(
+    license: *const KeygenLicense,
+    fingerprint: *const c_char,
+    out_machine: *mut *mut KeygenMachine,
+) -> KeygenStatus {
+    guard(|| {
+        let license = handle_arg(license, "license")?;
+        let fingerprint = str_arg(fingerprint, "fingerprint")?;
+        let machine =
+            blocking::license::activate(&license.0, fingerprint, &[]).map_err(|e| fail(&e))?;
+        write_out(out_machine, KeygenMachine::new(machine)?)
+    })
+}
+
+/// Deactivate the license's machine with ID `machine_id`.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_license_deactivate(
+    license: *const KeygenLicense,
+    machine_id: *const c_char,
+) -> KeygenStatus {
+    guard(|| {
+        let license = handle_arg(license, "license")?;
+        let machine_id = str_arg(machine_id, "machine_id")?;
+        blocking::license::deactivate(&license.0, machine_id).map_err(|e| fail(&e))
+    })
+}
+
+/// Check out a license file and write its certificate to `out_certificate`.
+/// A `ttl` of 0 uses the policy's default.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_license_checkout(
+    license: *const KeygenLicense,
+    ttl: i64,
+    out_certificate: *mut *mut c_char,
+) -> KeygenStatus {
+    guard(|| {
+        let license = handle_arg(license, "license")?;
+        let options = if ttl > 0 {
+            LicenseCheckoutOpts::with_ttl(ttl)
+        } else {
+            LicenseCheckoutOpts::new()
+        };
+        let file = blocking::license::checkout(&license.0, &options).map_err(|e| fail(&e))?;
+        write_out(out_certificate, into_c_string(&file.certificate))
+    })
+}
+
+/// Release a license. NULL is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_license_free(license: *mut KeygenLicense) {
+    if !license.is_null() {
+        drop(Box::from_raw(license));
+    }
+}
@@ -0,0 +1,45 @@
+use std::ffi::c_char;
+
+use keygen_rs::errors::Error;
+use keygen_rs::license_file::LicenseFile;
+
+use crate::{fail, guard, str_arg, to_json, write_out, KeygenStatus};
+
+/// Verify a license file's signature against the configured public keys,
+/// decrypt it with the license key and write its dataset as JSON to
+/// `out_dataset`.
+///
+/// An expired file fails with `KEYGEN_STATUS_FILE_EXPIRED`, but its dataset is
+/// still written so the caller can decide how to handle it.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_license_file_verify(
+    certificate: *const c_char,
+    license_key: *const c_char,
+    out_dataset: *mut *mut c_char,
+) -> KeygenStatus {
+    guard(|| {
+        let certificate = str_arg(certificate, "certificate")?;
+        let license_key = str_arg(license_key, "license_key")?;
+
+        let unverified = LicenseFile {
+            id: String::new(),
+            certificate: certificate.to_string(),
+            issued: Default::default(),
+            expiry: Default::default(),
+            ttl: 0,
+        };
+        unverified.verify().map_err(|e| fail(&e))?;
+
+        match LicenseFile::from_cert(license_key, certificate) {
+            Ok(file) => {
+                let dataset = file.decrypt(license_key).map_err(|e| fail(&e))?;
+                write_out(out_dataset, to_json(&dataset)?)
+            }
+            Err(Error::LicenseFileExpired(dataset)) => {
+                write_out(out_dataset, to_json(&dataset)?)?;
+                Err(fail(&Error::LicenseFileExpired(dataset)))
+            }
+            Err(e) => Err(fail(&e)),
+        }
+    })
+}
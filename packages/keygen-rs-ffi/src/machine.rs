@@ -0,0 +1,170 @@
+use std::ffi::{c_char, c_void};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use keygen_rs::blocking;
+use keygen_rs::config::get_config;
+use keygen_rs::machine::{Machine, MachineCheckoutOpts};
+use tokio::sync::mpsc;
+
+use crate::{
+    fail, guard, handle_arg, into_c_string, invalid_argument, to_json, write_out, KeygenStatus,
+};
+
+/// Opaque machine returned by `keygen_license_activate`.
+pub struct KeygenMachine(pub(crate) Arc<Machine>);
+
+impl KeygenMachine {
+    /// Box `machine` for C, bound to the installed configuration so that it
+    /// can send heartbeats.
+    pub(crate) fn new(machine: Machine) -> Result<*mut KeygenMachine, KeygenStatus> {
+        let config = get_config().map_err(|e| fail(&e))?;
+        Ok(Box::into_raw(Box::new(KeygenMachine(Arc::new(
+            machine.with_config(config),
+        )))))
+    }
+}
+
+/// Called after every heartbeat with its status and the `user_data` passed to
+/// `keygen_heartbeat_start`. It runs on the heartbeat's thread, where
+/// `keygen_last_error_message` describes a failed heartbeat.
+pub type KeygenHeartbeatCallback = Option<unsafe extern "C" fn(KeygenStatus, *mut c_void)>;
+
+/// Opaque running heartbeat monitor.
+pub struct KeygenHeartbeat {
+    cancel: mpsc::Sender<()>,
+    thread: JoinHandle<()>,
+}
+
+struct UserData(*mut c_void);
+
+// The caller vouches for `user_data` being usable from the heartbeat thread.
+unsafe impl Send for UserData {}
+
+/// Write the machine ID to `out_id`.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_machine_id(
+    machine: *const KeygenMachine,
+    out_id: *mut *mut c_char,
+) -> KeygenStatus {
+    guard(|| {
+        let machine = handle_arg(machine, "machine")?;
+        write_out(out_id, into_c_string(&machine.0.id))
+    })
+}
+
+/// Write the machine as a JSON object to `out_json`.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_machine_to_json(
+    machine: *const KeygenMachine,
+    out_json: *mut *mut c_char,
+) -> KeygenStatus {
+    guard(|| {
+        let machine = handle_arg(machine, "machine")?;
+        write_out(out_json, to_json(machine.0.as_ref())?)
+    })
+}
+
+/// Deactivate the machine.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_machine_deactivate(machine: *const KeygenMachine) -> KeygenStatus {
+    guard(|| {
+        let machine = handle_arg(machine, "machine")?;
+        blocking::machine::deactivate(&machine.0).map_err(|e| fail(&e))
+    })
+}
+
+/// Check out a machine file and write its certificate to `out_certificate`.
+/// A `ttl` of 0 uses the policy's default.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_machine_checkout(
+    machine: *const KeygenMachine,
+    ttl: i64,
+    out_certificate: *mut *mut c_char,
+) -> KeygenStatus {
+    guard(|| {
+        let machine = handle_arg(machine, "machine")?;
+        let options = if ttl > 0 {
+            MachineCheckoutOpts::with_ttl(ttl)
+        } else {
+            MachineCheckoutOpts::new()
+        };
+        let file = blocking::machine::checkout(&machine.0, &options).map_err(|e| fail(&e))?;
+        write_out(out_certificate, into_c_string(&file.certificate))
+    })
+}
+
+/// Release a machine. NULL is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_machine_free(machine: *mut KeygenMachine) {
+    if !machine.is_null() {
+        drop(Box::from_raw(machine));
+    }
+}
+
+/// Ping the machine now and then every `interval_secs` seconds on a
+/// background thread until `keygen_heartbeat_stop` is called. `callback`
+/// may be NULL.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_heartbeat_start(
+    machine: *const KeygenMachine,
+    interval_secs: u64,
+    callback: KeygenHeartbeatCallback,
+    user_data: *mut c_void,
+    out_heartbeat: *mut *mut KeygenHeartbeat,
+) -> KeygenStatus {
+    guard(|| {
+        let machine = handle_arg(machine, "machine")?.0.clone();
+        if interval_secs == 0 {
+            return Err(invalid_argument("interval_secs must be positive"));
+        }
+        if out_heartbeat.is_null() {
+            return Err(invalid_argument("out pointer must not be NULL"));
+        }
+
+        let (cancel, cancel_rx) = mpsc::channel(1);
+        let user_data = UserData(user_data);
+        let thread = thread::Builder::new()
+            .name("keygen-heartbeat".to_string())
+            .spawn(move || {
+                let user_data = user_data;
+                let (tx, mut rx) = mpsc::channel(1);
+                let monitor = machine.monitor(
+                    Duration::from_secs(interval_secs),
+                    Some(tx),
+                    Some(cancel_rx),
+                );
+                let report = async {
+                    while let Some(result) = rx.recv().await {
+                        let status = match result {
+                            Ok(_) => KeygenStatus::Ok,
+                            Err(e) => fail(&e),
+                        };
+                        if let Some(callback) = callback {
+                            callback(status, user_data.0);
+                        }
+                    }
+                };
+                blocking::block_on(futures::future::join(monitor, report));
+            })
+            .map_err(|e| fail(&keygen_rs::errors::Error::UnexpectedError(e.to_string())))?;
+
+        write_out(
+            out_heartbeat,
+            Box::into_raw(Box::new(KeygenHeartbeat { cancel, thread })),
+        )
+    })
+}
+
+/// Stop a heartbeat monitor, wait for its thread to finish and release it.
+/// NULL is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_heartbeat_stop(heartbeat: *mut KeygenHeartbeat) {
+    if heartbeat.is_null() {
+        return;
+    }
+    let heartbeat = Box::from_raw(heartbeat);
+    let _ = heartbeat.cancel.try_send(());
+    let _ = heartbeat.thread.join();
+}
@@ -0,0 +1,30 @@
+use std::ffi::c_char;
+
+use keygen_rs::machine_file::MachineFile;
+
+use crate::{fail, guard, str_arg, to_json, write_out, KeygenStatus};
+
+/// Verify a machine file's signature against the configured public keys,
+/// decrypt it and write its dataset as JSON to `out_dataset`. The decryption
+/// key is the license key followed by the machine fingerprint.
+///
+/// An expired file fails with `KEYGEN_STATUS_FILE_EXPIRED`, but its dataset is
+/// still written so the caller can decide how to handle it.
+#[no_mangle]
+pub unsafe extern "C" fn keygen_machine_file_verify(
+    certificate: *const c_char,
+    decryption_key: *const c_char,
+    out_dataset: *mut *mut c_char,
+) -> KeygenStatus {
+    guard(|| {
+        let certificate = str_arg(certificate, "certificate")?;
+        let key = str_arg(decryption_key, "decryption_key")?;
+
+        let file = MachineFile::from_cert(key, certificate).map_err(|e| fail(&e))?;
+        file.verify_signature().map_err(|e| fail(&e))?;
+        let dataset = file.decrypt(key).map_err(|e| fail(&e))?;
+        write_out(out_dataset, to_json(&dataset)?)?;
+
+        file.validate_ttl().map_err(|e| fail(&e))
+    })
+}
@@ -0,0 +1,182 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use base64::{engine::general_purpose, Engine};
+use ed25519_dalek::{Signer, SigningKey};
+use mockito::{mock, server_url, Matcher, Mock};
+use rand::rngs::OsRng;
+use serde_json::json;
+
+fn sign(keypair: &SigningKey, payload: &str) -> String {
+    let signing_data = format!("key/{}", general_purpose::URL_SAFE.encode(payload));
+    let signature = keypair.sign(signing_data.as_bytes());
+    format!(
+        "{signing_data}.{}",
+        general_purpose::URL_SAFE.encode(signature.to_bytes())
+    )
+}
+
+/// Directory cargo put the cdylib in, next to this test binary.
+fn library_dir() -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    exe.parent().unwrap().to_path_buf()
+}
+
+fn license_json() -> serde_json::Value {
+    json!({
+        "id": "license-1",
+        "type": "licenses",
+        "attributes": {
+            "name": null,
+            "key": "LICENSE-KEY",
+            "expiry": null,
+            "status": "ACTIVE",
+            "metadata": {}
+        },
+        "relationships": {}
+    })
+}
+
+fn machine_json() -> serde_json::Value {
+    json!({
+        "id": "machine-1",
+        "type": "machines",
+        "attributes": {
+            "fingerprint": "fingerprint-1",
+            "name": null,
+            "platform": null,
+            "hostname": null,
+            "ip": null,
+            "cores": null,
+            "metadata": null,
+            "requireHeartbeat": true,
+            "heartbeatStatus": "ALIVE",
+            "heartbeatDuration": 600,
+            "created": "2024-01-01T00:00:00Z",
+            "updated": "2024-01-01T00:00:00Z"
+        },
+        "relationships": {}
+    })
+}
+
+/// The API calls behind `keygen_validate`, `keygen_license_activate`,
+/// `keygen_license_checkout` and `keygen_heartbeat_start`.
+fn mock_api() -> Vec<Mock> {
+    let respond = |method: &str, path: &str, status: usize, body: serde_json::Value| {
+        mock(method, path)
+            .match_query(Matcher::Any)
+            .with_status(status)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(body.to_string())
+            .create()
+    };
+    vec![
+        respond("GET", "/v1/me", 200, json!({ "data": license_json() })),
+        respond(
+            "POST",
+            "/v1/licenses/actions/validate-key",
+            200,
+            json!({
+                "meta": {
+                    "ts": "2024-01-01T00:00:00Z",
+                    "valid": true,
+                    "detail": "is valid",
+                    "code": "VALID",
+                    "scope": {
+                        "fingerprint": "fingerprint-1",
+                        "product": "product-1"
+                    }
+                },
+                "data": license_json()
+            }),
+        ),
+        respond(
+            "POST",
+            "/v1/machines",
+            201,
+            json!({ "data": machine_json() }),
+        ),
+        respond(
+            "POST",
+            "/v1/licenses/license-1/actions/check-out",
+            200,
+            json!({
+                "data": {
+                    "id": "license-file-1",
+                    "type": "license-files",
+                    "attributes": {
+                        "certificate": "-----BEGIN LICENSE FILE-----\n...\n-----END LICENSE FILE-----\n",
+                        "issued": "2024-01-01T00:00:00Z",
+                        "expiry": "2024-02-01T00:00:00Z",
+                        "ttl": 2678400
+                    },
+                    "relationships": {}
+                }
+            }),
+        ),
+        respond(
+            "POST",
+            "/v1/machines/machine-1/actions/ping",
+            200,
+            json!({ "data": machine_json() }),
+        ),
+    ]
+}
+
+/// The header shipped in `include/` must match the one the build generates.
+#[test]
+fn test_header_is_up_to_date() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let generated = fs::read_to_string(Path::new(env!("OUT_DIR")).join("keygen.h")).unwrap();
+    let shipped = crate_dir.join("include/keygen.h");
+
+    if env::var_os("KEYGEN_FFI_UPDATE_HEADER").is_some() {
+        fs::write(&shipped, &generated).unwrap();
+    }
+    assert!(
+        fs::read_to_string(&shipped).unwrap_or_default() == generated,
+        "include/keygen.h is stale; rerun the tests with KEYGEN_FFI_UPDATE_HEADER=1"
+    );
+}
+
+#[test]
+fn test_c_harness() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let lib_dir = library_dir();
+    let harness = lib_dir.join("keygen_ffi_harness");
+
+    let status = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg(crate_dir.join("tests/harness.c"))
+        .arg("-I")
+        .arg(env!("OUT_DIR"))
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-lkeygen_rs_ffi")
+        .arg("-o")
+        .arg(&harness)
+        .status()
+        .expect("Failed to run the C compiler");
+    assert!(status.success(), "Failed to compile tests/harness.c");
+
+    let keypair = SigningKey::generate(&mut OsRng);
+    let other = SigningKey::generate(&mut OsRng);
+    let payload = r#"{"license": {"id": "license-1"}}"#;
+
+    let _mocks = mock_api();
+    let output = Command::new(&harness)
+        .arg(hex::encode(keypair.verifying_key().as_bytes()))
+        .arg(sign(&keypair, payload))
+        .arg(sign(&other, payload))
+        .arg(server_url())
+        .output()
+        .expect("Failed to run the C harness");
+    assert!(
+        output.status.success(),
+        "C harness failed:\n{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
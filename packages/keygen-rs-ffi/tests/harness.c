@@ -0,0 +1,133 @@
+/* Exercises the C API the way a native host would. Run by tests/c_harness.rs
+ * with a public key, a key signed by it, a key signed by another key and the
+ * URL of a mock API serving one license and machine. */
+#include <stdio.h>
+#include <string.h>
+#include <unistd.h>
+
+#include "keygen.h"
+
+static int failures = 0;
+
+#define CHECK(cond)                                                       \
+  do {                                                                    \
+    if (!(cond)) {                                                        \
+      const char *message = keygen_last_error_message();                  \
+      fprintf(stderr, "%s:%d: check failed: %s (last error: %s)\n",       \
+              __FILE__, __LINE__, #cond, message ? message : "none");     \
+      failures++;                                                         \
+    }                                                                     \
+  } while (0)
+
+static void on_heartbeat(enum KeygenStatus status, void *user_data) {
+  *(volatile int *)user_data = status == KEYGEN_STATUS_OK ? 1 : -1;
+}
+
+int main(int argc, char **argv) {
+  if (argc != 5) {
+    fprintf(stderr, "usage: %s PUBLIC_KEY SIGNED_KEY FORGED_KEY API_URL\n", argv[0]);
+    return 2;
+  }
+  const char *public_key = argv[1];
+  const char *signed_key = argv[2];
+  const char *forged_key = argv[3];
+  const char *api_url = argv[4];
+
+  KeygenConfig *config = keygen_config_new();
+  CHECK(config != NULL);
+
+  /* Configuration mistakes are reported with a message */
+  CHECK(keygen_config_set(config, "account", "Not An Account!") == KEYGEN_STATUS_OK);
+  CHECK(keygen_config_validate(config) == KEYGEN_STATUS_INVALID_CONFIGURATION);
+  CHECK(keygen_last_error_message() != NULL &&
+        strstr(keygen_last_error_message(), "account") != NULL);
+  CHECK(keygen_config_set(config, "colour", "blue") == KEYGEN_STATUS_INVALID_ARGUMENT);
+  CHECK(keygen_config_set(config, "deployment", "moon") == KEYGEN_STATUS_INVALID_CONFIGURATION);
+  CHECK(keygen_config_set(config, "account", NULL) == KEYGEN_STATUS_INVALID_ARGUMENT);
+  CHECK(keygen_config_set(NULL, "account", "acme") == KEYGEN_STATUS_INVALID_ARGUMENT);
+
+  CHECK(keygen_config_set(config, "account", "acme") == KEYGEN_STATUS_OK);
+  CHECK(keygen_config_set(config, "deployment", "cloud") == KEYGEN_STATUS_OK);
+  CHECK(keygen_config_set(config, "public_key", public_key) == KEYGEN_STATUS_OK);
+  CHECK(keygen_config_validate(config) == KEYGEN_STATUS_OK);
+  CHECK(keygen_config_apply(config) == KEYGEN_STATUS_OK);
+  keygen_config_free(config);
+
+  /* Offline key verification */
+  char *payload = NULL;
+  CHECK(keygen_verify_key("ED25519_SIGN", signed_key, &payload) == KEYGEN_STATUS_OK);
+  CHECK(payload != NULL && strstr(payload, "\"license-1\"") != NULL);
+  keygen_string_free(payload);
+
+  payload = NULL;
+  CHECK(keygen_verify_key("ED25519_SIGN", forged_key, &payload) ==
+        KEYGEN_STATUS_LICENSE_KEY_NOT_GENUINE);
+  CHECK(payload == NULL);
+  CHECK(keygen_verify_key("NOPE", signed_key, &payload) == KEYGEN_STATUS_INVALID_ARGUMENT);
+
+  /* Offline file verification */
+  char *dataset = NULL;
+  CHECK(keygen_license_file_verify("not a certificate", "key", &dataset) ==
+        KEYGEN_STATUS_FILE_INVALID);
+  CHECK(dataset == NULL);
+  CHECK(keygen_machine_file_verify(NULL, "key", &dataset) == KEYGEN_STATUS_INVALID_ARGUMENT);
+
+  /* NULL handles are rejected, and freeing NULL is a no-op */
+  char *id = NULL;
+  KeygenLicense *license = NULL;
+  KeygenHeartbeat *heartbeat = NULL;
+  CHECK(keygen_license_id(NULL, &id) == KEYGEN_STATUS_INVALID_ARGUMENT);
+  CHECK(keygen_validate(NULL, 1, NULL, 0, &license) == KEYGEN_STATUS_INVALID_ARGUMENT);
+  CHECK(license == NULL);
+  CHECK(keygen_heartbeat_start(NULL, 60, NULL, NULL, &heartbeat) ==
+        KEYGEN_STATUS_INVALID_ARGUMENT);
+  CHECK(strcmp(keygen_last_error_code(), "INVALID_ARGUMENT") == 0);
+  keygen_heartbeat_stop(NULL);
+  keygen_license_free(NULL);
+  keygen_machine_free(NULL);
+  keygen_string_free(NULL);
+
+  /* Online flow against the mock API: validate, activate, check out and
+   * send heartbeats */
+  config = keygen_config_new();
+  CHECK(keygen_config_set(config, "api_url", api_url) == KEYGEN_STATUS_OK);
+  CHECK(keygen_config_set(config, "account", "acme") == KEYGEN_STATUS_OK);
+  CHECK(keygen_config_set(config, "product", "product-1") == KEYGEN_STATUS_OK);
+  CHECK(keygen_config_set(config, "license_key", "LICENSE-KEY") == KEYGEN_STATUS_OK);
+  CHECK(keygen_config_apply(config) == KEYGEN_STATUS_OK);
+  keygen_config_free(config);
+
+  const char *fingerprints[] = {"fingerprint-1"};
+  CHECK(keygen_validate(fingerprints, 1, NULL, 0, &license) == KEYGEN_STATUS_OK);
+  CHECK(license != NULL);
+  CHECK(keygen_license_id(license, &id) == KEYGEN_STATUS_OK);
+  CHECK(id != NULL && strcmp(id, "license-1") == 0);
+  keygen_string_free(id);
+
+  KeygenMachine *machine = NULL;
+  CHECK(keygen_license_activate(license, "fingerprint-1", &machine) == KEYGEN_STATUS_OK);
+  CHECK(machine != NULL);
+
+  char *certificate = NULL;
+  CHECK(keygen_license_checkout(license, 0, &certificate) == KEYGEN_STATUS_OK);
+  CHECK(certificate != NULL && strstr(certificate, "BEGIN LICENSE FILE") != NULL);
+  keygen_string_free(certificate);
+
+  /* The first ping is sent right away */
+  volatile int beat = 0;
+  CHECK(keygen_heartbeat_start(machine, 60, on_heartbeat, (void *)&beat, &heartbeat) ==
+        KEYGEN_STATUS_OK);
+  for (int i = 0; i < 500 && beat == 0; i++) {
+    usleep(10000);
+  }
+  CHECK(beat == 1);
+  keygen_heartbeat_stop(heartbeat);
+
+  keygen_machine_free(machine);
+  keygen_license_free(license);
+
+  if (failures == 0) {
+    printf("ok\n");
+  }
+  return failures == 0 ? 0 : 1;
+}
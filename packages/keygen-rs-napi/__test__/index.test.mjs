@@ -594,4 +594,55 @@ describe("error format", () => {
       assert.match(err.message, /^\[.+\]/);
     }
   });
+
+  it("keygen errors are structured", () => {
+    keygen.setConfig({ account: "a", product: "p", publicKey: "a".repeat(64) });
+
+    assert.throws(
+      () => keygen.verify("ED25519_SIGN", "not-a-valid-signed-key"),
+      (err) => {
+        assert.ok(err instanceof Error);
+        assert.equal(err.name, "KeygenError");
+        assert.equal(typeof err.kind, "string");
+        assert.equal(typeof err.detail, "string");
+        assert.equal(err.message, `[${err.code}] ${err.detail}`);
+        return true;
+      },
+    );
+  });
+
+  it("file errors carry their kind", () => {
+    assert.throws(
+      () => keygen.verifyLicenseFile("garbage"),
+      (err) => {
+        assert.equal(err.kind, "LicenseFileInvalid");
+        assert.equal(err.code, "ERROR");
+        return true;
+      },
+    );
+  });
+
+  it("async functions reject with structured errors", async () => {
+    keygen.setConfig({
+      account: "a",
+      product: "p",
+      licenseKey: "k",
+      apiUrl: "https://127.0.0.1:9",
+    });
+
+    await assert.rejects(() => keygen.validate(["fp1"]), (err) => {
+      assert.equal(err.name, "KeygenError");
+      assert.equal(typeof err.kind, "string");
+      assert.equal(typeof err.code, "string");
+      return true;
+    });
+  });
+
+  it("invalid arguments are not keygen errors", async () => {
+    await assert.rejects(() => keygen.updateLicense("id", null), (err) => {
+      assert.notEqual(err.name, "KeygenError");
+      assert.equal(err.kind, undefined);
+      return true;
+    });
+  });
 });
@@ -0,0 +1,149 @@
+/* auto-generated by NAPI-RS */
+/* eslint-disable */
+
+/** The `Error` variant a keygen-rs error was raised from. */
+export type KeygenErrorKind =
+  | "UnexpectedError"
+  | "InvalidUrl"
+  | "SystemClockUnsynced"
+  | "DecryptionError"
+  | "HttpClient"
+  | "UrlParse"
+  | "JsonError"
+  | "InvalidHeader"
+  | "UrlEncode"
+  | "RateLimitExceeded"
+  | "LicenseKeyMissing"
+  | "LicenseSchemeMissing"
+  | "LicenseSchemeNotSupported"
+  | "LicenseNotSigned"
+  | "LicenseKeyNotGenuine"
+  | "PublicKeyMissing"
+  | "PublicKeyInvalid"
+  | "LicenseSchemeUnsupported"
+  | "CertificateFileInvalid"
+  | "CertificateFileNotGenuine"
+  | "CertificateFileNotSupported"
+  | "CertificateFileExpired"
+  | "LicenseFileInvalid"
+  | "LicenseFileNotGenuine"
+  | "LicenseFileNotSupported"
+  | "LicenseFileNotEncrypted"
+  | "LicenseFileExpired"
+  | "MachineFileInvalid"
+  | "MachineFileNotGenuine"
+  | "MachineFileNotSupported"
+  | "MachineFileExpired"
+  | "KeygenApiError"
+  | "TokenNotAllowed"
+  | "TokenFormatInvalid"
+  | "TokenInvalid"
+  | "TokenExpired"
+  | "LicenseSuspended"
+  | "LicenseExpired"
+  | "LicenseNotAllowed"
+  | "LicenseNotActivated"
+  | "LicenseKeyInvalid"
+  | "LicenseTokenInvalid"
+  | "LicenseTooManyMachines"
+  | "LicenseTooManyCores"
+  | "LicenseTooManyProcesses"
+  | "MachineAlreadyActivated"
+  | "MachineLimitExceeded"
+  | "MachineNotFound"
+  | "ProcessLimitExceeded"
+  | "ProcessNotFound"
+  | "ComponentConflict"
+  | "ComponentAlreadyActivated"
+  | "ComponentNotActivated"
+  | "EnvironmentError"
+  | "HeartbeatDead"
+  | "HeartbeatPingFailed"
+  | "HeartbeatRequired"
+  | "ValidationFingerprintMissing"
+  | "ValidationComponentsMissing"
+  | "ValidationProductMissing"
+  | "NotFound"
+  | "PasswordResetTokenInvalid"
+  | "PasswordResetTokenExpired"
+  | "KeygenSignatureInvalid"
+  | "KeygenSignatureMissing"
+  | "MissingConfiguration"
+  | "InvalidConfiguration"
+  | "FeatureNotSupported";
+
+interface KeygenErrorBase extends Error {
+  name: "KeygenError";
+  /** The Keygen error code, such as `LICENSE_NOT_ACTIVATED`, or `ERROR` for SDK errors. */
+  code: string;
+  detail: string;
+  kind: KeygenErrorKind;
+}
+
+export interface RateLimitExceededError extends KeygenErrorBase {
+  kind: "RateLimitExceeded";
+  window: string;
+  count: number;
+  limit: number;
+  remaining: number;
+  /** Unix time in seconds at which the window resets. */
+  reset: number;
+  /** Seconds to wait before retrying. */
+  retryAfter: number;
+}
+
+export interface LicenseNotActivatedError extends KeygenErrorBase {
+  kind: "LicenseNotActivated";
+  /** The validated license, to activate the machine with. */
+  license: License;
+}
+
+export interface LicenseFileExpiredError extends KeygenErrorBase {
+  kind: "LicenseFileExpired";
+  /** The decrypted contents of the expired file. */
+  dataset: LicenseFileDataset;
+}
+
+export interface MachineFileExpiredError extends KeygenErrorBase {
+  kind: "MachineFileExpired";
+  /** The decrypted contents of the expired file. */
+  dataset: MachineFileDataset;
+}
+
+export interface FeatureNotSupportedError extends KeygenErrorBase {
+  kind: "FeatureNotSupported";
+  feature: string;
+  required: string;
+  apiVersion: string;
+}
+
+export interface KeygenApiError extends KeygenErrorBase {
+  kind: "KeygenApiError";
+  /** The API's response body. */
+  body: any;
+}
+
+export interface OtherKeygenError extends KeygenErrorBase {
+  kind: Exclude<
+    KeygenErrorKind,
+    | "RateLimitExceeded"
+    | "LicenseNotActivated"
+    | "LicenseFileExpired"
+    | "MachineFileExpired"
+    | "FeatureNotSupported"
+    | "KeygenApiError"
+  >;
+}
+
+/**
+ * Thrown, or used to reject, by every function that fails with a keygen-rs
+ * error. Narrow on `kind` to reach the payload of a variant.
+ */
+export type KeygenError =
+  | RateLimitExceededError
+  | LicenseNotActivatedError
+  | LicenseFileExpiredError
+  | MachineFileExpiredError
+  | FeatureNotSupportedError
+  | KeygenApiError
+  | OtherKeygenError;
@@ -1,7 +1,7 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
-use crate::to_napi_error;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -36,22 +36,29 @@ pub struct ListArchesOptions {
 }
 
 #[napi]
-pub async fn list_arches(options: Option<ListArchesOptions>) -> Result<Vec<Arch>> {
-    let opts = options.map(|o| keygen_rs::arch::ListArchesOptions {
-        limit: o.limit,
-        page_size: o.page_size,
-        page_number: o.page_number,
-    });
-    keygen_rs::arch::Arch::list(opts)
-        .await
-        .map(|list| list.into_iter().map(Arch::from).collect())
-        .map_err(to_napi_error)
+pub fn list_arches(
+    env: &Env,
+    options: Option<ListArchesOptions>,
+) -> Result<PromiseRaw<'_, Vec<Arch>>> {
+    spawn(env, async move {
+        let opts = options.map(|o| keygen_rs::arch::ListArchesOptions {
+            limit: o.limit,
+            page_size: o.page_size,
+            page_number: o.page_number,
+        });
+        keygen_rs::arch::Arch::list(opts)
+            .await
+            .map(|list| list.into_iter().map(Arch::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_arch(id: String) -> Result<Arch> {
-    keygen_rs::arch::Arch::get(&id)
-        .await
-        .map(Arch::from)
-        .map_err(to_napi_error)
+pub fn get_arch(env: &Env, id: String) -> Result<PromiseRaw<'_, Arch>> {
+    spawn(env, async move {
+        keygen_rs::arch::Arch::get(&id)
+            .await
+            .map(Arch::from)
+            .map_err(BindingError::from)
+    })
 }
@@ -1,7 +1,7 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
-use crate::to_napi_error;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -116,89 +116,114 @@ fn make_minimal_artifact(id: String) -> keygen_rs::artifact::Artifact {
 }
 
 #[napi]
-pub async fn create_artifact(request: CreateArtifactRequest) -> Result<Artifact> {
-    let req = keygen_rs::artifact::CreateArtifactRequest {
-        filename: request.filename,
-        release_id: request.release_id,
-        filetype: Some(request.filetype),
-        filesize: Some(request.filesize as u64),
-        platform: Some(request.platform),
-        arch: Some(request.arch),
-        signature: request.signature,
-        checksum: request.checksum,
-        metadata: crate::opt_metadata(request.metadata)?,
-    };
-    keygen_rs::artifact::Artifact::create(req)
-        .await
-        .map(Artifact::from)
-        .map_err(to_napi_error)
+pub fn create_artifact(
+    env: &Env,
+    request: CreateArtifactRequest,
+) -> Result<PromiseRaw<'_, Artifact>> {
+    spawn(env, async move {
+        let req = keygen_rs::artifact::CreateArtifactRequest {
+            filename: request.filename,
+            release_id: request.release_id,
+            filetype: Some(request.filetype),
+            filesize: Some(request.filesize as u64),
+            platform: Some(request.platform),
+            arch: Some(request.arch),
+            signature: request.signature,
+            checksum: request.checksum,
+            metadata: crate::opt_metadata(request.metadata)?,
+        };
+        keygen_rs::artifact::Artifact::create(req)
+            .await
+            .map(Artifact::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_artifacts(options: Option<ListArtifactsOptions>) -> Result<Vec<Artifact>> {
-    let opts = match options {
-        Some(o) => {
-            let status = o.status.and_then(|s| {
-                serde_json::from_value::<keygen_rs::artifact::ArtifactStatus>(
-                    serde_json::Value::String(s),
-                )
-                .ok()
-            });
-            Some(keygen_rs::artifact::ListArtifactsOptions {
-                limit: o.limit,
-                page_size: o.page_size,
-                page_number: o.page_number,
-                release: o.release,
-                product: o.product,
-                channel: o.channel,
-                platform: o.platform,
-                arch: o.arch,
-                filetype: o.filetype,
-                status,
-            })
-        }
-        None => None,
-    };
-    keygen_rs::artifact::Artifact::list(opts)
-        .await
-        .map(|list| list.into_iter().map(Artifact::from).collect())
-        .map_err(to_napi_error)
+pub fn list_artifacts(
+    env: &Env,
+    options: Option<ListArtifactsOptions>,
+) -> Result<PromiseRaw<'_, Vec<Artifact>>> {
+    spawn(env, async move {
+        let opts = match options {
+            Some(o) => {
+                let status = o.status.and_then(|s| {
+                    serde_json::from_value::<keygen_rs::artifact::ArtifactStatus>(
+                        serde_json::Value::String(s),
+                    )
+                    .ok()
+                });
+                Some(keygen_rs::artifact::ListArtifactsOptions {
+                    limit: o.limit,
+                    page_size: o.page_size,
+                    page_number: o.page_number,
+                    release: o.release,
+                    product: o.product,
+                    channel: o.channel,
+                    platform: o.platform,
+                    arch: o.arch,
+                    filetype: o.filetype,
+                    status,
+                })
+            }
+            None => None,
+        };
+        keygen_rs::artifact::Artifact::list(opts)
+            .await
+            .map(|list| list.into_iter().map(Artifact::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_artifact(id: String) -> Result<Artifact> {
-    keygen_rs::artifact::Artifact::get(&id)
-        .await
-        .map(Artifact::from)
-        .map_err(to_napi_error)
+pub fn get_artifact(env: &Env, id: String) -> Result<PromiseRaw<'_, Artifact>> {
+    spawn(env, async move {
+        keygen_rs::artifact::Artifact::get(&id)
+            .await
+            .map(Artifact::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn update_artifact(id: String, request: UpdateArtifactRequest) -> Result<Artifact> {
-    let art = make_minimal_artifact(id);
-    let req = keygen_rs::artifact::UpdateArtifactRequest {
-        filename: request.filename,
-        filetype: request.filetype,
-        platform: request.platform,
-        arch: request.arch,
-        signature: request.signature,
-        checksum: request.checksum,
-        metadata: crate::opt_metadata(request.metadata)?,
-    };
-    art.update(req)
-        .await
-        .map(Artifact::from)
-        .map_err(to_napi_error)
+pub fn update_artifact(
+    env: &Env,
+    id: String,
+    request: UpdateArtifactRequest,
+) -> Result<PromiseRaw<'_, Artifact>> {
+    spawn(env, async move {
+        let art = make_minimal_artifact(id);
+        let req = keygen_rs::artifact::UpdateArtifactRequest {
+            filename: request.filename,
+            filetype: request.filetype,
+            platform: request.platform,
+            arch: request.arch,
+            signature: request.signature,
+            checksum: request.checksum,
+            metadata: crate::opt_metadata(request.metadata)?,
+        };
+        art.update(req)
+            .await
+            .map(Artifact::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn delete_artifact(id: String) -> Result<()> {
-    let art = make_minimal_artifact(id);
-    art.delete().await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn delete_artifact(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let art = make_minimal_artifact(id);
+        art.delete().await.map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn yank_artifact(id: String) -> Result<Artifact> {
-    let art = make_minimal_artifact(id);
-    art.yank().await.map(Artifact::from).map_err(to_napi_error)
+pub fn yank_artifact(env: &Env, id: String) -> Result<PromiseRaw<'_, Artifact>> {
+    spawn(env, async move {
+        let art = make_minimal_artifact(id);
+        art.yank()
+            .await
+            .map(Artifact::from)
+            .map_err(BindingError::from)
+    })
 }
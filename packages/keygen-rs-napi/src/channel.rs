@@ -1,7 +1,7 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
-use crate::to_napi_error;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -36,22 +36,29 @@ pub struct ListChannelsOptions {
 }
 
 #[napi]
-pub async fn list_channels(options: Option<ListChannelsOptions>) -> Result<Vec<Channel>> {
-    let opts = options.map(|o| keygen_rs::channel::ListChannelsOptions {
-        limit: o.limit,
-        page_size: o.page_size,
-        page_number: o.page_number,
-    });
-    keygen_rs::channel::Channel::list(opts)
-        .await
-        .map(|list| list.into_iter().map(Channel::from).collect())
-        .map_err(to_napi_error)
+pub fn list_channels(
+    env: &Env,
+    options: Option<ListChannelsOptions>,
+) -> Result<PromiseRaw<'_, Vec<Channel>>> {
+    spawn(env, async move {
+        let opts = options.map(|o| keygen_rs::channel::ListChannelsOptions {
+            limit: o.limit,
+            page_size: o.page_size,
+            page_number: o.page_number,
+        });
+        keygen_rs::channel::Channel::list(opts)
+            .await
+            .map(|list| list.into_iter().map(Channel::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_channel(id: String) -> Result<Channel> {
-    keygen_rs::channel::Channel::get(&id)
-        .await
-        .map(Channel::from)
-        .map_err(to_napi_error)
+pub fn get_channel(env: &Env, id: String) -> Result<PromiseRaw<'_, Channel>> {
+    spawn(env, async move {
+        keygen_rs::channel::Channel::get(&id)
+            .await
+            .map(Channel::from)
+            .map_err(BindingError::from)
+    })
 }
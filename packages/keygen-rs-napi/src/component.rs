@@ -1,7 +1,7 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
-use crate::to_napi_error;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -69,66 +69,86 @@ pub struct ListComponentsOptions {
 }
 
 #[napi]
-pub async fn create_component(request: CreateComponentRequest) -> Result<Component> {
-    let req = keygen_rs::component::CreateComponentRequest {
-        fingerprint: request.fingerprint,
-        name: request.name,
-        metadata: crate::opt_metadata(request.metadata)?,
-        machine_id: request.machine_id,
-    };
-    keygen_rs::component::Component::create(req)
-        .await
-        .map(Component::from)
-        .map_err(to_napi_error)
+pub fn create_component(
+    env: &Env,
+    request: CreateComponentRequest,
+) -> Result<PromiseRaw<'_, Component>> {
+    spawn(env, async move {
+        let req = keygen_rs::component::CreateComponentRequest {
+            fingerprint: request.fingerprint,
+            name: request.name,
+            metadata: crate::opt_metadata(request.metadata)?,
+            machine_id: request.machine_id,
+        };
+        keygen_rs::component::Component::create(req)
+            .await
+            .map(Component::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_components(options: Option<ListComponentsOptions>) -> Result<Vec<Component>> {
-    let opts = options.map(|o| keygen_rs::component::ListComponentsOptions {
-        limit: o.limit,
-        page_size: o.page_size,
-        page_number: o.page_number,
-        machine: o.machine,
-        license: o.license,
-        owner: o.owner,
-        user: o.user,
-        product: o.product,
-    });
-    keygen_rs::component::Component::list(opts)
-        .await
-        .map(|list| list.into_iter().map(Component::from).collect())
-        .map_err(to_napi_error)
+pub fn list_components(
+    env: &Env,
+    options: Option<ListComponentsOptions>,
+) -> Result<PromiseRaw<'_, Vec<Component>>> {
+    spawn(env, async move {
+        let opts = options.map(|o| keygen_rs::component::ListComponentsOptions {
+            limit: o.limit,
+            page_size: o.page_size,
+            page_number: o.page_number,
+            machine: o.machine,
+            license: o.license,
+            owner: o.owner,
+            user: o.user,
+            product: o.product,
+        });
+        keygen_rs::component::Component::list(opts)
+            .await
+            .map(|list| list.into_iter().map(Component::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_component(id: String) -> Result<Component> {
-    keygen_rs::component::Component::get(&id)
-        .await
-        .map(Component::from)
-        .map_err(to_napi_error)
+pub fn get_component(env: &Env, id: String) -> Result<PromiseRaw<'_, Component>> {
+    spawn(env, async move {
+        keygen_rs::component::Component::get(&id)
+            .await
+            .map(Component::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn update_component(id: String, request: UpdateComponentRequest) -> Result<Component> {
-    let comp = keygen_rs::component::Component {
-        id,
-        ..Default::default()
-    };
-    let req = keygen_rs::component::UpdateComponentRequest {
-        name: request.name,
-        metadata: crate::opt_metadata(request.metadata)?,
-    };
-    comp.update(req)
-        .await
-        .map(Component::from)
-        .map_err(to_napi_error)
+pub fn update_component(
+    env: &Env,
+    id: String,
+    request: UpdateComponentRequest,
+) -> Result<PromiseRaw<'_, Component>> {
+    spawn(env, async move {
+        let comp = keygen_rs::component::Component {
+            id,
+            ..Default::default()
+        };
+        let req = keygen_rs::component::UpdateComponentRequest {
+            name: request.name,
+            metadata: crate::opt_metadata(request.metadata)?,
+        };
+        comp.update(req)
+            .await
+            .map(Component::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn delete_component(id: String) -> Result<()> {
-    let comp = keygen_rs::component::Component {
-        id,
-        ..Default::default()
-    };
-    comp.delete().await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn delete_component(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let comp = keygen_rs::component::Component {
+            id,
+            ..Default::default()
+        };
+        comp.delete().await.map_err(BindingError::from)
+    })
 }
@@ -34,13 +34,18 @@ impl From<&KeygenConfig> for keygen_rs::config::KeygenConfig {
             api_prefix: cfg.api_prefix.clone().unwrap_or_else(|| "v1".to_string()),
             environment: cfg.environment.clone(),
             user_agent: cfg.user_agent.clone(),
+            deployment: None,
+            signature_host: None,
+            middleware: Default::default(),
             product: cfg.product.clone(),
             package: cfg.package.clone().unwrap_or_default(),
             license_key: cfg.license_key.clone(),
             public_key: cfg.public_key.clone(),
+            keyring: Default::default(),
             platform: cfg.platform.clone(),
             max_clock_drift: cfg.max_clock_drift.or(Some(5)),
             verify_keygen_signature: cfg.verify_keygen_signature.or(Some(true)),
+            signature_mode: Default::default(),
             token: cfg.token.clone(),
         }
     }
@@ -68,18 +73,18 @@ impl From<keygen_rs::config::KeygenConfig> for KeygenConfig {
 }
 
 #[napi]
-pub fn set_config(config: KeygenConfig) -> Result<()> {
-    keygen_rs::config::set_config((&config).into()).map_err(to_napi_error)
+pub fn set_config(env: &Env, config: KeygenConfig) -> Result<()> {
+    keygen_rs::config::set_config((&config).into()).map_err(|e| to_napi_error(env, e))
 }
 
 #[napi]
-pub fn get_config() -> Result<KeygenConfig> {
+pub fn get_config(env: &Env) -> Result<KeygenConfig> {
     keygen_rs::config::get_config()
         .map(KeygenConfig::from)
-        .map_err(to_napi_error)
+        .map_err(|e| to_napi_error(env, e))
 }
 
 #[napi]
-pub fn reset_config() -> Result<()> {
-    keygen_rs::config::reset_config().map_err(to_napi_error)
+pub fn reset_config(env: &Env) -> Result<()> {
+    keygen_rs::config::reset_config().map_err(|e| to_napi_error(env, e))
 }
@@ -1,7 +1,7 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
-use crate::to_napi_error;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -56,76 +56,91 @@ pub struct ListEntitlementsOptions {
 }
 
 #[napi]
-pub async fn create_entitlement(request: CreateEntitlementRequest) -> Result<Entitlement> {
-    let req = keygen_rs::entitlement::CreateEntitlementRequest {
-        name: request.name,
-        code: request.code,
-        metadata: crate::opt_metadata(request.metadata)?,
-    };
-    keygen_rs::entitlement::Entitlement::create(req)
-        .await
-        .map(Entitlement::from)
-        .map_err(to_napi_error)
+pub fn create_entitlement(
+    env: &Env,
+    request: CreateEntitlementRequest,
+) -> Result<PromiseRaw<'_, Entitlement>> {
+    spawn(env, async move {
+        let req = keygen_rs::entitlement::CreateEntitlementRequest {
+            name: request.name,
+            code: request.code,
+            metadata: crate::opt_metadata(request.metadata)?,
+        };
+        keygen_rs::entitlement::Entitlement::create(req)
+            .await
+            .map(Entitlement::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_entitlements(
+pub fn list_entitlements(
+    env: &Env,
     options: Option<ListEntitlementsOptions>,
-) -> Result<Vec<Entitlement>> {
-    let opts = options.map(|o| keygen_rs::entitlement::ListEntitlementsOptions {
-        limit: o.limit,
-        page_size: o.page_size,
-        page_number: o.page_number,
-    });
-    keygen_rs::entitlement::Entitlement::list(opts)
-        .await
-        .map(|list| list.into_iter().map(Entitlement::from).collect())
-        .map_err(to_napi_error)
+) -> Result<PromiseRaw<'_, Vec<Entitlement>>> {
+    spawn(env, async move {
+        let opts = options.map(|o| keygen_rs::entitlement::ListEntitlementsOptions {
+            limit: o.limit,
+            page_size: o.page_size,
+            page_number: o.page_number,
+        });
+        keygen_rs::entitlement::Entitlement::list(opts)
+            .await
+            .map(|list| list.into_iter().map(Entitlement::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_entitlement(id: String) -> Result<Entitlement> {
-    keygen_rs::entitlement::Entitlement::get(&id)
-        .await
-        .map(Entitlement::from)
-        .map_err(to_napi_error)
+pub fn get_entitlement(env: &Env, id: String) -> Result<PromiseRaw<'_, Entitlement>> {
+    spawn(env, async move {
+        keygen_rs::entitlement::Entitlement::get(&id)
+            .await
+            .map(Entitlement::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn update_entitlement(
+pub fn update_entitlement(
+    env: &Env,
     id: String,
     request: UpdateEntitlementRequest,
-) -> Result<Entitlement> {
-    let ent = keygen_rs::entitlement::Entitlement {
-        id,
-        name: None,
-        code: String::new(),
-        metadata: None,
-        created: chrono::Utc::now(),
-        updated: chrono::Utc::now(),
-        account_id: None,
-    };
-    let req = keygen_rs::entitlement::UpdateEntitlementRequest {
-        name: request.name,
-        code: request.code,
-        metadata: crate::opt_metadata(request.metadata)?,
-    };
-    ent.update(req)
-        .await
-        .map(Entitlement::from)
-        .map_err(to_napi_error)
+) -> Result<PromiseRaw<'_, Entitlement>> {
+    spawn(env, async move {
+        let ent = keygen_rs::entitlement::Entitlement {
+            id,
+            name: None,
+            code: String::new(),
+            metadata: None,
+            created: chrono::Utc::now(),
+            updated: chrono::Utc::now(),
+            account_id: None,
+        };
+        let req = keygen_rs::entitlement::UpdateEntitlementRequest {
+            name: request.name,
+            code: request.code,
+            metadata: crate::opt_metadata(request.metadata)?,
+        };
+        ent.update(req)
+            .await
+            .map(Entitlement::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn delete_entitlement(id: String) -> Result<()> {
-    let ent = keygen_rs::entitlement::Entitlement {
-        id,
-        name: None,
-        code: String::new(),
-        metadata: None,
-        created: chrono::Utc::now(),
-        updated: chrono::Utc::now(),
-        account_id: None,
-    };
-    ent.delete().await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn delete_entitlement(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let ent = keygen_rs::entitlement::Entitlement {
+            id,
+            name: None,
+            code: String::new(),
+            metadata: None,
+            created: chrono::Utc::now(),
+            updated: chrono::Utc::now(),
+            account_id: None,
+        };
+        ent.delete().await.map_err(BindingError::from)
+    })
 }
@@ -1,7 +1,7 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
-use crate::to_napi_error;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -94,94 +94,112 @@ fn make_environment(id: String) -> keygen_rs::environment::Environment {
 use crate::parse_enum;
 
 #[napi]
-pub async fn create_environment(request: CreateEnvironmentRequest) -> Result<Environment> {
-    let req = keygen_rs::environment::CreateEnvironmentRequest {
-        name: request.name,
-        code: request.code,
-        isolation_strategy: request
-            .isolation_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "isolation strategy"))
-            .transpose()?,
-    };
-
-    keygen_rs::environment::Environment::create(req)
-        .await
-        .map(Environment::from)
-        .map_err(to_napi_error)
+pub fn create_environment(
+    env: &Env,
+    request: CreateEnvironmentRequest,
+) -> Result<PromiseRaw<'_, Environment>> {
+    spawn(env, async move {
+        let req = keygen_rs::environment::CreateEnvironmentRequest {
+            name: request.name,
+            code: request.code,
+            isolation_strategy: request
+                .isolation_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "isolation strategy"))
+                .transpose()?,
+        };
+
+        keygen_rs::environment::Environment::create(req)
+            .await
+            .map(Environment::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_environments(
+pub fn list_environments(
+    env: &Env,
     options: Option<ListEnvironmentsOptions>,
-) -> Result<Vec<Environment>> {
-    let opts = options.map(|o| keygen_rs::environment::ListEnvironmentsOptions {
-        limit: o.limit,
-        page_size: o.page_size,
-        page_number: o.page_number,
-    });
-
-    keygen_rs::environment::Environment::list(opts)
-        .await
-        .map(|result| {
-            result
-                .environments
-                .into_iter()
-                .map(Environment::from)
-                .collect()
-        })
-        .map_err(to_napi_error)
+) -> Result<PromiseRaw<'_, Vec<Environment>>> {
+    spawn(env, async move {
+        let opts = options.map(|o| keygen_rs::environment::ListEnvironmentsOptions {
+            limit: o.limit,
+            page_size: o.page_size,
+            page_number: o.page_number,
+        });
+
+        keygen_rs::environment::Environment::list(opts)
+            .await
+            .map(|result| {
+                result
+                    .environments
+                    .into_iter()
+                    .map(Environment::from)
+                    .collect()
+            })
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_environment(id: String) -> Result<Environment> {
-    keygen_rs::environment::Environment::get(&id)
-        .await
-        .map(Environment::from)
-        .map_err(to_napi_error)
+pub fn get_environment(env: &Env, id: String) -> Result<PromiseRaw<'_, Environment>> {
+    spawn(env, async move {
+        keygen_rs::environment::Environment::get(&id)
+            .await
+            .map(Environment::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn update_environment(
+pub fn update_environment(
+    env: &Env,
     id: String,
     request: UpdateEnvironmentRequest,
-) -> Result<Environment> {
-    let env = make_environment(id);
-
-    let req = keygen_rs::environment::UpdateEnvironmentRequest {
-        name: request.name,
-        code: request.code,
-    };
-
-    env.update(req)
-        .await
-        .map(Environment::from)
-        .map_err(to_napi_error)
+) -> Result<PromiseRaw<'_, Environment>> {
+    spawn(env, async move {
+        let env = make_environment(id);
+
+        let req = keygen_rs::environment::UpdateEnvironmentRequest {
+            name: request.name,
+            code: request.code,
+        };
+
+        env.update(req)
+            .await
+            .map(Environment::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn delete_environment(id: String) -> Result<()> {
-    let env = make_environment(id);
-    env.delete().await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn delete_environment(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let env = make_environment(id);
+        env.delete().await.map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn generate_environment_token(
+pub fn generate_environment_token(
+    env: &Env,
     id: String,
     name: Option<String>,
     expiry: Option<String>,
     permissions: Option<Vec<String>>,
-) -> Result<EnvironmentToken> {
-    let env = make_environment(id);
-
-    let req = keygen_rs::environment::CreateEnvironmentTokenRequest {
-        name,
-        expiry,
-        permissions,
-    };
-
-    env.generate_token(Some(req))
-        .await
-        .map(EnvironmentToken::from)
-        .map_err(to_napi_error)
+) -> Result<PromiseRaw<'_, EnvironmentToken>> {
+    spawn(env, async move {
+        let env = make_environment(id);
+
+        let req = keygen_rs::environment::CreateEnvironmentTokenRequest {
+            name,
+            expiry,
+            permissions,
+        };
+
+        env.generate_token(Some(req))
+            .await
+            .map(EnvironmentToken::from)
+            .map_err(BindingError::from)
+    })
 }
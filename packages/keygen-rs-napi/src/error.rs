@@ -0,0 +1,134 @@
+use std::future::Future;
+
+use keygen_rs::errors::{Error, ErrorMeta};
+use napi::bindgen_prelude::*;
+use napi::JsValue;
+
+use crate::license::License;
+use crate::license_file::LicenseFileDataset;
+use crate::machine_file::MachineFileDataset;
+
+/// The error of a binding's body: a keygen-rs error, thrown as a structured
+/// `KeygenError` once back on the JS thread, or a napi error such as an
+/// invalid argument, thrown as is.
+pub enum BindingError {
+    Keygen(Box<Error>),
+    Napi(napi::Error),
+}
+
+pub type BindingResult<T> = std::result::Result<T, BindingError>;
+
+impl From<Error> for BindingError {
+    fn from(e: Error) -> Self {
+        BindingError::Keygen(Box::new(e))
+    }
+}
+
+impl From<napi::Error> for BindingError {
+    fn from(e: napi::Error) -> Self {
+        BindingError::Napi(e)
+    }
+}
+
+impl BindingError {
+    fn into_napi(self, env: &Env) -> napi::Error {
+        match self {
+            BindingError::Keygen(e) => to_napi_error(env, *e),
+            BindingError::Napi(e) => e,
+        }
+    }
+}
+
+/// Runs `future` on the napi runtime, returning a promise that resolves to
+/// its value. Keygen errors are converted when the promise settles, on the JS
+/// thread, since building the error object needs an `Env`.
+pub fn spawn<T, F>(env: &Env, future: F) -> Result<PromiseRaw<'_, T>>
+where
+    T: ToNapiValue + Send + 'static,
+    F: Future<Output = BindingResult<T>> + Send + 'static,
+{
+    env.spawn_future_with_callback(async move { Ok(future.await) }, |env, result| {
+        result.map_err(|e| e.into_napi(env))
+    })
+}
+
+/// Converts `e` into a JS `Error` with the `[CODE] detail` message, carrying
+/// `code`, `detail`, `kind` and the variant's payload as properties, in the
+/// shape of the `KeygenError` declaration in `errors.d.ts`.
+pub fn to_napi_error(env: &Env, e: Error) -> napi::Error {
+    let message = format!("[{}] {}", e.code(), e.detail());
+    match create_error(env, e, &message) {
+        Ok(error) => napi::Error::from(error.to_unknown()),
+        Err(_) => napi::Error::new(Status::GenericFailure, message),
+    }
+}
+
+fn create_error<'env>(env: &'env Env, e: Error, message: &str) -> Result<Object<'env>> {
+    let mut error = env.create_error(napi::Error::new(Status::GenericFailure, message))?;
+    error.set_named_property("name", "KeygenError")?;
+    error.set_named_property("code", e.code())?;
+    error.set_named_property("detail", e.detail())?;
+    error.set_named_property("kind", e.kind())?;
+
+    match e {
+        Error::RateLimitExceeded {
+            window,
+            count,
+            limit,
+            remaining,
+            reset,
+            retry_after,
+        } => {
+            error.set_named_property("window", window)?;
+            error.set_named_property("count", count)?;
+            error.set_named_property("limit", limit)?;
+            error.set_named_property("remaining", remaining)?;
+            error.set_named_property("reset", reset as f64)?;
+            error.set_named_property("retryAfter", retry_after as f64)?;
+        }
+        Error::LicenseNotActivated { license, .. } => {
+            error.set_named_property("license", License::from(*license))?;
+        }
+        Error::LicenseFileExpired(dataset) => {
+            error.set_named_property("dataset", LicenseFileDataset::from(*dataset))?;
+        }
+        Error::MachineFileExpired(dataset) => {
+            error.set_named_property("dataset", MachineFileDataset::from(*dataset))?;
+        }
+        Error::FeatureNotSupported {
+            feature,
+            required,
+            api_version,
+        } => {
+            error.set_named_property("feature", feature)?;
+            error.set_named_property("required", required)?;
+            error.set_named_property("apiVersion", api_version)?;
+        }
+        Error::KeygenApiError { body, .. } => {
+            error.set_named_property("body", body)?;
+        }
+        _ => {}
+    }
+
+    Ok(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use keygen_rs::errors::ERROR_KINDS;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_kind_union_matches_error_kinds() {
+        let source = include_str!("../errors.d.ts");
+        let start = source.find("export type KeygenErrorKind =").unwrap();
+        let end = start + source[start..].find(';').unwrap();
+        let union: BTreeSet<&str> = source[start..end]
+            .split("| \"")
+            .skip(1)
+            .map(|rest| &rest[..rest.find('"').unwrap()])
+            .collect();
+
+        assert_eq!(union, ERROR_KINDS.iter().copied().collect());
+    }
+}
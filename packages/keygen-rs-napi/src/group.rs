@@ -3,8 +3,8 @@ use napi_derive::napi;
 
 use crate::license::License;
 use crate::machine::Machine;
-use crate::to_napi_error;
 use crate::user::User;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -69,113 +69,138 @@ pub struct ListGroupsOptions {
 }
 
 #[napi]
-pub async fn create_group(request: CreateGroupRequest) -> Result<Group> {
-    let req = keygen_rs::group::CreateGroupRequest {
-        name: request.name,
-        max_users: request.max_users,
-        max_licenses: request.max_licenses,
-        max_machines: request.max_machines,
-        metadata: crate::opt_metadata(request.metadata)?,
-    };
-    keygen_rs::group::Group::create(req)
-        .await
-        .map(Group::from)
-        .map_err(to_napi_error)
+pub fn create_group(env: &Env, request: CreateGroupRequest) -> Result<PromiseRaw<'_, Group>> {
+    spawn(env, async move {
+        let req = keygen_rs::group::CreateGroupRequest {
+            name: request.name,
+            max_users: request.max_users,
+            max_licenses: request.max_licenses,
+            max_machines: request.max_machines,
+            metadata: crate::opt_metadata(request.metadata)?,
+        };
+        keygen_rs::group::Group::create(req)
+            .await
+            .map(Group::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_groups(options: Option<ListGroupsOptions>) -> Result<Vec<Group>> {
-    let opts = options.map(|o| keygen_rs::group::ListGroupsOptions {
-        limit: o.limit,
-        page_size: o.page_size,
-        page_number: o.page_number,
-    });
-    keygen_rs::group::Group::list(opts)
-        .await
-        .map(|list| list.into_iter().map(Group::from).collect())
-        .map_err(to_napi_error)
+pub fn list_groups(
+    env: &Env,
+    options: Option<ListGroupsOptions>,
+) -> Result<PromiseRaw<'_, Vec<Group>>> {
+    spawn(env, async move {
+        let opts = options.map(|o| keygen_rs::group::ListGroupsOptions {
+            limit: o.limit,
+            page_size: o.page_size,
+            page_number: o.page_number,
+        });
+        keygen_rs::group::Group::list(opts)
+            .await
+            .map(|list| list.into_iter().map(Group::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_group(id: String) -> Result<Group> {
-    keygen_rs::group::Group::get(&id)
-        .await
-        .map(Group::from)
-        .map_err(to_napi_error)
+pub fn get_group(env: &Env, id: String) -> Result<PromiseRaw<'_, Group>> {
+    spawn(env, async move {
+        keygen_rs::group::Group::get(&id)
+            .await
+            .map(Group::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn update_group(id: String, request: UpdateGroupRequest) -> Result<Group> {
-    let grp = keygen_rs::group::Group {
-        id,
-        ..Default::default()
-    };
-    let req = keygen_rs::group::UpdateGroupRequest {
-        name: request.name,
-        max_users: request.max_users,
-        max_licenses: request.max_licenses,
-        max_machines: request.max_machines,
-        metadata: crate::opt_metadata(request.metadata)?,
-    };
-    grp.update(req)
-        .await
-        .map(Group::from)
-        .map_err(to_napi_error)
+pub fn update_group(
+    env: &Env,
+    id: String,
+    request: UpdateGroupRequest,
+) -> Result<PromiseRaw<'_, Group>> {
+    spawn(env, async move {
+        let grp = keygen_rs::group::Group {
+            id,
+            ..Default::default()
+        };
+        let req = keygen_rs::group::UpdateGroupRequest {
+            name: request.name,
+            max_users: request.max_users,
+            max_licenses: request.max_licenses,
+            max_machines: request.max_machines,
+            metadata: crate::opt_metadata(request.metadata)?,
+        };
+        grp.update(req)
+            .await
+            .map(Group::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn delete_group(id: String) -> Result<()> {
-    let grp = keygen_rs::group::Group {
-        id,
-        ..Default::default()
-    };
-    grp.delete().await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn delete_group(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let grp = keygen_rs::group::Group {
+            id,
+            ..Default::default()
+        };
+        grp.delete().await.map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_group_owners(id: String) -> Result<Vec<User>> {
-    let grp = keygen_rs::group::Group {
-        id,
-        ..Default::default()
-    };
-    grp.owners(None)
-        .await
-        .map(|items| items.into_iter().map(User::from).collect())
-        .map_err(to_napi_error)
+pub fn list_group_owners(env: &Env, id: String) -> Result<PromiseRaw<'_, Vec<User>>> {
+    spawn(env, async move {
+        let grp = keygen_rs::group::Group {
+            id,
+            ..Default::default()
+        };
+        grp.owners(None)
+            .await
+            .map(|items| items.into_iter().map(User::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_group_users(id: String) -> Result<Vec<User>> {
-    let grp = keygen_rs::group::Group {
-        id,
-        ..Default::default()
-    };
-    grp.users(None)
-        .await
-        .map(|items| items.into_iter().map(User::from).collect())
-        .map_err(to_napi_error)
+pub fn list_group_users(env: &Env, id: String) -> Result<PromiseRaw<'_, Vec<User>>> {
+    spawn(env, async move {
+        let grp = keygen_rs::group::Group {
+            id,
+            ..Default::default()
+        };
+        grp.users(None)
+            .await
+            .map(|items| items.into_iter().map(User::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_group_licenses(id: String) -> Result<Vec<License>> {
-    let grp = keygen_rs::group::Group {
-        id,
-        ..Default::default()
-    };
-    grp.licenses(None)
-        .await
-        .map(|items| items.into_iter().map(License::from).collect())
-        .map_err(to_napi_error)
+pub fn list_group_licenses(env: &Env, id: String) -> Result<PromiseRaw<'_, Vec<License>>> {
+    spawn(env, async move {
+        let grp = keygen_rs::group::Group {
+            id,
+            ..Default::default()
+        };
+        grp.licenses(None)
+            .await
+            .map(|items| items.into_iter().map(License::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_group_machines(id: String) -> Result<Vec<Machine>> {
-    let grp = keygen_rs::group::Group {
-        id,
-        ..Default::default()
-    };
-    grp.machines(None)
-        .await
-        .map(|items| items.into_iter().map(Machine::from).collect())
-        .map_err(to_napi_error)
+pub fn list_group_machines(env: &Env, id: String) -> Result<PromiseRaw<'_, Vec<Machine>>> {
+    spawn(env, async move {
+        let grp = keygen_rs::group::Group {
+            id,
+            ..Default::default()
+        };
+        grp.machines(None)
+            .await
+            .map(|items| items.into_iter().map(Machine::from).collect())
+            .map_err(BindingError::from)
+    })
 }
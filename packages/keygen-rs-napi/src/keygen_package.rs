@@ -1,7 +1,7 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
-use crate::to_napi_error;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -92,69 +92,86 @@ fn make_minimal_package(id: String) -> keygen_rs::package::Package {
 }
 
 #[napi]
-pub async fn create_package(request: CreatePackageRequest) -> Result<Package> {
-    let engine = parse_engine(&request.engine)?;
-    let req = keygen_rs::package::CreatePackageRequest {
-        name: request.name,
-        key: request.key,
-        product_id: request.product_id,
-        engine: Some(engine),
-        metadata: crate::opt_metadata(request.metadata)?,
-    };
-    keygen_rs::package::Package::create(req)
-        .await
-        .map(Package::from)
-        .map_err(to_napi_error)
+pub fn create_package(env: &Env, request: CreatePackageRequest) -> Result<PromiseRaw<'_, Package>> {
+    spawn(env, async move {
+        let engine = parse_engine(&request.engine)?;
+        let req = keygen_rs::package::CreatePackageRequest {
+            name: request.name,
+            key: request.key,
+            product_id: request.product_id,
+            engine: Some(engine),
+            metadata: crate::opt_metadata(request.metadata)?,
+        };
+        keygen_rs::package::Package::create(req)
+            .await
+            .map(Package::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_packages(options: Option<ListPackagesOptions>) -> Result<Vec<Package>> {
-    let opts = match options {
-        Some(o) => {
-            let engine = match o.engine {
-                Some(ref e) => Some(parse_engine(e)?),
-                None => None,
-            };
-            Some(keygen_rs::package::ListPackagesOptions {
-                limit: o.limit,
-                page_size: o.page_size,
-                page_number: o.page_number,
-                product: o.product,
-                engine,
-            })
-        }
-        None => None,
-    };
-    keygen_rs::package::Package::list(opts)
-        .await
-        .map(|list| list.into_iter().map(Package::from).collect())
-        .map_err(to_napi_error)
+pub fn list_packages(
+    env: &Env,
+    options: Option<ListPackagesOptions>,
+) -> Result<PromiseRaw<'_, Vec<Package>>> {
+    spawn(env, async move {
+        let opts = match options {
+            Some(o) => {
+                let engine = match o.engine {
+                    Some(ref e) => Some(parse_engine(e)?),
+                    None => None,
+                };
+                Some(keygen_rs::package::ListPackagesOptions {
+                    limit: o.limit,
+                    page_size: o.page_size,
+                    page_number: o.page_number,
+                    product: o.product,
+                    engine,
+                })
+            }
+            None => None,
+        };
+        keygen_rs::package::Package::list(opts)
+            .await
+            .map(|list| list.into_iter().map(Package::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_package(id: String) -> Result<Package> {
-    keygen_rs::package::Package::get(&id)
-        .await
-        .map(Package::from)
-        .map_err(to_napi_error)
+pub fn get_package(env: &Env, id: String) -> Result<PromiseRaw<'_, Package>> {
+    spawn(env, async move {
+        keygen_rs::package::Package::get(&id)
+            .await
+            .map(Package::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn update_package(id: String, request: UpdatePackageRequest) -> Result<Package> {
-    let pkg = make_minimal_package(id);
-    let req = keygen_rs::package::UpdatePackageRequest {
-        name: request.name,
-        key: request.key,
-        metadata: crate::opt_metadata(request.metadata)?,
-    };
-    pkg.update(req)
-        .await
-        .map(Package::from)
-        .map_err(to_napi_error)
+pub fn update_package(
+    env: &Env,
+    id: String,
+    request: UpdatePackageRequest,
+) -> Result<PromiseRaw<'_, Package>> {
+    spawn(env, async move {
+        let pkg = make_minimal_package(id);
+        let req = keygen_rs::package::UpdatePackageRequest {
+            name: request.name,
+            key: request.key,
+            metadata: crate::opt_metadata(request.metadata)?,
+        };
+        pkg.update(req)
+            .await
+            .map(Package::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn delete_package(id: String) -> Result<()> {
-    let pkg = make_minimal_package(id);
-    pkg.delete().await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn delete_package(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let pkg = make_minimal_package(id);
+        pkg.delete().await.map_err(BindingError::from)
+    })
 }
@@ -1,7 +1,7 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
-use crate::to_napi_error;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -36,22 +36,29 @@ pub struct ListPlatformsOptions {
 }
 
 #[napi]
-pub async fn list_platforms(options: Option<ListPlatformsOptions>) -> Result<Vec<Platform>> {
-    let opts = options.map(|o| keygen_rs::platform::ListPlatformsOptions {
-        limit: o.limit,
-        page_size: o.page_size,
-        page_number: o.page_number,
-    });
-    keygen_rs::platform::Platform::list(opts)
-        .await
-        .map(|list| list.into_iter().map(Platform::from).collect())
-        .map_err(to_napi_error)
+pub fn list_platforms(
+    env: &Env,
+    options: Option<ListPlatformsOptions>,
+) -> Result<PromiseRaw<'_, Vec<Platform>>> {
+    spawn(env, async move {
+        let opts = options.map(|o| keygen_rs::platform::ListPlatformsOptions {
+            limit: o.limit,
+            page_size: o.page_size,
+            page_number: o.page_number,
+        });
+        keygen_rs::platform::Platform::list(opts)
+            .await
+            .map(|list| list.into_iter().map(Platform::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_platform(id: String) -> Result<Platform> {
-    keygen_rs::platform::Platform::get(&id)
-        .await
-        .map(Platform::from)
-        .map_err(to_napi_error)
+pub fn get_platform(env: &Env, id: String) -> Result<PromiseRaw<'_, Platform>> {
+    spawn(env, async move {
+        keygen_rs::platform::Platform::get(&id)
+            .await
+            .map(Platform::from)
+            .map_err(BindingError::from)
+    })
 }
@@ -1,6 +1,6 @@
 #![deny(clippy::all)]
 
-use keygen_rs::errors::ErrorMeta;
+mod error;
 
 pub mod config;
 pub mod license;
@@ -21,15 +21,12 @@ pub mod keygen_platform;
 pub mod policy;
 pub mod product;
 pub mod release;
+pub mod second_factor;
 pub mod token_module;
 pub mod user;
 pub mod webhook;
 
-fn to_napi_error(e: keygen_rs::errors::Error) -> napi::Error {
-    let code = e.code();
-    let detail = e.detail();
-    napi::Error::new(napi::Status::GenericFailure, format!("[{code}] {detail}"))
-}
+use error::{spawn, to_napi_error, BindingError};
 
 fn parse_enum<T: serde::de::DeserializeOwned>(s: &str, label: &str) -> napi::Result<T> {
     serde_json::from_value(serde_json::Value::String(s.to_string()))
@@ -4,9 +4,9 @@ use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
 use crate::license_file::LicenseFile;
-use crate::to_napi_error;
 use crate::token_module::Token;
 use crate::user::User;
+use crate::{spawn, to_napi_error, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -132,120 +132,132 @@ fn make_license(id: String) -> keygen_rs::license::License {
 }
 
 #[napi]
-pub async fn validate(
+pub fn validate(
+    env: &Env,
     fingerprints: Vec<String>,
     entitlements: Option<Vec<String>>,
-) -> Result<License> {
-    let entitlements = entitlements.unwrap_or_default();
-    keygen_rs::validate(&fingerprints, &entitlements)
-        .await
-        .map(License::from)
-        .map_err(to_napi_error)
+) -> Result<PromiseRaw<'_, License>> {
+    spawn(env, async move {
+        let entitlements = entitlements.unwrap_or_default();
+        keygen_rs::validate(&fingerprints, &entitlements)
+            .await
+            .map(License::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub fn verify(scheme: String, signed_key: String) -> Result<Buffer> {
+pub fn verify(env: &Env, scheme: String, signed_key: String) -> Result<Buffer> {
     let scheme_code: keygen_rs::license::SchemeCode =
         serde_json::from_value(serde_json::Value::String(scheme))
             .map_err(|e| napi::Error::new(Status::InvalidArg, format!("Invalid scheme: {e}")))?;
 
     keygen_rs::verify(scheme_code, &signed_key)
         .map(|bytes| bytes.into())
-        .map_err(to_napi_error)
+        .map_err(|e| to_napi_error(env, e))
 }
 
 #[napi]
-pub async fn create_license(request: LicenseCreateRequest) -> Result<License> {
-    let mut req = keygen_rs::license::LicenseCreateRequest::new(request.policy_id);
+pub fn create_license(env: &Env, request: LicenseCreateRequest) -> Result<PromiseRaw<'_, License>> {
+    spawn(env, async move {
+        let mut req = keygen_rs::license::LicenseCreateRequest::new(request.policy_id);
 
-    if let Some(name) = request.name {
-        req = req.with_name(name);
-    }
-    if let Some(key) = request.key {
-        req = req.with_key(key);
-    }
-    if let Some(expiry) = request.expiry {
-        let dt = chrono::DateTime::parse_from_rfc3339(&expiry)
-            .map_err(|e| napi::Error::new(Status::InvalidArg, format!("Invalid expiry: {e}")))?
-            .with_timezone(&chrono::Utc);
-        req = req.with_expiry(dt);
-    }
-    if let Some(max_machines) = request.max_machines {
-        req = req.with_max_machines(max_machines);
-    }
-    if let Some(max_processes) = request.max_processes {
-        req = req.with_max_processes(max_processes);
-    }
-    if let Some(max_users) = request.max_users {
-        req = req.with_max_users(max_users);
-    }
-    if let Some(max_cores) = request.max_cores {
-        req = req.with_max_cores(max_cores);
-    }
-    if let Some(max_uses) = request.max_uses {
-        req = req.with_max_uses(max_uses);
-    }
-    if let Some(protected) = request.protected {
-        req = req.with_protected(protected);
-    }
-    if let Some(suspended) = request.suspended {
-        req = req.with_suspended(suspended);
-    }
-    if let Some(permissions) = request.permissions {
-        req = req.with_permissions(permissions);
-    }
-    if let Some(meta) = request.metadata {
-        if let Ok(map) = serde_json::from_value::<HashMap<String, serde_json::Value>>(meta) {
-            req = req.with_metadata(map);
+        if let Some(name) = request.name {
+            req = req.with_name(name);
+        }
+        if let Some(key) = request.key {
+            req = req.with_key(key);
+        }
+        if let Some(expiry) = request.expiry {
+            let dt = chrono::DateTime::parse_from_rfc3339(&expiry)
+                .map_err(|e| napi::Error::new(Status::InvalidArg, format!("Invalid expiry: {e}")))?
+                .with_timezone(&chrono::Utc);
+            req = req.with_expiry(dt);
+        }
+        if let Some(max_machines) = request.max_machines {
+            req = req.with_max_machines(max_machines);
+        }
+        if let Some(max_processes) = request.max_processes {
+            req = req.with_max_processes(max_processes);
+        }
+        if let Some(max_users) = request.max_users {
+            req = req.with_max_users(max_users);
+        }
+        if let Some(max_cores) = request.max_cores {
+            req = req.with_max_cores(max_cores);
+        }
+        if let Some(max_uses) = request.max_uses {
+            req = req.with_max_uses(max_uses);
+        }
+        if let Some(protected) = request.protected {
+            req = req.with_protected(protected);
+        }
+        if let Some(suspended) = request.suspended {
+            req = req.with_suspended(suspended);
+        }
+        if let Some(permissions) = request.permissions {
+            req = req.with_permissions(permissions);
+        }
+        if let Some(meta) = request.metadata {
+            if let Ok(map) = serde_json::from_value::<HashMap<String, serde_json::Value>>(meta) {
+                req = req.with_metadata(map);
+            }
+        }
+        if let Some(owner_id) = request.owner_id {
+            req = req.with_owner_id(owner_id);
+        }
+        if let Some(group_id) = request.group_id {
+            req = req.with_group_id(group_id);
         }
-    }
-    if let Some(owner_id) = request.owner_id {
-        req = req.with_owner_id(owner_id);
-    }
-    if let Some(group_id) = request.group_id {
-        req = req.with_group_id(group_id);
-    }
 
-    keygen_rs::license::License::create(req)
-        .await
-        .map(License::from)
-        .map_err(to_napi_error)
+        keygen_rs::license::License::create(req)
+            .await
+            .map(License::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_licenses(options: Option<ListLicensesOptions>) -> Result<Vec<License>> {
-    let opts = options.map(|o| keygen_rs::license::LicenseListOptions {
-        limit: o.limit.map(|v| v as i32),
-        page_size: o.page_size.map(|v| v as i32),
-        page_number: o.page_number.map(|v| v as i32),
-        status: o.status,
-        product: o.product,
-        policy: o.policy,
-        owner: o.owner,
-        user: o.user,
-        group: o.group,
-        machine: o.machine,
-        assigned: o.assigned,
-        unassigned: o.unassigned,
-        activated: o.activated,
-        metadata: o
-            .metadata
-            .and_then(|meta| serde_json::from_value(meta).ok()),
-        ..Default::default()
-    });
-
-    keygen_rs::license::License::list(opts.as_ref())
-        .await
-        .map(|licenses| licenses.into_iter().map(License::from).collect())
-        .map_err(to_napi_error)
+pub fn list_licenses(
+    env: &Env,
+    options: Option<ListLicensesOptions>,
+) -> Result<PromiseRaw<'_, Vec<License>>> {
+    spawn(env, async move {
+        let opts = options.map(|o| keygen_rs::license::LicenseListOptions {
+            limit: o.limit.map(|v| v as i32),
+            page_size: o.page_size.map(|v| v as i32),
+            page_number: o.page_number.map(|v| v as i32),
+            status: o.status,
+            product: o.product,
+            policy: o.policy,
+            owner: o.owner,
+            user: o.user,
+            group: o.group,
+            machine: o.machine,
+            assigned: o.assigned,
+            unassigned: o.unassigned,
+            activated: o.activated,
+            metadata: o
+                .metadata
+                .and_then(|meta| serde_json::from_value(meta).ok()),
+            ..Default::default()
+        });
+
+        keygen_rs::license::License::list(opts.as_ref())
+            .await
+            .map(|licenses| licenses.into_iter().map(License::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_license(id: String) -> Result<License> {
-    keygen_rs::license::License::get(&id)
-        .await
-        .map(License::from)
-        .map_err(to_napi_error)
+pub fn get_license(env: &Env, id: String) -> Result<PromiseRaw<'_, License>> {
+    spawn(env, async move {
+        keygen_rs::license::License::get(&id)
+            .await
+            .map(License::from)
+            .map_err(BindingError::from)
+    })
 }
 
 /// Update a license. Clearable integer fields accept `number | null`:
@@ -255,258 +267,341 @@ pub async fn get_license(id: String) -> Result<License> {
 #[napi(
     ts_args_type = "id: string, request: { name?: string; expiry?: string; maxMachines?: number | null; maxProcesses?: number | null; maxUsers?: number | null; maxCores?: number | null; maxUses?: number | null; protected?: boolean; suspended?: boolean; permissions?: string[]; metadata?: any }"
 )]
-pub async fn update_license(id: String, request: serde_json::Value) -> Result<License> {
-    let lic = make_license(id);
+pub fn update_license(
+    env: &Env,
+    id: String,
+    request: serde_json::Value,
+) -> Result<PromiseRaw<'_, License>> {
+    spawn(env, async move {
+        let lic = make_license(id);
 
-    let obj = request
-        .as_object()
-        .ok_or_else(|| napi::Error::new(Status::InvalidArg, "request must be an object"))?;
+        let obj = request
+            .as_object()
+            .ok_or_else(|| napi::Error::new(Status::InvalidArg, "request must be an object"))?;
 
-    let mut req = keygen_rs::license::LicenseUpdateRequest::new();
+        let mut req = keygen_rs::license::LicenseUpdateRequest::new();
 
-    if let Some(serde_json::Value::String(name)) = obj.get("name") {
-        req = req.with_name(name.clone());
-    }
-    if let Some(v) = obj.get("expiry") {
-        if let Some(s) = v.as_str() {
-            let dt = chrono::DateTime::parse_from_rfc3339(s)
-                .map_err(|e| napi::Error::new(Status::InvalidArg, format!("Invalid expiry: {e}")))?
-                .with_timezone(&chrono::Utc);
-            req = req.with_expiry(dt);
+        if let Some(serde_json::Value::String(name)) = obj.get("name") {
+            req = req.with_name(name.clone());
+        }
+        if let Some(v) = obj.get("expiry") {
+            if let Some(s) = v.as_str() {
+                let dt = chrono::DateTime::parse_from_rfc3339(s)
+                    .map_err(|e| {
+                        napi::Error::new(Status::InvalidArg, format!("Invalid expiry: {e}"))
+                    })?
+                    .with_timezone(&chrono::Utc);
+                req = req.with_expiry(dt);
+            }
         }
-    }
 
-    // Clearable integer fields: null → clear, number → set, absent → keep
-    macro_rules! apply_clearable {
-        ($obj:expr, $req:expr, $field:literal, $set:ident, $clear:ident) => {
-            if let Some(v) = $obj.get($field) {
-                if v.is_null() {
-                    $req = $req.$clear();
-                } else if let Some(n) = v.as_i64() {
-                    $req = $req.$set(n as i32);
+        // Clearable integer fields: null → clear, number → set, absent → keep
+        macro_rules! apply_clearable {
+            ($obj:expr, $req:expr, $field:literal, $set:ident, $clear:ident) => {
+                if let Some(v) = $obj.get($field) {
+                    if v.is_null() {
+                        $req = $req.$clear();
+                    } else if let Some(n) = v.as_i64() {
+                        $req = $req.$set(n as i32);
+                    }
                 }
-            }
-        };
-    }
+            };
+        }
 
-    apply_clearable!(
-        obj,
-        req,
-        "maxMachines",
-        with_max_machines,
-        clear_max_machines
-    );
-    apply_clearable!(
-        obj,
-        req,
-        "maxProcesses",
-        with_max_processes,
-        clear_max_processes
-    );
-    apply_clearable!(obj, req, "maxUsers", with_max_users, clear_max_users);
-    apply_clearable!(obj, req, "maxCores", with_max_cores, clear_max_cores);
-    apply_clearable!(obj, req, "maxUses", with_max_uses, clear_max_uses);
-
-    if let Some(serde_json::Value::Bool(protected)) = obj.get("protected") {
-        req = req.with_protected(*protected);
-    }
-    if let Some(serde_json::Value::Bool(suspended)) = obj.get("suspended") {
-        req = req.with_suspended(*suspended);
-    }
-    if let Some(serde_json::Value::Array(perms)) = obj.get("permissions") {
-        let permissions: Vec<String> = perms
-            .iter()
-            .filter_map(|v| v.as_str().map(String::from))
-            .collect();
-        req = req.with_permissions(permissions);
-    }
-    if let Some(meta) = obj.get("metadata") {
-        if !meta.is_null() {
-            if let Ok(map) =
-                serde_json::from_value::<HashMap<String, serde_json::Value>>(meta.clone())
-            {
-                req = req.with_metadata(map);
+        apply_clearable!(
+            obj,
+            req,
+            "maxMachines",
+            with_max_machines,
+            clear_max_machines
+        );
+        apply_clearable!(
+            obj,
+            req,
+            "maxProcesses",
+            with_max_processes,
+            clear_max_processes
+        );
+        apply_clearable!(obj, req, "maxUsers", with_max_users, clear_max_users);
+        apply_clearable!(obj, req, "maxCores", with_max_cores, clear_max_cores);
+        apply_clearable!(obj, req, "maxUses", with_max_uses, clear_max_uses);
+
+        if let Some(serde_json::Value::Bool(protected)) = obj.get("protected") {
+            req = req.with_protected(*protected);
+        }
+        if let Some(serde_json::Value::Bool(suspended)) = obj.get("suspended") {
+            req = req.with_suspended(*suspended);
+        }
+        if let Some(serde_json::Value::Array(perms)) = obj.get("permissions") {
+            let permissions: Vec<String> = perms
+                .iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect();
+            req = req.with_permissions(permissions);
+        }
+        if let Some(meta) = obj.get("metadata") {
+            if !meta.is_null() {
+                if let Ok(map) =
+                    serde_json::from_value::<HashMap<String, serde_json::Value>>(meta.clone())
+                {
+                    req = req.with_metadata(map);
+                }
             }
         }
-    }
 
-    lic.update(req)
-        .await
-        .map(License::from)
-        .map_err(to_napi_error)
+        lic.update(req)
+            .await
+            .map(License::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn delete_license(id: String) -> Result<()> {
-    let lic = make_license(id);
-    lic.delete().await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn delete_license(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.delete().await.map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn suspend_license(id: String) -> Result<License> {
-    let lic = make_license(id);
-    lic.suspend()
-        .await
-        .map(License::from)
-        .map_err(to_napi_error)
+pub fn suspend_license(env: &Env, id: String) -> Result<PromiseRaw<'_, License>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.suspend()
+            .await
+            .map(License::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn reinstate_license(id: String) -> Result<License> {
-    let lic = make_license(id);
-    lic.reinstate()
-        .await
-        .map(License::from)
-        .map_err(to_napi_error)
+pub fn reinstate_license(env: &Env, id: String) -> Result<PromiseRaw<'_, License>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.reinstate()
+            .await
+            .map(License::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn renew_license(id: String) -> Result<License> {
-    let lic = make_license(id);
-    lic.renew().await.map(License::from).map_err(to_napi_error)
+pub fn renew_license(env: &Env, id: String) -> Result<PromiseRaw<'_, License>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.renew()
+            .await
+            .map(License::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn revoke_license(id: String) -> Result<()> {
-    let lic = make_license(id);
-    lic.revoke().await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn revoke_license(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.revoke().await.map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn increment_license_usage(id: String) -> Result<License> {
-    let lic = make_license(id);
-    lic.increment_usage()
-        .await
-        .map(License::from)
-        .map_err(to_napi_error)
+pub fn increment_license_usage(env: &Env, id: String) -> Result<PromiseRaw<'_, License>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.increment_usage()
+            .await
+            .map(License::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn decrement_license_usage(id: String) -> Result<License> {
-    let lic = make_license(id);
-    lic.decrement_usage()
-        .await
-        .map(License::from)
-        .map_err(to_napi_error)
+pub fn decrement_license_usage(env: &Env, id: String) -> Result<PromiseRaw<'_, License>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.decrement_usage()
+            .await
+            .map(License::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn reset_license_usage(id: String) -> Result<License> {
-    let lic = make_license(id);
-    lic.reset_usage()
-        .await
-        .map(License::from)
-        .map_err(to_napi_error)
+pub fn reset_license_usage(env: &Env, id: String) -> Result<PromiseRaw<'_, License>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.reset_usage()
+            .await
+            .map(License::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn checkout_license(
+pub fn checkout_license(
+    env: &Env,
     id: String,
     opts: Option<LicenseCheckoutOpts>,
-) -> Result<LicenseFile> {
-    let lic = make_license(id);
-    let checkout_opts = keygen_rs::license::LicenseCheckoutOpts {
-        ttl: opts.as_ref().and_then(|o| o.ttl),
-        include: opts.and_then(|o| o.include),
-    };
-
-    lic.checkout(&checkout_opts)
-        .await
-        .map(LicenseFile::from)
-        .map_err(to_napi_error)
-}
+) -> Result<PromiseRaw<'_, LicenseFile>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        let checkout_opts = keygen_rs::license::LicenseCheckoutOpts {
+            ttl: opts.as_ref().and_then(|o| o.ttl),
+            include: opts.and_then(|o| o.include),
+        };
 
-#[napi]
-pub async fn attach_license_entitlements(id: String, entitlement_ids: Vec<String>) -> Result<()> {
-    let lic = make_license(id);
-    lic.attach_entitlements(&entitlement_ids)
-        .await
-        .map_err(to_napi_error)
+        lic.checkout(&checkout_opts)
+            .await
+            .map(LicenseFile::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn detach_license_entitlements(id: String, entitlement_ids: Vec<String>) -> Result<()> {
-    let lic = make_license(id);
-    lic.detach_entitlements(&entitlement_ids)
-        .await
-        .map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn attach_license_entitlements(
+    env: &Env,
+    id: String,
+    entitlement_ids: Vec<String>,
+) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.attach_entitlements(&entitlement_ids)
+            .await
+            .map_err(BindingError::from)
+    })
+}
+
+#[napi(ts_return_type = "Promise<void>")]
+pub fn detach_license_entitlements(
+    env: &Env,
+    id: String,
+    entitlement_ids: Vec<String>,
+) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.detach_entitlements(&entitlement_ids)
+            .await
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn check_in_license(id: String) -> Result<License> {
-    let lic = make_license(id);
-    lic.check_in()
-        .await
-        .map(License::from)
-        .map_err(to_napi_error)
+pub fn check_in_license(env: &Env, id: String) -> Result<PromiseRaw<'_, License>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.check_in()
+            .await
+            .map(License::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn generate_license_token(
+pub fn generate_license_token(
+    env: &Env,
     id: String,
     request: Option<CreateTokenRequest>,
-) -> Result<Token> {
-    let lic = make_license(id);
-    let req = request
-        .map(|request| -> Result<keygen_rs::token::CreateTokenRequest> {
-            Ok(keygen_rs::token::CreateTokenRequest {
-                name: request.name,
-                expiry: request.expiry,
-                permissions: request.permissions,
-                metadata: request.metadata.map(crate::to_metadata).transpose()?,
+) -> Result<PromiseRaw<'_, Token>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        let req = request
+            .map(|request| -> Result<keygen_rs::token::CreateTokenRequest> {
+                Ok(keygen_rs::token::CreateTokenRequest {
+                    name: request.name,
+                    expiry: request.expiry,
+                    permissions: request.permissions,
+                    metadata: request.metadata.map(crate::to_metadata).transpose()?,
+                })
             })
-        })
-        .transpose()?;
-    lic.generate_token(req)
-        .await
-        .map(Token::from)
-        .map_err(to_napi_error)
+            .transpose()?;
+        lic.generate_token(req)
+            .await
+            .map(Token::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn attach_license_users(id: String, user_ids: Vec<String>) -> Result<()> {
-    let lic = make_license(id);
-    lic.attach_users(&user_ids).await.map_err(to_napi_error)
-}
-
-#[napi]
-pub async fn detach_license_users(id: String, user_ids: Vec<String>) -> Result<()> {
-    let lic = make_license(id);
-    lic.detach_users(&user_ids).await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn attach_license_users(
+    env: &Env,
+    id: String,
+    user_ids: Vec<String>,
+) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.attach_users(&user_ids)
+            .await
+            .map_err(BindingError::from)
+    })
+}
+
+#[napi(ts_return_type = "Promise<void>")]
+pub fn detach_license_users(
+    env: &Env,
+    id: String,
+    user_ids: Vec<String>,
+) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.detach_users(&user_ids)
+            .await
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_license_users(id: String) -> Result<Vec<User>> {
-    let lic = make_license(id);
-    lic.users(None)
-        .await
-        .map(|users| users.into_iter().map(User::from).collect())
-        .map_err(to_napi_error)
+pub fn list_license_users(env: &Env, id: String) -> Result<PromiseRaw<'_, Vec<User>>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.users(None)
+            .await
+            .map(|users| users.into_iter().map(User::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn change_license_policy(id: String, policy_id: String) -> Result<License> {
-    let lic = make_license(id);
-    lic.change_policy(&policy_id)
-        .await
-        .map(License::from)
-        .map_err(to_napi_error)
+pub fn change_license_policy(
+    env: &Env,
+    id: String,
+    policy_id: String,
+) -> Result<PromiseRaw<'_, License>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.change_policy(&policy_id)
+            .await
+            .map(License::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn change_license_owner(id: String, owner_id: String) -> Result<License> {
-    let lic = make_license(id);
-    lic.change_owner(&owner_id)
-        .await
-        .map(License::from)
-        .map_err(to_napi_error)
+pub fn change_license_owner(
+    env: &Env,
+    id: String,
+    owner_id: String,
+) -> Result<PromiseRaw<'_, License>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.change_owner(&owner_id)
+            .await
+            .map(License::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn change_license_group(id: String, group_id: String) -> Result<License> {
-    let lic = make_license(id);
-    lic.change_group(&group_id)
-        .await
-        .map(License::from)
-        .map_err(to_napi_error)
+pub fn change_license_group(
+    env: &Env,
+    id: String,
+    group_id: String,
+) -> Result<PromiseRaw<'_, License>> {
+    spawn(env, async move {
+        let lic = make_license(id);
+        lic.change_group(&group_id)
+            .await
+            .map(License::from)
+            .map_err(BindingError::from)
+    })
 }
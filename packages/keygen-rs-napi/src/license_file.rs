@@ -35,15 +35,26 @@ pub struct LicenseFileDataset {
     pub ttl: i32,
 }
 
+impl From<keygen_rs::license_file::LicenseFileDataset> for LicenseFileDataset {
+    fn from(ds: keygen_rs::license_file::LicenseFileDataset) -> Self {
+        LicenseFileDataset {
+            license: License::from(ds.license),
+            issued: ds.issued.to_rfc3339(),
+            expiry: ds.expiry.to_rfc3339(),
+            ttl: ds.ttl,
+        }
+    }
+}
+
 #[napi]
-pub fn license_file_from_cert(key: String, content: String) -> Result<LicenseFile> {
+pub fn license_file_from_cert(env: &Env, key: String, content: String) -> Result<LicenseFile> {
     keygen_rs::license_file::LicenseFile::from_cert(&key, &content)
         .map(LicenseFile::from)
-        .map_err(to_napi_error)
+        .map_err(|e| to_napi_error(env, e))
 }
 
 #[napi]
-pub fn verify_license_file(certificate: String) -> Result<()> {
+pub fn verify_license_file(env: &Env, certificate: String) -> Result<()> {
     let lf = keygen_rs::license_file::LicenseFile {
         id: String::new(),
         certificate,
@@ -51,11 +62,15 @@ pub fn verify_license_file(certificate: String) -> Result<()> {
         expiry: chrono::Utc::now(),
         ttl: 0,
     };
-    lf.verify().map_err(to_napi_error)
+    lf.verify().map_err(|e| to_napi_error(env, e))
 }
 
 #[napi]
-pub fn decrypt_license_file(certificate: String, key: String) -> Result<LicenseFileDataset> {
+pub fn decrypt_license_file(
+    env: &Env,
+    certificate: String,
+    key: String,
+) -> Result<LicenseFileDataset> {
     let lf = keygen_rs::license_file::LicenseFile {
         id: String::new(),
         certificate,
@@ -64,11 +79,6 @@ pub fn decrypt_license_file(certificate: String, key: String) -> Result<LicenseF
         ttl: 0,
     };
     lf.decrypt(&key)
-        .map(|ds| LicenseFileDataset {
-            license: License::from(ds.license),
-            issued: ds.issued.to_rfc3339(),
-            expiry: ds.expiry.to_rfc3339(),
-            ttl: ds.ttl,
-        })
-        .map_err(to_napi_error)
+        .map(LicenseFileDataset::from)
+        .map_err(|e| to_napi_error(env, e))
 }
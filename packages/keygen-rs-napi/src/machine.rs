@@ -4,7 +4,7 @@ use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
 use crate::machine_file::MachineFile;
-use crate::to_napi_error;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -136,159 +136,195 @@ pub struct ListMachinesOptions {
 }
 
 #[napi]
-pub async fn create_machine(request: MachineCreateRequest) -> Result<Machine> {
-    let mut metadata_map: Option<HashMap<String, serde_json::Value>> = None;
-    if let Some(meta) = request.metadata {
-        if let Ok(map) = serde_json::from_value::<HashMap<String, serde_json::Value>>(meta) {
-            metadata_map = Some(map);
+pub fn create_machine(env: &Env, request: MachineCreateRequest) -> Result<PromiseRaw<'_, Machine>> {
+    spawn(env, async move {
+        let mut metadata_map: Option<HashMap<String, serde_json::Value>> = None;
+        if let Some(meta) = request.metadata {
+            if let Ok(map) = serde_json::from_value::<HashMap<String, serde_json::Value>>(meta) {
+                metadata_map = Some(map);
+            }
         }
-    }
 
-    let req = keygen_rs::machine::MachineCreateRequest {
-        fingerprint: request.fingerprint,
-        license_id: request.license_id,
-        name: request.name,
-        platform: request.platform,
-        hostname: request.hostname,
-        ip: request.ip,
-        cores: request.cores,
-        metadata: metadata_map,
-    };
+        let req = keygen_rs::machine::MachineCreateRequest {
+            fingerprint: request.fingerprint,
+            license_id: request.license_id,
+            name: request.name,
+            platform: request.platform,
+            hostname: request.hostname,
+            ip: request.ip,
+            cores: request.cores,
+            metadata: metadata_map,
+        };
 
-    keygen_rs::machine::Machine::create(req)
-        .await
-        .map(Machine::from)
-        .map_err(to_napi_error)
+        keygen_rs::machine::Machine::create(req)
+            .await
+            .map(Machine::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_machines(options: Option<ListMachinesOptions>) -> Result<Vec<Machine>> {
-    let filters = options.map(|o| {
-        let metadata_map = o.metadata.and_then(|meta| {
-            serde_json::from_value::<HashMap<String, serde_json::Value>>(meta).ok()
-        });
+pub fn list_machines(
+    env: &Env,
+    options: Option<ListMachinesOptions>,
+) -> Result<PromiseRaw<'_, Vec<Machine>>> {
+    spawn(env, async move {
+        let filters = options.map(|o| {
+            let metadata_map = o.metadata.and_then(|meta| {
+                serde_json::from_value::<HashMap<String, serde_json::Value>>(meta).ok()
+            });
 
-        keygen_rs::machine::MachineListFilters {
-            license: o.license,
-            user: o.user,
-            platform: o.platform,
-            name: o.name,
-            fingerprint: o.fingerprint,
-            ip: o.ip,
-            hostname: o.hostname,
-            product: o.product,
-            owner: o.owner,
-            group: o.group,
-            policy: o.policy,
-            key: o.key,
-            metadata: metadata_map,
-            page_number: o.page_number,
-            page_size: o.page_size,
-            limit: o.limit,
-        }
-    });
+            keygen_rs::machine::MachineListFilters {
+                license: o.license,
+                user: o.user,
+                platform: o.platform,
+                name: o.name,
+                fingerprint: o.fingerprint,
+                ip: o.ip,
+                hostname: o.hostname,
+                product: o.product,
+                owner: o.owner,
+                group: o.group,
+                policy: o.policy,
+                key: o.key,
+                metadata: metadata_map,
+                page_number: o.page_number,
+                page_size: o.page_size,
+                limit: o.limit,
+            }
+        });
 
-    keygen_rs::machine::Machine::list(filters)
-        .await
-        .map(|machines| machines.into_iter().map(Machine::from).collect())
-        .map_err(to_napi_error)
+        keygen_rs::machine::Machine::list(filters)
+            .await
+            .map(|machines| machines.into_iter().map(Machine::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_machine(id: String) -> Result<Machine> {
-    keygen_rs::machine::Machine::get(&id)
-        .await
-        .map(Machine::from)
-        .map_err(to_napi_error)
+pub fn get_machine(env: &Env, id: String) -> Result<PromiseRaw<'_, Machine>> {
+    spawn(env, async move {
+        keygen_rs::machine::Machine::get(&id)
+            .await
+            .map(Machine::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn update_machine(id: String, request: MachineUpdateRequest) -> Result<Machine> {
-    let machine = make_machine(id);
+pub fn update_machine(
+    env: &Env,
+    id: String,
+    request: MachineUpdateRequest,
+) -> Result<PromiseRaw<'_, Machine>> {
+    spawn(env, async move {
+        let machine = make_machine(id);
 
-    let mut metadata_map: Option<HashMap<String, serde_json::Value>> = None;
-    if let Some(meta) = request.metadata {
-        if let Ok(map) = serde_json::from_value::<HashMap<String, serde_json::Value>>(meta) {
-            metadata_map = Some(map);
+        let mut metadata_map: Option<HashMap<String, serde_json::Value>> = None;
+        if let Some(meta) = request.metadata {
+            if let Ok(map) = serde_json::from_value::<HashMap<String, serde_json::Value>>(meta) {
+                metadata_map = Some(map);
+            }
         }
-    }
 
-    let req = keygen_rs::machine::MachineUpdateRequest {
-        name: request.name,
-        platform: request.platform,
-        hostname: request.hostname,
-        ip: request.ip,
-        cores: request.cores,
-        metadata: metadata_map,
-    };
+        let req = keygen_rs::machine::MachineUpdateRequest {
+            name: request.name,
+            platform: request.platform,
+            hostname: request.hostname,
+            ip: request.ip,
+            cores: request.cores,
+            metadata: metadata_map,
+        };
 
-    machine
-        .update(req)
-        .await
-        .map(Machine::from)
-        .map_err(to_napi_error)
+        machine
+            .update(req)
+            .await
+            .map(Machine::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn deactivate_machine(id: String) -> Result<()> {
-    let machine = make_machine(id);
-    machine.deactivate().await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn deactivate_machine(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let machine = make_machine(id);
+        machine.deactivate().await.map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn checkout_machine(
+pub fn checkout_machine(
+    env: &Env,
     id: String,
     opts: Option<MachineCheckoutOpts>,
-) -> Result<MachineFile> {
-    let machine = make_machine(id);
-    let checkout_opts = keygen_rs::machine::MachineCheckoutOpts {
-        ttl: opts.as_ref().and_then(|o| o.ttl),
-        include: opts.and_then(|o| o.include),
-    };
+) -> Result<PromiseRaw<'_, MachineFile>> {
+    spawn(env, async move {
+        let machine = make_machine(id);
+        let checkout_opts = keygen_rs::machine::MachineCheckoutOpts {
+            ttl: opts.as_ref().and_then(|o| o.ttl),
+            include: opts.and_then(|o| o.include),
+        };
 
-    machine
-        .checkout(&checkout_opts)
-        .await
-        .map(MachineFile::from)
-        .map_err(to_napi_error)
+        machine
+            .checkout(&checkout_opts)
+            .await
+            .map(MachineFile::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn ping_machine(id: String) -> Result<Machine> {
-    let machine = make_machine(id);
-    machine
-        .ping()
-        .await
-        .map(Machine::from)
-        .map_err(to_napi_error)
+pub fn ping_machine(env: &Env, id: String) -> Result<PromiseRaw<'_, Machine>> {
+    spawn(env, async move {
+        let machine = make_machine(id);
+        machine
+            .ping()
+            .await
+            .map(Machine::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn reset_machine(id: String) -> Result<Machine> {
-    let machine = make_machine(id);
-    machine
-        .reset()
-        .await
-        .map(Machine::from)
-        .map_err(to_napi_error)
+pub fn reset_machine(env: &Env, id: String) -> Result<PromiseRaw<'_, Machine>> {
+    spawn(env, async move {
+        let machine = make_machine(id);
+        machine
+            .reset()
+            .await
+            .map(Machine::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn change_machine_owner(id: String, owner_id: String) -> Result<Machine> {
-    let machine = make_machine(id);
-    machine
-        .change_owner(&owner_id)
-        .await
-        .map(Machine::from)
-        .map_err(to_napi_error)
+pub fn change_machine_owner(
+    env: &Env,
+    id: String,
+    owner_id: String,
+) -> Result<PromiseRaw<'_, Machine>> {
+    spawn(env, async move {
+        let machine = make_machine(id);
+        machine
+            .change_owner(&owner_id)
+            .await
+            .map(Machine::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn change_machine_group(id: String, group_id: String) -> Result<Machine> {
-    let machine = make_machine(id);
-    machine
-        .change_group(&group_id)
-        .await
-        .map(Machine::from)
-        .map_err(to_napi_error)
+pub fn change_machine_group(
+    env: &Env,
+    id: String,
+    group_id: String,
+) -> Result<PromiseRaw<'_, Machine>> {
+    spawn(env, async move {
+        let machine = make_machine(id);
+        machine
+            .change_group(&group_id)
+            .await
+            .map(Machine::from)
+            .map_err(BindingError::from)
+    })
 }
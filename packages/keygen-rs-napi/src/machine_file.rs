@@ -37,15 +37,27 @@ pub struct MachineFileDataset {
     pub ttl: i32,
 }
 
+impl From<keygen_rs::machine_file::MachineFileDataset> for MachineFileDataset {
+    fn from(ds: keygen_rs::machine_file::MachineFileDataset) -> Self {
+        MachineFileDataset {
+            license: License::from(ds.license),
+            machine: Machine::from(ds.machine),
+            issued: ds.issued.to_rfc3339(),
+            expiry: ds.expiry.to_rfc3339(),
+            ttl: ds.ttl,
+        }
+    }
+}
+
 #[napi]
-pub fn machine_file_from_cert(key: String, content: String) -> Result<MachineFile> {
+pub fn machine_file_from_cert(env: &Env, key: String, content: String) -> Result<MachineFile> {
     keygen_rs::machine_file::MachineFile::from_cert(&key, &content)
         .map(MachineFile::from)
-        .map_err(to_napi_error)
+        .map_err(|e| to_napi_error(env, e))
 }
 
 #[napi]
-pub fn verify_machine_file(certificate: String) -> Result<()> {
+pub fn verify_machine_file(env: &Env, certificate: String) -> Result<()> {
     let mf = keygen_rs::machine_file::MachineFile {
         id: String::new(),
         certificate,
@@ -53,11 +65,15 @@ pub fn verify_machine_file(certificate: String) -> Result<()> {
         expiry: chrono::Utc::now(),
         ttl: 0,
     };
-    mf.verify().map_err(to_napi_error)
+    mf.verify().map_err(|e| to_napi_error(env, e))
 }
 
 #[napi]
-pub fn decrypt_machine_file(certificate: String, key: String) -> Result<MachineFileDataset> {
+pub fn decrypt_machine_file(
+    env: &Env,
+    certificate: String,
+    key: String,
+) -> Result<MachineFileDataset> {
     let mf = keygen_rs::machine_file::MachineFile {
         id: String::new(),
         certificate,
@@ -66,12 +82,6 @@ pub fn decrypt_machine_file(certificate: String, key: String) -> Result<MachineF
         ttl: 0,
     };
     mf.decrypt(&key)
-        .map(|ds| MachineFileDataset {
-            license: License::from(ds.license),
-            machine: Machine::from(ds.machine),
-            issued: ds.issued.to_rfc3339(),
-            expiry: ds.expiry.to_rfc3339(),
-            ttl: ds.ttl,
-        })
-        .map_err(to_napi_error)
+        .map(MachineFileDataset::from)
+        .map_err(|e| to_napi_error(env, e))
 }
@@ -2,7 +2,7 @@ use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
 use crate::entitlement::Entitlement;
-use crate::to_napi_error;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -240,239 +240,270 @@ use crate::parse_enum;
 use crate::to_metadata;
 
 #[napi]
-pub async fn create_policy(request: CreatePolicyRequest) -> Result<Policy> {
-    let req = keygen_rs::policy::CreatePolicyRequest {
-        product_id: request.product_id,
-        name: request.name,
-        duration: request.duration,
-        strict: request.strict,
-        floating: request.floating,
-        require_heartbeat: request.require_heartbeat,
-        heartbeat_duration: request.heartbeat_duration,
-        heartbeat_cull_strategy: request.heartbeat_cull_strategy,
-        heartbeat_resurrection_strategy: request.heartbeat_resurrection_strategy,
-        heartbeat_basis: request.heartbeat_basis,
-        machine_uniqueness_strategy: request
-            .machine_uniqueness_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "machine uniqueness strategy"))
-            .transpose()?,
-        component_uniqueness_strategy: request
-            .component_uniqueness_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "component uniqueness strategy"))
-            .transpose()?,
-        machine_matching_strategy: request
-            .machine_matching_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "machine matching strategy"))
-            .transpose()?,
-        component_matching_strategy: request
-            .component_matching_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "component matching strategy"))
-            .transpose()?,
-        expiration_strategy: request
-            .expiration_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "expiration strategy"))
-            .transpose()?,
-        expiration_basis: request.expiration_basis,
-        renewal_basis: request.renewal_basis,
-        authentication_strategy: request
-            .authentication_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "authentication strategy"))
-            .transpose()?,
-        machine_leasing_strategy: request
-            .machine_leasing_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "machine leasing strategy"))
-            .transpose()?,
-        process_leasing_strategy: request
-            .process_leasing_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "process leasing strategy"))
-            .transpose()?,
-        overage_strategy: request
-            .overage_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "overage strategy"))
-            .transpose()?,
-        transfer_strategy: request
-            .transfer_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "transfer strategy"))
-            .transpose()?,
-        max_machines: request.max_machines,
-        max_processes: request.max_processes,
-        max_cores: request.max_cores,
-        max_uses: request.max_uses,
-        encrypted: request.encrypted,
-        protected: request.protected,
-        require_check_in: request.require_check_in,
-        check_in_interval: request.check_in_interval,
-        check_in_interval_count: request.check_in_interval_count,
-        use_pool: request.use_pool,
-        max_licenses: request.max_licenses,
-        max_users: request.max_users,
-        scheme: request
-            .scheme
-            .as_deref()
-            .map(|s| parse_enum(s, "scheme"))
-            .transpose()?,
-        metadata: request.metadata.map(to_metadata).transpose()?,
-    };
+pub fn create_policy(env: &Env, request: CreatePolicyRequest) -> Result<PromiseRaw<'_, Policy>> {
+    spawn(env, async move {
+        let req = keygen_rs::policy::CreatePolicyRequest {
+            product_id: request.product_id,
+            name: request.name,
+            duration: request.duration,
+            strict: request.strict,
+            floating: request.floating,
+            require_heartbeat: request.require_heartbeat,
+            heartbeat_duration: request.heartbeat_duration,
+            heartbeat_cull_strategy: request.heartbeat_cull_strategy,
+            heartbeat_resurrection_strategy: request.heartbeat_resurrection_strategy,
+            heartbeat_basis: request.heartbeat_basis,
+            machine_uniqueness_strategy: request
+                .machine_uniqueness_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "machine uniqueness strategy"))
+                .transpose()?,
+            component_uniqueness_strategy: request
+                .component_uniqueness_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "component uniqueness strategy"))
+                .transpose()?,
+            machine_matching_strategy: request
+                .machine_matching_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "machine matching strategy"))
+                .transpose()?,
+            component_matching_strategy: request
+                .component_matching_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "component matching strategy"))
+                .transpose()?,
+            expiration_strategy: request
+                .expiration_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "expiration strategy"))
+                .transpose()?,
+            expiration_basis: request.expiration_basis,
+            renewal_basis: request.renewal_basis,
+            authentication_strategy: request
+                .authentication_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "authentication strategy"))
+                .transpose()?,
+            machine_leasing_strategy: request
+                .machine_leasing_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "machine leasing strategy"))
+                .transpose()?,
+            process_leasing_strategy: request
+                .process_leasing_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "process leasing strategy"))
+                .transpose()?,
+            overage_strategy: request
+                .overage_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "overage strategy"))
+                .transpose()?,
+            transfer_strategy: request
+                .transfer_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "transfer strategy"))
+                .transpose()?,
+            max_machines: request.max_machines,
+            max_processes: request.max_processes,
+            max_cores: request.max_cores,
+            max_uses: request.max_uses,
+            encrypted: request.encrypted,
+            protected: request.protected,
+            require_check_in: request.require_check_in,
+            check_in_interval: request.check_in_interval,
+            check_in_interval_count: request.check_in_interval_count,
+            use_pool: request.use_pool,
+            max_licenses: request.max_licenses,
+            max_users: request.max_users,
+            scheme: request
+                .scheme
+                .as_deref()
+                .map(|s| parse_enum(s, "scheme"))
+                .transpose()?,
+            metadata: request.metadata.map(to_metadata).transpose()?,
+        };
 
-    keygen_rs::policy::Policy::create(req)
-        .await
-        .map(Policy::from)
-        .map_err(to_napi_error)
+        keygen_rs::policy::Policy::create(req)
+            .await
+            .map(Policy::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_policies(options: Option<ListPoliciesOptions>) -> Result<Vec<Policy>> {
-    let opts = options.map(|o| keygen_rs::policy::ListPoliciesOptions {
-        limit: o.limit,
-        page_size: o.page_size,
-        page_number: o.page_number,
-        product: o.product,
-    });
+pub fn list_policies(
+    env: &Env,
+    options: Option<ListPoliciesOptions>,
+) -> Result<PromiseRaw<'_, Vec<Policy>>> {
+    spawn(env, async move {
+        let opts = options.map(|o| keygen_rs::policy::ListPoliciesOptions {
+            limit: o.limit,
+            page_size: o.page_size,
+            page_number: o.page_number,
+            product: o.product,
+        });
 
-    keygen_rs::policy::Policy::list(opts)
-        .await
-        .map(|ps| ps.into_iter().map(Policy::from).collect())
-        .map_err(to_napi_error)
+        keygen_rs::policy::Policy::list(opts)
+            .await
+            .map(|ps| ps.into_iter().map(Policy::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_policy(id: String) -> Result<Policy> {
-    keygen_rs::policy::Policy::get(&id)
-        .await
-        .map(Policy::from)
-        .map_err(to_napi_error)
+pub fn get_policy(env: &Env, id: String) -> Result<PromiseRaw<'_, Policy>> {
+    spawn(env, async move {
+        keygen_rs::policy::Policy::get(&id)
+            .await
+            .map(Policy::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn update_policy(id: String, request: UpdatePolicyRequest) -> Result<Policy> {
-    let policy = make_policy(id);
+pub fn update_policy(
+    env: &Env,
+    id: String,
+    request: UpdatePolicyRequest,
+) -> Result<PromiseRaw<'_, Policy>> {
+    spawn(env, async move {
+        let policy = make_policy(id);
 
-    let req = keygen_rs::policy::UpdatePolicyRequest {
-        name: request.name,
-        duration: request.duration,
-        strict: request.strict,
-        floating: request.floating,
-        require_heartbeat: request.require_heartbeat,
-        heartbeat_duration: request.heartbeat_duration,
-        heartbeat_cull_strategy: request.heartbeat_cull_strategy,
-        heartbeat_resurrection_strategy: request.heartbeat_resurrection_strategy,
-        heartbeat_basis: request.heartbeat_basis,
-        machine_uniqueness_strategy: request
-            .machine_uniqueness_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "machine uniqueness strategy"))
-            .transpose()?,
-        component_uniqueness_strategy: request
-            .component_uniqueness_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "component uniqueness strategy"))
-            .transpose()?,
-        machine_matching_strategy: request
-            .machine_matching_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "machine matching strategy"))
-            .transpose()?,
-        component_matching_strategy: request
-            .component_matching_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "component matching strategy"))
-            .transpose()?,
-        expiration_strategy: request
-            .expiration_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "expiration strategy"))
-            .transpose()?,
-        expiration_basis: request.expiration_basis,
-        renewal_basis: request.renewal_basis,
-        authentication_strategy: request
-            .authentication_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "authentication strategy"))
-            .transpose()?,
-        machine_leasing_strategy: request
-            .machine_leasing_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "machine leasing strategy"))
-            .transpose()?,
-        process_leasing_strategy: request
-            .process_leasing_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "process leasing strategy"))
-            .transpose()?,
-        overage_strategy: request
-            .overage_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "overage strategy"))
-            .transpose()?,
-        transfer_strategy: request
-            .transfer_strategy
-            .as_deref()
-            .map(|s| parse_enum(s, "transfer strategy"))
-            .transpose()?,
-        max_machines: request.max_machines,
-        max_processes: request.max_processes,
-        max_cores: request.max_cores,
-        max_uses: request.max_uses,
-        protected: request.protected,
-        require_check_in: request.require_check_in,
-        check_in_interval: request.check_in_interval,
-        check_in_interval_count: request.check_in_interval_count,
-        max_users: request.max_users,
-        scheme: request
-            .scheme
-            .as_deref()
-            .map(|s| parse_enum(s, "scheme"))
-            .transpose()?,
-        metadata: request.metadata.map(to_metadata).transpose()?,
-    };
+        let req = keygen_rs::policy::UpdatePolicyRequest {
+            name: request.name,
+            duration: request.duration,
+            strict: request.strict,
+            floating: request.floating,
+            require_heartbeat: request.require_heartbeat,
+            heartbeat_duration: request.heartbeat_duration,
+            heartbeat_cull_strategy: request.heartbeat_cull_strategy,
+            heartbeat_resurrection_strategy: request.heartbeat_resurrection_strategy,
+            heartbeat_basis: request.heartbeat_basis,
+            machine_uniqueness_strategy: request
+                .machine_uniqueness_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "machine uniqueness strategy"))
+                .transpose()?,
+            component_uniqueness_strategy: request
+                .component_uniqueness_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "component uniqueness strategy"))
+                .transpose()?,
+            machine_matching_strategy: request
+                .machine_matching_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "machine matching strategy"))
+                .transpose()?,
+            component_matching_strategy: request
+                .component_matching_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "component matching strategy"))
+                .transpose()?,
+            expiration_strategy: request
+                .expiration_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "expiration strategy"))
+                .transpose()?,
+            expiration_basis: request.expiration_basis,
+            renewal_basis: request.renewal_basis,
+            authentication_strategy: request
+                .authentication_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "authentication strategy"))
+                .transpose()?,
+            machine_leasing_strategy: request
+                .machine_leasing_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "machine leasing strategy"))
+                .transpose()?,
+            process_leasing_strategy: request
+                .process_leasing_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "process leasing strategy"))
+                .transpose()?,
+            overage_strategy: request
+                .overage_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "overage strategy"))
+                .transpose()?,
+            transfer_strategy: request
+                .transfer_strategy
+                .as_deref()
+                .map(|s| parse_enum(s, "transfer strategy"))
+                .transpose()?,
+            max_machines: request.max_machines,
+            max_processes: request.max_processes,
+            max_cores: request.max_cores,
+            max_uses: request.max_uses,
+            protected: request.protected,
+            require_check_in: request.require_check_in,
+            check_in_interval: request.check_in_interval,
+            check_in_interval_count: request.check_in_interval_count,
+            max_users: request.max_users,
+            scheme: request
+                .scheme
+                .as_deref()
+                .map(|s| parse_enum(s, "scheme"))
+                .transpose()?,
+            metadata: request.metadata.map(to_metadata).transpose()?,
+        };
 
-    policy
-        .update(req)
-        .await
-        .map(Policy::from)
-        .map_err(to_napi_error)
+        policy
+            .update(req)
+            .await
+            .map(Policy::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn delete_policy(id: String) -> Result<()> {
-    let policy = make_policy(id);
-    policy.delete().await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn delete_policy(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let policy = make_policy(id);
+        policy.delete().await.map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn attach_policy_entitlements(id: String, entitlement_ids: Vec<String>) -> Result<()> {
-    let policy = make_policy(id);
-    policy
-        .attach_entitlements(&entitlement_ids)
-        .await
-        .map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn attach_policy_entitlements(
+    env: &Env,
+    id: String,
+    entitlement_ids: Vec<String>,
+) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let policy = make_policy(id);
+        policy
+            .attach_entitlements(&entitlement_ids)
+            .await
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn detach_policy_entitlements(id: String, entitlement_ids: Vec<String>) -> Result<()> {
-    let policy = make_policy(id);
-    policy
-        .detach_entitlements(&entitlement_ids)
-        .await
-        .map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn detach_policy_entitlements(
+    env: &Env,
+    id: String,
+    entitlement_ids: Vec<String>,
+) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let policy = make_policy(id);
+        policy
+            .detach_entitlements(&entitlement_ids)
+            .await
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_policy_entitlements(id: String) -> Result<Vec<Entitlement>> {
-    let policy = make_policy(id);
-    policy
-        .entitlements(None)
-        .await
-        .map(|items| items.into_iter().map(Entitlement::from).collect())
-        .map_err(to_napi_error)
+pub fn list_policy_entitlements(env: &Env, id: String) -> Result<PromiseRaw<'_, Vec<Entitlement>>> {
+    spawn(env, async move {
+        let policy = make_policy(id);
+        policy
+            .entitlements(None)
+            .await
+            .map(|items| items.into_iter().map(Entitlement::from).collect())
+            .map_err(BindingError::from)
+    })
 }
@@ -1,8 +1,8 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
-use crate::to_napi_error;
 use crate::token_module::Token;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -131,100 +131,120 @@ fn parse_platforms(ps: Vec<String>) -> Result<Vec<keygen_rs::product::Platform>>
 use crate::to_metadata;
 
 #[napi]
-pub async fn create_product(request: CreateProductRequest) -> Result<Product> {
-    let req = keygen_rs::product::CreateProductRequest {
-        name: request.name,
-        code: request.code,
-        distribution_strategy: request
-            .distribution_strategy
-            .as_deref()
-            .map(parse_distribution_strategy)
-            .transpose()?,
-        url: request.url,
-        platforms: request.platforms.map(parse_platforms).transpose()?,
-        permissions: request.permissions,
-        metadata: request.metadata.map(to_metadata).transpose()?,
-    };
-
-    keygen_rs::product::Product::create(req)
-        .await
-        .map(Product::from)
-        .map_err(to_napi_error)
+pub fn create_product(env: &Env, request: CreateProductRequest) -> Result<PromiseRaw<'_, Product>> {
+    spawn(env, async move {
+        let req = keygen_rs::product::CreateProductRequest {
+            name: request.name,
+            code: request.code,
+            distribution_strategy: request
+                .distribution_strategy
+                .as_deref()
+                .map(parse_distribution_strategy)
+                .transpose()?,
+            url: request.url,
+            platforms: request.platforms.map(parse_platforms).transpose()?,
+            permissions: request.permissions,
+            metadata: request.metadata.map(to_metadata).transpose()?,
+        };
+
+        keygen_rs::product::Product::create(req)
+            .await
+            .map(Product::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_products(options: Option<ListProductsOptions>) -> Result<Vec<Product>> {
-    let opts = options.map(|o| keygen_rs::product::ListProductsOptions {
-        limit: o.limit,
-        page_size: o.page_size,
-        page_number: o.page_number,
-    });
-
-    keygen_rs::product::Product::list(opts)
-        .await
-        .map(|ps| ps.into_iter().map(Product::from).collect())
-        .map_err(to_napi_error)
+pub fn list_products(
+    env: &Env,
+    options: Option<ListProductsOptions>,
+) -> Result<PromiseRaw<'_, Vec<Product>>> {
+    spawn(env, async move {
+        let opts = options.map(|o| keygen_rs::product::ListProductsOptions {
+            limit: o.limit,
+            page_size: o.page_size,
+            page_number: o.page_number,
+        });
+
+        keygen_rs::product::Product::list(opts)
+            .await
+            .map(|ps| ps.into_iter().map(Product::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_product(id: String) -> Result<Product> {
-    keygen_rs::product::Product::get(&id)
-        .await
-        .map(Product::from)
-        .map_err(to_napi_error)
+pub fn get_product(env: &Env, id: String) -> Result<PromiseRaw<'_, Product>> {
+    spawn(env, async move {
+        keygen_rs::product::Product::get(&id)
+            .await
+            .map(Product::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn update_product(id: String, request: UpdateProductRequest) -> Result<Product> {
-    let product = make_product(id);
-
-    let req = keygen_rs::product::UpdateProductRequest {
-        name: request.name,
-        code: request.code,
-        distribution_strategy: request
-            .distribution_strategy
-            .as_deref()
-            .map(parse_distribution_strategy)
-            .transpose()?,
-        url: request.url,
-        platforms: request.platforms.map(parse_platforms).transpose()?,
-        permissions: request.permissions,
-        metadata: request.metadata.map(to_metadata).transpose()?,
-    };
-
-    product
-        .update(req)
-        .await
-        .map(Product::from)
-        .map_err(to_napi_error)
+pub fn update_product(
+    env: &Env,
+    id: String,
+    request: UpdateProductRequest,
+) -> Result<PromiseRaw<'_, Product>> {
+    spawn(env, async move {
+        let product = make_product(id);
+
+        let req = keygen_rs::product::UpdateProductRequest {
+            name: request.name,
+            code: request.code,
+            distribution_strategy: request
+                .distribution_strategy
+                .as_deref()
+                .map(parse_distribution_strategy)
+                .transpose()?,
+            url: request.url,
+            platforms: request.platforms.map(parse_platforms).transpose()?,
+            permissions: request.permissions,
+            metadata: request.metadata.map(to_metadata).transpose()?,
+        };
+
+        product
+            .update(req)
+            .await
+            .map(Product::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn delete_product(id: String) -> Result<()> {
-    let product = make_product(id);
-    product.delete().await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn delete_product(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let product = make_product(id);
+        product.delete().await.map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn generate_product_token(
+pub fn generate_product_token(
+    env: &Env,
     id: String,
     request: Option<CreateTokenRequest>,
-) -> Result<Token> {
-    let product = make_product(id);
-    let req = request
-        .map(|request| -> Result<keygen_rs::token::CreateTokenRequest> {
-            Ok(keygen_rs::token::CreateTokenRequest {
-                name: request.name,
-                expiry: request.expiry,
-                permissions: request.permissions,
-                metadata: request.metadata.map(to_metadata).transpose()?,
+) -> Result<PromiseRaw<'_, Token>> {
+    spawn(env, async move {
+        let product = make_product(id);
+        let req = request
+            .map(|request| -> Result<keygen_rs::token::CreateTokenRequest> {
+                Ok(keygen_rs::token::CreateTokenRequest {
+                    name: request.name,
+                    expiry: request.expiry,
+                    permissions: request.permissions,
+                    metadata: request.metadata.map(to_metadata).transpose()?,
+                })
             })
-        })
-        .transpose()?;
+            .transpose()?;
 
-    product
-        .generate_token_with_options(req)
-        .await
-        .map(Token::from)
-        .map_err(to_napi_error)
+        product
+            .generate_token_with_options(req)
+            .await
+            .map(Token::from)
+            .map_err(BindingError::from)
+    })
 }
@@ -1,7 +1,7 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
-use crate::to_napi_error;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -110,70 +110,79 @@ fn parse_channel(s: &str) -> std::result::Result<keygen_rs::release::ReleaseChan
 }
 
 #[napi]
-pub async fn create_release(request: CreateReleaseRequest) -> Result<Release> {
-    let channel = match &request.channel {
-        Some(c) => parse_channel(c)?,
-        None => keygen_rs::release::ReleaseChannel::Stable,
-    };
-    let req = keygen_rs::release::CreateReleaseRequest {
-        version: request.version,
-        channel,
-        product_id: request.product_id,
-        name: request.name,
-        description: request.description,
-        status: request
-            .status
-            .as_deref()
-            .map(|s| crate::parse_enum(s, "release status"))
-            .transpose()?,
-        tag: request.tag,
-        metadata: crate::opt_metadata(request.metadata)?,
-    };
-    keygen_rs::release::Release::create(req)
-        .await
-        .map(Release::from)
-        .map_err(to_napi_error)
+pub fn create_release(env: &Env, request: CreateReleaseRequest) -> Result<PromiseRaw<'_, Release>> {
+    spawn(env, async move {
+        let channel = match &request.channel {
+            Some(c) => parse_channel(c)?,
+            None => keygen_rs::release::ReleaseChannel::Stable,
+        };
+        let req = keygen_rs::release::CreateReleaseRequest {
+            version: request.version,
+            channel,
+            product_id: request.product_id,
+            name: request.name,
+            description: request.description,
+            status: request
+                .status
+                .as_deref()
+                .map(|s| crate::parse_enum(s, "release status"))
+                .transpose()?,
+            tag: request.tag,
+            metadata: crate::opt_metadata(request.metadata)?,
+        };
+        keygen_rs::release::Release::create(req)
+            .await
+            .map(Release::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_releases(options: Option<ListReleasesOptions>) -> Result<Vec<Release>> {
-    let opts = match options {
-        Some(o) => {
-            let channel = match o.channel {
-                Some(ref c) => Some(parse_channel(c)?),
-                None => None,
-            };
-            Some(keygen_rs::release::ListReleasesOptions {
-                limit: o.limit,
-                page_size: o.page_size,
-                page_number: o.page_number,
-                channel,
-                status: o
-                    .status
-                    .as_deref()
-                    .map(|s| crate::parse_enum(s, "release status"))
-                    .transpose()?,
-                version: None,
-                product: o.product,
-                package: o.package,
-                engine: o.engine,
-                entitlements: o.entitlements,
-            })
-        }
-        None => None,
-    };
-    keygen_rs::release::Release::list(opts)
-        .await
-        .map(|list| list.into_iter().map(Release::from).collect())
-        .map_err(to_napi_error)
+pub fn list_releases(
+    env: &Env,
+    options: Option<ListReleasesOptions>,
+) -> Result<PromiseRaw<'_, Vec<Release>>> {
+    spawn(env, async move {
+        let opts = match options {
+            Some(o) => {
+                let channel = match o.channel {
+                    Some(ref c) => Some(parse_channel(c)?),
+                    None => None,
+                };
+                Some(keygen_rs::release::ListReleasesOptions {
+                    limit: o.limit,
+                    page_size: o.page_size,
+                    page_number: o.page_number,
+                    channel,
+                    status: o
+                        .status
+                        .as_deref()
+                        .map(|s| crate::parse_enum(s, "release status"))
+                        .transpose()?,
+                    version: None,
+                    product: o.product,
+                    package: o.package,
+                    engine: o.engine,
+                    entitlements: o.entitlements,
+                })
+            }
+            None => None,
+        };
+        keygen_rs::release::Release::list(opts)
+            .await
+            .map(|list| list.into_iter().map(Release::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_release(id: String) -> Result<Release> {
-    keygen_rs::release::Release::get(&id)
-        .await
-        .map(Release::from)
-        .map_err(to_napi_error)
+pub fn get_release(env: &Env, id: String) -> Result<PromiseRaw<'_, Release>> {
+    spawn(env, async move {
+        keygen_rs::release::Release::get(&id)
+            .await
+            .map(Release::from)
+            .map_err(BindingError::from)
+    })
 }
 
 fn make_minimal_release(id: String) -> keygen_rs::release::Release {
@@ -197,92 +206,119 @@ fn make_minimal_release(id: String) -> keygen_rs::release::Release {
 }
 
 #[napi]
-pub async fn update_release(id: String, request: UpdateReleaseRequest) -> Result<Release> {
-    let rel = make_minimal_release(id);
-    let channel = match request.channel {
-        Some(ref c) => Some(parse_channel(c)?),
-        None => None,
-    };
-    let req = keygen_rs::release::UpdateReleaseRequest {
-        name: request.name,
-        description: request.description,
-        channel,
-        tag: request.tag,
-        metadata: crate::opt_metadata(request.metadata)?,
-    };
-    rel.update(req)
-        .await
-        .map(Release::from)
-        .map_err(to_napi_error)
+pub fn update_release(
+    env: &Env,
+    id: String,
+    request: UpdateReleaseRequest,
+) -> Result<PromiseRaw<'_, Release>> {
+    spawn(env, async move {
+        let rel = make_minimal_release(id);
+        let channel = match request.channel {
+            Some(ref c) => Some(parse_channel(c)?),
+            None => None,
+        };
+        let req = keygen_rs::release::UpdateReleaseRequest {
+            name: request.name,
+            description: request.description,
+            channel,
+            tag: request.tag,
+            metadata: crate::opt_metadata(request.metadata)?,
+        };
+        rel.update(req)
+            .await
+            .map(Release::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn delete_release(id: String) -> Result<()> {
-    let rel = make_minimal_release(id);
-    rel.delete().await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn delete_release(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let rel = make_minimal_release(id);
+        rel.delete().await.map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn publish_release(id: String) -> Result<Release> {
-    let rel = make_minimal_release(id);
-    rel.publish()
-        .await
-        .map(Release::from)
-        .map_err(to_napi_error)
+pub fn publish_release(env: &Env, id: String) -> Result<PromiseRaw<'_, Release>> {
+    spawn(env, async move {
+        let rel = make_minimal_release(id);
+        rel.publish()
+            .await
+            .map(Release::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn yank_release(id: String) -> Result<Release> {
-    let rel = make_minimal_release(id);
-    rel.yank().await.map(Release::from).map_err(to_napi_error)
+pub fn yank_release(env: &Env, id: String) -> Result<PromiseRaw<'_, Release>> {
+    spawn(env, async move {
+        let rel = make_minimal_release(id);
+        rel.yank()
+            .await
+            .map(Release::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn upgrade_release(
+pub fn upgrade_release(
+    env: &Env,
     id: String,
     request: Option<ReleaseUpgradeRequest>,
-) -> Result<Release> {
-    let rel = make_minimal_release(id);
-    let req = request
-        .map(
-            |request| -> Result<keygen_rs::release::ReleaseUpgradeRequest> {
-                Ok(keygen_rs::release::ReleaseUpgradeRequest {
-                    product: request.product,
-                    constraint: request.constraint,
-                    package: request.package,
-                    channel: request.channel.as_deref().map(parse_channel).transpose()?,
-                })
-            },
-        )
-        .transpose()?;
+) -> Result<PromiseRaw<'_, Release>> {
+    spawn(env, async move {
+        let rel = make_minimal_release(id);
+        let req = request
+            .map(
+                |request| -> Result<keygen_rs::release::ReleaseUpgradeRequest> {
+                    Ok(keygen_rs::release::ReleaseUpgradeRequest {
+                        product: request.product,
+                        constraint: request.constraint,
+                        package: request.package,
+                        channel: request.channel.as_deref().map(parse_channel).transpose()?,
+                    })
+                },
+            )
+            .transpose()?;
 
-    rel.upgrade(req.as_ref())
-        .await
-        .map(Release::from)
-        .map_err(to_napi_error)
+        rel.upgrade(req.as_ref())
+            .await
+            .map(Release::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn download_release_artifact(
+pub fn download_release_artifact(
+    env: &Env,
     id: String,
     artifact: String,
-) -> Result<ReleaseArtifactDownload> {
-    let rel = make_minimal_release(id);
-    rel.download_artifact(&artifact)
-        .await
-        .map(|download| ReleaseArtifactDownload {
-            location: download.location,
-        })
-        .map_err(to_napi_error)
+) -> Result<PromiseRaw<'_, ReleaseArtifactDownload>> {
+    spawn(env, async move {
+        let rel = make_minimal_release(id);
+        rel.download_artifact(&artifact)
+            .await
+            .map(|download| ReleaseArtifactDownload {
+                location: download.location,
+            })
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn change_release_package(id: String, package_id: String) -> Result<Release> {
-    let rel = make_minimal_release(id);
-    rel.change_package(&package_id)
-        .await
-        .map(Release::from)
-        .map_err(to_napi_error)
+pub fn change_release_package(
+    env: &Env,
+    id: String,
+    package_id: String,
+) -> Result<PromiseRaw<'_, Release>> {
+    spawn(env, async move {
+        let rel = make_minimal_release(id);
+        rel.change_package(&package_id)
+            .await
+            .map(Release::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi(object)]
@@ -310,64 +346,78 @@ impl From<keygen_rs::release::Constraint> for Constraint {
 }
 
 #[napi]
-pub async fn release_artifacts(
+pub fn release_artifacts(
+    env: &Env,
     id: String,
     options: Option<crate::artifact::ListArtifactsOptions>,
-) -> Result<Vec<crate::artifact::Artifact>> {
-    let rel = make_minimal_release(id);
-    let opts = options.map(|o| keygen_rs::artifact::ListArtifactsOptions {
-        limit: o.limit,
-        page_size: o.page_size,
-        page_number: o.page_number,
-        release: o.release,
-        product: o.product,
-        channel: o.channel,
-        platform: o.platform,
-        arch: o.arch,
-        filetype: o.filetype,
-        status: o.status.and_then(|s| {
-            serde_json::from_value::<keygen_rs::artifact::ArtifactStatus>(
-                serde_json::Value::String(s),
-            )
-            .ok()
-        }),
-    });
-    rel.artifacts(opts)
-        .await
-        .map(|artifacts| {
-            artifacts
-                .into_iter()
-                .map(crate::artifact::Artifact::from)
-                .collect()
-        })
-        .map_err(to_napi_error)
+) -> Result<PromiseRaw<'_, Vec<crate::artifact::Artifact>>> {
+    spawn(env, async move {
+        let rel = make_minimal_release(id);
+        let opts = options.map(|o| keygen_rs::artifact::ListArtifactsOptions {
+            limit: o.limit,
+            page_size: o.page_size,
+            page_number: o.page_number,
+            release: o.release,
+            product: o.product,
+            channel: o.channel,
+            platform: o.platform,
+            arch: o.arch,
+            filetype: o.filetype,
+            status: o.status.and_then(|s| {
+                serde_json::from_value::<keygen_rs::artifact::ArtifactStatus>(
+                    serde_json::Value::String(s),
+                )
+                .ok()
+            }),
+        });
+        rel.artifacts(opts)
+            .await
+            .map(|artifacts| {
+                artifacts
+                    .into_iter()
+                    .map(crate::artifact::Artifact::from)
+                    .collect()
+            })
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn attach_release_constraints(
+pub fn attach_release_constraints(
+    env: &Env,
     id: String,
     entitlement_ids: Vec<String>,
-) -> Result<Vec<Constraint>> {
-    let rel = make_minimal_release(id);
-    rel.attach_constraints(&entitlement_ids)
-        .await
-        .map(|constraints| constraints.into_iter().map(Constraint::from).collect())
-        .map_err(to_napi_error)
+) -> Result<PromiseRaw<'_, Vec<Constraint>>> {
+    spawn(env, async move {
+        let rel = make_minimal_release(id);
+        rel.attach_constraints(&entitlement_ids)
+            .await
+            .map(|constraints| constraints.into_iter().map(Constraint::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn detach_release_constraints(id: String, constraint_ids: Vec<String>) -> Result<()> {
-    let rel = make_minimal_release(id);
-    rel.detach_constraints(&constraint_ids)
-        .await
-        .map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn detach_release_constraints(
+    env: &Env,
+    id: String,
+    constraint_ids: Vec<String>,
+) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let rel = make_minimal_release(id);
+        rel.detach_constraints(&constraint_ids)
+            .await
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn release_constraints(id: String) -> Result<Vec<Constraint>> {
-    let rel = make_minimal_release(id);
-    rel.constraints(None)
-        .await
-        .map(|constraints| constraints.into_iter().map(Constraint::from).collect())
-        .map_err(to_napi_error)
+pub fn release_constraints(env: &Env, id: String) -> Result<PromiseRaw<'_, Vec<Constraint>>> {
+    spawn(env, async move {
+        let rel = make_minimal_release(id);
+        rel.constraints(None)
+            .await
+            .map(|constraints| constraints.into_iter().map(Constraint::from).collect())
+            .map_err(BindingError::from)
+    })
 }
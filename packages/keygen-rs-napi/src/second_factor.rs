@@ -0,0 +1,142 @@
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::{spawn, BindingError};
+
+#[napi(object)]
+#[derive(Clone)]
+pub struct SecondFactor {
+    pub id: String,
+    pub uri: Option<String>,
+    pub enabled: bool,
+    pub created: String,
+    pub updated: String,
+    pub user_id: Option<String>,
+    pub account_id: Option<String>,
+}
+
+impl From<keygen_rs::second_factor::SecondFactor> for SecondFactor {
+    fn from(sf: keygen_rs::second_factor::SecondFactor) -> Self {
+        SecondFactor {
+            id: sf.id,
+            uri: sf.uri,
+            enabled: sf.enabled,
+            created: sf.created,
+            updated: sf.updated,
+            user_id: sf.user_id,
+            account_id: sf.account_id,
+        }
+    }
+}
+
+#[napi(object)]
+#[derive(Clone)]
+pub struct UpdateSecondFactorRequest {
+    pub enabled: bool,
+    pub otp: String,
+}
+
+fn make_second_factor(user_id: String, id: String) -> keygen_rs::second_factor::SecondFactor {
+    keygen_rs::second_factor::SecondFactor {
+        id,
+        uri: None,
+        enabled: false,
+        created: String::new(),
+        updated: String::new(),
+        user_id: Some(user_id),
+        account_id: None,
+    }
+}
+
+#[napi]
+pub fn create_second_factor(
+    env: &Env,
+    user_id: String,
+    password: String,
+) -> Result<PromiseRaw<'_, SecondFactor>> {
+    spawn(env, async move {
+        keygen_rs::second_factor::SecondFactor::create(&user_id, &password)
+            .await
+            .map(SecondFactor::from)
+            .map_err(BindingError::from)
+    })
+}
+
+#[napi]
+pub fn list_second_factors(
+    env: &Env,
+    user_id: String,
+) -> Result<PromiseRaw<'_, Vec<SecondFactor>>> {
+    spawn(env, async move {
+        keygen_rs::second_factor::SecondFactor::list(&user_id)
+            .await
+            .map(|factors| factors.into_iter().map(SecondFactor::from).collect())
+            .map_err(BindingError::from)
+    })
+}
+
+#[napi]
+pub fn get_second_factor(
+    env: &Env,
+    user_id: String,
+    id: String,
+) -> Result<PromiseRaw<'_, SecondFactor>> {
+    spawn(env, async move {
+        keygen_rs::second_factor::SecondFactor::get(&user_id, &id)
+            .await
+            .map(SecondFactor::from)
+            .map_err(BindingError::from)
+    })
+}
+
+#[napi]
+pub fn update_second_factor(
+    env: &Env,
+    user_id: String,
+    id: String,
+    request: UpdateSecondFactorRequest,
+) -> Result<PromiseRaw<'_, SecondFactor>> {
+    spawn(env, async move {
+        let req = keygen_rs::second_factor::UpdateSecondFactorRequest {
+            enabled: request.enabled,
+            otp: request.otp,
+        };
+
+        make_second_factor(user_id, id)
+            .update(req)
+            .await
+            .map(SecondFactor::from)
+            .map_err(BindingError::from)
+    })
+}
+
+#[napi]
+pub fn enable_second_factor(
+    env: &Env,
+    user_id: String,
+    id: String,
+    otp: String,
+) -> Result<PromiseRaw<'_, SecondFactor>> {
+    spawn(env, async move {
+        make_second_factor(user_id, id)
+            .enable(&otp)
+            .await
+            .map(SecondFactor::from)
+            .map_err(BindingError::from)
+    })
+}
+
+#[napi(ts_return_type = "Promise<void>")]
+pub fn delete_second_factor(
+    env: &Env,
+    user_id: String,
+    id: String,
+    otp: String,
+) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        make_second_factor(user_id, id)
+            .delete(&otp)
+            .await
+            .map_err(BindingError::from)
+    })
+}
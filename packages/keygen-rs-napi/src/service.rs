@@ -1,7 +1,7 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
-use crate::to_napi_error;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -21,33 +21,39 @@ pub struct PingResponse {
 }
 
 #[napi]
-pub async fn get_service_info() -> Result<ServiceInfo> {
-    keygen_rs::service::get_service_info()
-        .await
-        .map(|info| ServiceInfo {
-            timestamp: info.timestamp,
-            api_version: info.api_version,
-            message: info.message,
-            headers: serde_json::to_value(info.headers).unwrap_or_default(),
-        })
-        .map_err(to_napi_error)
+pub fn get_service_info(env: &Env) -> Result<PromiseRaw<'_, ServiceInfo>> {
+    spawn(env, async move {
+        keygen_rs::service::get_service_info()
+            .await
+            .map(|info| ServiceInfo {
+                timestamp: info.timestamp,
+                api_version: info.api_version,
+                message: info.message,
+                headers: serde_json::to_value(info.headers).unwrap_or_default(),
+            })
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn ping() -> Result<PingResponse> {
-    keygen_rs::service::ping()
-        .await
-        .map(|resp| PingResponse {
-            message: resp.message,
-            version: resp.version,
-            timestamp: resp.timestamp,
-        })
-        .map_err(to_napi_error)
+pub fn ping(env: &Env) -> Result<PromiseRaw<'_, PingResponse>> {
+    spawn(env, async move {
+        keygen_rs::service::ping()
+            .await
+            .map(|resp| PingResponse {
+                message: resp.message,
+                version: resp.version,
+                timestamp: resp.timestamp,
+            })
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn supports_product_code() -> Result<bool> {
-    keygen_rs::service::supports_product_code()
-        .await
-        .map_err(to_napi_error)
+pub fn supports_product_code(env: &Env) -> Result<PromiseRaw<'_, bool>> {
+    spawn(env, async move {
+        keygen_rs::service::supports_product_code()
+            .await
+            .map_err(BindingError::from)
+    })
 }
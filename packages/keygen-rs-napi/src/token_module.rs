@@ -1,7 +1,7 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
-use crate::to_napi_error;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -68,49 +68,91 @@ fn make_token(id: String) -> keygen_rs::token::Token {
 }
 
 #[napi]
-pub async fn list_tokens(options: Option<ListTokensOptions>) -> Result<Vec<Token>> {
-    let opts = options.map(|o| keygen_rs::token::ListTokensOptions {
-        limit: o.limit,
-        page_size: o.page_size,
-        page_number: o.page_number,
-        bearer_type: o.bearer_type,
-        bearer_id: o.bearer_id,
-    });
+pub fn list_tokens(
+    env: &Env,
+    options: Option<ListTokensOptions>,
+) -> Result<PromiseRaw<'_, Vec<Token>>> {
+    spawn(env, async move {
+        let opts = options.map(|o| keygen_rs::token::ListTokensOptions {
+            limit: o.limit,
+            page_size: o.page_size,
+            page_number: o.page_number,
+            bearer_type: o.bearer_type,
+            bearer_id: o.bearer_id,
+        });
 
-    keygen_rs::token::Token::list(opts)
-        .await
-        .map(|ts| ts.into_iter().map(Token::from).collect())
-        .map_err(to_napi_error)
+        keygen_rs::token::Token::list(opts)
+            .await
+            .map(|ts| ts.into_iter().map(Token::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_token(id: String) -> Result<Token> {
-    keygen_rs::token::Token::get(&id)
-        .await
-        .map(Token::from)
-        .map_err(to_napi_error)
+pub fn get_token(env: &Env, id: String) -> Result<PromiseRaw<'_, Token>> {
+    spawn(env, async move {
+        keygen_rs::token::Token::get(&id)
+            .await
+            .map(Token::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn regenerate_token(id: String, request: RegenerateTokenRequest) -> Result<Token> {
-    let token = make_token(id);
+pub fn regenerate_token(
+    env: &Env,
+    id: String,
+    request: RegenerateTokenRequest,
+) -> Result<PromiseRaw<'_, Token>> {
+    spawn(env, async move {
+        let token = make_token(id);
 
-    let req = keygen_rs::token::RegenerateTokenRequest {
-        name: request.name,
-        expiry: request.expiry,
-        permissions: request.permissions,
-        metadata: crate::opt_metadata(request.metadata)?,
-    };
+        let req = keygen_rs::token::RegenerateTokenRequest {
+            name: request.name,
+            expiry: request.expiry,
+            permissions: request.permissions,
+            metadata: crate::opt_metadata(request.metadata)?,
+        };
 
-    token
-        .regenerate(req)
-        .await
-        .map(Token::from)
-        .map_err(to_napi_error)
+        token
+            .regenerate(req)
+            .await
+            .map(Token::from)
+            .map_err(BindingError::from)
+    })
+}
+
+#[napi(ts_return_type = "Promise<void>")]
+pub fn revoke_token(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let token = make_token(id);
+        token.revoke().await.map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn revoke_token(id: String) -> Result<()> {
-    let token = make_token(id);
-    token.revoke().await.map_err(to_napi_error)
+pub fn generate_token(
+    env: &Env,
+    email: String,
+    password: String,
+    otp: Option<String>,
+    request: Option<crate::user::CreateTokenRequest>,
+) -> Result<PromiseRaw<'_, Token>> {
+    spawn(env, async move {
+        let req = request
+            .map(|request| -> Result<keygen_rs::token::CreateTokenRequest> {
+                Ok(keygen_rs::token::CreateTokenRequest {
+                    name: request.name,
+                    expiry: request.expiry,
+                    permissions: request.permissions,
+                    metadata: crate::opt_metadata(request.metadata)?,
+                })
+            })
+            .transpose()?;
+
+        keygen_rs::token::Token::generate(&email, &password, otp.as_deref(), req)
+            .await
+            .map(Token::from)
+            .map_err(BindingError::from)
+    })
 }
@@ -1,8 +1,8 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
-use crate::to_napi_error;
 use crate::token_module::Token;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -95,6 +95,7 @@ pub struct CreateTokenRequest {
 pub struct UpdatePasswordRequest {
     pub old_password: Option<String>,
     pub new_password: String,
+    pub otp: Option<String>,
 }
 
 #[napi(object)]
@@ -125,164 +126,209 @@ fn make_minimal_user(id: String) -> keygen_rs::user::User {
 }
 
 #[napi]
-pub async fn create_user(request: CreateUserRequest) -> Result<User> {
-    let role: keygen_rs::user::UserRole = parse_enum(&request.role, "user role")?;
+pub fn create_user(env: &Env, request: CreateUserRequest) -> Result<PromiseRaw<'_, User>> {
+    spawn(env, async move {
+        let role: keygen_rs::user::UserRole = parse_enum(&request.role, "user role")?;
 
-    let req = keygen_rs::user::CreateUserRequest {
-        email: request.email,
-        first_name: request.first_name,
-        last_name: request.last_name,
-        role: Some(role),
-        permissions: request.permissions,
-        metadata: request.metadata.map(to_metadata).transpose()?,
-    };
+        let req = keygen_rs::user::CreateUserRequest {
+            email: request.email,
+            first_name: request.first_name,
+            last_name: request.last_name,
+            role: Some(role),
+            permissions: request.permissions,
+            metadata: request.metadata.map(to_metadata).transpose()?,
+        };
 
-    keygen_rs::user::User::create(req)
-        .await
-        .map(User::from)
-        .map_err(to_napi_error)
+        keygen_rs::user::User::create(req)
+            .await
+            .map(User::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_users(options: Option<ListUsersOptions>) -> Result<Vec<User>> {
-    let opts = options
-        .map(|o| -> Result<keygen_rs::user::ListUsersOptions> {
-            Ok(keygen_rs::user::ListUsersOptions {
-                limit: o.limit,
-                page_size: o.page_size,
-                page_number: o.page_number,
-                status: o
-                    .status
-                    .as_deref()
-                    .map(|s| parse_enum(s, "user status"))
-                    .transpose()?,
-                assigned: o.assigned,
-                product: o.product,
-                group: o.group,
-                roles: o
-                    .role
-                    .as_deref()
-                    .map(|s| {
-                        parse_enum::<keygen_rs::user::UserRole>(s, "user role").map(|r| vec![r])
-                    })
-                    .transpose()?,
-                metadata: o.metadata.map(to_metadata).transpose()?,
-                ..Default::default()
+pub fn list_users(
+    env: &Env,
+    options: Option<ListUsersOptions>,
+) -> Result<PromiseRaw<'_, Vec<User>>> {
+    spawn(env, async move {
+        let opts = options
+            .map(|o| -> Result<keygen_rs::user::ListUsersOptions> {
+                Ok(keygen_rs::user::ListUsersOptions {
+                    limit: o.limit,
+                    page_size: o.page_size,
+                    page_number: o.page_number,
+                    status: o
+                        .status
+                        .as_deref()
+                        .map(|s| parse_enum(s, "user status"))
+                        .transpose()?,
+                    assigned: o.assigned,
+                    product: o.product,
+                    group: o.group,
+                    roles: o
+                        .role
+                        .as_deref()
+                        .map(|s| {
+                            parse_enum::<keygen_rs::user::UserRole>(s, "user role").map(|r| vec![r])
+                        })
+                        .transpose()?,
+                    metadata: o.metadata.map(to_metadata).transpose()?,
+                    ..Default::default()
+                })
             })
-        })
-        .transpose()?;
+            .transpose()?;
 
-    keygen_rs::user::User::list(opts)
-        .await
-        .map(|result| result.users.into_iter().map(User::from).collect())
-        .map_err(to_napi_error)
+        keygen_rs::user::User::list(opts)
+            .await
+            .map(|result| result.users.into_iter().map(User::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_user(id: String) -> Result<User> {
-    keygen_rs::user::User::get(&id)
-        .await
-        .map(User::from)
-        .map_err(to_napi_error)
+pub fn get_user(env: &Env, id: String) -> Result<PromiseRaw<'_, User>> {
+    spawn(env, async move {
+        keygen_rs::user::User::get(&id)
+            .await
+            .map(User::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn update_user(id: String, request: UpdateUserRequest) -> Result<User> {
-    let req = keygen_rs::user::UpdateUserRequest {
-        email: request.email,
-        first_name: request.first_name,
-        last_name: request.last_name,
-        role: request
-            .role
-            .as_deref()
-            .map(|s| parse_enum(s, "user role"))
-            .transpose()?,
-        metadata: request.metadata.map(to_metadata).transpose()?,
-    };
+pub fn update_user(
+    env: &Env,
+    id: String,
+    request: UpdateUserRequest,
+) -> Result<PromiseRaw<'_, User>> {
+    spawn(env, async move {
+        let req = keygen_rs::user::UpdateUserRequest {
+            email: request.email,
+            first_name: request.first_name,
+            last_name: request.last_name,
+            role: request
+                .role
+                .as_deref()
+                .map(|s| parse_enum(s, "user role"))
+                .transpose()?,
+            metadata: request.metadata.map(to_metadata).transpose()?,
+        };
 
-    make_minimal_user(id)
-        .update(req)
-        .await
-        .map(User::from)
-        .map_err(to_napi_error)
+        make_minimal_user(id)
+            .update(req)
+            .await
+            .map(User::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn delete_user(id: String) -> Result<()> {
-    make_minimal_user(id).delete().await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn delete_user(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        make_minimal_user(id)
+            .delete()
+            .await
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn ban_user(id: String) -> Result<User> {
-    make_minimal_user(id)
-        .ban()
-        .await
-        .map(User::from)
-        .map_err(to_napi_error)
+pub fn ban_user(env: &Env, id: String) -> Result<PromiseRaw<'_, User>> {
+    spawn(env, async move {
+        make_minimal_user(id)
+            .ban()
+            .await
+            .map(User::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn unban_user(id: String) -> Result<User> {
-    make_minimal_user(id)
-        .unban()
-        .await
-        .map(User::from)
-        .map_err(to_napi_error)
+pub fn unban_user(env: &Env, id: String) -> Result<PromiseRaw<'_, User>> {
+    spawn(env, async move {
+        make_minimal_user(id)
+            .unban()
+            .await
+            .map(User::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn generate_user_token(id: String, request: Option<CreateTokenRequest>) -> Result<Token> {
-    let req = request
-        .map(|request| -> Result<keygen_rs::token::CreateTokenRequest> {
-            Ok(keygen_rs::token::CreateTokenRequest {
-                name: request.name,
-                expiry: request.expiry,
-                permissions: request.permissions,
-                metadata: request.metadata.map(to_metadata).transpose()?,
+pub fn generate_user_token(
+    env: &Env,
+    id: String,
+    request: Option<CreateTokenRequest>,
+) -> Result<PromiseRaw<'_, Token>> {
+    spawn(env, async move {
+        let req = request
+            .map(|request| -> Result<keygen_rs::token::CreateTokenRequest> {
+                Ok(keygen_rs::token::CreateTokenRequest {
+                    name: request.name,
+                    expiry: request.expiry,
+                    permissions: request.permissions,
+                    metadata: request.metadata.map(to_metadata).transpose()?,
+                })
             })
-        })
-        .transpose()?;
+            .transpose()?;
 
-    make_minimal_user(id)
-        .generate_token(req)
-        .await
-        .map(Token::from)
-        .map_err(to_napi_error)
+        make_minimal_user(id)
+            .generate_token(req)
+            .await
+            .map(Token::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn change_user_group(id: String, group_id: String) -> Result<User> {
-    make_minimal_user(id)
-        .change_group(&group_id)
-        .await
-        .map(User::from)
-        .map_err(to_napi_error)
+pub fn change_user_group(env: &Env, id: String, group_id: String) -> Result<PromiseRaw<'_, User>> {
+    spawn(env, async move {
+        make_minimal_user(id)
+            .change_group(&group_id)
+            .await
+            .map(User::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn update_user_password(id: String, request: UpdatePasswordRequest) -> Result<User> {
-    let req = keygen_rs::user::UpdatePasswordRequest {
-        old_password: request.old_password,
-        new_password: request.new_password,
-    };
+pub fn update_user_password(
+    env: &Env,
+    id: String,
+    request: UpdatePasswordRequest,
+) -> Result<PromiseRaw<'_, User>> {
+    spawn(env, async move {
+        let req = keygen_rs::user::UpdatePasswordRequest {
+            old_password: request.old_password,
+            new_password: request.new_password,
+            otp: request.otp,
+        };
 
-    make_minimal_user(id)
-        .update_password(req)
-        .await
-        .map(User::from)
-        .map_err(to_napi_error)
+        make_minimal_user(id)
+            .update_password(req)
+            .await
+            .map(User::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn reset_user_password(id: String, request: ResetPasswordRequest) -> Result<User> {
-    let req = keygen_rs::user::ResetPasswordRequest {
-        password_reset_token: request.password_reset_token,
-        new_password: request.new_password,
-    };
+pub fn reset_user_password(
+    env: &Env,
+    id: String,
+    request: ResetPasswordRequest,
+) -> Result<PromiseRaw<'_, User>> {
+    spawn(env, async move {
+        let req = keygen_rs::user::ResetPasswordRequest {
+            password_reset_token: request.password_reset_token,
+            new_password: request.new_password,
+        };
 
-    make_minimal_user(id)
-        .reset_password(req)
-        .await
-        .map(User::from)
-        .map_err(to_napi_error)
+        make_minimal_user(id)
+            .reset_password(req)
+            .await
+            .map(User::from)
+            .map_err(BindingError::from)
+    })
 }
@@ -1,7 +1,7 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
-use crate::to_napi_error;
+use crate::{spawn, BindingError};
 
 #[napi(object)]
 #[derive(Clone)]
@@ -157,125 +157,147 @@ fn make_minimal_event_record(id: String) -> keygen_rs::webhook::event::WebhookEv
 }
 
 #[napi]
-pub async fn create_webhook_endpoint(
+pub fn create_webhook_endpoint(
+    env: &Env,
     request: CreateWebhookEndpointRequest,
-) -> Result<WebhookEndpoint> {
-    let url = request.url.unwrap_or_default();
-    let mut req = keygen_rs::webhook::endpoint::WebhookEndpointCreateRequest::new(url);
+) -> Result<PromiseRaw<'_, WebhookEndpoint>> {
+    spawn(env, async move {
+        let url = request.url.unwrap_or_default();
+        let mut req = keygen_rs::webhook::endpoint::WebhookEndpointCreateRequest::new(url);
 
-    if let Some(subs) = request.subscriptions {
-        req = req.with_subscriptions(parse_webhook_events(subs));
-    }
-    if let Some(algo) = request.signature_algorithm {
-        req = req.with_signature_algorithm(parse_signature_algorithm(&algo)?);
-    }
-    if let Some(env_id) = request.environment_id {
-        req = req.with_environment_id(env_id);
-    }
+        if let Some(subs) = request.subscriptions {
+            req = req.with_subscriptions(parse_webhook_events(subs));
+        }
+        if let Some(algo) = request.signature_algorithm {
+            req = req.with_signature_algorithm(parse_signature_algorithm(&algo)?);
+        }
+        if let Some(env_id) = request.environment_id {
+            req = req.with_environment_id(env_id);
+        }
 
-    keygen_rs::webhook::endpoint::WebhookEndpoint::create(req)
-        .await
-        .map(WebhookEndpoint::from)
-        .map_err(to_napi_error)
+        keygen_rs::webhook::endpoint::WebhookEndpoint::create(req)
+            .await
+            .map(WebhookEndpoint::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_webhook_endpoints(
+pub fn list_webhook_endpoints(
+    env: &Env,
     options: Option<ListWebhookEndpointsOptions>,
-) -> Result<Vec<WebhookEndpoint>> {
-    let opts = options.map(
-        |o| keygen_rs::webhook::endpoint::WebhookEndpointListOptions {
-            limit: o.limit.map(|v| v as i32),
-            page_size: o.page_size.map(|v| v as i32),
-            page_number: o.page_number.map(|v| v as i32),
-        },
-    );
-    keygen_rs::webhook::endpoint::WebhookEndpoint::list(opts.as_ref())
-        .await
-        .map(|list| list.into_iter().map(WebhookEndpoint::from).collect())
-        .map_err(to_napi_error)
+) -> Result<PromiseRaw<'_, Vec<WebhookEndpoint>>> {
+    spawn(env, async move {
+        let opts = options.map(
+            |o| keygen_rs::webhook::endpoint::WebhookEndpointListOptions {
+                limit: o.limit.map(|v| v as i32),
+                page_size: o.page_size.map(|v| v as i32),
+                page_number: o.page_number.map(|v| v as i32),
+            },
+        );
+        keygen_rs::webhook::endpoint::WebhookEndpoint::list(opts.as_ref())
+            .await
+            .map(|list| list.into_iter().map(WebhookEndpoint::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_webhook_endpoint(id: String) -> Result<WebhookEndpoint> {
-    keygen_rs::webhook::endpoint::WebhookEndpoint::get(&id)
-        .await
-        .map(WebhookEndpoint::from)
-        .map_err(to_napi_error)
+pub fn get_webhook_endpoint(env: &Env, id: String) -> Result<PromiseRaw<'_, WebhookEndpoint>> {
+    spawn(env, async move {
+        keygen_rs::webhook::endpoint::WebhookEndpoint::get(&id)
+            .await
+            .map(WebhookEndpoint::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn update_webhook_endpoint(
+pub fn update_webhook_endpoint(
+    env: &Env,
     id: String,
     request: UpdateWebhookEndpointRequest,
-) -> Result<WebhookEndpoint> {
-    let ep = make_minimal_endpoint(id);
-    let mut req = keygen_rs::webhook::endpoint::WebhookEndpointUpdateRequest::new();
-    if let Some(url) = request.url {
-        req = req.with_url(url);
-    }
-    if let Some(subs) = request.subscriptions {
-        req = req.with_subscriptions(parse_webhook_events(subs));
-    }
-    if let Some(algo) = request.signature_algorithm {
-        req = req.with_signature_algorithm(parse_signature_algorithm(&algo)?);
-    }
-    ep.update(req)
-        .await
-        .map(WebhookEndpoint::from)
-        .map_err(to_napi_error)
+) -> Result<PromiseRaw<'_, WebhookEndpoint>> {
+    spawn(env, async move {
+        let ep = make_minimal_endpoint(id);
+        let mut req = keygen_rs::webhook::endpoint::WebhookEndpointUpdateRequest::new();
+        if let Some(url) = request.url {
+            req = req.with_url(url);
+        }
+        if let Some(subs) = request.subscriptions {
+            req = req.with_subscriptions(parse_webhook_events(subs));
+        }
+        if let Some(algo) = request.signature_algorithm {
+            req = req.with_signature_algorithm(parse_signature_algorithm(&algo)?);
+        }
+        ep.update(req)
+            .await
+            .map(WebhookEndpoint::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn delete_webhook_endpoint(id: String) -> Result<()> {
-    let ep = make_minimal_endpoint(id);
-    ep.delete().await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn delete_webhook_endpoint(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let ep = make_minimal_endpoint(id);
+        ep.delete().await.map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn list_webhook_events(
+pub fn list_webhook_events(
+    env: &Env,
     options: Option<ListWebhookEventsOptions>,
-) -> Result<Vec<WebhookEvent>> {
-    let opts = options.map(|o| {
-        let status = o.status.and_then(|s| {
-            serde_json::from_value::<keygen_rs::webhook::event::WebhookEventStatus>(
-                serde_json::Value::String(s),
-            )
-            .ok()
+) -> Result<PromiseRaw<'_, Vec<WebhookEvent>>> {
+    spawn(env, async move {
+        let opts = options.map(|o| {
+            let status = o.status.and_then(|s| {
+                serde_json::from_value::<keygen_rs::webhook::event::WebhookEventStatus>(
+                    serde_json::Value::String(s),
+                )
+                .ok()
+            });
+            keygen_rs::webhook::event::WebhookEventListOptions {
+                limit: o.limit.map(|v| v as i32),
+                page_number: o.page_number.map(|v| v as i32),
+                page_size: o.page_size.map(|v| v as i32),
+                event_type: o.event_type,
+                status,
+            }
         });
-        keygen_rs::webhook::event::WebhookEventListOptions {
-            limit: o.limit.map(|v| v as i32),
-            page_number: o.page_number.map(|v| v as i32),
-            page_size: o.page_size.map(|v| v as i32),
-            event_type: o.event_type,
-            status,
-        }
-    });
-    keygen_rs::webhook::event::WebhookEventRecord::list(opts.as_ref())
-        .await
-        .map(|list| list.into_iter().map(WebhookEvent::from).collect())
-        .map_err(to_napi_error)
+        keygen_rs::webhook::event::WebhookEventRecord::list(opts.as_ref())
+            .await
+            .map(|list| list.into_iter().map(WebhookEvent::from).collect())
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn get_webhook_event(id: String) -> Result<WebhookEvent> {
-    keygen_rs::webhook::event::WebhookEventRecord::get(&id)
-        .await
-        .map(WebhookEvent::from)
-        .map_err(to_napi_error)
+pub fn get_webhook_event(env: &Env, id: String) -> Result<PromiseRaw<'_, WebhookEvent>> {
+    spawn(env, async move {
+        keygen_rs::webhook::event::WebhookEventRecord::get(&id)
+            .await
+            .map(WebhookEvent::from)
+            .map_err(BindingError::from)
+    })
 }
 
 #[napi]
-pub async fn retry_webhook_event(id: String) -> Result<WebhookEvent> {
-    let evt = make_minimal_event_record(id);
-    evt.retry()
-        .await
-        .map(WebhookEvent::from)
-        .map_err(to_napi_error)
+pub fn retry_webhook_event(env: &Env, id: String) -> Result<PromiseRaw<'_, WebhookEvent>> {
+    spawn(env, async move {
+        let evt = make_minimal_event_record(id);
+        evt.retry()
+            .await
+            .map(WebhookEvent::from)
+            .map_err(BindingError::from)
+    })
 }
 
-#[napi]
-pub async fn delete_webhook_event(id: String) -> Result<()> {
-    let evt = make_minimal_event_record(id);
-    evt.delete().await.map_err(to_napi_error)
+#[napi(ts_return_type = "Promise<void>")]
+pub fn delete_webhook_event(env: &Env, id: String) -> Result<PromiseRaw<'_, ()>> {
+    spawn(env, async move {
+        let evt = make_minimal_event_record(id);
+        evt.delete().await.map_err(BindingError::from)
+    })
 }
@@ -0,0 +1,284 @@
+"""Type stubs for the keygen-rs Python bindings.
+
+Coroutine functions must be awaited inside a running asyncio event loop.
+Management functions resolve to resources as plain dicts, with timestamps as
+ISO 8601 strings.
+"""
+
+from typing import Any, Literal, Optional
+
+Scheme = Literal["ED25519_SIGN", "RSA_2048_PKCS1_PSS_SIGN"]
+
+# Config
+
+def set_config(config: Optional[dict[str, Any]] = None, **settings: Any) -> None: ...
+def set_config_from_env() -> None: ...
+def get_config() -> dict[str, Any]: ...
+def reset_config() -> None: ...
+
+# Errors
+
+class KeygenError(Exception):
+    code: str
+    detail: str
+
+class ConfigurationError(KeygenError): ...
+
+class FeatureNotSupportedError(ConfigurationError):
+    feature: str
+    required: str
+    api_version: str
+
+class NetworkError(KeygenError): ...
+
+class RateLimitError(KeygenError):
+    window: str
+    count: int
+    limit: int
+    remaining: int
+    reset: int
+    retry_after: int
+
+class SignatureError(KeygenError): ...
+class LicenseKeyError(KeygenError): ...
+class LicenseError(KeygenError): ...
+class LicenseInvalidError(LicenseError): ...
+class LicenseExpiredError(LicenseError): ...
+class LicenseSuspendedError(LicenseError): ...
+
+class LicenseNotActivatedError(LicenseError):
+    license: License
+
+class LicenseLimitError(LicenseError): ...
+class MachineError(KeygenError): ...
+class HeartbeatError(MachineError): ...
+class TokenError(KeygenError): ...
+class FileError(KeygenError): ...
+
+class FileExpiredError(FileError):
+    dataset: dict[str, Any]
+
+class NotFoundError(KeygenError): ...
+
+class ApiError(KeygenError):
+    body: Any
+
+# Licenses
+
+class License:
+    @property
+    def id(self) -> str: ...
+    @property
+    def key(self) -> str: ...
+    @property
+    def name(self) -> Optional[str]: ...
+    @property
+    def expiry(self) -> Optional[str]: ...
+    @property
+    def status(self) -> Optional[str]: ...
+    @property
+    def uses(self) -> Optional[int]: ...
+    @property
+    def max_machines(self) -> Optional[int]: ...
+    @property
+    def max_cores(self) -> Optional[int]: ...
+    @property
+    def max_uses(self) -> Optional[int]: ...
+    @property
+    def max_processes(self) -> Optional[int]: ...
+    @property
+    def max_users(self) -> Optional[int]: ...
+    @property
+    def protected(self) -> Optional[bool]: ...
+    @property
+    def suspended(self) -> Optional[bool]: ...
+    @property
+    def permissions(self) -> Optional[list[str]]: ...
+    @property
+    def policy(self) -> Optional[str]: ...
+    @property
+    def metadata(self) -> dict[str, Any]: ...
+    @property
+    def account_id(self) -> Optional[str]: ...
+    @property
+    def product_id(self) -> Optional[str]: ...
+    @property
+    def group_id(self) -> Optional[str]: ...
+    @property
+    def owner_id(self) -> Optional[str]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def verify(self) -> bytes: ...
+    async def validate(
+        self,
+        fingerprints: Optional[list[str]] = None,
+        entitlements: Optional[list[str]] = None,
+    ) -> License: ...
+    async def activate(
+        self, fingerprint: str, components: Optional[list[dict[str, Any]]] = None
+    ) -> Machine: ...
+    async def deactivate(self, machine_id: str) -> None: ...
+    async def machine(self, machine_id: str) -> Machine: ...
+    async def machines(self) -> list[Machine]: ...
+    async def entitlements(self) -> list[dict[str, Any]]: ...
+    async def checkout(
+        self, ttl: Optional[int] = None, include: Optional[list[str]] = None
+    ) -> LicenseFile: ...
+    async def check_in(self) -> License: ...
+    async def update(self, **fields: Any) -> License: ...
+    async def delete(self) -> None: ...
+    async def suspend(self) -> License: ...
+    async def reinstate(self) -> License: ...
+    async def renew(self) -> License: ...
+    async def revoke(self) -> None: ...
+    async def increment_usage(self) -> License: ...
+    async def decrement_usage(self) -> License: ...
+    async def reset_usage(self) -> License: ...
+    async def attach_entitlements(self, entitlement_ids: list[str]) -> None: ...
+    async def detach_entitlements(self, entitlement_ids: list[str]) -> None: ...
+    async def change_policy(self, policy_id: str) -> License: ...
+    async def change_owner(self, owner_id: str) -> License: ...
+    async def change_group(self, group_id: str) -> License: ...
+
+async def validate(
+    fingerprints: Optional[list[str]] = None,
+    entitlements: Optional[list[str]] = None,
+) -> License: ...
+def verify(scheme: Scheme, signed_key: str) -> bytes: ...
+async def create_license(policy_id: str, **fields: Any) -> License: ...
+async def list_licenses(**options: Any) -> list[License]: ...
+async def get_license(id: str) -> License: ...
+
+# Machines
+
+class Machine:
+    @property
+    def id(self) -> str: ...
+    @property
+    def fingerprint(self) -> str: ...
+    @property
+    def name(self) -> Optional[str]: ...
+    @property
+    def platform(self) -> Optional[str]: ...
+    @property
+    def hostname(self) -> Optional[str]: ...
+    @property
+    def ip(self) -> Optional[str]: ...
+    @property
+    def cores(self) -> Optional[int]: ...
+    @property
+    def metadata(self) -> Optional[dict[str, Any]]: ...
+    @property
+    def require_heartbeat(self) -> bool: ...
+    @property
+    def heartbeat_status(self) -> str: ...
+    @property
+    def heartbeat_duration(self) -> Optional[int]: ...
+    @property
+    def created(self) -> str: ...
+    @property
+    def updated(self) -> str: ...
+    @property
+    def account_id(self) -> Optional[str]: ...
+    @property
+    def environment_id(self) -> Optional[str]: ...
+    @property
+    def product_id(self) -> Optional[str]: ...
+    @property
+    def license_id(self) -> Optional[str]: ...
+    @property
+    def owner_id(self) -> Optional[str]: ...
+    @property
+    def group_id(self) -> Optional[str]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    async def deactivate(self) -> None: ...
+    async def checkout(
+        self, ttl: Optional[int] = None, include: Optional[list[str]] = None
+    ) -> MachineFile: ...
+    async def ping(self) -> Machine: ...
+    async def reset(self) -> Machine: ...
+    async def update(self, **fields: Any) -> Machine: ...
+    async def change_owner(self, owner_id: str) -> Machine: ...
+    async def change_group(self, group_id: str) -> Machine: ...
+
+async def create_machine(license_id: str, fingerprint: str, **fields: Any) -> Machine: ...
+async def list_machines(**filters: Any) -> list[Machine]: ...
+async def get_machine(id: str) -> Machine: ...
+
+# License and machine files
+
+class LicenseFile:
+    @property
+    def id(self) -> str: ...
+    @property
+    def certificate(self) -> str: ...
+    @property
+    def issued(self) -> str: ...
+    @property
+    def expiry(self) -> str: ...
+    @property
+    def ttl(self) -> int: ...
+    def verify(self) -> None: ...
+    def decrypt(self, key: str) -> dict[str, Any]: ...
+
+class MachineFile:
+    @property
+    def id(self) -> str: ...
+    @property
+    def certificate(self) -> str: ...
+    @property
+    def issued(self) -> str: ...
+    @property
+    def expiry(self) -> str: ...
+    @property
+    def ttl(self) -> int: ...
+    def verify(self) -> None: ...
+    def decrypt(self, key: str) -> dict[str, Any]: ...
+
+def verify_license_file(certificate: str, license_key: str) -> dict[str, Any]: ...
+def decrypt_license_file(certificate: str, key: str) -> dict[str, Any]: ...
+def verify_machine_file(certificate: str, decryption_key: str) -> dict[str, Any]: ...
+def decrypt_machine_file(certificate: str, key: str) -> dict[str, Any]: ...
+
+# Products
+
+async def create_product(name: str, **fields: Any) -> dict[str, Any]: ...
+async def list_products(**options: Any) -> list[dict[str, Any]]: ...
+async def get_product(id: str) -> dict[str, Any]: ...
+async def update_product(id: str, **fields: Any) -> dict[str, Any]: ...
+async def delete_product(id: str) -> None: ...
+
+# Policies
+
+async def create_policy(product_id: str, name: str, **fields: Any) -> dict[str, Any]: ...
+async def list_policies(**options: Any) -> list[dict[str, Any]]: ...
+async def get_policy(id: str) -> dict[str, Any]: ...
+async def update_policy(id: str, **fields: Any) -> dict[str, Any]: ...
+async def delete_policy(id: str) -> None: ...
+async def attach_policy_entitlements(id: str, entitlement_ids: list[str]) -> None: ...
+async def detach_policy_entitlements(id: str, entitlement_ids: list[str]) -> None: ...
+
+# Users
+
+async def create_user(email: str, **fields: Any) -> dict[str, Any]: ...
+async def list_users(**options: Any) -> list[dict[str, Any]]: ...
+async def get_user(id: str) -> dict[str, Any]: ...
+async def update_user(id: str, **fields: Any) -> dict[str, Any]: ...
+async def delete_user(id: str) -> None: ...
+async def ban_user(id: str) -> dict[str, Any]: ...
+async def unban_user(id: str) -> dict[str, Any]: ...
+
+# Entitlements
+
+async def create_entitlement(code: str, **fields: Any) -> dict[str, Any]: ...
+async def list_entitlements(**options: Any) -> list[dict[str, Any]]: ...
+async def get_entitlement(id: str) -> dict[str, Any]: ...
+async def update_entitlement(id: str, **fields: Any) -> dict[str, Any]: ...
+async def delete_entitlement(id: str) -> None: ...
+
+# Groups
+
+async def create_group(name: str, **fields: Any) -> dict[str, Any]: ...
+async def list_groups(**options: Any) -> list[dict[str, Any]]: ...
+async def get_group(id: str) -> dict[str, Any]: ...
+async def update_group(id: str, **fields: Any) -> dict[str, Any]: ...
+async def delete_group(id: str) -> None: ...
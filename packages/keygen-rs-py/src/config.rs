@@ -0,0 +1,76 @@
+use keygen_rs::config::{ConfigLayer, KeygenConfig};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::errors::to_py_err;
+use crate::{from_py, to_py};
+
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(set_config, m)?)?;
+    m.add_function(wrap_pyfunction!(set_config_from_env, m)?)?;
+    m.add_function(wrap_pyfunction!(get_config, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_config, m)?)?;
+    Ok(())
+}
+
+/// Install the configuration for every following call. Settings are given as
+/// a dict, as keyword arguments, or both with the keywords taking precedence,
+/// and use the names of the `KeygenConfig` fields. Unset fields keep their
+/// defaults, and the result is validated before it's installed.
+#[pyfunction]
+#[pyo3(signature = (config=None, **settings))]
+pub fn set_config(
+    config: Option<&Bound<'_, PyAny>>,
+    settings: Option<&Bound<'_, PyDict>>,
+) -> PyResult<()> {
+    let mut layer = ConfigLayer::default();
+    if let Some(config) = config.filter(|c| !c.is_none()) {
+        layer = layer.merge(from_py(config, "config")?);
+    }
+    if let Some(settings) = settings {
+        layer = layer.merge(from_py(settings.as_any(), "config")?);
+    }
+
+    let config = layer.into_config().map_err(to_py_err)?;
+    keygen_rs::config::set_config(config).map_err(to_py_err)
+}
+
+/// Install a configuration read from the `KEYGEN_*` environment variables.
+#[pyfunction]
+pub fn set_config_from_env() -> PyResult<()> {
+    let config = KeygenConfig::from_env().map_err(to_py_err)?;
+    keygen_rs::config::set_config(config).map_err(to_py_err)
+}
+
+/// The installed configuration as a dict, in the shape `set_config` accepts.
+#[pyfunction]
+pub fn get_config(py: Python<'_>) -> PyResult<PyObject> {
+    let config = keygen_rs::config::get_config().map_err(to_py_err)?;
+    let layer = ConfigLayer {
+        api_url: Some(config.api_url),
+        api_version: Some(config.api_version),
+        api_prefix: Some(config.api_prefix),
+        account: Some(config.account),
+        environment: config.environment,
+        user_agent: config.user_agent,
+        deployment: config.deployment,
+        signature_host: config.signature_host,
+        product: Some(config.product),
+        package: Some(config.package),
+        license_key: config.license_key,
+        public_key: config.public_key,
+        keyring: Some(config.keyring),
+        platform: config.platform,
+        max_clock_drift: config.max_clock_drift,
+        verify_keygen_signature: config.verify_keygen_signature,
+        signature_mode: Some(config.signature_mode),
+        token: config.token,
+    };
+    to_py(py, &layer)
+}
+
+/// Restore the default configuration.
+#[pyfunction]
+pub fn reset_config() -> PyResult<()> {
+    keygen_rs::config::reset_config().map_err(to_py_err)
+}
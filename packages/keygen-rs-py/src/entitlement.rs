@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use keygen_rs::entitlement::{
+    CreateEntitlementRequest, Entitlement, ListEntitlementsOptions, UpdateEntitlementRequest,
+};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde_json::Value;
+
+use crate::errors::to_py_err;
+use crate::{awaitable, from_kwargs, request_kwargs, Json};
+
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(create_entitlement, m)?)?;
+    m.add_function(wrap_pyfunction!(list_entitlements, m)?)?;
+    m.add_function(wrap_pyfunction!(get_entitlement, m)?)?;
+    m.add_function(wrap_pyfunction!(update_entitlement, m)?)?;
+    m.add_function(wrap_pyfunction!(delete_entitlement, m)?)?;
+    Ok(())
+}
+
+request_kwargs!(CreateEntitlementKwargs => CreateEntitlementRequest {
+    name: Option<String>,
+    code: String,
+    metadata: Option<HashMap<String, Value>>,
+});
+
+request_kwargs!(UpdateEntitlementKwargs => UpdateEntitlementRequest {
+    name: Option<String>,
+    code: Option<String>,
+    metadata: Option<HashMap<String, Value>>,
+});
+
+request_kwargs!(ListEntitlementsKwargs => ListEntitlementsOptions {
+    limit: Option<u32>,
+    page_size: Option<u32>,
+    page_number: Option<u32>,
+});
+
+fn make_entitlement(id: String) -> Entitlement {
+    Entitlement {
+        id,
+        name: None,
+        code: String::new(),
+        metadata: None,
+        created: Utc::now(),
+        updated: Utc::now(),
+        account_id: None,
+    }
+}
+
+/// Create an entitlement with the given `code`, resolving to it as a dict.
+#[pyfunction]
+#[pyo3(signature = (code, **fields))]
+pub fn create_entitlement<'py>(
+    py: Python<'py>,
+    code: String,
+    fields: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let fields = fields.cloned().unwrap_or_else(|| PyDict::new(py));
+    fields.set_item("code", code)?;
+    let request: CreateEntitlementKwargs = from_kwargs(py, Some(&fields), "entitlement")?;
+    awaitable(py, async move {
+        Entitlement::create(request.into())
+            .await
+            .map(Json)
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (**options))]
+pub fn list_entitlements<'py>(
+    py: Python<'py>,
+    options: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let options: ListEntitlementsKwargs = from_kwargs(py, options, "entitlement list options")?;
+    awaitable(py, async move {
+        Entitlement::list(Some(options.into()))
+            .await
+            .map(Json)
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn get_entitlement(py: Python<'_>, id: String) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        Entitlement::get(&id).await.map(Json).map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (id, **fields))]
+pub fn update_entitlement<'py>(
+    py: Python<'py>,
+    id: String,
+    fields: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let request: UpdateEntitlementKwargs = from_kwargs(py, fields, "entitlement update")?;
+    awaitable(py, async move {
+        make_entitlement(id)
+            .update(request.into())
+            .await
+            .map(Json)
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn delete_entitlement(py: Python<'_>, id: String) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        make_entitlement(id).delete().await.map_err(to_py_err)
+    })
+}
@@ -0,0 +1,289 @@
+use keygen_rs::errors::{Error, ErrorMeta};
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+
+use crate::license::License;
+use crate::to_py;
+
+create_exception!(
+    keygen_rs,
+    KeygenError,
+    PyException,
+    "Base class for errors raised by keygen-rs, with the error `code` and `detail`."
+);
+create_exception!(
+    keygen_rs,
+    ConfigurationError,
+    KeygenError,
+    "The SDK configuration is missing, invalid or incomplete."
+);
+create_exception!(
+    keygen_rs,
+    FeatureNotSupportedError,
+    ConfigurationError,
+    "The configured API version is too old for the request, see `feature`, `required` and `api_version`."
+);
+create_exception!(
+    keygen_rs,
+    NetworkError,
+    KeygenError,
+    "The request could not be sent or its response could not be read."
+);
+create_exception!(
+    keygen_rs,
+    RateLimitError,
+    KeygenError,
+    "The API rate limit was exceeded, see `retry_after` for when to try again."
+);
+create_exception!(
+    keygen_rs,
+    SignatureError,
+    KeygenError,
+    "A response signature is missing or invalid, or the system clock is off."
+);
+create_exception!(
+    keygen_rs,
+    LicenseKeyError,
+    KeygenError,
+    "A signed license key could not be verified offline."
+);
+create_exception!(
+    keygen_rs,
+    LicenseError,
+    KeygenError,
+    "The license failed validation."
+);
+create_exception!(
+    keygen_rs,
+    LicenseInvalidError,
+    LicenseError,
+    "The license key or token is invalid, or the license can't be used here."
+);
+create_exception!(
+    keygen_rs,
+    LicenseExpiredError,
+    LicenseError,
+    "The license has expired."
+);
+create_exception!(
+    keygen_rs,
+    LicenseSuspendedError,
+    LicenseError,
+    "The license has been suspended."
+);
+create_exception!(
+    keygen_rs,
+    LicenseNotActivatedError,
+    LicenseError,
+    "The license is valid but the machine isn't activated, `license` holds it for `License.activate`."
+);
+create_exception!(
+    keygen_rs,
+    LicenseLimitError,
+    LicenseError,
+    "The license has too many machines, cores or processes."
+);
+create_exception!(
+    keygen_rs,
+    MachineError,
+    KeygenError,
+    "A machine, process or component could not be activated."
+);
+create_exception!(
+    keygen_rs,
+    HeartbeatError,
+    MachineError,
+    "A machine heartbeat is required, failed, or is dead."
+);
+create_exception!(
+    keygen_rs,
+    TokenError,
+    KeygenError,
+    "The API token is invalid, expired or not allowed."
+);
+create_exception!(
+    keygen_rs,
+    FileError,
+    KeygenError,
+    "A license or machine file is invalid, not genuine or can't be decrypted."
+);
+create_exception!(
+    keygen_rs,
+    FileExpiredError,
+    FileError,
+    "A license or machine file has expired, `dataset` holds its decrypted contents."
+);
+create_exception!(
+    keygen_rs,
+    NotFoundError,
+    KeygenError,
+    "The requested resource does not exist."
+);
+create_exception!(
+    keygen_rs,
+    ApiError,
+    KeygenError,
+    "The API rejected the request, `body` holds its response."
+);
+
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("KeygenError", py.get_type::<KeygenError>())?;
+    m.add("ConfigurationError", py.get_type::<ConfigurationError>())?;
+    m.add(
+        "FeatureNotSupportedError",
+        py.get_type::<FeatureNotSupportedError>(),
+    )?;
+    m.add("NetworkError", py.get_type::<NetworkError>())?;
+    m.add("RateLimitError", py.get_type::<RateLimitError>())?;
+    m.add("SignatureError", py.get_type::<SignatureError>())?;
+    m.add("LicenseKeyError", py.get_type::<LicenseKeyError>())?;
+    m.add("LicenseError", py.get_type::<LicenseError>())?;
+    m.add("LicenseInvalidError", py.get_type::<LicenseInvalidError>())?;
+    m.add("LicenseExpiredError", py.get_type::<LicenseExpiredError>())?;
+    m.add(
+        "LicenseSuspendedError",
+        py.get_type::<LicenseSuspendedError>(),
+    )?;
+    m.add(
+        "LicenseNotActivatedError",
+        py.get_type::<LicenseNotActivatedError>(),
+    )?;
+    m.add("LicenseLimitError", py.get_type::<LicenseLimitError>())?;
+    m.add("MachineError", py.get_type::<MachineError>())?;
+    m.add("HeartbeatError", py.get_type::<HeartbeatError>())?;
+    m.add("TokenError", py.get_type::<TokenError>())?;
+    m.add("FileError", py.get_type::<FileError>())?;
+    m.add("FileExpiredError", py.get_type::<FileExpiredError>())?;
+    m.add("NotFoundError", py.get_type::<NotFoundError>())?;
+    m.add("ApiError", py.get_type::<ApiError>())?;
+    Ok(())
+}
+
+/// Raises `e` as the matching `KeygenError` subclass. Besides `code` and
+/// `detail`, the exception carries the payload of errors that have one.
+pub(crate) fn to_py_err(e: Error) -> PyErr {
+    let message = format!("[{}] {}", e.code(), e.detail());
+    let err = match &e {
+        Error::MissingConfiguration
+        | Error::InvalidConfiguration(_)
+        | Error::InvalidUrl
+        | Error::UrlParse(_)
+        | Error::InvalidHeader(_)
+        | Error::UrlEncode(_)
+        | Error::LicenseKeyMissing
+        | Error::PublicKeyMissing
+        | Error::PublicKeyInvalid
+        | Error::ValidationFingerprintMissing { .. }
+        | Error::ValidationComponentsMissing { .. }
+        | Error::ValidationProductMissing { .. }
+        | Error::EnvironmentError { .. } => ConfigurationError::new_err(message),
+        Error::FeatureNotSupported { .. } => FeatureNotSupportedError::new_err(message),
+        Error::HttpClient(_) => NetworkError::new_err(message),
+        Error::RateLimitExceeded { .. } => RateLimitError::new_err(message),
+        Error::KeygenSignatureInvalid { .. }
+        | Error::KeygenSignatureMissing
+        | Error::SystemClockUnsynced => SignatureError::new_err(message),
+        Error::LicenseSchemeMissing
+        | Error::LicenseSchemeNotSupported
+        | Error::LicenseSchemeUnsupported
+        | Error::LicenseNotSigned
+        | Error::LicenseKeyNotGenuine => LicenseKeyError::new_err(message),
+        Error::LicenseKeyInvalid { .. }
+        | Error::LicenseTokenInvalid { .. }
+        | Error::LicenseNotAllowed { .. } => LicenseInvalidError::new_err(message),
+        Error::LicenseExpired { .. } => LicenseExpiredError::new_err(message),
+        Error::LicenseSuspended { .. } => LicenseSuspendedError::new_err(message),
+        Error::LicenseNotActivated { .. } => LicenseNotActivatedError::new_err(message),
+        Error::LicenseTooManyMachines { .. }
+        | Error::LicenseTooManyCores { .. }
+        | Error::LicenseTooManyProcesses { .. } => LicenseLimitError::new_err(message),
+        Error::MachineAlreadyActivated { .. }
+        | Error::MachineLimitExceeded { .. }
+        | Error::ProcessLimitExceeded { .. }
+        | Error::ComponentConflict { .. }
+        | Error::ComponentAlreadyActivated { .. }
+        | Error::ComponentNotActivated { .. } => MachineError::new_err(message),
+        Error::HeartbeatDead { .. }
+        | Error::HeartbeatPingFailed { .. }
+        | Error::HeartbeatRequired { .. } => HeartbeatError::new_err(message),
+        Error::TokenNotAllowed { .. }
+        | Error::TokenFormatInvalid { .. }
+        | Error::TokenInvalid { .. }
+        | Error::TokenExpired { .. } => TokenError::new_err(message),
+        Error::DecryptionError(_)
+        | Error::CertificateFileInvalid(_)
+        | Error::CertificateFileNotGenuine(_)
+        | Error::CertificateFileNotSupported(_)
+        | Error::LicenseFileInvalid(_)
+        | Error::LicenseFileNotGenuine(_)
+        | Error::LicenseFileNotSupported(_)
+        | Error::LicenseFileNotEncrypted
+        | Error::MachineFileInvalid(_)
+        | Error::MachineFileNotGenuine(_)
+        | Error::MachineFileNotSupported(_) => FileError::new_err(message),
+        Error::CertificateFileExpired
+        | Error::LicenseFileExpired(_)
+        | Error::MachineFileExpired(_) => FileExpiredError::new_err(message),
+        Error::NotFound { .. } | Error::MachineNotFound | Error::ProcessNotFound => {
+            NotFoundError::new_err(message)
+        }
+        Error::KeygenApiError { .. }
+        | Error::PasswordResetTokenInvalid { .. }
+        | Error::PasswordResetTokenExpired { .. } => ApiError::new_err(message),
+        Error::UnexpectedError(_) | Error::JsonError(_) => KeygenError::new_err(message),
+    };
+
+    Python::with_gil(|py| match annotate(py, &err, e) {
+        Ok(()) => err,
+        Err(annotation_err) => annotation_err,
+    })
+}
+
+fn annotate(py: Python<'_>, err: &PyErr, e: Error) -> PyResult<()> {
+    let value = err.value(py);
+    value.setattr("code", e.code())?;
+    value.setattr("detail", e.detail())?;
+
+    match e {
+        Error::RateLimitExceeded {
+            window,
+            count,
+            limit,
+            remaining,
+            reset,
+            retry_after,
+        } => {
+            value.setattr("window", window)?;
+            value.setattr("count", count)?;
+            value.setattr("limit", limit)?;
+            value.setattr("remaining", remaining)?;
+            value.setattr("reset", reset)?;
+            value.setattr("retry_after", retry_after)?;
+        }
+        Error::LicenseNotActivated { license, .. } => {
+            value.setattr("license", License::from(*license))?;
+        }
+        Error::LicenseFileExpired(dataset) => {
+            value.setattr("dataset", to_py(py, &dataset)?)?;
+        }
+        Error::MachineFileExpired(dataset) => {
+            value.setattr("dataset", to_py(py, &dataset)?)?;
+        }
+        Error::FeatureNotSupported {
+            feature,
+            required,
+            api_version,
+        } => {
+            value.setattr("feature", feature)?;
+            value.setattr("required", required)?;
+            value.setattr("api_version", api_version)?;
+        }
+        Error::KeygenApiError { body, .. } => {
+            value.setattr("body", to_py(py, &body)?)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use keygen_rs::group::{CreateGroupRequest, Group, ListGroupsOptions, UpdateGroupRequest};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde_json::Value;
+
+use crate::errors::to_py_err;
+use crate::{awaitable, from_kwargs, request_kwargs, Json};
+
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(create_group, m)?)?;
+    m.add_function(wrap_pyfunction!(list_groups, m)?)?;
+    m.add_function(wrap_pyfunction!(get_group, m)?)?;
+    m.add_function(wrap_pyfunction!(update_group, m)?)?;
+    m.add_function(wrap_pyfunction!(delete_group, m)?)?;
+    Ok(())
+}
+
+request_kwargs!(CreateGroupKwargs => CreateGroupRequest {
+    name: String,
+    max_users: Option<i32>,
+    max_licenses: Option<i32>,
+    max_machines: Option<i32>,
+    metadata: Option<HashMap<String, Value>>,
+});
+
+request_kwargs!(UpdateGroupKwargs => UpdateGroupRequest {
+    name: Option<String>,
+    max_users: Option<i32>,
+    max_licenses: Option<i32>,
+    max_machines: Option<i32>,
+    metadata: Option<HashMap<String, Value>>,
+});
+
+request_kwargs!(ListGroupsKwargs => ListGroupsOptions {
+    limit: Option<u32>,
+    page_size: Option<u32>,
+    page_number: Option<u32>,
+});
+
+fn make_group(id: String) -> Group {
+    Group {
+        id,
+        ..Default::default()
+    }
+}
+
+/// Create a group named `name`, resolving to it as a dict.
+#[pyfunction]
+#[pyo3(signature = (name, **fields))]
+pub fn create_group<'py>(
+    py: Python<'py>,
+    name: String,
+    fields: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let fields = fields.cloned().unwrap_or_else(|| PyDict::new(py));
+    fields.set_item("name", name)?;
+    let request: CreateGroupKwargs = from_kwargs(py, Some(&fields), "group")?;
+    awaitable(py, async move {
+        Group::create(request.into())
+            .await
+            .map(Json)
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (**options))]
+pub fn list_groups<'py>(
+    py: Python<'py>,
+    options: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let options: ListGroupsKwargs = from_kwargs(py, options, "group list options")?;
+    awaitable(py, async move {
+        Group::list(Some(options.into()))
+            .await
+            .map(Json)
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn get_group(py: Python<'_>, id: String) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        Group::get(&id).await.map(Json).map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (id, **fields))]
+pub fn update_group<'py>(
+    py: Python<'py>,
+    id: String,
+    fields: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let request: UpdateGroupKwargs = from_kwargs(py, fields, "group update")?;
+    awaitable(py, async move {
+        make_group(id)
+            .update(request.into())
+            .await
+            .map(Json)
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn delete_group(py: Python<'_>, id: String) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        make_group(id).delete().await.map_err(to_py_err)
+    })
+}
@@ -0,0 +1,143 @@
+use std::future::Future;
+use std::sync::Mutex;
+
+use keygen_rs::blocking;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyCFunction, PyDict, PyString};
+use pyo3::IntoPyObjectExt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+pub mod config;
+pub mod entitlement;
+pub mod errors;
+pub mod group;
+pub mod license;
+pub mod license_file;
+pub mod machine;
+pub mod machine_file;
+pub mod policy;
+pub mod product;
+pub mod user;
+
+use errors::to_py_err;
+
+/// Converts a serializable value into the equivalent Python object, with
+/// objects as dicts, and timestamps as ISO 8601 strings.
+fn to_py<T: Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let json = serde_json::to_string(value).map_err(|e| to_py_err(e.into()))?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+/// Converts a Python object, usually a dict, into `T`. Values that JSON can't
+/// represent, such as datetimes, are converted with `str()`.
+fn from_py<T: DeserializeOwned>(value: &Bound<'_, PyAny>, label: &str) -> PyResult<T> {
+    let py = value.py();
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("default", py.get_type::<PyString>())?;
+    let json: String = py
+        .import("json")?
+        .call_method("dumps", (value,), Some(&kwargs))?
+        .extract()?;
+    serde_json::from_str(&json).map_err(|e| PyValueError::new_err(format!("Invalid {label}: {e}")))
+}
+
+fn opt_from_py<T: DeserializeOwned>(
+    value: Option<&Bound<'_, PyAny>>,
+    label: &str,
+) -> PyResult<Option<T>> {
+    value
+        .filter(|v| !v.is_none())
+        .map(|v| from_py(v, label))
+        .transpose()
+}
+
+/// Converts the keyword arguments collected by a `**fields` parameter into `T`.
+fn from_kwargs<T: DeserializeOwned>(
+    py: Python<'_>,
+    fields: Option<&Bound<'_, PyDict>>,
+    label: &str,
+) -> PyResult<T> {
+    match fields {
+        Some(fields) => from_py(fields.as_any(), label),
+        None => from_py(PyDict::new(py).as_any(), label),
+    }
+}
+
+/// Runs `future` on the running event loop's default executor, returning an
+/// asyncio future for its result. The executor's threads are Python threads
+/// that the interpreter waits for at exit, which tokio's threads are not.
+fn awaitable<'py, F, T>(py: Python<'py>, future: F) -> PyResult<Bound<'py, PyAny>>
+where
+    F: Future<Output = PyResult<T>> + Send + 'static,
+    T: for<'a> IntoPyObject<'a> + Send + 'static,
+{
+    let future = Mutex::new(Some(future));
+    let run = PyCFunction::new_closure(py, None, None, move |args, _kwargs| {
+        let py = args.py();
+        let future = future
+            .lock()
+            .ok()
+            .and_then(|mut future| future.take())
+            .ok_or_else(|| PyRuntimeError::new_err("the request has already run"))?;
+        let value = py.allow_threads(|| blocking::block_on(future))?;
+        value.into_py_any(py)
+    })?;
+
+    py.import("asyncio")?
+        .call_method0("get_running_loop")?
+        .call_method1("run_in_executor", (py.None(), run))
+}
+
+/// Declares the keyword arguments accepted for the request type `$target`
+/// under the snake_case names Python callers use, whatever names `$target`
+/// serializes with, and converts them into it.
+macro_rules! request_kwargs {
+    ($name:ident => $target:ident { $($field:ident: $ty:ty),* $(,)? }) => {
+        #[derive(serde::Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct $name {
+            $($field: $ty),*
+        }
+
+        impl From<$name> for $target {
+            fn from(kwargs: $name) -> Self {
+                $target {
+                    $($field: kwargs.$field),*
+                }
+            }
+        }
+    };
+}
+pub(crate) use request_kwargs;
+
+/// Returned from coroutines to convert serializable results with [`to_py`].
+struct Json<T>(T);
+
+impl<'py, T: Serialize> IntoPyObject<'py> for Json<T> {
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+        to_py(py, &self.0).map(|obj| obj.into_bound(py))
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "keygen_rs")]
+fn keygen_rs_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    errors::register(m)?;
+    config::register(m)?;
+    license::register(m)?;
+    machine::register(m)?;
+    license_file::register(m)?;
+    machine_file::register(m)?;
+    product::register(m)?;
+    policy::register(m)?;
+    user::register(m)?;
+    entitlement::register(m)?;
+    group::register(m)?;
+    Ok(())
+}
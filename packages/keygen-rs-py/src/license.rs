@@ -0,0 +1,649 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use keygen_rs::component::Component;
+use keygen_rs::license::{LicenseCheckoutOpts, LicenseListOptions, SchemeCode};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::errors::to_py_err;
+use crate::license_file::LicenseFile;
+use crate::machine::Machine;
+use crate::{awaitable, from_kwargs, from_py, opt_from_py, to_py, Json};
+
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<License>()?;
+    m.add_function(wrap_pyfunction!(validate, m)?)?;
+    m.add_function(wrap_pyfunction!(verify, m)?)?;
+    m.add_function(wrap_pyfunction!(create_license, m)?)?;
+    m.add_function(wrap_pyfunction!(list_licenses, m)?)?;
+    m.add_function(wrap_pyfunction!(get_license, m)?)?;
+    Ok(())
+}
+
+/// A license, as returned by `validate` and the license management
+/// functions. Requests made through its methods use the installed
+/// configuration.
+#[pyclass(module = "keygen_rs", frozen)]
+#[derive(Clone)]
+pub struct License(pub(crate) keygen_rs::license::License);
+
+impl From<keygen_rs::license::License> for License {
+    fn from(license: keygen_rs::license::License) -> Self {
+        License(license)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CreateLicenseRequest {
+    name: Option<String>,
+    key: Option<String>,
+    expiry: Option<DateTime<Utc>>,
+    max_machines: Option<i32>,
+    max_processes: Option<i32>,
+    max_users: Option<i32>,
+    max_cores: Option<i32>,
+    max_uses: Option<i32>,
+    protected: Option<bool>,
+    suspended: Option<bool>,
+    permissions: Option<Vec<String>>,
+    metadata: Option<HashMap<String, Value>>,
+    owner_id: Option<String>,
+    group_id: Option<String>,
+}
+
+/// Limits are `Some(None)` when passed as `None`, which clears them.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct UpdateLicenseRequest {
+    name: Option<String>,
+    expiry: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "present")]
+    max_machines: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "present")]
+    max_processes: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "present")]
+    max_users: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "present")]
+    max_cores: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "present")]
+    max_uses: Option<Option<i32>>,
+    protected: Option<bool>,
+    suspended: Option<bool>,
+    permissions: Option<Vec<String>>,
+    metadata: Option<HashMap<String, Value>>,
+}
+
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ListLicensesOptions {
+    limit: Option<i32>,
+    page_size: Option<i32>,
+    page_number: Option<i32>,
+    status: Option<String>,
+    product: Option<String>,
+    policy: Option<String>,
+    owner: Option<String>,
+    user: Option<String>,
+    group: Option<String>,
+    machine: Option<String>,
+    assigned: Option<bool>,
+    unassigned: Option<bool>,
+    activated: Option<bool>,
+    metadata: Option<HashMap<String, Value>>,
+}
+
+#[pymethods]
+impl License {
+    #[getter]
+    fn id(&self) -> &str {
+        &self.0.id
+    }
+
+    #[getter]
+    fn key(&self) -> &str {
+        &self.0.key
+    }
+
+    #[getter]
+    fn name(&self) -> Option<&str> {
+        self.0.name.as_deref()
+    }
+
+    /// Expiry as an ISO 8601 string.
+    #[getter]
+    fn expiry(&self) -> Option<String> {
+        self.0.expiry.map(|e| e.to_rfc3339())
+    }
+
+    #[getter]
+    fn status(&self) -> Option<&str> {
+        self.0.status.as_deref()
+    }
+
+    #[getter]
+    fn uses(&self) -> Option<i32> {
+        self.0.uses
+    }
+
+    #[getter]
+    fn max_machines(&self) -> Option<i32> {
+        self.0.max_machines
+    }
+
+    #[getter]
+    fn max_cores(&self) -> Option<i32> {
+        self.0.max_cores
+    }
+
+    #[getter]
+    fn max_uses(&self) -> Option<i32> {
+        self.0.max_uses
+    }
+
+    #[getter]
+    fn max_processes(&self) -> Option<i32> {
+        self.0.max_processes
+    }
+
+    #[getter]
+    fn max_users(&self) -> Option<i32> {
+        self.0.max_users
+    }
+
+    #[getter]
+    fn protected(&self) -> Option<bool> {
+        self.0.protected
+    }
+
+    #[getter]
+    fn suspended(&self) -> Option<bool> {
+        self.0.suspended
+    }
+
+    #[getter]
+    fn permissions(&self) -> Option<Vec<String>> {
+        self.0.permissions.clone()
+    }
+
+    #[getter]
+    fn policy(&self) -> Option<&str> {
+        self.0.policy.as_deref()
+    }
+
+    #[getter]
+    fn metadata(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_py(py, &self.0.metadata)
+    }
+
+    #[getter]
+    fn account_id(&self) -> Option<&str> {
+        self.0.account_id.as_deref()
+    }
+
+    #[getter]
+    fn product_id(&self) -> Option<&str> {
+        self.0.product_id.as_deref()
+    }
+
+    #[getter]
+    fn group_id(&self) -> Option<&str> {
+        self.0.group_id.as_deref()
+    }
+
+    #[getter]
+    fn owner_id(&self) -> Option<&str> {
+        self.0.owner_id.as_deref()
+    }
+
+    /// The license as a dict.
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_py(py, &self.0)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("License(id={:?}, status={:?})", self.0.id, self.0.status)
+    }
+
+    /// Validate the license for the given machine fingerprints and
+    /// entitlement codes, resolving to the refreshed license.
+    #[pyo3(signature = (fingerprints=None, entitlements=None))]
+    fn validate<'py>(
+        &self,
+        py: Python<'py>,
+        fingerprints: Option<Vec<String>>,
+        entitlements: Option<Vec<String>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .validate(
+                    &fingerprints.unwrap_or_default(),
+                    &entitlements.unwrap_or_default(),
+                )
+                .await
+                .map(License::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    /// Verify the signature of a signed license key offline, returning its
+    /// decoded data.
+    fn verify<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let data = self.0.verify().map_err(to_py_err)?;
+        Ok(PyBytes::new(py, &data))
+    }
+
+    /// Activate the machine `fingerprint`, with optional components as dicts
+    /// with `fingerprint` and `name`, resolving to the new `Machine`.
+    #[pyo3(signature = (fingerprint, components=None))]
+    fn activate<'py>(
+        &self,
+        py: Python<'py>,
+        fingerprint: String,
+        components: Option<&Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let components: Vec<Component> = opt_from_py(components, "components")?.unwrap_or_default();
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .activate(&fingerprint, &components)
+                .await
+                .map(Machine::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    /// Deactivate the license's machine with ID `machine_id`.
+    fn deactivate<'py>(&self, py: Python<'py>, machine_id: String) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license.deactivate(&machine_id).await.map_err(to_py_err)
+        })
+    }
+
+    /// Fetch the license's machine with ID `machine_id`.
+    fn machine<'py>(&self, py: Python<'py>, machine_id: String) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .machine(&machine_id)
+                .await
+                .map(Machine::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    /// List the license's machines.
+    fn machines<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .machines(None)
+                .await
+                .map(|machines| machines.into_iter().map(Machine::from).collect::<Vec<_>>())
+                .map_err(to_py_err)
+        })
+    }
+
+    /// List the license's entitlements as dicts.
+    fn entitlements<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .entitlements(None)
+                .await
+                .map(Json)
+                .map_err(to_py_err)
+        })
+    }
+
+    /// Check out a license file. `ttl` is in seconds, and `include` lists the
+    /// related resources to embed, entitlements by default.
+    #[pyo3(signature = (ttl=None, include=None))]
+    fn checkout<'py>(
+        &self,
+        py: Python<'py>,
+        ttl: Option<i64>,
+        include: Option<Vec<String>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .checkout(&LicenseCheckoutOpts { ttl, include })
+                .await
+                .map(LicenseFile::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    /// Check the license back in, invalidating its license files.
+    fn check_in<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .check_in()
+                .await
+                .map(License::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    /// Update the license's attributes. Passing `None` for a limit such as
+    /// `max_machines` clears it.
+    #[pyo3(signature = (**fields))]
+    fn update<'py>(
+        &self,
+        py: Python<'py>,
+        fields: Option<&Bound<'py, PyDict>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let fields: UpdateLicenseRequest = from_kwargs(py, fields, "license update")?;
+
+        let mut request = keygen_rs::license::LicenseUpdateRequest::new();
+        if let Some(name) = fields.name {
+            request = request.with_name(name);
+        }
+        if let Some(expiry) = fields.expiry {
+            request = request.with_expiry(expiry);
+        }
+        macro_rules! clearable {
+            ($($field:ident: $set:ident, $clear:ident);*) => {
+                $(match fields.$field {
+                    Some(Some(value)) => request = request.$set(value),
+                    Some(None) => request = request.$clear(),
+                    None => {}
+                })*
+            };
+        }
+        clearable!(
+            max_machines: with_max_machines, clear_max_machines;
+            max_processes: with_max_processes, clear_max_processes;
+            max_users: with_max_users, clear_max_users;
+            max_cores: with_max_cores, clear_max_cores;
+            max_uses: with_max_uses, clear_max_uses
+        );
+        if let Some(protected) = fields.protected {
+            request = request.with_protected(protected);
+        }
+        if let Some(suspended) = fields.suspended {
+            request = request.with_suspended(suspended);
+        }
+        if let Some(permissions) = fields.permissions {
+            request = request.with_permissions(permissions);
+        }
+        if let Some(metadata) = fields.metadata {
+            request = request.with_metadata(metadata);
+        }
+
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .update(request)
+                .await
+                .map(License::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    fn delete<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move { license.delete().await.map_err(to_py_err) })
+    }
+
+    fn suspend<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .suspend()
+                .await
+                .map(License::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    fn reinstate<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .reinstate()
+                .await
+                .map(License::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    fn renew<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license.renew().await.map(License::from).map_err(to_py_err)
+        })
+    }
+
+    fn revoke<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move { license.revoke().await.map_err(to_py_err) })
+    }
+
+    fn increment_usage<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .increment_usage()
+                .await
+                .map(License::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    fn decrement_usage<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .decrement_usage()
+                .await
+                .map(License::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    fn reset_usage<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .reset_usage()
+                .await
+                .map(License::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    fn attach_entitlements<'py>(
+        &self,
+        py: Python<'py>,
+        entitlement_ids: Vec<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .attach_entitlements(&entitlement_ids)
+                .await
+                .map_err(to_py_err)
+        })
+    }
+
+    fn detach_entitlements<'py>(
+        &self,
+        py: Python<'py>,
+        entitlement_ids: Vec<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .detach_entitlements(&entitlement_ids)
+                .await
+                .map_err(to_py_err)
+        })
+    }
+
+    fn change_policy<'py>(
+        &self,
+        py: Python<'py>,
+        policy_id: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .change_policy(&policy_id)
+                .await
+                .map(License::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    fn change_owner<'py>(&self, py: Python<'py>, owner_id: String) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .change_owner(&owner_id)
+                .await
+                .map(License::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    fn change_group<'py>(&self, py: Python<'py>, group_id: String) -> PyResult<Bound<'py, PyAny>> {
+        let license = self.0.clone();
+        awaitable(py, async move {
+            license
+                .change_group(&group_id)
+                .await
+                .map(License::from)
+                .map_err(to_py_err)
+        })
+    }
+}
+
+/// Validate the configured license key for the given machine fingerprints
+/// and entitlement codes. When the machine isn't activated yet, the
+/// `LicenseNotActivatedError` carries the license to activate it with.
+#[pyfunction]
+#[pyo3(signature = (fingerprints=None, entitlements=None))]
+pub fn validate(
+    py: Python<'_>,
+    fingerprints: Option<Vec<String>>,
+    entitlements: Option<Vec<String>>,
+) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        keygen_rs::validate(
+            &fingerprints.unwrap_or_default(),
+            &entitlements.unwrap_or_default(),
+        )
+        .await
+        .map(License::from)
+        .map_err(to_py_err)
+    })
+}
+
+/// Verify a signed license key offline with the configured public key,
+/// returning its decoded data. `scheme` is `ED25519_SIGN` or `RSA_2048_PKCS1_PSS_SIGN`.
+#[pyfunction]
+pub fn verify<'py>(
+    py: Python<'py>,
+    scheme: &Bound<'py, PyAny>,
+    signed_key: &str,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let scheme: SchemeCode = from_py(scheme, "scheme")?;
+    let data = keygen_rs::verify(scheme, signed_key).map_err(to_py_err)?;
+    Ok(PyBytes::new(py, &data))
+}
+
+/// Create a license for the policy `policy_id`.
+#[pyfunction]
+#[pyo3(signature = (policy_id, **fields))]
+pub fn create_license<'py>(
+    py: Python<'py>,
+    policy_id: String,
+    fields: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let fields: CreateLicenseRequest = from_kwargs(py, fields, "license")?;
+
+    let mut request = keygen_rs::license::LicenseCreateRequest::new(policy_id);
+    request.name = fields.name;
+    request.key = fields.key;
+    request.expiry = fields.expiry;
+    request.max_machines = fields.max_machines;
+    request.max_processes = fields.max_processes;
+    request.max_users = fields.max_users;
+    request.max_cores = fields.max_cores;
+    request.max_uses = fields.max_uses;
+    request.protected = fields.protected;
+    request.suspended = fields.suspended;
+    request.permissions = fields.permissions;
+    request.metadata = fields.metadata;
+    request.owner_id = fields.owner_id;
+    request.group_id = fields.group_id;
+
+    awaitable(py, async move {
+        keygen_rs::license::License::create(request)
+            .await
+            .map(License::from)
+            .map_err(to_py_err)
+    })
+}
+
+/// List licenses, filtered by keyword arguments such as `status`, `policy`,
+/// `user` or `metadata`, and paginated with `limit`, `page_size` and
+/// `page_number`.
+#[pyfunction]
+#[pyo3(signature = (**options))]
+pub fn list_licenses<'py>(
+    py: Python<'py>,
+    options: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let o: ListLicensesOptions = from_kwargs(py, options, "license list options")?;
+    let options = LicenseListOptions {
+        limit: o.limit,
+        page_size: o.page_size,
+        page_number: o.page_number,
+        status: o.status,
+        product: o.product,
+        policy: o.policy,
+        owner: o.owner,
+        user: o.user,
+        group: o.group,
+        machine: o.machine,
+        assigned: o.assigned,
+        unassigned: o.unassigned,
+        activated: o.activated,
+        metadata: o.metadata,
+        ..Default::default()
+    };
+
+    awaitable(py, async move {
+        keygen_rs::license::License::list(Some(&options))
+            .await
+            .map(|licenses| licenses.into_iter().map(License::from).collect::<Vec<_>>())
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn get_license(py: Python<'_>, id: String) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        keygen_rs::license::License::get(&id)
+            .await
+            .map(License::from)
+            .map_err(to_py_err)
+    })
+}
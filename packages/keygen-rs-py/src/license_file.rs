@@ -0,0 +1,105 @@
+use pyo3::prelude::*;
+
+use crate::errors::to_py_err;
+use crate::to_py;
+
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<LicenseFile>()?;
+    m.add_function(wrap_pyfunction!(verify_license_file, m)?)?;
+    m.add_function(wrap_pyfunction!(decrypt_license_file, m)?)?;
+    Ok(())
+}
+
+/// A license file, as returned by `License.checkout`. Save `certificate` to
+/// verify it offline later with `verify_license_file`.
+#[pyclass(module = "keygen_rs", frozen)]
+#[derive(Clone)]
+pub struct LicenseFile(pub(crate) keygen_rs::license_file::LicenseFile);
+
+impl From<keygen_rs::license_file::LicenseFile> for LicenseFile {
+    fn from(file: keygen_rs::license_file::LicenseFile) -> Self {
+        LicenseFile(file)
+    }
+}
+
+#[pymethods]
+impl LicenseFile {
+    #[getter]
+    fn id(&self) -> &str {
+        &self.0.id
+    }
+
+    #[getter]
+    fn certificate(&self) -> &str {
+        &self.0.certificate
+    }
+
+    /// Issue time as an ISO 8601 string.
+    #[getter]
+    fn issued(&self) -> String {
+        self.0.issued.to_rfc3339()
+    }
+
+    /// Expiry as an ISO 8601 string.
+    #[getter]
+    fn expiry(&self) -> String {
+        self.0.expiry.to_rfc3339()
+    }
+
+    #[getter]
+    fn ttl(&self) -> i32 {
+        self.0.ttl
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "LicenseFile(id={:?}, expiry={:?})",
+            self.0.id,
+            self.expiry()
+        )
+    }
+
+    /// Verify the file's signature against the configured public keys.
+    fn verify(&self) -> PyResult<()> {
+        self.0.verify().map_err(to_py_err)
+    }
+
+    /// Decrypt the file with the license key, returning its dataset as a dict.
+    fn decrypt(&self, py: Python<'_>, key: &str) -> PyResult<PyObject> {
+        let dataset = self.0.decrypt(key).map_err(to_py_err)?;
+        to_py(py, &dataset)
+    }
+}
+
+fn unverified(certificate: &str) -> keygen_rs::license_file::LicenseFile {
+    keygen_rs::license_file::LicenseFile {
+        certificate: certificate.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Verify a license file's signature against the configured public keys,
+/// decrypt it with the license key and check that it hasn't expired,
+/// returning its dataset as a dict. An expired file raises
+/// `FileExpiredError` with the dataset attached.
+#[pyfunction]
+pub fn verify_license_file(
+    py: Python<'_>,
+    certificate: &str,
+    license_key: &str,
+) -> PyResult<PyObject> {
+    unverified(certificate).verify().map_err(to_py_err)?;
+
+    let file = keygen_rs::license_file::LicenseFile::from_cert(license_key, certificate)
+        .map_err(to_py_err)?;
+    let dataset = file.decrypt(license_key).map_err(to_py_err)?;
+    to_py(py, &dataset)
+}
+
+/// Decrypt a license file with the license key without verifying its
+/// signature or expiry, returning its dataset as a dict.
+#[pyfunction]
+pub fn decrypt_license_file(py: Python<'_>, certificate: &str, key: &str) -> PyResult<PyObject> {
+    let dataset = unverified(certificate).decrypt(key).map_err(to_py_err)?;
+    to_py(py, &dataset)
+}
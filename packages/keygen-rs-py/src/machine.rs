@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+
+use keygen_rs::machine::{
+    MachineCheckoutOpts, MachineCreateRequest, MachineListFilters, MachineUpdateRequest,
+};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde_json::Value;
+
+use crate::errors::to_py_err;
+use crate::machine_file::MachineFile;
+use crate::{awaitable, from_kwargs, request_kwargs, to_py};
+
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Machine>()?;
+    m.add_function(wrap_pyfunction!(create_machine, m)?)?;
+    m.add_function(wrap_pyfunction!(list_machines, m)?)?;
+    m.add_function(wrap_pyfunction!(get_machine, m)?)?;
+    Ok(())
+}
+
+request_kwargs!(CreateMachineKwargs => MachineCreateRequest {
+    fingerprint: String,
+    name: Option<String>,
+    platform: Option<String>,
+    hostname: Option<String>,
+    ip: Option<String>,
+    cores: Option<i32>,
+    metadata: Option<HashMap<String, Value>>,
+    license_id: String,
+});
+
+request_kwargs!(UpdateMachineKwargs => MachineUpdateRequest {
+    name: Option<String>,
+    platform: Option<String>,
+    hostname: Option<String>,
+    ip: Option<String>,
+    cores: Option<i32>,
+    metadata: Option<HashMap<String, Value>>,
+});
+
+request_kwargs!(MachineFilterKwargs => MachineListFilters {
+    license: Option<String>,
+    user: Option<String>,
+    platform: Option<String>,
+    name: Option<String>,
+    fingerprint: Option<String>,
+    ip: Option<String>,
+    hostname: Option<String>,
+    product: Option<String>,
+    owner: Option<String>,
+    group: Option<String>,
+    policy: Option<String>,
+    key: Option<String>,
+    metadata: Option<HashMap<String, Value>>,
+    page_number: Option<i32>,
+    page_size: Option<i32>,
+    limit: Option<i32>,
+});
+
+/// A machine, as returned by `License.activate` and the machine management
+/// functions.
+#[pyclass(module = "keygen_rs", frozen)]
+#[derive(Clone)]
+pub struct Machine(pub(crate) keygen_rs::machine::Machine);
+
+impl From<keygen_rs::machine::Machine> for Machine {
+    fn from(machine: keygen_rs::machine::Machine) -> Self {
+        Machine(machine)
+    }
+}
+
+#[pymethods]
+impl Machine {
+    #[getter]
+    fn id(&self) -> &str {
+        &self.0.id
+    }
+
+    #[getter]
+    fn fingerprint(&self) -> &str {
+        &self.0.fingerprint
+    }
+
+    #[getter]
+    fn name(&self) -> Option<&str> {
+        self.0.name.as_deref()
+    }
+
+    #[getter]
+    fn platform(&self) -> Option<&str> {
+        self.0.platform.as_deref()
+    }
+
+    #[getter]
+    fn hostname(&self) -> Option<&str> {
+        self.0.hostname.as_deref()
+    }
+
+    #[getter]
+    fn ip(&self) -> Option<&str> {
+        self.0.ip.as_deref()
+    }
+
+    #[getter]
+    fn cores(&self) -> Option<i32> {
+        self.0.cores
+    }
+
+    #[getter]
+    fn metadata(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_py(py, &self.0.metadata)
+    }
+
+    #[getter]
+    fn require_heartbeat(&self) -> bool {
+        self.0.require_heartbeat
+    }
+
+    #[getter]
+    fn heartbeat_status(&self) -> &str {
+        &self.0.heartbeat_status
+    }
+
+    #[getter]
+    fn heartbeat_duration(&self) -> Option<i32> {
+        self.0.heartbeat_duration
+    }
+
+    /// Creation time as an ISO 8601 string.
+    #[getter]
+    fn created(&self) -> String {
+        self.0.created.to_rfc3339()
+    }
+
+    /// Last update time as an ISO 8601 string.
+    #[getter]
+    fn updated(&self) -> String {
+        self.0.updated.to_rfc3339()
+    }
+
+    #[getter]
+    fn account_id(&self) -> Option<&str> {
+        self.0.account_id.as_deref()
+    }
+
+    #[getter]
+    fn environment_id(&self) -> Option<&str> {
+        self.0.environment_id.as_deref()
+    }
+
+    #[getter]
+    fn product_id(&self) -> Option<&str> {
+        self.0.product_id.as_deref()
+    }
+
+    #[getter]
+    fn license_id(&self) -> Option<&str> {
+        self.0.license_id.as_deref()
+    }
+
+    #[getter]
+    fn owner_id(&self) -> Option<&str> {
+        self.0.owner_id.as_deref()
+    }
+
+    #[getter]
+    fn group_id(&self) -> Option<&str> {
+        self.0.group_id.as_deref()
+    }
+
+    /// The machine as a dict.
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_py(py, &self.0)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Machine(id={:?}, fingerprint={:?})",
+            self.0.id, self.0.fingerprint
+        )
+    }
+
+    fn deactivate<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let machine = self.0.clone();
+        awaitable(
+            py,
+            async move { machine.deactivate().await.map_err(to_py_err) },
+        )
+    }
+
+    /// Check out a machine file. `ttl` is in seconds, and `include` lists the
+    /// related resources to embed, the license by default.
+    #[pyo3(signature = (ttl=None, include=None))]
+    fn checkout<'py>(
+        &self,
+        py: Python<'py>,
+        ttl: Option<i64>,
+        include: Option<Vec<String>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let machine = self.0.clone();
+        awaitable(py, async move {
+            machine
+                .checkout(&MachineCheckoutOpts { ttl, include })
+                .await
+                .map(MachineFile::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    /// Send a heartbeat ping.
+    fn ping<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let machine = self.0.clone();
+        awaitable(py, async move {
+            machine.ping().await.map(Machine::from).map_err(to_py_err)
+        })
+    }
+
+    /// Reset the machine's heartbeat.
+    fn reset<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let machine = self.0.clone();
+        awaitable(py, async move {
+            machine.reset().await.map(Machine::from).map_err(to_py_err)
+        })
+    }
+
+    /// Update the machine's `name`, `platform`, `hostname`, `ip`, `cores` or
+    /// `metadata`.
+    #[pyo3(signature = (**fields))]
+    fn update<'py>(
+        &self,
+        py: Python<'py>,
+        fields: Option<&Bound<'py, PyDict>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let request: UpdateMachineKwargs = from_kwargs(py, fields, "machine update")?;
+        let machine = self.0.clone();
+        awaitable(py, async move {
+            machine
+                .update(request.into())
+                .await
+                .map(Machine::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    fn change_owner<'py>(&self, py: Python<'py>, owner_id: String) -> PyResult<Bound<'py, PyAny>> {
+        let machine = self.0.clone();
+        awaitable(py, async move {
+            machine
+                .change_owner(&owner_id)
+                .await
+                .map(Machine::from)
+                .map_err(to_py_err)
+        })
+    }
+
+    fn change_group<'py>(&self, py: Python<'py>, group_id: String) -> PyResult<Bound<'py, PyAny>> {
+        let machine = self.0.clone();
+        awaitable(py, async move {
+            machine
+                .change_group(&group_id)
+                .await
+                .map(Machine::from)
+                .map_err(to_py_err)
+        })
+    }
+}
+
+/// Create a machine for the license `license_id`. Other attributes such as
+/// `name` or `metadata` are passed as keyword arguments.
+#[pyfunction]
+#[pyo3(signature = (license_id, fingerprint, **fields))]
+pub fn create_machine<'py>(
+    py: Python<'py>,
+    license_id: String,
+    fingerprint: String,
+    fields: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let fields = fields.cloned().unwrap_or_else(|| PyDict::new(py));
+    fields.set_item("license_id", license_id)?;
+    fields.set_item("fingerprint", fingerprint)?;
+    let request: CreateMachineKwargs = from_kwargs(py, Some(&fields), "machine")?;
+
+    awaitable(py, async move {
+        keygen_rs::machine::Machine::create(request.into())
+            .await
+            .map(Machine::from)
+            .map_err(to_py_err)
+    })
+}
+
+/// List machines, filtered by keyword arguments such as `license`,
+/// `fingerprint` or `metadata`, and paginated with `limit`, `page_size` and
+/// `page_number`.
+#[pyfunction]
+#[pyo3(signature = (**filters))]
+pub fn list_machines<'py>(
+    py: Python<'py>,
+    filters: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let filters: MachineFilterKwargs = from_kwargs(py, filters, "machine filters")?;
+    awaitable(py, async move {
+        keygen_rs::machine::Machine::list(Some(filters.into()))
+            .await
+            .map(|machines| machines.into_iter().map(Machine::from).collect::<Vec<_>>())
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn get_machine(py: Python<'_>, id: String) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        keygen_rs::machine::Machine::get(&id)
+            .await
+            .map(Machine::from)
+            .map_err(to_py_err)
+    })
+}
@@ -0,0 +1,112 @@
+use keygen_rs::errors::Error;
+use pyo3::prelude::*;
+
+use crate::errors::to_py_err;
+use crate::to_py;
+
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<MachineFile>()?;
+    m.add_function(wrap_pyfunction!(verify_machine_file, m)?)?;
+    m.add_function(wrap_pyfunction!(decrypt_machine_file, m)?)?;
+    Ok(())
+}
+
+/// A machine file, as returned by `Machine.checkout`. Save `certificate` to
+/// verify it offline later with `verify_machine_file`.
+#[pyclass(module = "keygen_rs", frozen)]
+#[derive(Clone)]
+pub struct MachineFile(pub(crate) keygen_rs::machine_file::MachineFile);
+
+impl From<keygen_rs::machine_file::MachineFile> for MachineFile {
+    fn from(file: keygen_rs::machine_file::MachineFile) -> Self {
+        MachineFile(file)
+    }
+}
+
+#[pymethods]
+impl MachineFile {
+    #[getter]
+    fn id(&self) -> &str {
+        &self.0.id
+    }
+
+    #[getter]
+    fn certificate(&self) -> &str {
+        &self.0.certificate
+    }
+
+    /// Issue time as an ISO 8601 string.
+    #[getter]
+    fn issued(&self) -> String {
+        self.0.issued.to_rfc3339()
+    }
+
+    /// Expiry as an ISO 8601 string.
+    #[getter]
+    fn expiry(&self) -> String {
+        self.0.expiry.to_rfc3339()
+    }
+
+    #[getter]
+    fn ttl(&self) -> i32 {
+        self.0.ttl
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "MachineFile(id={:?}, expiry={:?})",
+            self.0.id,
+            self.expiry()
+        )
+    }
+
+    /// Verify the file's signature against the configured public keys and
+    /// check that it hasn't expired.
+    fn verify(&self) -> PyResult<()> {
+        self.0.verify().map_err(to_py_err)
+    }
+
+    /// Decrypt the file with the license key followed by the machine
+    /// fingerprint, returning its dataset as a dict.
+    fn decrypt(&self, py: Python<'_>, key: &str) -> PyResult<PyObject> {
+        let dataset = self.0.decrypt(key).map_err(to_py_err)?;
+        to_py(py, &dataset)
+    }
+}
+
+/// Verify a machine file's signature against the configured public keys,
+/// decrypt it and check that it hasn't expired, returning its dataset as a
+/// dict. The decryption key is the license key followed by the machine
+/// fingerprint. An expired file raises `FileExpiredError` with the dataset
+/// attached.
+#[pyfunction]
+pub fn verify_machine_file(
+    py: Python<'_>,
+    certificate: &str,
+    decryption_key: &str,
+) -> PyResult<PyObject> {
+    let file = keygen_rs::machine_file::MachineFile::from_cert(decryption_key, certificate)
+        .map_err(to_py_err)?;
+    file.verify_signature().map_err(to_py_err)?;
+    let dataset = file.decrypt(decryption_key).map_err(to_py_err)?;
+
+    match file.validate_ttl() {
+        Ok(()) => to_py(py, &dataset),
+        Err(Error::MachineFileExpired(_)) => {
+            Err(to_py_err(Error::MachineFileExpired(Box::new(dataset))))
+        }
+        Err(e) => Err(to_py_err(e)),
+    }
+}
+
+/// Decrypt a machine file without verifying its signature or expiry,
+/// returning its dataset as a dict.
+#[pyfunction]
+pub fn decrypt_machine_file(py: Python<'_>, certificate: &str, key: &str) -> PyResult<PyObject> {
+    let file = keygen_rs::machine_file::MachineFile {
+        certificate: certificate.to_string(),
+        ..Default::default()
+    };
+    let dataset = file.decrypt(key).map_err(to_py_err)?;
+    to_py(py, &dataset)
+}
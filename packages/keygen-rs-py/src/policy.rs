@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use keygen_rs::policy::{
+    AuthenticationStrategy, CreatePolicyRequest, ExpirationStrategy, LeasingStrategy,
+    ListPoliciesOptions, MatchingStrategy, OverageStrategy, Policy, Scheme, TransferStrategy,
+    UniquenessStrategy, UpdatePolicyRequest,
+};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde_json::Value;
+
+use crate::errors::to_py_err;
+use crate::{awaitable, from_kwargs, request_kwargs, Json};
+
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(create_policy, m)?)?;
+    m.add_function(wrap_pyfunction!(list_policies, m)?)?;
+    m.add_function(wrap_pyfunction!(get_policy, m)?)?;
+    m.add_function(wrap_pyfunction!(update_policy, m)?)?;
+    m.add_function(wrap_pyfunction!(delete_policy, m)?)?;
+    m.add_function(wrap_pyfunction!(attach_policy_entitlements, m)?)?;
+    m.add_function(wrap_pyfunction!(detach_policy_entitlements, m)?)?;
+    Ok(())
+}
+
+request_kwargs!(CreatePolicyKwargs => CreatePolicyRequest {
+    name: String,
+    duration: Option<i64>,
+    strict: Option<bool>,
+    floating: Option<bool>,
+    require_heartbeat: Option<bool>,
+    heartbeat_duration: Option<i64>,
+    heartbeat_cull_strategy: Option<String>,
+    heartbeat_resurrection_strategy: Option<String>,
+    heartbeat_basis: Option<String>,
+    machine_uniqueness_strategy: Option<UniquenessStrategy>,
+    component_uniqueness_strategy: Option<UniquenessStrategy>,
+    machine_matching_strategy: Option<MatchingStrategy>,
+    component_matching_strategy: Option<MatchingStrategy>,
+    expiration_strategy: Option<ExpirationStrategy>,
+    expiration_basis: Option<String>,
+    renewal_basis: Option<String>,
+    authentication_strategy: Option<AuthenticationStrategy>,
+    machine_leasing_strategy: Option<LeasingStrategy>,
+    process_leasing_strategy: Option<LeasingStrategy>,
+    overage_strategy: Option<OverageStrategy>,
+    transfer_strategy: Option<TransferStrategy>,
+    max_machines: Option<i32>,
+    max_processes: Option<i32>,
+    max_cores: Option<i32>,
+    max_uses: Option<i32>,
+    encrypted: Option<bool>,
+    protected: Option<bool>,
+    require_check_in: Option<bool>,
+    check_in_interval: Option<String>,
+    check_in_interval_count: Option<i32>,
+    use_pool: Option<bool>,
+    max_licenses: Option<i32>,
+    max_users: Option<i32>,
+    scheme: Option<Scheme>,
+    metadata: Option<HashMap<String, Value>>,
+    product_id: String,
+});
+
+request_kwargs!(UpdatePolicyKwargs => UpdatePolicyRequest {
+    name: Option<String>,
+    duration: Option<i64>,
+    strict: Option<bool>,
+    floating: Option<bool>,
+    require_heartbeat: Option<bool>,
+    heartbeat_duration: Option<i64>,
+    heartbeat_cull_strategy: Option<String>,
+    heartbeat_resurrection_strategy: Option<String>,
+    heartbeat_basis: Option<String>,
+    machine_uniqueness_strategy: Option<UniquenessStrategy>,
+    component_uniqueness_strategy: Option<UniquenessStrategy>,
+    machine_matching_strategy: Option<MatchingStrategy>,
+    component_matching_strategy: Option<MatchingStrategy>,
+    expiration_strategy: Option<ExpirationStrategy>,
+    expiration_basis: Option<String>,
+    renewal_basis: Option<String>,
+    authentication_strategy: Option<AuthenticationStrategy>,
+    machine_leasing_strategy: Option<LeasingStrategy>,
+    process_leasing_strategy: Option<LeasingStrategy>,
+    overage_strategy: Option<OverageStrategy>,
+    transfer_strategy: Option<TransferStrategy>,
+    max_machines: Option<i32>,
+    max_processes: Option<i32>,
+    max_cores: Option<i32>,
+    max_uses: Option<i32>,
+    protected: Option<bool>,
+    require_check_in: Option<bool>,
+    check_in_interval: Option<String>,
+    check_in_interval_count: Option<i32>,
+    max_users: Option<i32>,
+    scheme: Option<Scheme>,
+    metadata: Option<HashMap<String, Value>>,
+});
+
+request_kwargs!(ListPoliciesKwargs => ListPoliciesOptions {
+    limit: Option<u32>,
+    page_size: Option<u32>,
+    page_number: Option<u32>,
+    product: Option<String>,
+});
+
+fn make_policy(id: String) -> Policy {
+    Policy {
+        id,
+        name: String::new(),
+        duration: None,
+        strict: false,
+        floating: false,
+        require_heartbeat: false,
+        heartbeat_duration: None,
+        heartbeat_cull_strategy: None,
+        heartbeat_resurrection_strategy: None,
+        heartbeat_basis: None,
+        machine_uniqueness_strategy: None,
+        component_uniqueness_strategy: None,
+        machine_matching_strategy: None,
+        component_matching_strategy: None,
+        expiration_strategy: ExpirationStrategy::RestrictAccess,
+        expiration_basis: None,
+        renewal_basis: None,
+        authentication_strategy: AuthenticationStrategy::Token,
+        machine_leasing_strategy: LeasingStrategy::PerMachine,
+        process_leasing_strategy: LeasingStrategy::PerMachine,
+        overage_strategy: OverageStrategy::NoOverage,
+        transfer_strategy: TransferStrategy::KeepPolicy,
+        max_machines: None,
+        max_processes: None,
+        max_cores: None,
+        max_uses: None,
+        encrypted: false,
+        protected: false,
+        require_check_in: false,
+        check_in_interval: None,
+        check_in_interval_count: None,
+        use_pool: false,
+        max_licenses: None,
+        max_users: None,
+        scheme: None,
+        metadata: None,
+        created: String::new(),
+        updated: String::new(),
+        account_id: None,
+        product_id: None,
+    }
+}
+
+/// Create a policy named `name` for the product `product_id`, resolving to it
+/// as a dict. Strategies use the API's names, such as `UNIQUE_PER_LICENSE`.
+#[pyfunction]
+#[pyo3(signature = (product_id, name, **fields))]
+pub fn create_policy<'py>(
+    py: Python<'py>,
+    product_id: String,
+    name: String,
+    fields: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let fields = fields.cloned().unwrap_or_else(|| PyDict::new(py));
+    fields.set_item("product_id", product_id)?;
+    fields.set_item("name", name)?;
+    let request: CreatePolicyKwargs = from_kwargs(py, Some(&fields), "policy")?;
+    awaitable(py, async move {
+        Policy::create(request.into())
+            .await
+            .map(Json)
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (**options))]
+pub fn list_policies<'py>(
+    py: Python<'py>,
+    options: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let options: ListPoliciesKwargs = from_kwargs(py, options, "policy list options")?;
+    awaitable(py, async move {
+        Policy::list(Some(options.into()))
+            .await
+            .map(Json)
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn get_policy(py: Python<'_>, id: String) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        Policy::get(&id).await.map(Json).map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (id, **fields))]
+pub fn update_policy<'py>(
+    py: Python<'py>,
+    id: String,
+    fields: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let request: UpdatePolicyKwargs = from_kwargs(py, fields, "policy update")?;
+    awaitable(py, async move {
+        make_policy(id)
+            .update(request.into())
+            .await
+            .map(Json)
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn delete_policy(py: Python<'_>, id: String) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        make_policy(id).delete().await.map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn attach_policy_entitlements(
+    py: Python<'_>,
+    id: String,
+    entitlement_ids: Vec<String>,
+) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        make_policy(id)
+            .attach_entitlements(&entitlement_ids)
+            .await
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn detach_policy_entitlements(
+    py: Python<'_>,
+    id: String,
+    entitlement_ids: Vec<String>,
+) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        make_policy(id)
+            .detach_entitlements(&entitlement_ids)
+            .await
+            .map_err(to_py_err)
+    })
+}
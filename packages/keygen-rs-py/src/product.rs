@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use keygen_rs::product::{
+    CreateProductRequest, DistributionStrategy, ListProductsOptions, Platform, Product,
+    UpdateProductRequest,
+};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde_json::Value;
+
+use crate::errors::to_py_err;
+use crate::{awaitable, from_kwargs, request_kwargs, Json};
+
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(create_product, m)?)?;
+    m.add_function(wrap_pyfunction!(list_products, m)?)?;
+    m.add_function(wrap_pyfunction!(get_product, m)?)?;
+    m.add_function(wrap_pyfunction!(update_product, m)?)?;
+    m.add_function(wrap_pyfunction!(delete_product, m)?)?;
+    Ok(())
+}
+
+request_kwargs!(CreateProductKwargs => CreateProductRequest {
+    name: String,
+    code: Option<String>,
+    distribution_strategy: Option<DistributionStrategy>,
+    url: Option<String>,
+    platforms: Option<Vec<Platform>>,
+    permissions: Option<Vec<String>>,
+    metadata: Option<HashMap<String, Value>>,
+});
+
+request_kwargs!(UpdateProductKwargs => UpdateProductRequest {
+    name: Option<String>,
+    code: Option<String>,
+    distribution_strategy: Option<DistributionStrategy>,
+    url: Option<String>,
+    platforms: Option<Vec<Platform>>,
+    permissions: Option<Vec<String>>,
+    metadata: Option<HashMap<String, Value>>,
+});
+
+request_kwargs!(ListProductsKwargs => ListProductsOptions {
+    limit: Option<u32>,
+    page_size: Option<u32>,
+    page_number: Option<u32>,
+});
+
+fn make_product(id: String) -> Product {
+    Product {
+        id,
+        name: String::new(),
+        code: None,
+        distribution_strategy: None,
+        url: None,
+        platforms: None,
+        permissions: None,
+        metadata: None,
+        created: String::new(),
+        updated: String::new(),
+        account_id: None,
+    }
+}
+
+/// Create a product named `name`, resolving to it as a dict.
+#[pyfunction]
+#[pyo3(signature = (name, **fields))]
+pub fn create_product<'py>(
+    py: Python<'py>,
+    name: String,
+    fields: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let fields = fields.cloned().unwrap_or_else(|| PyDict::new(py));
+    fields.set_item("name", name)?;
+    let request: CreateProductKwargs = from_kwargs(py, Some(&fields), "product")?;
+    awaitable(py, async move {
+        Product::create(request.into())
+            .await
+            .map(Json)
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (**options))]
+pub fn list_products<'py>(
+    py: Python<'py>,
+    options: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let options: ListProductsKwargs = from_kwargs(py, options, "product list options")?;
+    awaitable(py, async move {
+        Product::list(Some(options.into()))
+            .await
+            .map(Json)
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn get_product(py: Python<'_>, id: String) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        Product::get(&id).await.map(Json).map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (id, **fields))]
+pub fn update_product<'py>(
+    py: Python<'py>,
+    id: String,
+    fields: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let request: UpdateProductKwargs = from_kwargs(py, fields, "product update")?;
+    awaitable(py, async move {
+        make_product(id)
+            .update(request.into())
+            .await
+            .map(Json)
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn delete_product(py: Python<'_>, id: String) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        make_product(id).delete().await.map_err(to_py_err)
+    })
+}
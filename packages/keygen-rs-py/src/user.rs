@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use keygen_rs::user::{
+    CreateUserRequest, ListUsersOptions, UpdateUserRequest, User, UserRole, UserStatus,
+};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde_json::Value;
+
+use crate::errors::to_py_err;
+use crate::{awaitable, from_kwargs, request_kwargs, Json};
+
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(create_user, m)?)?;
+    m.add_function(wrap_pyfunction!(list_users, m)?)?;
+    m.add_function(wrap_pyfunction!(get_user, m)?)?;
+    m.add_function(wrap_pyfunction!(update_user, m)?)?;
+    m.add_function(wrap_pyfunction!(delete_user, m)?)?;
+    m.add_function(wrap_pyfunction!(ban_user, m)?)?;
+    m.add_function(wrap_pyfunction!(unban_user, m)?)?;
+    Ok(())
+}
+
+request_kwargs!(CreateUserKwargs => CreateUserRequest {
+    email: String,
+    first_name: Option<String>,
+    last_name: Option<String>,
+    role: Option<UserRole>,
+    permissions: Option<Vec<String>>,
+    metadata: Option<HashMap<String, Value>>,
+});
+
+request_kwargs!(UpdateUserKwargs => UpdateUserRequest {
+    email: Option<String>,
+    first_name: Option<String>,
+    last_name: Option<String>,
+    role: Option<UserRole>,
+    metadata: Option<HashMap<String, Value>>,
+});
+
+request_kwargs!(ListUsersKwargs => ListUsersOptions {
+    limit: Option<u32>,
+    page_size: Option<u32>,
+    page_number: Option<u32>,
+    status: Option<UserStatus>,
+    assigned: Option<bool>,
+    product: Option<String>,
+    group: Option<String>,
+    roles: Option<Vec<UserRole>>,
+    sort: Option<String>,
+    include: Option<String>,
+    metadata: Option<HashMap<String, Value>>,
+});
+
+fn make_user(id: String) -> User {
+    User {
+        id,
+        email: String::new(),
+        first_name: None,
+        last_name: None,
+        full_name: None,
+        status: UserStatus::Active,
+        role: UserRole::User,
+        permissions: None,
+        metadata: None,
+        last_seen_at: None,
+        ban_reason: None,
+        created: String::new(),
+        updated: String::new(),
+    }
+}
+
+/// Create a user with the given `email`, resolving to it as a dict.
+#[pyfunction]
+#[pyo3(signature = (email, **fields))]
+pub fn create_user<'py>(
+    py: Python<'py>,
+    email: String,
+    fields: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let fields = fields.cloned().unwrap_or_else(|| PyDict::new(py));
+    fields.set_item("email", email)?;
+    let request: CreateUserKwargs = from_kwargs(py, Some(&fields), "user")?;
+    awaitable(py, async move {
+        User::create(request.into())
+            .await
+            .map(Json)
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (**options))]
+pub fn list_users<'py>(
+    py: Python<'py>,
+    options: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let options: ListUsersKwargs = from_kwargs(py, options, "user list options")?;
+    awaitable(py, async move {
+        User::list(Some(options.into()))
+            .await
+            .map(|result| Json(result.users))
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn get_user(py: Python<'_>, id: String) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        User::get(&id).await.map(Json).map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (id, **fields))]
+pub fn update_user<'py>(
+    py: Python<'py>,
+    id: String,
+    fields: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let request: UpdateUserKwargs = from_kwargs(py, fields, "user update")?;
+    awaitable(py, async move {
+        make_user(id)
+            .update(request.into())
+            .await
+            .map(Json)
+            .map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn delete_user(py: Python<'_>, id: String) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        make_user(id).delete().await.map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn ban_user(py: Python<'_>, id: String) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        make_user(id).ban().await.map(Json).map_err(to_py_err)
+    })
+}
+
+#[pyfunction]
+pub fn unban_user(py: Python<'_>, id: String) -> PyResult<Bound<'_, PyAny>> {
+    awaitable(py, async move {
+        make_user(id).unban().await.map(Json).map_err(to_py_err)
+    })
+}
@@ -0,0 +1,168 @@
+import asyncio
+import unittest
+
+import keygen_rs
+
+
+class ExportsTest(unittest.TestCase):
+    def test_functions(self):
+        for name in [
+            "set_config",
+            "set_config_from_env",
+            "get_config",
+            "reset_config",
+            "validate",
+            "verify",
+            "verify_license_file",
+            "decrypt_license_file",
+            "verify_machine_file",
+            "decrypt_machine_file",
+            "create_license",
+            "list_licenses",
+            "get_license",
+            "create_machine",
+            "list_machines",
+            "get_machine",
+            "create_product",
+            "create_policy",
+            "attach_policy_entitlements",
+            "create_user",
+            "ban_user",
+            "create_entitlement",
+            "create_group",
+        ]:
+            with self.subTest(name=name):
+                self.assertTrue(callable(getattr(keygen_rs, name)))
+
+    def test_exception_hierarchy(self):
+        self.assertTrue(issubclass(keygen_rs.KeygenError, Exception))
+        self.assertTrue(
+            issubclass(keygen_rs.LicenseNotActivatedError, keygen_rs.LicenseError)
+        )
+        self.assertTrue(issubclass(keygen_rs.LicenseError, keygen_rs.KeygenError))
+        self.assertTrue(issubclass(keygen_rs.FileExpiredError, keygen_rs.FileError))
+        self.assertTrue(
+            issubclass(
+                keygen_rs.FeatureNotSupportedError, keygen_rs.ConfigurationError
+            )
+        )
+        self.assertTrue(issubclass(keygen_rs.HeartbeatError, keygen_rs.MachineError))
+
+
+class ConfigTest(unittest.TestCase):
+    def setUp(self):
+        keygen_rs.reset_config()
+
+    def test_roundtrip(self):
+        keygen_rs.set_config(
+            account="acc-123",
+            product="prod-456",
+            license_key="LK-789",
+            public_key="a" * 64,
+            token="tok-xyz",
+        )
+        config = keygen_rs.get_config()
+        self.assertEqual(config["account"], "acc-123")
+        self.assertEqual(config["product"], "prod-456")
+        self.assertEqual(config["license_key"], "LK-789")
+        self.assertEqual(config["public_key"], "a" * 64)
+        self.assertEqual(config["token"], "tok-xyz")
+
+    def test_defaults(self):
+        keygen_rs.set_config(account="a", product="p")
+        config = keygen_rs.get_config()
+        self.assertEqual(config["api_url"], "https://api.keygen.sh")
+        self.assertEqual(config["api_prefix"], "v1")
+        self.assertIsNone(config.get("license_key"))
+
+    def test_keywords_override_dict(self):
+        keygen_rs.set_config({"account": "first", "product": "p"}, account="second")
+        self.assertEqual(keygen_rs.get_config()["account"], "second")
+
+    def test_reset(self):
+        keygen_rs.set_config(account="a", product="p", license_key="LK")
+        keygen_rs.reset_config()
+        config = keygen_rs.get_config()
+        self.assertEqual(config["account"], "")
+        self.assertIsNone(config.get("license_key"))
+
+    def test_invalid_url(self):
+        with self.assertRaises(keygen_rs.ConfigurationError) as cm:
+            keygen_rs.set_config(account="a", product="p", api_url="not a url")
+        self.assertIsInstance(cm.exception.code, str)
+        self.assertIsInstance(cm.exception.detail, str)
+
+    def test_unknown_setting(self):
+        with self.assertRaises(ValueError):
+            keygen_rs.set_config(account="a", product="p", bogus=1)
+
+
+class VerifyTest(unittest.TestCase):
+    def setUp(self):
+        keygen_rs.reset_config()
+
+    def test_missing_public_key(self):
+        keygen_rs.set_config(account="a", product="p")
+        with self.assertRaises(keygen_rs.ConfigurationError):
+            keygen_rs.verify("ED25519_SIGN", "some-signed-key")
+
+    def test_invalid_scheme(self):
+        keygen_rs.set_config(account="a", product="p", public_key="a" * 64)
+        with self.assertRaises(ValueError):
+            keygen_rs.verify("INVALID_SCHEME", "some-key")
+
+    def test_malformed_key(self):
+        keygen_rs.set_config(account="a", product="p", public_key="a" * 64)
+        with self.assertRaises(keygen_rs.KeygenError) as cm:
+            keygen_rs.verify("ED25519_SIGN", "not-a-valid-signed-key")
+        self.assertTrue(str(cm.exception).startswith(f"[{cm.exception.code}]"))
+
+
+class FileTest(unittest.TestCase):
+    def setUp(self):
+        keygen_rs.reset_config()
+        keygen_rs.set_config(account="a", product="p", public_key="a" * 64)
+
+    def test_invalid_license_file(self):
+        with self.assertRaises(keygen_rs.FileError):
+            keygen_rs.verify_license_file("garbage", "LK")
+
+    def test_invalid_machine_file(self):
+        with self.assertRaises(keygen_rs.FileError):
+            keygen_rs.verify_machine_file("garbage", "LK-fingerprint")
+
+
+class ManagementTest(unittest.TestCase):
+    def test_unknown_field(self):
+        with self.assertRaises(ValueError):
+            keygen_rs.create_policy("prod-1", "Pro", bogus=1)
+
+    def test_invalid_enum(self):
+        with self.assertRaises(ValueError):
+            keygen_rs.list_users(status="NOPE")
+
+
+class AsyncTest(unittest.TestCase):
+    def setUp(self):
+        keygen_rs.reset_config()
+        keygen_rs.set_config(
+            account="a",
+            product="p",
+            license_key="LK",
+            api_url="https://127.0.0.1:9",
+        )
+
+    def test_returns_awaitable(self):
+        async def run():
+            with self.assertRaises(keygen_rs.NetworkError):
+                await keygen_rs.validate(["fingerprint"])
+
+        asyncio.run(run())
+
+    def test_requires_running_loop(self):
+        with self.assertRaises(RuntimeError):
+            keygen_rs.validate(["fingerprint"])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -250,3 +250,51 @@ describe("input validation", () => {
     assert.throws(() => wasm.setConfig({ account: "a" }));
   });
 });
+
+// ---------------------------------------------------------------------------
+// Error format
+// ---------------------------------------------------------------------------
+
+describe("error format", () => {
+  beforeEach(() => {
+    wasm.resetConfig();
+  });
+
+  it("keygen errors are structured", () => {
+    wasm.setConfig({ account: "a", product: "p", publicKey: "a".repeat(64) });
+
+    assert.throws(
+      () => wasm.verify("ED25519_SIGN", "not-a-valid-signed-key"),
+      (err) => {
+        assert.ok(err instanceof Error);
+        assert.equal(err.name, "KeygenError");
+        assert.equal(typeof err.kind, "string");
+        assert.equal(typeof err.detail, "string");
+        assert.equal(err.message, `[${err.code}] ${err.detail}`);
+        return true;
+      },
+    );
+  });
+
+  it("file errors carry their kind", () => {
+    assert.throws(
+      () => wasm.verifyLicenseFile("garbage"),
+      (err) => {
+        assert.equal(err.kind, "LicenseFileInvalid");
+        assert.equal(err.code, "ERROR");
+        return true;
+      },
+    );
+  });
+
+  it("invalid arguments are not keygen errors", () => {
+    assert.throws(
+      () => wasm.verify("INVALID_SCHEME", "some-key"),
+      (err) => {
+        assert.notEqual(err.name, "KeygenError");
+        assert.equal(err.kind, undefined);
+        return true;
+      },
+    );
+  });
+});
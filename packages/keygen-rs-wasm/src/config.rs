@@ -46,13 +46,18 @@ impl From<&KeygenConfig> for keygen_rs::config::KeygenConfig {
             api_prefix: cfg.api_prefix.clone().unwrap_or_else(|| "v1".to_string()),
             environment: cfg.environment.clone(),
             user_agent: cfg.user_agent.clone(),
+            deployment: None,
+            signature_host: None,
+            middleware: Default::default(),
             product: cfg.product.clone(),
             package: cfg.package.clone().unwrap_or_default(),
             license_key: cfg.license_key.clone(),
             public_key: cfg.public_key.clone(),
+            keyring: Default::default(),
             platform: cfg.platform.clone(),
             max_clock_drift: cfg.max_clock_drift.or(Some(5)),
             verify_keygen_signature: cfg.verify_keygen_signature.or(Some(true)),
+            signature_mode: Default::default(),
             token: cfg.token.clone(),
         }
     }
@@ -0,0 +1,251 @@
+use keygen_rs::errors::{Error, ErrorMeta};
+use wasm_bindgen::prelude::*;
+
+use crate::license::License;
+use crate::license_file::LicenseFileDataset;
+use crate::machine_file::MachineFileDataset;
+
+#[wasm_bindgen(typescript_custom_section)]
+const KEYGEN_ERROR: &'static str = r#"
+/** The `Error` variant a keygen-rs error was raised from. */
+export type KeygenErrorKind =
+  | "UnexpectedError"
+  | "InvalidUrl"
+  | "SystemClockUnsynced"
+  | "DecryptionError"
+  | "HttpClient"
+  | "UrlParse"
+  | "JsonError"
+  | "InvalidHeader"
+  | "UrlEncode"
+  | "RateLimitExceeded"
+  | "LicenseKeyMissing"
+  | "LicenseSchemeMissing"
+  | "LicenseSchemeNotSupported"
+  | "LicenseNotSigned"
+  | "LicenseKeyNotGenuine"
+  | "PublicKeyMissing"
+  | "PublicKeyInvalid"
+  | "LicenseSchemeUnsupported"
+  | "CertificateFileInvalid"
+  | "CertificateFileNotGenuine"
+  | "CertificateFileNotSupported"
+  | "CertificateFileExpired"
+  | "LicenseFileInvalid"
+  | "LicenseFileNotGenuine"
+  | "LicenseFileNotSupported"
+  | "LicenseFileNotEncrypted"
+  | "LicenseFileExpired"
+  | "MachineFileInvalid"
+  | "MachineFileNotGenuine"
+  | "MachineFileNotSupported"
+  | "MachineFileExpired"
+  | "KeygenApiError"
+  | "TokenNotAllowed"
+  | "TokenFormatInvalid"
+  | "TokenInvalid"
+  | "TokenExpired"
+  | "LicenseSuspended"
+  | "LicenseExpired"
+  | "LicenseNotAllowed"
+  | "LicenseNotActivated"
+  | "LicenseKeyInvalid"
+  | "LicenseTokenInvalid"
+  | "LicenseTooManyMachines"
+  | "LicenseTooManyCores"
+  | "LicenseTooManyProcesses"
+  | "MachineAlreadyActivated"
+  | "MachineLimitExceeded"
+  | "MachineNotFound"
+  | "ProcessLimitExceeded"
+  | "ProcessNotFound"
+  | "ComponentConflict"
+  | "ComponentAlreadyActivated"
+  | "ComponentNotActivated"
+  | "EnvironmentError"
+  | "HeartbeatDead"
+  | "HeartbeatPingFailed"
+  | "HeartbeatRequired"
+  | "ValidationFingerprintMissing"
+  | "ValidationComponentsMissing"
+  | "ValidationProductMissing"
+  | "NotFound"
+  | "PasswordResetTokenInvalid"
+  | "PasswordResetTokenExpired"
+  | "KeygenSignatureInvalid"
+  | "KeygenSignatureMissing"
+  | "MissingConfiguration"
+  | "InvalidConfiguration"
+  | "FeatureNotSupported";
+
+interface KeygenErrorBase extends Error {
+  name: "KeygenError";
+  /** The Keygen error code, such as `LICENSE_NOT_ACTIVATED`, or `ERROR` for SDK errors. */
+  code: string;
+  detail: string;
+  kind: KeygenErrorKind;
+}
+
+export interface RateLimitExceededError extends KeygenErrorBase {
+  kind: "RateLimitExceeded";
+  window: string;
+  count: number;
+  limit: number;
+  remaining: number;
+  /** Unix time in seconds at which the window resets. */
+  reset: number;
+  /** Seconds to wait before retrying. */
+  retryAfter: number;
+}
+
+export interface LicenseNotActivatedError extends KeygenErrorBase {
+  kind: "LicenseNotActivated";
+  /** The validated license, to activate the machine with. */
+  license: Record<string, any>;
+}
+
+export interface LicenseFileExpiredError extends KeygenErrorBase {
+  kind: "LicenseFileExpired";
+  /** The decrypted contents of the expired file. */
+  dataset: Record<string, any>;
+}
+
+export interface MachineFileExpiredError extends KeygenErrorBase {
+  kind: "MachineFileExpired";
+  /** The decrypted contents of the expired file. */
+  dataset: Record<string, any>;
+}
+
+export interface FeatureNotSupportedError extends KeygenErrorBase {
+  kind: "FeatureNotSupported";
+  feature: string;
+  required: string;
+  apiVersion: string;
+}
+
+export interface KeygenApiError extends KeygenErrorBase {
+  kind: "KeygenApiError";
+  /** The API's response body. */
+  body: any;
+}
+
+export interface OtherKeygenError extends KeygenErrorBase {
+  kind: Exclude<
+    KeygenErrorKind,
+    | "RateLimitExceeded"
+    | "LicenseNotActivated"
+    | "LicenseFileExpired"
+    | "MachineFileExpired"
+    | "FeatureNotSupported"
+    | "KeygenApiError"
+  >;
+}
+
+/**
+ * Thrown, or used to reject, by every function that fails with a keygen-rs
+ * error. Narrow on `kind` to reach the payload of a variant.
+ */
+export type KeygenError =
+  | RateLimitExceededError
+  | LicenseNotActivatedError
+  | LicenseFileExpiredError
+  | MachineFileExpiredError
+  | FeatureNotSupportedError
+  | KeygenApiError
+  | OtherKeygenError;
+"#;
+
+/// Converts `e` into a JS `Error` with the `[CODE] detail` message, carrying
+/// `code`, `detail`, `kind` and the variant's payload as properties, in the
+/// shape of the `KeygenError` declaration above.
+pub(crate) fn to_js_error(e: Error) -> JsError {
+    let error = JsError::new(&format!("[{}] {}", e.code(), e.detail()));
+    // A clone refers to the same JS object, so the properties set on its
+    // value end up on the thrown error.
+    let value = JsValue::from(error.clone());
+    set(&value, "name", "KeygenError".into());
+    set(&value, "code", e.code().into());
+    set(&value, "detail", e.detail().into());
+    set(&value, "kind", e.kind().into());
+
+    match e {
+        Error::RateLimitExceeded {
+            window,
+            count,
+            limit,
+            remaining,
+            reset,
+            retry_after,
+        } => {
+            set(&value, "window", window.into());
+            set(&value, "count", count.into());
+            set(&value, "limit", limit.into());
+            set(&value, "remaining", remaining.into());
+            set(&value, "reset", (reset as f64).into());
+            set(&value, "retryAfter", (retry_after as f64).into());
+        }
+        Error::LicenseNotActivated { license, .. } => {
+            set(&value, "license", to_value(&License::from(*license)));
+        }
+        Error::LicenseFileExpired(dataset) => {
+            set(
+                &value,
+                "dataset",
+                to_value(&LicenseFileDataset::from(*dataset)),
+            );
+        }
+        Error::MachineFileExpired(dataset) => {
+            set(
+                &value,
+                "dataset",
+                to_value(&MachineFileDataset::from(*dataset)),
+            );
+        }
+        Error::FeatureNotSupported {
+            feature,
+            required,
+            api_version,
+        } => {
+            set(&value, "feature", feature.into());
+            set(&value, "required", required.into());
+            set(&value, "apiVersion", api_version.into());
+        }
+        Error::KeygenApiError { body, .. } => {
+            set(&value, "body", to_value(&body));
+        }
+        _ => {}
+    }
+
+    error
+}
+
+fn set(target: &JsValue, key: &str, value: JsValue) {
+    let _ = js_sys::Reflect::set(target, &JsValue::from_str(key), &value);
+}
+
+fn to_value<T: serde::Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::UNDEFINED)
+}
+
+#[cfg(test)]
+mod tests {
+    use keygen_rs::errors::ERROR_KINDS;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_kind_union_matches_error_kinds() {
+        // The `typescript_custom_section` const isn't kept on native targets
+        let source = include_str!("error.rs");
+        let start = source.find("export type KeygenErrorKind =").unwrap();
+        let end = start + source[start..].find(';').unwrap();
+        let union: BTreeSet<&str> = source[start..end]
+            .split("| \"")
+            .skip(1)
+            .map(|rest| &rest[..rest.find('"').unwrap()])
+            .collect();
+
+        assert_eq!(union, ERROR_KINDS.iter().copied().collect());
+    }
+}
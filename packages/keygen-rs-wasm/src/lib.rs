@@ -1,8 +1,9 @@
 #![deny(clippy::all)]
 
-use keygen_rs::errors::ErrorMeta;
 use wasm_bindgen::JsError;
 
+mod error;
+
 pub mod config;
 pub mod license;
 pub mod license_file;
@@ -17,15 +18,12 @@ pub mod group;
 pub mod policy;
 pub mod product;
 pub mod release;
+pub mod second_factor;
 pub mod token_module;
 pub mod user;
 pub mod webhook;
 
-fn to_js_error(e: keygen_rs::errors::Error) -> JsError {
-    let code = e.code();
-    let detail = e.detail();
-    JsError::new(&format!("[{code}] {detail}"))
-}
+use error::to_js_error;
 
 fn to_metadata(
     v: serde_json::Value,
@@ -35,6 +35,17 @@ pub struct LicenseFileDataset {
     pub ttl: i32,
 }
 
+impl From<keygen_rs::license_file::LicenseFileDataset> for LicenseFileDataset {
+    fn from(ds: keygen_rs::license_file::LicenseFileDataset) -> Self {
+        LicenseFileDataset {
+            license: License::from(ds.license),
+            issued: ds.issued.to_rfc3339(),
+            expiry: ds.expiry.to_rfc3339(),
+            ttl: ds.ttl,
+        }
+    }
+}
+
 #[wasm_bindgen(js_name = "licenseFileFromCert")]
 pub fn license_file_from_cert(key: String, content: String) -> Result<JsValue, JsError> {
     let lf = keygen_rs::license_file::LicenseFile::from_cert(&key, &content)
@@ -64,12 +75,9 @@ pub fn decrypt_license_file(certificate: String, key: String) -> Result<JsValue,
         expiry: chrono::Utc::now(),
         ttl: 0,
     };
-    let ds = lf.decrypt(&key).map_err(to_js_error)?;
-    let dataset = LicenseFileDataset {
-        license: License::from(ds.license),
-        issued: ds.issued.to_rfc3339(),
-        expiry: ds.expiry.to_rfc3339(),
-        ttl: ds.ttl,
-    };
+    let dataset = lf
+        .decrypt(&key)
+        .map(LicenseFileDataset::from)
+        .map_err(to_js_error)?;
     serde_wasm_bindgen::to_value(&dataset).map_err(|e| JsError::new(&e.to_string()))
 }
@@ -37,6 +37,18 @@ pub struct MachineFileDataset {
     pub ttl: i32,
 }
 
+impl From<keygen_rs::machine_file::MachineFileDataset> for MachineFileDataset {
+    fn from(ds: keygen_rs::machine_file::MachineFileDataset) -> Self {
+        MachineFileDataset {
+            license: License::from(ds.license),
+            machine: Machine::from(ds.machine),
+            issued: ds.issued.to_rfc3339(),
+            expiry: ds.expiry.to_rfc3339(),
+            ttl: ds.ttl,
+        }
+    }
+}
+
 #[wasm_bindgen(js_name = "machineFileFromCert")]
 pub fn machine_file_from_cert(key: String, content: String) -> Result<JsValue, JsError> {
     let mf = keygen_rs::machine_file::MachineFile::from_cert(&key, &content)
@@ -66,13 +78,9 @@ pub fn decrypt_machine_file(certificate: String, key: String) -> Result<JsValue,
         expiry: chrono::Utc::now(),
         ttl: 0,
     };
-    let ds = mf.decrypt(&key).map_err(to_js_error)?;
-    let dataset = MachineFileDataset {
-        license: License::from(ds.license),
-        machine: Machine::from(ds.machine),
-        issued: ds.issued.to_rfc3339(),
-        expiry: ds.expiry.to_rfc3339(),
-        ttl: ds.ttl,
-    };
+    let dataset = mf
+        .decrypt(&key)
+        .map(MachineFileDataset::from)
+        .map_err(to_js_error)?;
     serde_wasm_bindgen::to_value(&dataset).map_err(|e| JsError::new(&e.to_string()))
 }
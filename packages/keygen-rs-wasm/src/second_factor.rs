@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::to_js_error;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecondFactor {
+    pub id: String,
+    pub uri: Option<String>,
+    pub enabled: bool,
+    pub created: String,
+    pub updated: String,
+    pub user_id: Option<String>,
+    pub account_id: Option<String>,
+}
+
+impl From<keygen_rs::second_factor::SecondFactor> for SecondFactor {
+    fn from(sf: keygen_rs::second_factor::SecondFactor) -> Self {
+        SecondFactor {
+            id: sf.id,
+            uri: sf.uri,
+            enabled: sf.enabled,
+            created: sf.created,
+            updated: sf.updated,
+            user_id: sf.user_id,
+            account_id: sf.account_id,
+        }
+    }
+}
+
+fn make_second_factor(user_id: String, id: String) -> keygen_rs::second_factor::SecondFactor {
+    keygen_rs::second_factor::SecondFactor {
+        id,
+        uri: None,
+        enabled: false,
+        created: String::new(),
+        updated: String::new(),
+        user_id: Some(user_id),
+        account_id: None,
+    }
+}
+
+#[wasm_bindgen(js_name = "createSecondFactor")]
+pub async fn create_second_factor(user_id: String, password: String) -> Result<JsValue, JsError> {
+    let second_factor = keygen_rs::second_factor::SecondFactor::create(&user_id, &password)
+        .await
+        .map(SecondFactor::from)
+        .map_err(to_js_error)?;
+    serde_wasm_bindgen::to_value(&second_factor).map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[wasm_bindgen(js_name = "listSecondFactors")]
+pub async fn list_second_factors(user_id: String) -> Result<JsValue, JsError> {
+    let second_factors: Vec<SecondFactor> = keygen_rs::second_factor::SecondFactor::list(&user_id)
+        .await
+        .map(|factors| factors.into_iter().map(SecondFactor::from).collect())
+        .map_err(to_js_error)?;
+    serde_wasm_bindgen::to_value(&second_factors).map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[wasm_bindgen(js_name = "getSecondFactor")]
+pub async fn get_second_factor(user_id: String, id: String) -> Result<JsValue, JsError> {
+    let second_factor = keygen_rs::second_factor::SecondFactor::get(&user_id, &id)
+        .await
+        .map(SecondFactor::from)
+        .map_err(to_js_error)?;
+    serde_wasm_bindgen::to_value(&second_factor).map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[wasm_bindgen(js_name = "updateSecondFactor")]
+pub async fn update_second_factor(
+    user_id: String,
+    id: String,
+    request: JsValue,
+) -> Result<JsValue, JsError> {
+    #[derive(Deserialize)]
+    struct Req {
+        enabled: bool,
+        otp: String,
+    }
+    let req: Req =
+        serde_wasm_bindgen::from_value(request).map_err(|e| JsError::new(&e.to_string()))?;
+
+    let second_factor = make_second_factor(user_id, id)
+        .update(keygen_rs::second_factor::UpdateSecondFactorRequest {
+            enabled: req.enabled,
+            otp: req.otp,
+        })
+        .await
+        .map(SecondFactor::from)
+        .map_err(to_js_error)?;
+    serde_wasm_bindgen::to_value(&second_factor).map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[wasm_bindgen(js_name = "enableSecondFactor")]
+pub async fn enable_second_factor(
+    user_id: String,
+    id: String,
+    otp: String,
+) -> Result<JsValue, JsError> {
+    let second_factor = make_second_factor(user_id, id)
+        .enable(&otp)
+        .await
+        .map(SecondFactor::from)
+        .map_err(to_js_error)?;
+    serde_wasm_bindgen::to_value(&second_factor).map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[wasm_bindgen(js_name = "deleteSecondFactor")]
+pub async fn delete_second_factor(user_id: String, id: String, otp: String) -> Result<(), JsError> {
+    make_second_factor(user_id, id)
+        .delete(&otp)
+        .await
+        .map_err(to_js_error)
+}
@@ -125,3 +125,45 @@ pub async fn revoke_token(id: String) -> Result<(), JsError> {
     let token = make_token(id);
     token.revoke().await.map_err(to_js_error)
 }
+
+#[wasm_bindgen(js_name = "generateToken")]
+pub async fn generate_token(
+    email: String,
+    password: String,
+    otp: Option<String>,
+    request: JsValue,
+) -> Result<JsValue, JsError> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Req {
+        name: Option<String>,
+        expiry: Option<String>,
+        permissions: Option<Vec<String>>,
+        metadata: Option<serde_json::Value>,
+    }
+
+    let req: Option<Req> = if request.is_undefined() || request.is_null() {
+        None
+    } else {
+        Some(serde_wasm_bindgen::from_value(request).map_err(|e| JsError::new(&e.to_string()))?)
+    };
+
+    let req = req
+        .map(
+            |request| -> Result<keygen_rs::token::CreateTokenRequest, JsError> {
+                Ok(keygen_rs::token::CreateTokenRequest {
+                    name: request.name,
+                    expiry: request.expiry,
+                    permissions: request.permissions,
+                    metadata: crate::opt_metadata(request.metadata)?,
+                })
+            },
+        )
+        .transpose()?;
+
+    let token = keygen_rs::token::Token::generate(&email, &password, otp.as_deref(), req)
+        .await
+        .map(Token::from)
+        .map_err(to_js_error)?;
+    serde_wasm_bindgen::to_value(&token).map_err(|e| JsError::new(&e.to_string()))
+}
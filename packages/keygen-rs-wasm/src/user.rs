@@ -286,6 +286,7 @@ pub async fn update_user_password(id: String, request: JsValue) -> Result<JsValu
     struct Req {
         old_password: Option<String>,
         new_password: String,
+        otp: Option<String>,
     }
     let req: Req =
         serde_wasm_bindgen::from_value(request).map_err(|e| JsError::new(&e.to_string()))?;
@@ -294,6 +295,7 @@ pub async fn update_user_password(id: String, request: JsValue) -> Result<JsValu
         .update_password(keygen_rs::user::UpdatePasswordRequest {
             old_password: req.old_password,
             new_password: req.new_password,
+            otp: req.otp,
         })
         .await
         .map(User::from)
@@ -9,6 +9,8 @@ const COMMANDS: &[&str] = &[
     "checkout_machine",
     "reset_license",
     "get_license_metadata",
+    "check_update",
+    "download_and_install_update",
 ];
 
 fn main() {
@@ -1,4 +1,4 @@
-import { invoke } from '@tauri-apps/api/core';
+import { Channel, invoke } from '@tauri-apps/api/core';
 
 export interface KeygenLicense {
   id: string;
@@ -11,6 +11,18 @@ export interface KeygenLicense {
   metadata?: Record<string, any>;
 }
 
+export interface KeygenUpdate {
+  version: string;
+  currentVersion: string;
+  date?: string;
+  body?: string;
+}
+
+export type DownloadEvent =
+  | { event: 'Started'; data: { contentLength?: number } }
+  | { event: 'Progress'; data: { chunkLength: number } }
+  | { event: 'Finished' };
+
 interface InvokeError {
   code: string;
   detail: string;
@@ -161,3 +173,36 @@ export async function getLicenseMetadata(): Promise<Record<string, any> | null>
   }
 }
 
+
+export async function checkUpdate(channel?: string): Promise<KeygenUpdate | null> {
+  try {
+    const update = await invoke<KeygenUpdate | null>('plugin:keygen-rs2|check_update', {
+      channel,
+    });
+    return update;
+  } catch (err) {
+    if (isInvokeError(err)) {
+      const { code, detail } = err;
+      throw new KeygenError(code, detail);
+    }
+    throw new KeygenError('ERROR', (err as Error).message);
+  }
+}
+
+export async function downloadAndInstallUpdate(onEvent?: (event: DownloadEvent) => void) {
+  try {
+    const channel = new Channel<DownloadEvent>();
+    if (onEvent) {
+      channel.onmessage = onEvent;
+    }
+    await invoke('plugin:keygen-rs2|download_and_install_update', {
+      onEvent: channel,
+    });
+  } catch (err) {
+    if (isInvokeError(err)) {
+      const { code, detail } = err;
+      throw new KeygenError(code, detail);
+    }
+    throw new KeygenError('ERROR', (err as Error).message);
+  }
+}
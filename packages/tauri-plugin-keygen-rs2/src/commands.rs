@@ -1,4 +1,8 @@
-use crate::{error::InvokeError, AppHandleExt};
+use crate::{
+    error::{Error, InvokeError},
+    updater::{self, DownloadEvent, UpdateMetadata},
+    AppHandleExt,
+};
 
 use keygen_rs::{
     component::Component,
@@ -9,7 +13,7 @@ use keygen_rs::{
 };
 use serde_json::Value;
 use std::collections::HashMap;
-use tauri::{command, AppHandle, Runtime};
+use tauri::{command, ipc::Channel, AppHandle, Runtime};
 
 type Result<T> = std::result::Result<T, InvokeError>;
 
@@ -148,3 +152,36 @@ pub async fn get_license_metadata<R: Runtime>(
         Ok(None)
     }
 }
+
+#[command]
+pub async fn check_update<R: Runtime>(
+    channel: Option<String>,
+    app_handle: AppHandle<R>,
+) -> Result<Option<UpdateMetadata>> {
+    let update = updater::check_update(&app_handle, channel.as_deref()).await?;
+    let metadata = update.as_ref().map(UpdateMetadata::from);
+
+    let updater_state = app_handle.get_updater_state();
+    let mut updater_state = updater_state.lock().await;
+    updater_state.pending = update;
+    Ok(metadata)
+}
+
+#[command]
+pub async fn download_and_install_update<R: Runtime>(
+    on_event: Channel<DownloadEvent>,
+    app_handle: AppHandle<R>,
+) -> Result<()> {
+    // Don't hold the lock for the whole download, and keep the update pending
+    // until it is installed so a failed download can be retried
+    let update = {
+        let updater_state = app_handle.get_updater_state();
+        let updater_state = updater_state.lock().await;
+        updater_state.pending.clone().ok_or(Error::NoUpdateError)?
+    };
+    updater::download_and_install(&update, on_event).await?;
+
+    let updater_state = app_handle.get_updater_state();
+    updater_state.lock().await.pending = None;
+    Ok(())
+}
@@ -18,6 +18,15 @@ pub enum Error {
 
     #[error("No machine found")]
     NoMachineError,
+
+    #[error("Updater error: {0}")]
+    UpdaterError(#[from] tauri_plugin_updater::Error),
+
+    #[error("No package configured")]
+    NoPackageError,
+
+    #[error("No pending update")]
+    NoUpdateError,
 }
 
 #[derive(Debug, Serialize)]
@@ -37,6 +46,18 @@ impl From<Error> for InvokeError {
                 code: "NO LICENSE".into(),
                 detail: "Can't activate a machine. Current app state has no license. Call validate(key) first.".into(),
             },
+            Error::UpdaterError(err) => Self {
+                code: "UPDATER_ERROR".into(),
+                detail: err.to_string(),
+            },
+            Error::NoPackageError => Self {
+                code: "NO_PACKAGE".into(),
+                detail: "Can't check for updates. Set the Keygen package with Builder::package(package) first.".into(),
+            },
+            Error::NoUpdateError => Self {
+                code: "NO_UPDATE".into(),
+                detail: "No pending update to install. Call checkUpdate() first.".into(),
+            },
             err => {
                 let msg = match err {
                     Error::IoError(err) => err.to_string(),
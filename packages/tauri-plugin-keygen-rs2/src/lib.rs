@@ -8,11 +8,13 @@ use tauri::{
     Manager, Runtime, State,
 };
 use tokio::sync::Mutex;
+use updater::UpdaterState;
 
 mod commands;
 pub mod error;
 pub mod license;
 pub mod machine;
+pub mod updater;
 mod utils;
 
 pub use keygen_rs;
@@ -48,6 +50,7 @@ pub trait AppHandleExt {
     fn get_machine_state(&self) -> State<'_, Mutex<MachineState>>;
     fn load_machine_file(&self, key: &str) -> Result<Option<MachineFile>>;
     fn remove_machine_file(&self) -> Result<()>;
+    fn get_updater_state(&self) -> State<'_, Mutex<UpdaterState>>;
 }
 
 impl<R: Runtime> AppHandleExt for tauri::AppHandle<R> {
@@ -78,12 +81,17 @@ impl<R: Runtime> AppHandleExt for tauri::AppHandle<R> {
     fn remove_machine_file(&self) -> Result<()> {
         MachineState::remove_machine_file(self)
     }
+
+    fn get_updater_state(&self) -> State<'_, Mutex<UpdaterState>> {
+        self.state::<Mutex<UpdaterState>>()
+    }
 }
 
 pub struct Builder {
     account: String,
     product: String,
     public_key: String,
+    package: Option<String>,
     api_url: Option<String>,
     api_version: Option<String>,
     api_prefix: Option<String>,
@@ -99,12 +107,19 @@ impl Builder {
             account: account.into(),
             product: product.into(),
             public_key: public_key.into(),
+            package: None,
             api_url: None,
             api_version: None,
             api_prefix: None,
         }
     }
 
+    /// Set the Keygen package used by the Tauri updater engine (`/engines/tauri/<package>`).
+    pub fn package(mut self, package: impl Into<String>) -> Self {
+        self.package = Some(package.into());
+        self
+    }
+
     pub fn api_url(mut self, api_url: impl Into<String>) -> Self {
         self.api_url = Some(api_url.into());
         self
@@ -127,6 +142,7 @@ impl Builder {
             api_prefix: self.api_prefix.unwrap_or("v1".to_string()),
             account: self.account,
             product: self.product,
+            package: self.package.unwrap_or_default(),
             public_key: Some(self.public_key),
             ..Default::default()
         };
@@ -143,6 +159,8 @@ impl Builder {
                 commands::checkout_machine,
                 commands::reset_license,
                 commands::get_license_metadata,
+                commands::check_update,
+                commands::download_and_install_update,
             ])
             .setup(move |app_handle, _api| {
                 let app_name = app_handle.package_info().name.clone();
@@ -165,6 +183,7 @@ impl Builder {
 
                 app_handle.manage(Mutex::new(keygen_config));
                 app_handle.manage(Mutex::new(machine_state));
+                app_handle.manage(Mutex::new(UpdaterState::default()));
 
                 let license_state = LicenseState::load(app_handle);
                 match license_state {
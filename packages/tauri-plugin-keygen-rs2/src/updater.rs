@@ -0,0 +1,178 @@
+use keygen_rs::config::KeygenConfig;
+use serde::Serialize;
+use tauri::{ipc::Channel, AppHandle, Runtime};
+use tauri_plugin_updater::{Update, UpdaterExt};
+use url::{form_urlencoded, Url};
+
+use crate::{error::Error, AppHandleExt, Result};
+
+/// Query string understood by Keygen's Tauri engine. The `{{...}}` placeholders are
+/// substituted by the Tauri updater before each request.
+const ENGINE_QUERY: &str = "platform={{target}}&arch={{arch}}&version={{current_version}}";
+
+#[derive(Default)]
+pub struct UpdaterState {
+    pub pending: Option<Update>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateMetadata {
+    pub version: String,
+    pub current_version: String,
+    pub date: Option<String>,
+    pub body: Option<String>,
+}
+
+impl From<&Update> for UpdateMetadata {
+    fn from(update: &Update) -> Self {
+        Self {
+            version: update.version.clone(),
+            current_version: update.current_version.clone(),
+            date: update.date.map(|date| date.to_string()),
+            body: update.body.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", content = "data")]
+pub enum DownloadEvent {
+    #[serde(rename_all = "camelCase")]
+    Started {
+        content_length: Option<u64>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Progress {
+        chunk_length: usize,
+    },
+    Finished,
+}
+
+/// Build the Keygen Tauri engine endpoint (`/engines/tauri/<package>`) for the configured package.
+pub fn updater_endpoint(config: &KeygenConfig, channel: Option<&str>) -> Result<Url> {
+    if config.package.is_empty() {
+        return Err(Error::NoPackageError);
+    }
+
+    let mut url = config.endpoint_url(&format!("engines/tauri/{}", config.package))?;
+
+    let mut query = ENGINE_QUERY.to_string();
+    if let Some(channel) = channel {
+        query.push_str("&channel=");
+        query.extend(form_urlencoded::byte_serialize(channel.as_bytes()));
+    }
+    url.set_query(Some(&query));
+
+    Ok(url)
+}
+
+/// Check Keygen for an update to the current app version.
+///
+/// The license is validated against this machine's fingerprint first, so suspended,
+/// expired or unactivated licenses never receive updates. Release entitlement
+/// constraints are enforced by Keygen based on the license key used for the request.
+pub async fn check_update<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    channel: Option<&str>,
+) -> Result<Option<Update>> {
+    let fingerprint = {
+        let machine_state = app_handle.get_machine_state();
+        let machine_state = machine_state.lock().await;
+        machine_state.fingerprint.clone()
+    };
+
+    let key = {
+        let license_state = app_handle.get_license_state();
+        let mut license_state = license_state.lock().await;
+        let key = license_state.key.clone().ok_or(Error::NoLicenseError)?;
+        license_state
+            .validate_key(app_handle, &key, &[fingerprint], &[])
+            .await?;
+        key
+    };
+
+    let endpoint = {
+        let config_state = app_handle.get_keygen_config();
+        let config = config_state.lock().await;
+        updater_endpoint(&config, channel)?
+    };
+
+    log::info!("Checking for updates: {}", endpoint);
+    let updater = app_handle
+        .updater_builder()
+        .endpoints(vec![endpoint])?
+        .header("Authorization", format!("License {}", key))?
+        .build()?;
+    Ok(updater.check().await?)
+}
+
+pub async fn download_and_install(update: &Update, on_event: Channel<DownloadEvent>) -> Result<()> {
+    log::info!("Installing update: {}", update.version);
+    let mut started = false;
+    update
+        .download_and_install(
+            |chunk_length, content_length| {
+                if !started {
+                    let _ = on_event.send(DownloadEvent::Started { content_length });
+                    started = true;
+                }
+                let _ = on_event.send(DownloadEvent::Progress { chunk_length });
+            },
+            || {
+                let _ = on_event.send(DownloadEvent::Finished);
+            },
+        )
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use keygen_rs::config::Deployment;
+
+    #[test]
+    fn test_updater_endpoint() {
+        let config = KeygenConfig {
+            account: "acme".to_string(),
+            package: "app".to_string(),
+            ..KeygenConfig::default()
+        };
+        assert_eq!(
+            updater_endpoint(&config, Some("beta")).unwrap().as_str(),
+            "https://api.keygen.sh/v1/accounts/acme/engines/tauri/app\
+             ?platform={{target}}&arch={{arch}}&version={{current_version}}&channel=beta"
+        );
+
+        let config = KeygenConfig {
+            api_url: "https://licensing.example.com".to_string(),
+            deployment: Some(Deployment::SelfHosted),
+            ..config
+        };
+        assert_eq!(
+            updater_endpoint(&config, None).unwrap().as_str(),
+            "https://licensing.example.com/v1/accounts/acme/engines/tauri/app\
+             ?platform={{target}}&arch={{arch}}&version={{current_version}}"
+        );
+
+        let config = KeygenConfig {
+            deployment: Some(Deployment::SelfHostedSingleTenant),
+            ..config
+        };
+        assert_eq!(
+            updater_endpoint(&config, None).unwrap().as_str(),
+            "https://licensing.example.com/v1/engines/tauri/app\
+             ?platform={{target}}&arch={{arch}}&version={{current_version}}"
+        );
+
+        let config = KeygenConfig {
+            package: String::new(),
+            ..config
+        };
+        assert!(matches!(
+            updater_endpoint(&config, None),
+            Err(Error::NoPackageError)
+        ));
+    }
+}
@@ -110,7 +110,7 @@ pub struct ListArtifactsOptions {
 }
 
 /// An artifact represents a distributable file associated with a release
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Artifact {
     pub id: String,
     pub filename: String,
@@ -167,58 +167,74 @@ impl Artifact {
     /// Create a new artifact
     ///
     /// Note: After creating an artifact, you need to upload the actual file
-    /// using the upload URL provided in the response links.
+    /// using the upload URL provided in the response links, or use
+    /// [`Artifact::upload`] to do both in one step.
     pub async fn create(request: CreateArtifactRequest) -> Result<Artifact, Error> {
         let client = Client::from_global_config()?;
+        let body = create_body(request)?;
 
-        let mut attributes = serde_json::Map::new();
-        attributes.insert(
-            "filename".to_string(),
-            serde_json::Value::String(request.filename),
-        );
+        let response = client.post("artifacts", Some(&body), None::<&()>).await?;
+        let artifact_response: ArtifactResponse = serde_json::from_value(response.body)?;
+        Ok(Artifact::from(artifact_response.data))
+    }
 
-        if let Some(filetype) = request.filetype {
-            attributes.insert("filetype".to_string(), serde_json::Value::String(filetype));
-        }
-        if let Some(filesize) = request.filesize {
-            attributes.insert("filesize".to_string(), serde_json::json!(filesize));
-        }
-        if let Some(platform) = request.platform {
-            attributes.insert("platform".to_string(), serde_json::Value::String(platform));
-        }
-        if let Some(arch) = request.arch {
-            attributes.insert("arch".to_string(), serde_json::Value::String(arch));
-        }
-        if let Some(signature) = request.signature {
-            attributes.insert(
-                "signature".to_string(),
-                serde_json::Value::String(signature),
-            );
-        }
-        if let Some(checksum) = request.checksum {
-            attributes.insert("checksum".to_string(), serde_json::Value::String(checksum));
-        }
-        if let Some(metadata) = request.metadata {
-            attributes.insert("metadata".to_string(), serde_json::to_value(metadata)?);
+    /// Create an artifact and upload its file contents.
+    ///
+    /// Keygen answers the create request with a redirect to a presigned upload
+    /// URL; the contents are PUT there without Keygen credentials. The returned
+    /// artifact may still be `Waiting` until Keygen processes the upload.
+    ///
+    /// `contents` can be a `Vec<u8>` or, to stream a large file rather than
+    /// read it into memory, a `tokio::fs::File`.
+    ///
+    /// Not available on WASM targets — use [`create`](Self::create) and upload
+    /// from JavaScript instead.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn upload(
+        request: CreateArtifactRequest,
+        contents: impl Into<reqwest::Body>,
+    ) -> Result<Artifact, Error> {
+        let client = Client::from_global_config()?;
+        let body = create_body(request)?;
+        let request =
+            client.build_request(reqwest::Method::POST, "artifacts", Some(&body), true)?;
+
+        let no_redirect = client.without_redirects()?;
+
+        let trace = client.trace(&request);
+        let response = trace
+            .instrument(client.execute_verified(&no_redirect, request, &trace))
+            .await?;
+        let status = response.status;
+        let headers = response.headers;
+        let body: serde_json::Value = serde_json::from_slice(&response.body)?;
+        if status.is_client_error() || status.is_server_error() {
+            return Err(client.handle_error(status, &headers, body));
         }
 
-        let body = serde_json::json!({
-            "data": {
-                "type": "artifacts",
-                "attributes": attributes,
-                "relationships": {
-                    "release": {
-                        "data": {
-                            "type": "releases",
-                            "id": request.release_id
-                        }
-                    }
-                }
-            }
-        });
+        let upload_url = headers
+            .get(reqwest::header::LOCATION)
+            .and_then(|value| value.to_str().ok())
+            .or_else(|| body["data"]["links"]["redirect"].as_str())
+            .ok_or_else(|| Error::UnexpectedError("Missing artifact upload URL".to_string()))?
+            .to_string();
+        let artifact_response: ArtifactResponse = serde_json::from_value(body)?;
+
+        // The presigned URL carries its own authorization, so the contents
+        // go straight to storage rather than through the middleware chain
+        let upload = client
+            .for_uploads()?
+            .put(&upload_url)
+            .body(contents)
+            .send()
+            .await?;
+        if !upload.status().is_success() {
+            return Err(Error::UnexpectedError(format!(
+                "Artifact upload failed: status={}",
+                upload.status()
+            )));
+        }
 
-        let response = client.post("artifacts", Some(&body), None::<&()>).await?;
-        let artifact_response: ArtifactResponse = serde_json::from_value(response.body)?;
         Ok(Artifact::from(artifact_response.data))
     }
 
@@ -304,6 +320,55 @@ impl Artifact {
     }
 }
 
+fn create_body(request: CreateArtifactRequest) -> Result<serde_json::Value, Error> {
+    let mut attributes = serde_json::Map::new();
+    attributes.insert(
+        "filename".to_string(),
+        serde_json::Value::String(request.filename),
+    );
+
+    if let Some(filetype) = request.filetype {
+        attributes.insert("filetype".to_string(), serde_json::Value::String(filetype));
+    }
+    if let Some(filesize) = request.filesize {
+        attributes.insert("filesize".to_string(), serde_json::json!(filesize));
+    }
+    if let Some(platform) = request.platform {
+        attributes.insert("platform".to_string(), serde_json::Value::String(platform));
+    }
+    if let Some(arch) = request.arch {
+        attributes.insert("arch".to_string(), serde_json::Value::String(arch));
+    }
+    if let Some(signature) = request.signature {
+        attributes.insert(
+            "signature".to_string(),
+            serde_json::Value::String(signature),
+        );
+    }
+    if let Some(checksum) = request.checksum {
+        attributes.insert("checksum".to_string(), serde_json::Value::String(checksum));
+    }
+    if let Some(metadata) = request.metadata {
+        attributes.insert("metadata".to_string(), serde_json::to_value(metadata)?);
+    }
+
+    let body = serde_json::json!({
+        "data": {
+            "type": "artifacts",
+            "attributes": attributes,
+            "relationships": {
+                "release": {
+                    "data": {
+                        "type": "releases",
+                        "id": request.release_id
+                    }
+                }
+            }
+        }
+    });
+    Ok(body)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -381,4 +446,118 @@ mod tests {
             "\"YANKED\""
         );
     }
+
+    #[tokio::test]
+    async fn test_upload_puts_contents_to_redirect_url() {
+        use crate::config::{reset_config, set_config, KeygenConfig};
+        use mockito::{mock, server_url, Matcher};
+
+        let upload_url = format!("{}/uploads/app-1.0.0.dmg", server_url());
+        let _create = mock("POST", "/v1/artifacts")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "data": {"attributes": {"filename": "app-1.0.0.dmg", "filesize": 5}}
+            })))
+            .with_status(307)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_header("location", &upload_url)
+            .with_body(
+                serde_json::json!({
+                    "data": {
+                        "id": "artifact-1",
+                        "type": "artifacts",
+                        "attributes": {
+                            "filename": "app-1.0.0.dmg",
+                            "filetype": null,
+                            "filesize": 5,
+                            "platform": null,
+                            "arch": null,
+                            "signature": null,
+                            "checksum": null,
+                            "status": "WAITING",
+                            "metadata": {},
+                            "created": "2024-01-01T00:00:00Z",
+                            "updated": "2024-01-01T00:00:00Z",
+                            "yanked": null
+                        },
+                        "relationships": {}
+                    }
+                })
+                .to_string(),
+            )
+            .create();
+        let _upload = mock("PUT", "/uploads/app-1.0.0.dmg")
+            .match_header("authorization", Matcher::Missing)
+            .match_body("hello")
+            .with_status(200)
+            .create();
+
+        let _ = set_config(KeygenConfig {
+            api_url: server_url(),
+            account: "test_account".to_string(),
+            token: Some("admin-token".to_string()),
+            ..Default::default()
+        });
+
+        let artifact = Artifact::upload(
+            CreateArtifactRequest {
+                filename: "app-1.0.0.dmg".to_string(),
+                release_id: "release-1".to_string(),
+                filetype: None,
+                filesize: Some(5),
+                platform: None,
+                arch: None,
+                signature: None,
+                checksum: None,
+                metadata: None,
+            },
+            b"hello".to_vec(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(artifact.id, "artifact-1");
+        _upload.assert();
+
+        let _ = reset_config();
+    }
+
+    #[cfg(all(feature = "license-key", not(target_arch = "wasm32")))]
+    #[tokio::test]
+    async fn test_upload_rejects_unsigned_redirect_in_strict_mode() {
+        use crate::config::{reset_config, set_config, KeygenConfig, SignatureMode};
+        use mockito::{mock, server_url};
+
+        let _create = mock("POST", "/v1/artifacts")
+            .with_status(307)
+            .with_header("location", "https://attacker.example/uploads/app.dmg")
+            .with_body("{}")
+            .create();
+
+        let _ = set_config(KeygenConfig {
+            api_url: server_url(),
+            account: "test_account".to_string(),
+            token: Some("admin-token".to_string()),
+            public_key: Some("0".repeat(64)),
+            signature_mode: SignatureMode::Strict,
+            ..Default::default()
+        });
+
+        let result = Artifact::upload(
+            CreateArtifactRequest {
+                filename: "app.dmg".to_string(),
+                release_id: "release-1".to_string(),
+                filetype: None,
+                filesize: Some(5),
+                platform: None,
+                arch: None,
+                signature: None,
+                checksum: None,
+                metadata: None,
+            },
+            b"hello".to_vec(),
+        )
+        .await;
+        assert!(matches!(result, Err(Error::KeygenSignatureMissing)));
+
+        let _ = reset_config();
+    }
 }
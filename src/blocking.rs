@@ -0,0 +1,581 @@
+//! Synchronous facade over the async API.
+//!
+//! Every function here runs its async counterpart to completion on a small
+//! runtime owned by the SDK, so callers need neither `async` nor their own
+//! tokio runtime. Configuration and error types are shared with the async API.
+//! Methods become free functions that take the resource as their first
+//! argument:
+//!
+//! ```no_run
+//! use keygen_rs::blocking;
+//! use keygen_rs::config::{self, KeygenConfig};
+//!
+//! fn main() -> Result<(), keygen_rs::errors::Error> {
+//!     config::set_config(KeygenConfig {
+//!         account: "YOUR_KEYGEN_ACCOUNT_ID".to_string(),
+//!         product: "YOUR_KEYGEN_PRODUCT_ID".to_string(),
+//!         license_key: Some("A_KEYGEN_LICENSE_KEY".to_string()),
+//!         public_key: Some("YOUR_KEYGEN_PUBLIC_KEY".to_string()),
+//!         ..Default::default()
+//!     })?;
+//!
+//!     let fingerprint = "machine-fingerprint".to_string();
+//!     let license = blocking::validate(&[fingerprint.clone()], &[])?;
+//!     let machine = blocking::license::activate(&license, &fingerprint, &[])?;
+//!     blocking::machine::ping(&machine)?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Anything not mirrored here can be run with [`block_on`]. None of these
+//! functions may be called from within an async runtime; they return an error
+//! (or, for [`block_on`], panic) when they are.
+
+use std::future::Future;
+
+use lazy_static::lazy_static;
+use tokio::runtime::{Builder, Handle, Runtime};
+
+use crate::config::KeygenConfig;
+use crate::errors::Error;
+use crate::license::License;
+
+lazy_static! {
+    static ref RUNTIME: Result<Runtime, String> = Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("keygen-blocking")
+        .enable_all()
+        .build()
+        .map_err(|e| e.to_string());
+}
+
+/// Run `future` to completion on the SDK's blocking runtime.
+///
+/// # Panics
+///
+/// Panics when called from within an async runtime, or when the runtime
+/// could not be started.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    match RUNTIME.as_ref() {
+        Ok(runtime) => runtime.block_on(future),
+        Err(error) => panic!("Failed to start the blocking runtime: {error}"),
+    }
+}
+
+fn run<T>(future: impl Future<Output = Result<T, Error>>) -> Result<T, Error> {
+    if Handle::try_current().is_ok() {
+        return Err(Error::UnexpectedError(
+            "The blocking API can't be called from within an async runtime".to_string(),
+        ));
+    }
+    let runtime = RUNTIME.as_ref().map_err(|e| {
+        Error::UnexpectedError(format!("Failed to start the blocking runtime: {e}"))
+    })?;
+    runtime.block_on(future)
+}
+
+/// Generate blocking wrappers for `$ty`'s async functions. Methods are called
+/// with the resource as the first argument.
+macro_rules! blocking {
+    ($ty:ident {
+        $(
+            $(#[$meta:meta])*
+            fn $name:ident($($arg:ident: $arg_ty:ty),* $(,)?) -> $ret:ty;
+        )*
+    }) => {
+        $(
+            #[doc = concat!("Blocking version of [`", stringify!($ty), "::", stringify!($name), "`].")]
+            $(#[$meta])*
+            pub fn $name($($arg: $arg_ty),*) -> Result<$ret, Error> {
+                super::run($ty::$name($($arg),*))
+            }
+        )*
+    };
+}
+
+/// Blocking version of [`crate::validate`].
+pub fn validate(fingerprints: &[String], entitlements: &[String]) -> Result<License, Error> {
+    run(crate::validate(fingerprints, entitlements))
+}
+
+/// Blocking version of [`crate::validate_with_config`].
+pub fn validate_with_config(
+    config: KeygenConfig,
+    fingerprints: &[String],
+    entitlements: &[String],
+) -> Result<License, Error> {
+    run(crate::validate_with_config(
+        config,
+        fingerprints,
+        entitlements,
+    ))
+}
+
+pub mod license {
+    use crate::component::Component;
+    use crate::entitlement::Entitlement;
+    use crate::errors::Error;
+    use crate::license::{License, LicenseCheckoutOpts, PaginationOptions};
+    #[cfg(feature = "token")]
+    use crate::license::{LicenseCreateRequest, LicenseListOptions, LicenseUpdateRequest};
+    use crate::license_file::LicenseFile;
+    use crate::machine::Machine;
+    #[cfg(feature = "token")]
+    use crate::token::{CreateTokenRequest, Token};
+    #[cfg(feature = "token")]
+    use crate::user::User;
+
+    blocking!(License {
+        fn validate(license: License, fingerprints: &[String], entitlements: &[String]) -> License;
+        fn validate_key(license: License, fingerprints: &[String], entitlements: &[String]) -> License;
+        fn activate(license: &License, fingerprint: &str, components: &[Component]) -> Machine;
+        fn deactivate(license: &License, id: &str) -> ();
+        fn machine(license: &License, id: &str) -> Machine;
+        fn machines(license: &License, options: Option<&PaginationOptions>) -> Vec<Machine>;
+        fn entitlements(license: &License, options: Option<&PaginationOptions>) -> Vec<Entitlement>;
+        fn checkout(license: &License, options: &LicenseCheckoutOpts) -> LicenseFile;
+        fn check_in(license: &License) -> License;
+        fn increment_usage(license: &License) -> License;
+        #[cfg(feature = "token")]
+        fn create(request: LicenseCreateRequest) -> License;
+        #[cfg(feature = "token")]
+        fn list(options: Option<&LicenseListOptions>) -> Vec<License>;
+        #[cfg(feature = "token")]
+        fn get(id: &str) -> License;
+        #[cfg(feature = "token")]
+        fn update(license: &License, request: LicenseUpdateRequest) -> License;
+        #[cfg(feature = "token")]
+        fn delete(license: &License) -> ();
+        #[cfg(feature = "token")]
+        fn suspend(license: &License) -> License;
+        #[cfg(feature = "token")]
+        fn reinstate(license: &License) -> License;
+        #[cfg(feature = "token")]
+        fn renew(license: &License) -> License;
+        #[cfg(feature = "token")]
+        fn revoke(license: &License) -> ();
+        #[cfg(feature = "token")]
+        fn decrement_usage(license: &License) -> License;
+        #[cfg(feature = "token")]
+        fn reset_usage(license: &License) -> License;
+        #[cfg(feature = "token")]
+        fn attach_entitlements(license: &License, entitlement_ids: &[String]) -> ();
+        #[cfg(feature = "token")]
+        fn detach_entitlements(license: &License, entitlement_ids: &[String]) -> ();
+        #[cfg(feature = "token")]
+        fn generate_token(license: &License, request: Option<CreateTokenRequest>) -> Token;
+        #[cfg(feature = "token")]
+        fn attach_users(license: &License, user_ids: &[String]) -> ();
+        #[cfg(feature = "token")]
+        fn detach_users(license: &License, user_ids: &[String]) -> ();
+        #[cfg(feature = "token")]
+        fn users(license: &License, options: Option<&PaginationOptions>) -> Vec<User>;
+        #[cfg(feature = "token")]
+        fn change_policy(license: &License, policy_id: &str) -> License;
+        #[cfg(feature = "token")]
+        fn change_owner(license: &License, owner_id: &str) -> License;
+        #[cfg(feature = "token")]
+        fn change_group(license: &License, group_id: &str) -> License;
+    });
+}
+
+pub mod machine {
+    use crate::errors::Error;
+    use crate::machine::{Machine, MachineCheckoutOpts};
+    #[cfg(feature = "token")]
+    use crate::machine::{MachineCreateRequest, MachineListFilters, MachineUpdateRequest};
+    use crate::machine_file::MachineFile;
+
+    blocking!(Machine {
+        fn deactivate(machine: &Machine) -> ();
+        fn checkout(machine: &Machine, options: &MachineCheckoutOpts) -> MachineFile;
+        fn ping(machine: &Machine) -> Machine;
+        #[cfg(feature = "token")]
+        fn create(request: MachineCreateRequest) -> Machine;
+        #[cfg(feature = "token")]
+        fn list(filters: Option<MachineListFilters>) -> Vec<Machine>;
+        #[cfg(feature = "token")]
+        fn get(id: &str) -> Machine;
+        #[cfg(feature = "token")]
+        fn update(machine: &Machine, request: MachineUpdateRequest) -> Machine;
+        #[cfg(feature = "token")]
+        fn reset(machine: &Machine) -> Machine;
+        #[cfg(feature = "token")]
+        fn change_owner(machine: &Machine, owner_id: &str) -> Machine;
+        #[cfg(feature = "token")]
+        fn change_group(machine: &Machine, group_id: &str) -> Machine;
+    });
+}
+
+#[cfg(feature = "token")]
+pub mod entitlement {
+    use crate::entitlement::{
+        CreateEntitlementRequest, Entitlement, ListEntitlementsOptions, UpdateEntitlementRequest,
+    };
+    use crate::errors::Error;
+
+    blocking!(Entitlement {
+        fn create(request: CreateEntitlementRequest) -> Entitlement;
+        fn list(options: Option<ListEntitlementsOptions>) -> Vec<Entitlement>;
+        fn get(id: &str) -> Entitlement;
+        fn update(entitlement: &Entitlement, request: UpdateEntitlementRequest) -> Entitlement;
+        fn delete(entitlement: &Entitlement) -> ();
+    });
+}
+
+#[cfg(feature = "token")]
+pub mod component {
+    use crate::component::{
+        Component, CreateComponentRequest, ListComponentsOptions, UpdateComponentRequest,
+    };
+    use crate::errors::Error;
+
+    blocking!(Component {
+        fn create(request: CreateComponentRequest) -> Component;
+        fn list(options: Option<ListComponentsOptions>) -> Vec<Component>;
+        fn get(id: &str) -> Component;
+        fn update(component: &Component, request: UpdateComponentRequest) -> Component;
+        fn delete(component: &Component) -> ();
+    });
+}
+
+#[cfg(feature = "token")]
+pub mod group {
+    use crate::errors::Error;
+    use crate::group::{CreateGroupRequest, Group, ListGroupsOptions, UpdateGroupRequest};
+    use crate::license::{License, PaginationOptions};
+    use crate::machine::Machine;
+    use crate::user::User;
+
+    blocking!(Group {
+        fn create(request: CreateGroupRequest) -> Group;
+        fn list(options: Option<ListGroupsOptions>) -> Vec<Group>;
+        fn get(id: &str) -> Group;
+        fn update(group: &Group, request: UpdateGroupRequest) -> Group;
+        fn delete(group: &Group) -> ();
+        fn owners(group: &Group, options: Option<&PaginationOptions>) -> Vec<User>;
+        fn users(group: &Group, options: Option<&PaginationOptions>) -> Vec<User>;
+        fn licenses(group: &Group, options: Option<&PaginationOptions>) -> Vec<License>;
+        fn machines(group: &Group, options: Option<&PaginationOptions>) -> Vec<Machine>;
+    });
+}
+
+#[cfg(feature = "token")]
+pub mod product {
+    use crate::errors::Error;
+    use crate::product::{
+        CreateProductRequest, ListProductsOptions, Product, UpdateProductRequest,
+    };
+    use crate::token::{CreateTokenRequest, Token};
+
+    blocking!(Product {
+        fn create(request: CreateProductRequest) -> Product;
+        fn list(options: Option<ListProductsOptions>) -> Vec<Product>;
+        fn get(id: &str) -> Product;
+        fn update(product: &Product, request: UpdateProductRequest) -> Product;
+        fn delete(product: &Product) -> ();
+        fn generate_token(product: &Product) -> String;
+        fn generate_token_with_options(product: &Product, request: Option<CreateTokenRequest>) -> Token;
+    });
+}
+
+#[cfg(feature = "token")]
+pub mod policy {
+    use crate::entitlement::Entitlement;
+    use crate::errors::Error;
+    use crate::license::PaginationOptions;
+    use crate::policy::{CreatePolicyRequest, ListPoliciesOptions, Policy, UpdatePolicyRequest};
+
+    blocking!(Policy {
+        fn create(request: CreatePolicyRequest) -> Policy;
+        fn list(options: Option<ListPoliciesOptions>) -> Vec<Policy>;
+        fn get(id: &str) -> Policy;
+        fn update(policy: &Policy, request: UpdatePolicyRequest) -> Policy;
+        fn delete(policy: &Policy) -> ();
+        fn attach_entitlements(policy: &Policy, entitlement_ids: &[String]) -> ();
+        fn detach_entitlements(policy: &Policy, entitlement_ids: &[String]) -> ();
+        fn entitlements(policy: &Policy, options: Option<&PaginationOptions>) -> Vec<Entitlement>;
+        fn pop_key(policy: &Policy) -> String;
+    });
+}
+
+#[cfg(feature = "token")]
+pub mod user {
+    use crate::errors::Error;
+    use crate::token::{CreateTokenRequest, Token};
+    use crate::user::{
+        CreateUserRequest, ListUsersOptions, ResetPasswordRequest, UpdatePasswordRequest,
+        UpdateUserRequest, User, UsersListResult,
+    };
+
+    blocking!(User {
+        fn create(request: CreateUserRequest) -> User;
+        fn list(options: Option<ListUsersOptions>) -> UsersListResult;
+        fn get(user_id: &str) -> User;
+        fn update(user: &User, request: UpdateUserRequest) -> User;
+        fn delete(user: &User) -> ();
+        fn ban(user: &User) -> User;
+        fn unban(user: &User) -> User;
+        fn generate_token(user: &User, request: Option<CreateTokenRequest>) -> Token;
+        fn change_group(user: &User, group_id: &str) -> User;
+        fn update_password(user: &User, request: UpdatePasswordRequest) -> User;
+        fn reset_password(user: &User, request: ResetPasswordRequest) -> User;
+    });
+}
+
+#[cfg(feature = "token")]
+pub mod token {
+    use crate::errors::Error;
+    use crate::token::{ListTokensOptions, RegenerateTokenRequest, Token};
+
+    blocking!(Token {
+        fn list(options: Option<ListTokensOptions>) -> Vec<Token>;
+        fn get(id: &str) -> Token;
+        fn regenerate(token: &Token, request: RegenerateTokenRequest) -> Token;
+        fn revoke(token: &Token) -> ();
+    });
+}
+
+#[cfg(feature = "token")]
+pub mod release {
+    use crate::artifact::{Artifact, ListArtifactsOptions};
+    use crate::errors::Error;
+    use crate::license::PaginationOptions;
+    use crate::release::{
+        Constraint, CreateReleaseRequest, ListReleasesOptions, Release, ReleaseArtifactDownload,
+        ReleaseUpgradeRequest, UpdateReleaseRequest,
+    };
+
+    blocking!(Release {
+        fn create(request: CreateReleaseRequest) -> Release;
+        fn list(options: Option<ListReleasesOptions>) -> Vec<Release>;
+        fn get(id: &str) -> Release;
+        fn upgrade(release: &Release, request: Option<&ReleaseUpgradeRequest>) -> Release;
+        fn update(release: &Release, request: UpdateReleaseRequest) -> Release;
+        fn delete(release: &Release) -> ();
+        fn publish(release: &Release) -> Release;
+        fn yank(release: &Release) -> Release;
+        fn download_artifact(release: &Release, artifact: &str) -> ReleaseArtifactDownload;
+        fn artifacts(release: &Release, options: Option<ListArtifactsOptions>) -> Vec<Artifact>;
+        fn attach_constraints(release: &Release, entitlement_ids: &[String]) -> Vec<Constraint>;
+        fn detach_constraints(release: &Release, constraint_ids: &[String]) -> ();
+        fn constraints(release: &Release, options: Option<&PaginationOptions>) -> Vec<Constraint>;
+        fn change_package(release: &Release, package_id: &str) -> Release;
+    });
+}
+
+#[cfg(feature = "token")]
+pub mod artifact {
+    use crate::artifact::{
+        Artifact, CreateArtifactRequest, ListArtifactsOptions, UpdateArtifactRequest,
+    };
+    use crate::errors::Error;
+
+    blocking!(Artifact {
+        fn create(request: CreateArtifactRequest) -> Artifact;
+        fn upload(request: CreateArtifactRequest, contents: Vec<u8>) -> Artifact;
+        fn list(options: Option<ListArtifactsOptions>) -> Vec<Artifact>;
+        fn get(id: &str) -> Artifact;
+        fn update(artifact: &Artifact, request: UpdateArtifactRequest) -> Artifact;
+        fn delete(artifact: &Artifact) -> ();
+        fn yank(artifact: &Artifact) -> Artifact;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{reset_config, set_config};
+    use mockito::{mock, server_url, Mock};
+    use serde_json::json;
+
+    /// Configure an API prefix of the test's own, so its mocks can't answer
+    /// the async tests' requests or the other way round
+    fn set_test_config(api_prefix: &str) {
+        set_config(KeygenConfig {
+            api_url: server_url(),
+            api_prefix: api_prefix.to_string(),
+            account: "test_account".to_string(),
+            product: "test_product".to_string(),
+            license_key: Some("BLOCKING-KEY".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+    }
+
+    fn license_json() -> serde_json::Value {
+        json!({
+            "id": "blocking-license",
+            "type": "licenses",
+            "attributes": {
+                "name": "Blocking License",
+                "key": "BLOCKING-KEY",
+                "expiry": null,
+                "status": "ACTIVE",
+                "metadata": {}
+            },
+            "relationships": {}
+        })
+    }
+
+    fn machine_json() -> serde_json::Value {
+        json!({
+            "id": "blocking-machine",
+            "type": "machines",
+            "attributes": {
+                "fingerprint": "blocking-fingerprint",
+                "name": null,
+                "platform": null,
+                "hostname": null,
+                "ip": null,
+                "cores": null,
+                "metadata": {},
+                "requireHeartbeat": true,
+                "heartbeatStatus": "ALIVE",
+                "heartbeatDuration": 600,
+                "created": "2024-01-01T00:00:00Z",
+                "updated": "2024-01-01T00:00:00Z"
+            },
+            "relationships": {}
+        })
+    }
+
+    fn respond(method: &str, path: &str, status: usize, body: serde_json::Value) -> Mock {
+        mock(method, path)
+            .with_status(status)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(body.to_string())
+            .create()
+    }
+
+    fn validation(valid: bool, code: &str) -> serde_json::Value {
+        json!({
+            "meta": {
+                "ts": "2024-01-01T00:00:00Z",
+                "valid": valid,
+                "detail": code.to_lowercase(),
+                "code": code,
+                "scope": { "fingerprint": "blocking-fingerprint" }
+            },
+            "data": license_json()
+        })
+    }
+
+    #[test]
+    fn test_block_on_runs_future() {
+        assert_eq!(block_on(async { 40 + 2 }), 42);
+    }
+
+    #[test]
+    fn test_validate_activate_and_ping() {
+        let prefix = "/blocking-ok";
+        let _me = respond(
+            "GET",
+            &format!("{prefix}/me"),
+            200,
+            json!({ "data": license_json() }),
+        );
+        let _validate = respond(
+            "POST",
+            &format!("{prefix}/licenses/actions/validate-key"),
+            200,
+            validation(true, "VALID"),
+        );
+        let _activate = respond(
+            "POST",
+            &format!("{prefix}/machines"),
+            201,
+            json!({ "data": machine_json() }),
+        );
+        let _ping = respond(
+            "POST",
+            &format!("{prefix}/machines/blocking-machine/actions/ping"),
+            200,
+            json!({ "data": machine_json() }),
+        );
+        set_test_config("blocking-ok");
+
+        let fingerprint = "blocking-fingerprint".to_string();
+        let license = validate(std::slice::from_ref(&fingerprint), &[]).unwrap();
+        assert_eq!(license.id, "blocking-license");
+        assert_eq!(license.key, "BLOCKING-KEY");
+
+        let machine = license::activate(&license, &fingerprint, &[]).unwrap();
+        assert_eq!(machine.id, "blocking-machine");
+        assert_eq!(machine.fingerprint, fingerprint);
+
+        let machine = machine::ping(&machine).unwrap();
+        assert_eq!(machine.heartbeat_status, "ALIVE");
+
+        let _ = reset_config();
+    }
+
+    #[test]
+    fn test_errors_are_returned() {
+        let prefix = "/blocking-errors";
+        let _me = respond(
+            "GET",
+            &format!("{prefix}/me"),
+            200,
+            json!({ "data": license_json() }),
+        );
+        let _validate = respond(
+            "POST",
+            &format!("{prefix}/licenses/actions/validate-key"),
+            200,
+            validation(false, "EXPIRED"),
+        );
+        let _activate = respond(
+            "POST",
+            &format!("{prefix}/machines"),
+            422,
+            json!({
+                "errors": [{
+                    "title": "Unprocessable Entity",
+                    "detail": "License has reached machine limit",
+                    "code": "MACHINE_LIMIT_EXCEEDED"
+                }]
+            }),
+        );
+        let _ping = respond(
+            "POST",
+            &format!("{prefix}/machines/blocking-machine/actions/ping"),
+            422,
+            json!({
+                "errors": [{
+                    "title": "Unprocessable Entity",
+                    "detail": "machine heartbeat is dead",
+                    "code": "MACHINE_HEARTBEAT_DEAD"
+                }]
+            }),
+        );
+        set_test_config("blocking-errors");
+
+        let fingerprint = "blocking-fingerprint".to_string();
+        assert!(matches!(
+            validate(std::slice::from_ref(&fingerprint), &[]),
+            Err(Error::LicenseExpired { .. })
+        ));
+
+        let license = License::from(serde_json::from_value(license_json()).unwrap());
+        assert!(matches!(
+            license::activate(&license, &fingerprint, &[]),
+            Err(Error::MachineLimitExceeded { .. })
+        ));
+
+        let machine =
+            crate::machine::Machine::from(serde_json::from_value(machine_json()).unwrap());
+        assert!(matches!(
+            machine::ping(&machine),
+            Err(Error::HeartbeatDead { .. })
+        ));
+
+        let _ = reset_config();
+    }
+
+    #[tokio::test]
+    async fn test_run_rejects_async_context() {
+        let result = run(async { Ok(()) });
+        assert!(matches!(result, Err(Error::UnexpectedError(_))));
+    }
+}
@@ -0,0 +1,540 @@
+//! Record and replay API traffic for deterministic tests.
+//!
+//! A [`Cassette`] is a [`Middleware`]. In record mode it captures every
+//! request/response pair the SDK makes, response headers included so that
+//! `Keygen-Signature` still verifies later. In replay mode it answers requests
+//! from the recorded file without touching the network.
+//!
+//! License keys and tokens from the global config, plus any secrets added with
+//! [`Cassette::secret`], are written to the file as `{{name}}` placeholders and
+//! filled back in on replay. Signed responses therefore verify offline as long
+//! as the replaying test is configured with the same secrets.
+//!
+//! # Example
+//! ```no_run
+//! use std::sync::Arc;
+//! use keygen_rs::cassette::Cassette;
+//! use keygen_rs::config::add_middleware;
+//!
+//! # fn main() -> Result<(), keygen_rs::errors::Error> {
+//! let path = "tests/cassettes/validate.json";
+//! let cassette = Arc::new(if std::env::var("KEYGEN_RECORD").is_ok() {
+//!     Cassette::record(path)
+//! } else {
+//!     Cassette::replay(path)?
+//! });
+//! add_middleware(cassette.clone())?;
+//!
+//! // ... exercise the SDK ...
+//!
+//! cassette.save()?; // no-op when replaying
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+
+use crate::config::get_config;
+use crate::errors::Error;
+use crate::middleware::{Middleware, MiddlewareFuture, Request, ResponseParts, StatusCode, Url};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    Record,
+    Replay,
+}
+
+/// How replayed requests are matched against recorded ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Matching {
+    /// Requests must arrive in recorded order with the same method, path,
+    /// query and body.
+    #[default]
+    Strict,
+    /// Requests match the first recorded interaction with the same method and
+    /// path, in any order. Query and body are ignored, and interactions are
+    /// reused once every match has been played.
+    Lenient,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// Path and query string, without the host
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    interactions: Vec<Interaction>,
+    played: Vec<bool>,
+    cursor: usize,
+}
+
+/// Recorded API traffic backed by a JSON file.
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    matching: Matching,
+    secrets: Vec<(String, String)>,
+    state: Mutex<State>,
+}
+
+impl Cassette {
+    /// Start recording. Nothing is written until [`save`](Self::save).
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self::new(path.into(), CassetteMode::Record, Vec::new())
+    }
+
+    /// Load a recorded cassette for replay.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let contents = fs::read_to_string(&path).map_err(|e| {
+            Error::UnexpectedError(format!("Failed to read cassette {}: {e}", path.display()))
+        })?;
+        let file: CassetteFile = serde_json::from_str(&contents)?;
+        Ok(Self::new(path, CassetteMode::Replay, file.interactions))
+    }
+
+    fn new(path: PathBuf, mode: CassetteMode, interactions: Vec<Interaction>) -> Self {
+        let played = vec![false; interactions.len()];
+        Cassette {
+            path,
+            mode,
+            matching: Matching::default(),
+            secrets: Vec::new(),
+            state: Mutex::new(State {
+                interactions,
+                played,
+                cursor: 0,
+            }),
+        }
+    }
+
+    pub fn matching(mut self, matching: Matching) -> Self {
+        self.matching = matching;
+        self
+    }
+
+    /// Redact `value` as `{{name}}` in the recording and restore it on replay.
+    pub fn secret(mut self, name: &str, value: &str) -> Self {
+        self.secrets.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// The interactions recorded so far, or loaded for replay.
+    pub fn interactions(&self) -> Result<Vec<Interaction>, Error> {
+        Ok(self.lock()?.interactions.clone())
+    }
+
+    /// Write the recorded interactions to the cassette file. Does nothing when
+    /// replaying.
+    pub fn save(&self) -> Result<(), Error> {
+        if self.mode == CassetteMode::Replay {
+            return Ok(());
+        }
+        let file = CassetteFile {
+            interactions: self.interactions()?,
+        };
+        let write_error = |e: std::io::Error| {
+            Error::UnexpectedError(format!(
+                "Failed to write cassette {}: {e}",
+                self.path.display()
+            ))
+        };
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(write_error)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&file)? + "\n").map_err(write_error)
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, State>, Error> {
+        self.state
+            .lock()
+            .map_err(|_| Error::UnexpectedError("Cassette lock poisoned".to_string()))
+    }
+
+    /// Explicit secrets plus the credentials in the global config.
+    #[cfg_attr(
+        not(any(feature = "license-key", feature = "token")),
+        allow(unused_variables)
+    )]
+    fn secrets(&self) -> Vec<(String, String)> {
+        let mut secrets = self.secrets.clone();
+        if let Ok(config) = get_config() {
+            #[cfg(feature = "license-key")]
+            if let Some(license_key) = config.license_key {
+                secrets.push(("license_key".to_string(), license_key));
+            }
+            #[cfg(feature = "token")]
+            if let Some(token) = config.token {
+                secrets.push(("token".to_string(), token));
+            }
+        }
+        secrets.retain(|(_, value)| !value.is_empty());
+        secrets
+    }
+
+    fn record_response(&self, response: &ResponseParts) -> Result<(), Error> {
+        let secrets = self.secrets();
+        let interaction = Interaction {
+            request: RecordedRequest {
+                method: response.method.to_string(),
+                path: redact(&request_target(&response.url), &secrets),
+                body: response
+                    .request_body
+                    .as_deref()
+                    .map(|body| redact(&String::from_utf8_lossy(body), &secrets)),
+            },
+            response: RecordedResponse {
+                status: response.status.as_u16(),
+                headers: response
+                    .headers
+                    .iter()
+                    .map(|(name, value)| {
+                        let value = String::from_utf8_lossy(value.as_bytes());
+                        (name.to_string(), redact(&value, &secrets))
+                    })
+                    .collect(),
+                body: redact(&String::from_utf8_lossy(&response.body), &secrets),
+            },
+        };
+        self.lock()?.interactions.push(interaction);
+        Ok(())
+    }
+
+    fn replay_response(&self, request: &Request) -> Result<ResponseParts, Error> {
+        let secrets = self.secrets();
+        let method = request.method().to_string();
+        let path = redact(&request_target(request.url()), &secrets);
+        let request_body = request.body().and_then(|body| body.as_bytes());
+        let body = request_body.map(|body| redact(&String::from_utf8_lossy(body), &secrets));
+
+        let mut state = self.lock()?;
+        let index = match self.matching {
+            Matching::Strict => {
+                let index = state.cursor;
+                let recorded = state.interactions.get(index).ok_or_else(|| {
+                    Error::UnexpectedError(format!(
+                        "Cassette has no interaction left for {method} {path}"
+                    ))
+                })?;
+                if recorded.request.method != method
+                    || recorded.request.path != path
+                    || recorded.request.body != body
+                {
+                    return Err(Error::UnexpectedError(format!(
+                        "Cassette mismatch: expected {} {}, got {method} {path}",
+                        recorded.request.method, recorded.request.path
+                    )));
+                }
+                state.cursor += 1;
+                index
+            }
+            Matching::Lenient => {
+                let route = path_only(&path);
+                let candidates: Vec<usize> = state
+                    .interactions
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, recorded)| {
+                        recorded.request.method == method
+                            && path_only(&recorded.request.path) == route
+                    })
+                    .map(|(index, _)| index)
+                    .collect();
+                let index = candidates
+                    .iter()
+                    .copied()
+                    .find(|index| !state.played[*index])
+                    .or_else(|| candidates.last().copied())
+                    .ok_or_else(|| {
+                        Error::UnexpectedError(format!(
+                            "Cassette has no interaction for {method} {route}"
+                        ))
+                    })?;
+                index
+            }
+        };
+        state.played[index] = true;
+        let recorded = &state.interactions[index].response;
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in &recorded.headers {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                Error::UnexpectedError(format!("Invalid header name in cassette: {e}"))
+            })?;
+            headers.append(name, HeaderValue::from_str(&restore(value, &secrets))?);
+        }
+        Ok(ResponseParts {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            status: StatusCode::from_u16(recorded.status)
+                .map_err(|e| Error::UnexpectedError(format!("Invalid status in cassette: {e}")))?,
+            headers,
+            body: restore(&recorded.body, &secrets).into_bytes(),
+            request_body: request_body.map(<[u8]>::to_vec),
+        })
+    }
+}
+
+impl Middleware for Cassette {
+    fn intercept<'a>(
+        &'a self,
+        request: &'a Request,
+    ) -> MiddlewareFuture<'a, Option<ResponseParts>> {
+        let result = match self.mode {
+            CassetteMode::Replay => self.replay_response(request).map(Some),
+            CassetteMode::Record => Ok(None),
+        };
+        Box::pin(std::future::ready(result))
+    }
+
+    fn after_response<'a>(&'a self, response: &'a mut ResponseParts) -> MiddlewareFuture<'a> {
+        let result = match self.mode {
+            CassetteMode::Record => self.record_response(response),
+            CassetteMode::Replay => Ok(()),
+        };
+        Box::pin(std::future::ready(result))
+    }
+}
+
+fn request_target(url: &Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{query}", url.path()),
+        None => url.path().to_string(),
+    }
+}
+
+fn path_only(target: &str) -> &str {
+    target.split('?').next().unwrap_or(target)
+}
+
+fn redact(text: &str, secrets: &[(String, String)]) -> String {
+    secrets
+        .iter()
+        .fold(text.to_string(), |text, (name, value)| {
+            text.replace(value.as_str(), &format!("{{{{{name}}}}}"))
+        })
+}
+
+fn restore(text: &str, secrets: &[(String, String)]) -> String {
+    secrets
+        .iter()
+        .fold(text.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{{{name}}}}}"), value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{Client, ClientOptions, Response};
+    use crate::config::SignatureMode;
+    use crate::middleware::MiddlewareChain;
+    use base64::{engine::general_purpose, Engine};
+    use ed25519_dalek::{Signer, SigningKey};
+    use mockito::{mock, server_url};
+    use rand::rngs::OsRng;
+    use sha2::{Digest, Sha256};
+    use std::sync::Arc;
+
+    #[allow(deprecated)]
+    fn client(api_url: String, public_key: Option<String>, cassette: Arc<Cassette>) -> Client {
+        Client::new(ClientOptions {
+            account: "acme".to_string(),
+            environment: None,
+            license_key: Some("KEY-123".to_string()),
+            token: None,
+            user_agent: None,
+            deployment: None,
+            signature_host: None,
+            api_url,
+            api_version: "1.7".to_string(),
+            api_prefix: "v1".to_string(),
+            signature_mode: SignatureMode::Lenient,
+            verify_keygen_signature: true,
+            public_key,
+            keyring: Default::default(),
+            middleware: MiddlewareChain::new().with(cassette),
+        })
+        .unwrap()
+    }
+
+    fn cassette_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "keygen-cassette-{}-{name}.json",
+            std::process::id()
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_record_redacts_secrets_and_replays() {
+        let path = cassette_path("roundtrip");
+        let body = r#"{"data":{"id":"lic1","attributes":{"key":"KEY-123"}}}"#;
+        let mock = mock("GET", "/v1/cassette/KEY-123")
+            .with_status(200)
+            .with_body(body)
+            .create();
+
+        let recorder = Arc::new(Cassette::record(&path).secret("license_key", "KEY-123"));
+        let _: Response<serde_json::Value> = client(server_url(), None, recorder.clone())
+            .get("cassette/KEY-123", None::<&()>)
+            .await
+            .unwrap();
+        recorder.save().unwrap();
+        drop(mock);
+
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(!saved.contains("KEY-123"));
+        assert!(saved.contains("/v1/cassette/{{license_key}}"));
+
+        let player = Arc::new(
+            Cassette::replay(&path)
+                .unwrap()
+                .secret("license_key", "KEY-123"),
+        );
+        let response: Response<serde_json::Value> = client(server_url(), None, player)
+            .get("cassette/KEY-123", None::<&()>)
+            .await
+            .unwrap();
+        assert_eq!(response.body["data"]["attributes"]["key"], "KEY-123");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_replay_verifies_recorded_signature() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let public_key = hex::encode(signing_key.verifying_key().as_bytes());
+        let body = r#"{"data":{"id":"lic1","attributes":{"key":"KEY-123"}}}"#;
+        let digest = format!(
+            "sha-256={}",
+            general_purpose::STANDARD.encode(Sha256::digest(body.as_bytes()))
+        );
+        let date = "Wed, 09 Jun 2021 16:08:15 GMT";
+        let signing_data = format!(
+            "(request-target): get /v1/accounts/acme/licenses/KEY-123\nhost: api.keygen.sh\ndate: {date}\ndigest: {digest}"
+        );
+        let signature =
+            general_purpose::STANDARD.encode(signing_key.sign(signing_data.as_bytes()).to_bytes());
+
+        let secrets = [("license_key".to_string(), "KEY-123".to_string())];
+        let path = cassette_path("signed");
+        let file = CassetteFile {
+            interactions: vec![Interaction {
+                request: RecordedRequest {
+                    method: "GET".to_string(),
+                    path: "/v1/accounts/acme/licenses/{{license_key}}".to_string(),
+                    body: None,
+                },
+                response: RecordedResponse {
+                    status: 200,
+                    headers: vec![
+                        (
+                            "keygen-signature".to_string(),
+                            format!(
+                                r#"keyid="acme", algorithm="ed25519", signature="{signature}", headers="(request-target) host date digest""#
+                            ),
+                        ),
+                        ("keygen-date".to_string(), date.to_string()),
+                        ("keygen-digest".to_string(), digest),
+                    ],
+                    body: redact(body, &secrets),
+                },
+            }],
+        };
+        fs::write(&path, serde_json::to_string(&file).unwrap()).unwrap();
+
+        let player = Arc::new(
+            Cassette::replay(&path)
+                .unwrap()
+                .secret("license_key", "KEY-123"),
+        );
+        let client = client(
+            "https://api.keygen.sh".to_string(),
+            Some(public_key),
+            player,
+        );
+        let response: Response<serde_json::Value> =
+            client.get("licenses/KEY-123", None::<&()>).await.unwrap();
+        assert_eq!(response.body["data"]["id"], "lic1");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_matching_modes() {
+        let interaction = |path: &str| Interaction {
+            request: RecordedRequest {
+                method: "GET".to_string(),
+                path: path.to_string(),
+                body: None,
+            },
+            response: RecordedResponse {
+                status: 200,
+                headers: Vec::new(),
+                body: "{}".to_string(),
+            },
+        };
+        let interactions = vec![interaction("/v1/a?page=1"), interaction("/v1/b")];
+        let request = |url: &str| Request::new(reqwest::Method::GET, Url::parse(url).unwrap());
+
+        let strict = Cassette::new(PathBuf::new(), CassetteMode::Replay, interactions.clone());
+        assert!(strict
+            .replay_response(&request("https://x.test/v1/b"))
+            .is_err());
+        assert!(strict
+            .replay_response(&request("https://x.test/v1/a?page=1"))
+            .is_ok());
+        assert!(strict
+            .replay_response(&request("https://x.test/v1/b"))
+            .is_ok());
+        assert!(strict
+            .replay_response(&request("https://x.test/v1/b"))
+            .is_err());
+
+        let lenient = Cassette::new(PathBuf::new(), CassetteMode::Replay, interactions)
+            .matching(Matching::Lenient);
+        assert!(lenient
+            .replay_response(&request("https://x.test/v1/b"))
+            .is_ok());
+        assert!(lenient
+            .replay_response(&request("https://x.test/v1/a?page=2"))
+            .is_ok());
+        assert!(lenient
+            .replay_response(&request("https://x.test/v1/b"))
+            .is_ok());
+        assert!(lenient
+            .replay_response(&request("https://x.test/v1/c"))
+            .is_err());
+    }
+}
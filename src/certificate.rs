@@ -10,6 +10,14 @@ pub struct Certificate {
     pub alg: String,
 }
 
+impl Certificate {
+    /// Whether the contents are encrypted (`aes-256-gcm+*`) and need a key to
+    /// be read, rather than only base64-encoded (`base64+*`).
+    pub fn is_encrypted(&self) -> bool {
+        self.alg.starts_with("aes-256-gcm+")
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CertificateFileMeta {
     pub issued: DateTime<Utc>,
@@ -3,8 +3,13 @@
 //! This module provides the low-level HTTP client used to communicate with the Keygen API.
 //! It handles authentication, request signing verification, and error handling.
 
-use crate::config::get_config;
+use crate::config::{endpoint_url, get_config, Deployment, SignatureMode};
 use crate::errors::Error;
+use crate::keyring::Keyring;
+use crate::metrics;
+use crate::middleware::{MiddlewareChain, ResponseParts};
+use crate::service::Capability;
+use crate::trace::{redact, redact_license_keys, trace_event, RequestTrace};
 use crate::verifier::Verifier;
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
 use reqwest::{Client as ReqwestClient, Request, StatusCode};
@@ -28,11 +33,20 @@ pub struct ClientOptions {
     pub license_key: Option<String>,
     pub token: Option<String>,
     pub user_agent: Option<String>,
+    pub deployment: Option<Deployment>,
+    pub signature_host: Option<String>,
     pub api_url: String,
     pub api_version: String,
     pub api_prefix: String,
+    pub signature_mode: SignatureMode,
+    /// `false` is the same as [`SignatureMode::Insecure`], unless
+    /// `signature_mode` is [`SignatureMode::Strict`]
+    #[deprecated(note = "use `signature_mode` instead")]
     pub verify_keygen_signature: bool,
     pub public_key: Option<String>,
+    /// Additional trusted public keys, selected by the signature's `keyid`
+    pub keyring: Keyring,
+    pub middleware: MiddlewareChain,
 }
 
 #[derive(Debug)]
@@ -55,7 +69,22 @@ struct ErrorMeta {
     pub detail: String,
 }
 
+impl ClientOptions {
+    /// The signature mode in effect once the deprecated
+    /// `verify_keygen_signature` flag is taken into account. `Strict` always
+    /// wins over the legacy flag.
+    #[allow(deprecated)]
+    pub fn effective_signature_mode(&self) -> SignatureMode {
+        match self.signature_mode {
+            SignatureMode::Strict => SignatureMode::Strict,
+            _ if !self.verify_keygen_signature => SignatureMode::Insecure,
+            mode => mode,
+        }
+    }
+}
+
 impl From<crate::config::KeygenConfig> for ClientOptions {
+    #[allow(deprecated)]
     fn from(config: crate::config::KeygenConfig) -> Self {
         Self {
             account: config.account,
@@ -69,10 +98,16 @@ impl From<crate::config::KeygenConfig> for ClientOptions {
             #[cfg(not(feature = "token"))]
             token: None,
             user_agent: config.user_agent,
+            deployment: config.deployment,
+            signature_host: config.signature_host,
             api_url: config.api_url,
             api_version: config.api_version,
             api_prefix: config.api_prefix,
             #[cfg(feature = "license-key")]
+            signature_mode: config.signature_mode,
+            #[cfg(not(feature = "license-key"))]
+            signature_mode: SignatureMode::default(),
+            #[cfg(feature = "license-key")]
             verify_keygen_signature: config.verify_keygen_signature.unwrap_or(true),
             #[cfg(not(feature = "license-key"))]
             verify_keygen_signature: true,
@@ -80,6 +115,11 @@ impl From<crate::config::KeygenConfig> for ClientOptions {
             public_key: config.public_key,
             #[cfg(not(feature = "license-key"))]
             public_key: None,
+            #[cfg(feature = "license-key")]
+            keyring: config.keyring,
+            #[cfg(not(feature = "license-key"))]
+            keyring: Keyring::default(),
+            middleware: config.middleware,
         }
     }
 }
@@ -95,19 +135,26 @@ impl Client {
 
     /// Creates a new client with the specified options.
     pub fn new(options: ClientOptions) -> Result<Self, Error> {
-        let builder = ReqwestClient::builder();
-        #[cfg(not(target_arch = "wasm32"))]
-        let builder = builder.timeout(Duration::from_secs(30));
-        let client = builder
-            .build()
-            .map_err(|e| Error::UnexpectedError(format!("Failed to build HTTP client: {e}")))?;
-
         Ok(Self {
-            inner: client,
+            inner: build_http(transport())?,
             options,
         })
     }
 
+    /// A transport configured like the client's own, but that doesn't follow
+    /// redirects, for callers that need the `Location` of a redirect.
+    #[cfg(all(feature = "token", not(target_arch = "wasm32")))]
+    pub(crate) fn without_redirects(&self) -> Result<ReqwestClient, Error> {
+        build_http(transport().redirect(reqwest::redirect::Policy::none()))
+    }
+
+    /// A transport for uploading file contents to storage, which only bounds
+    /// the time to connect since a large upload can take any amount of time.
+    #[cfg(all(feature = "token", not(target_arch = "wasm32")))]
+    pub(crate) fn for_uploads(&self) -> Result<ReqwestClient, Error> {
+        build_http(streaming_transport().redirect(reqwest::redirect::Policy::none()))
+    }
+
     pub fn set_query<T: Serialize + ?Sized>(
         &self,
         request: Request,
@@ -147,6 +194,31 @@ impl Client {
         self.send(request).await
     }
 
+    /// POST with a user's email and password as HTTP Basic credentials in
+    /// place of the configured license key or token.
+    #[cfg(feature = "token")]
+    pub(crate) async fn post_with_credentials<T, U>(
+        &self,
+        path: &str,
+        email: &str,
+        password: &str,
+        body: Option<&T>,
+    ) -> Result<Response<U>, Error>
+    where
+        T: Serialize + ?Sized,
+        U: DeserializeOwned + Serialize,
+    {
+        use base64::{engine::general_purpose, Engine};
+
+        let mut request = self.new_request(reqwest::Method::POST, path, body)?;
+        let credentials = general_purpose::STANDARD.encode(format!("{email}:{password}"));
+        request.headers_mut().insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Basic {credentials}"))?,
+        );
+        self.send(request).await
+    }
+
     #[allow(dead_code)]
     pub async fn put<T, U, Q>(
         &self,
@@ -218,24 +290,22 @@ impl Client {
         self.build_request(method, path, params, false)
     }
 
-    pub(crate) fn build_url(&self, path: &str) -> Result<Url, Error> {
-        let mut url = Url::parse(&self.options.api_url)?;
-
-        if self.options.api_url == "https://api.keygen.sh" {
-            url.path_segments_mut()
-                .map_err(|_| Error::InvalidUrl)?
-                .push(self.options.api_prefix.as_str())
-                .push("accounts")
-                .push(self.options.account.as_str())
-                .extend(path.split('/'));
-        } else {
-            url.path_segments_mut()
-                .map_err(|_| Error::InvalidUrl)?
-                .push(self.options.api_prefix.as_str())
-                .extend(path.split('/'));
-        }
+    /// Fail fast when the configured API version lacks `capability`, rather
+    /// than sending a request the server would reject.
+    pub(crate) fn require(&self, capability: Capability) -> Result<(), Error> {
+        capability.require(&self.options.api_version)
+    }
 
-        Ok(url)
+    pub(crate) fn build_url(&self, path: &str) -> Result<Url, Error> {
+        endpoint_url(
+            &self.options.api_url,
+            &self.options.api_prefix,
+            self.options
+                .deployment
+                .unwrap_or_else(|| Deployment::infer(&self.options.api_url)),
+            &self.options.account,
+            path,
+        )
     }
 
     pub(crate) fn build_request<T: Serialize + ?Sized>(
@@ -245,6 +315,12 @@ impl Client {
         params: Option<&T>,
         include_version: bool,
     ) -> Result<Request, Error> {
+        if let Some(capability) = Capability::for_path(path) {
+            self.require(capability)?;
+        }
+        if self.options.environment.is_some() {
+            self.require(Capability::Environments)?;
+        }
         let mut url = self.build_url(path)?;
 
         if method == reqwest::Method::GET {
@@ -301,38 +377,26 @@ impl Client {
         &self,
         request: Request,
     ) -> Result<Response<U>, Error> {
-        let method = request.method().as_str().to_owned();
-        let url = request.url().clone();
-        let host = match (url.host_str(), url.port()) {
-            (Some(h), Some(p)) => format!("{h}:{p}"),
-            (Some(h), None) => h.to_string(),
-            _ => "api.keygen.sh".to_string(),
-        };
-        let response = self.inner.execute(request).await?;
+        let trace = self.trace(&request);
+        trace.instrument(self.send_traced(request, &trace)).await
+    }
 
-        let status = response.status();
-        let headers = response.headers().clone();
+    async fn send_traced<U: DeserializeOwned + Serialize>(
+        &self,
+        request: Request,
+        trace: &RequestTrace,
+    ) -> Result<Response<U>, Error> {
+        let ResponseParts {
+            status,
+            headers,
+            body: bytes,
+            ..
+        } = self.execute_verified(&self.inner, request, trace).await?;
 
         if status.is_client_error() || status.is_server_error() {
-            let error_body: serde_json::Value = response.json().await?;
+            let error_body: serde_json::Value = serde_json::from_slice(&bytes)?;
             return Err(self.handle_error(status, &headers, error_body));
         }
-        let bytes = response.bytes().await?;
-
-        if self.options.verify_keygen_signature {
-            if let Some(public_key) = &self.options.public_key {
-                let verifier = Verifier::new(public_key.clone());
-
-                let base_path = url.path();
-                let full_path = if let Some(query) = url.query() {
-                    format!("{base_path}?{query}")
-                } else {
-                    base_path.to_string()
-                };
-
-                verifier.verify_keygen_signature(&headers, &bytes, &method, &full_path, &host)?;
-            }
-        }
 
         let body: U = if status == StatusCode::NO_CONTENT {
             serde_json::from_value(serde_json::Value::Null)?
@@ -348,68 +412,208 @@ impl Client {
     }
 
     async fn send_text(&self, request: Request) -> Result<Response<String>, Error> {
-        let method = request.method().as_str().to_owned();
-        let url = request.url().clone();
-        let host = match (url.host_str(), url.port()) {
-            (Some(h), Some(p)) => format!("{h}:{p}"),
-            (Some(h), None) => h.to_string(),
-            _ => "api.keygen.sh".to_string(),
-        };
-
-        let response = self.inner.execute(request).await?;
+        let trace = self.trace(&request);
+        trace
+            .instrument(self.send_text_traced(request, &trace))
+            .await
+    }
 
-        let status = response.status();
-        let headers = response.headers().clone();
+    async fn send_text_traced(
+        &self,
+        request: Request,
+        trace: &RequestTrace,
+    ) -> Result<Response<String>, Error> {
+        let ResponseParts {
+            status,
+            headers,
+            body: bytes,
+            ..
+        } = self.execute_verified(&self.inner, request, trace).await?;
 
         if status.is_client_error() || status.is_server_error() {
-            let error_body: serde_json::Value = response.json().await?;
+            let error_body: serde_json::Value = serde_json::from_slice(&bytes)?;
             return Err(self.handle_error(status, &headers, error_body));
         }
 
-        let text = response.text().await?;
-
-        if self.options.verify_keygen_signature {
-            if let Some(public_key) = &self.options.public_key {
-                let verifier = Verifier::new(public_key.clone());
+        Ok(Response {
+            status,
+            headers,
+            body: String::from_utf8_lossy(&bytes).into_owned(),
+        })
+    }
 
-                let base_path = url.path();
-                let full_path = if let Some(query) = url.query() {
-                    format!("{base_path}?{query}")
-                } else {
-                    base_path.to_string()
+    /// Send `request` with `http` through the middleware chain and read the
+    /// response body. `http` is the client's own transport unless the caller
+    /// needs different redirect handling.
+    async fn execute(
+        &self,
+        http: &ReqwestClient,
+        mut request: Request,
+        trace: &RequestTrace,
+    ) -> Result<ResponseParts, Error> {
+        self.options.middleware.before_request(&mut request).await?;
+
+        let mut parts = match self.options.middleware.intercept(&request).await? {
+            Some(parts) => {
+                trace.finish(parts.status, &parts.headers);
+                parts
+            }
+            None => {
+                let method = request.method().clone();
+                let url = request.url().clone();
+                let request_body = request
+                    .body()
+                    .and_then(|body| body.as_bytes())
+                    .map(<[u8]>::to_vec);
+
+                let response = match http.execute(request).await {
+                    Ok(response) => response,
+                    Err(error) => {
+                        let error = Error::from(error);
+                        trace.fail(&error);
+                        return Err(error);
+                    }
                 };
+                let status = response.status();
+                let headers = response.headers().clone();
+                trace.finish(status, &headers);
+
+                ResponseParts {
+                    method,
+                    url,
+                    status,
+                    headers,
+                    body: response.bytes().await?.into(),
+                    request_body,
+                }
+            }
+        };
+        self.options.middleware.after_response(&mut parts).await?;
+        Ok(parts)
+    }
+
+    /// [`execute`](Self::execute), then check the response signature as the
+    /// signature mode requires. Every response the SDK acts on, including
+    /// redirects, must go through here so that strict mode fails closed.
+    pub(crate) async fn execute_verified(
+        &self,
+        http: &ReqwestClient,
+        request: Request,
+        trace: &RequestTrace,
+    ) -> Result<ResponseParts, Error> {
+        let parts = self.execute(http, request, trace).await?;
+        self.verify_response(
+            &parts.method,
+            &parts.url,
+            parts.status,
+            &parts.headers,
+            &parts.body,
+        )?;
+        Ok(parts)
+    }
 
-                verifier.verify_keygen_signature(
-                    &headers,
-                    text.as_bytes(),
-                    &method,
-                    &full_path,
-                    &host,
-                )?;
+    /// Check the `Keygen-Signature` of a response as the signature mode requires.
+    fn verify_response(
+        &self,
+        method: &reqwest::Method,
+        url: &Url,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<(), Error> {
+        let public_keys = self
+            .options
+            .keyring
+            .including(self.options.public_key.as_ref());
+        match self.options.effective_signature_mode() {
+            SignatureMode::Insecure => return Ok(()),
+            SignatureMode::Lenient
+                if public_keys.is_empty()
+                    || status.is_client_error()
+                    || status.is_server_error() =>
+            {
+                return Ok(())
             }
+            SignatureMode::Strict if public_keys.is_empty() => return Err(Error::PublicKeyMissing),
+            _ => {}
         }
 
-        Ok(Response {
-            status,
-            headers,
-            body: text,
-        })
+        // Behind a reverse proxy the server signs for its own host, not ours
+        let host = match (&self.options.signature_host, url.host_str(), url.port()) {
+            (Some(host), _, _) => host.clone(),
+            (None, Some(h), Some(p)) => format!("{h}:{p}"),
+            (None, Some(h), None) => h.to_string(),
+            _ => "api.keygen.sh".to_string(),
+        };
+        // A proxy mounted under a path prefix strips it before Keygen signs
+        let api_path = self.api_url_path();
+        let base_path = url
+            .path()
+            .strip_prefix(api_path.as_str())
+            .filter(|path| path.starts_with('/'))
+            .unwrap_or(url.path());
+        let full_path = if let Some(query) = url.query() {
+            format!("{base_path}?{query}")
+        } else {
+            base_path.to_string()
+        };
+
+        let verifier = Verifier::new(public_keys);
+        verifier.verify_keygen_signature(headers, body, method.as_str(), &full_path, &host)
+    }
+
+    /// Open a request span, with credentials redacted from the recorded path.
+    pub(crate) fn trace(&self, request: &Request) -> RequestTrace {
+        let path = redact_license_keys(&redact(
+            request.url().path(),
+            &[
+                self.options.license_key.as_deref(),
+                self.options.token.as_deref(),
+            ],
+        ));
+        RequestTrace::start(request.method(), &path, &self.relative_path(&path))
+    }
+
+    /// The path of the API URL itself, e.g. `/keygen` for
+    /// `https://host/keygen/`, or empty when it has none.
+    fn api_url_path(&self) -> String {
+        Url::parse(&self.options.api_url)
+            .map(|url| url.path().trim_end_matches('/').to_string())
+            .unwrap_or_default()
+    }
+
+    /// Strip the API URL's own path, the API prefix and the account scope
+    /// from `path`, leaving e.g. `licenses/KEY/actions/validate`.
+    pub(crate) fn relative_path(&self, path: &str) -> String {
+        let mut segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let base = self.api_url_path();
+        let mut prefix: Vec<&str> = base.split('/').filter(|s| !s.is_empty()).collect();
+        prefix.push(self.options.api_prefix.as_str());
+        if segments.starts_with(&prefix) {
+            segments.drain(..prefix.len());
+        }
+        if segments.starts_with(&["accounts", self.options.account.as_str()]) {
+            segments.drain(..2);
+        }
+        segments.join("/")
     }
 
-    fn handle_error(
+    pub(crate) fn handle_error(
         &self,
         status: StatusCode,
         headers: &HeaderMap,
         body: serde_json::Value,
     ) -> Error {
-        match status {
+        let error = match status {
             StatusCode::TOO_MANY_REQUESTS => self.handle_rate_limit_error(headers),
             StatusCode::FORBIDDEN => self.handle_forbidden_error(&body),
             _ if status.is_server_error() => Error::UnexpectedError(format!(
                 "Unexpected API error: status={status}, body={body}"
             )),
             _ => self.handle_other_error(&body),
-        }
+        };
+        metrics::record_error(&error);
+        error
     }
 
     fn handle_rate_limit_error(&self, headers: &HeaderMap) -> Error {
@@ -444,6 +648,7 @@ impl Client {
             .and_then(|v| v.parse().ok())
             .unwrap_or(0);
 
+        trace_event!(warn, window, remaining, retry_after, "rate limit exceeded");
         Error::RateLimitExceeded {
             window: window.to_string(),
             count,
@@ -486,6 +691,7 @@ impl Client {
     fn handle_other_error(&self, body: &serde_json::Value) -> Error {
         let meta: Result<ErrorMeta, serde_json::Error> =
             serde_json::from_value(body["errors"][0].clone());
+        let reset_token = body["errors"][0]["source"]["pointer"] == "/meta/passwordResetToken";
         if let Ok(meta) = meta {
             let detail = meta.detail.clone();
             let code = meta.code.clone();
@@ -504,12 +710,18 @@ impl Client {
                 "TOKEN_INVALID" => Error::LicenseTokenInvalid { code, detail },
                 "LICENSE_INVALID" => Error::LicenseKeyInvalid { code, detail },
                 "NOT_FOUND" => Error::NotFound { code, detail },
+                "PASSWORD_RESET_TOKEN_EXPIRED" => Error::PasswordResetTokenExpired { code, detail },
+                "PASSWORD_RESET_TOKEN_INVALID" => Error::PasswordResetTokenInvalid { code, detail },
+                _ if reset_token => password_reset_token_error(Some(code), detail),
                 _ => Error::KeygenApiError {
                     code: code.clone(),
                     detail: detail.clone(),
                     body: body.clone(),
                 },
             }
+        } else if reset_token {
+            let detail = body["errors"][0]["detail"].as_str().unwrap_or_default();
+            password_reset_token_error(None, detail.to_string())
         } else {
             Error::KeygenApiError {
                 code: "API_ERROR".to_string(),
@@ -520,12 +732,52 @@ impl Client {
     }
 }
 
+/// A password reset token error without one of the reset token codes, told
+/// apart by its detail, e.g. "is expired"
+fn password_reset_token_error(code: Option<String>, detail: String) -> Error {
+    if detail.contains("expired") {
+        Error::PasswordResetTokenExpired {
+            code: code.unwrap_or_else(|| "PASSWORD_RESET_TOKEN_EXPIRED".to_string()),
+            detail,
+        }
+    } else {
+        Error::PasswordResetTokenInvalid {
+            code: code.unwrap_or_else(|| "PASSWORD_RESET_TOKEN_INVALID".to_string()),
+            detail,
+        }
+    }
+}
+
+/// The HTTP transport settings shared by every request the client sends
+fn transport() -> reqwest::ClientBuilder {
+    let builder = streaming_transport();
+    #[cfg(not(target_arch = "wasm32"))]
+    let builder = builder.timeout(Duration::from_secs(30));
+    builder
+}
+
+/// [`transport`] without the whole-request timeout, for bodies that take as
+/// long to send as their size requires
+fn streaming_transport() -> reqwest::ClientBuilder {
+    let builder = ReqwestClient::builder();
+    #[cfg(not(target_arch = "wasm32"))]
+    let builder = builder.connect_timeout(Duration::from_secs(30));
+    builder
+}
+
+fn build_http(builder: reqwest::ClientBuilder) -> Result<ReqwestClient, Error> {
+    builder
+        .build()
+        .map_err(|e| Error::UnexpectedError(format!("Failed to build HTTP client: {e}")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use mockito::{mock, server_url};
     use serde_json::json;
 
+    #[allow(deprecated)]
     fn create_test_client() -> Client {
         Client::new(ClientOptions {
             account: "test_account".to_string(),
@@ -533,11 +785,16 @@ mod tests {
             license_key: Some("test_license_key".to_string()),
             token: None,
             user_agent: Some("test_user_agent".to_string()),
+            deployment: None,
+            signature_host: None,
             api_url: server_url(),
             api_version: "1.0".to_string(),
             api_prefix: "v1".to_string(),
             public_key: None,
-            verify_keygen_signature: true, // Enable Keygen-Signature verification for tests
+            keyring: Keyring::default(),
+            signature_mode: SignatureMode::Lenient,
+            verify_keygen_signature: true,
+            middleware: MiddlewareChain::default(),
         })
         .expect("Failed to create test client")
     }
@@ -558,6 +815,24 @@ mod tests {
         assert_eq!(response.body["data"]["id"], "123");
     }
 
+    #[tokio::test]
+    async fn test_unsupported_feature_fails_before_request() {
+        let m = mock("GET", "/v1/machines/m-1/components")
+            .expect(0)
+            .create();
+
+        let client = create_test_client();
+        let result: Result<Response<serde_json::Value>, Error> =
+            client.get("machines/m-1/components", None::<&()>).await;
+
+        assert!(matches!(
+            result,
+            Err(Error::FeatureNotSupported { feature, required, api_version })
+                if feature == "components" && required == "1.4" && api_version == "1.0"
+        ));
+        m.assert();
+    }
+
     #[tokio::test]
     async fn test_post_request() {
         let _m = mock("POST", "/v1/test_path")
@@ -689,4 +964,234 @@ mod tests {
             _ => panic!("Expected RateLimitExceeded error"),
         }
     }
+
+    struct CorrelationId;
+
+    impl crate::middleware::Middleware for CorrelationId {
+        fn before_request<'a>(
+            &'a self,
+            request: &'a mut Request,
+        ) -> crate::middleware::MiddlewareFuture<'a> {
+            Box::pin(async move {
+                request
+                    .headers_mut()
+                    .insert("X-Correlation-Id", HeaderValue::from_static("job-42"));
+                Ok(())
+            })
+        }
+    }
+
+    struct ServerError;
+
+    impl crate::middleware::Middleware for ServerError {
+        fn after_response<'a>(
+            &'a self,
+            response: &'a mut ResponseParts,
+        ) -> crate::middleware::MiddlewareFuture<'a> {
+            Box::pin(async move {
+                response.status = StatusCode::SERVICE_UNAVAILABLE;
+                response.body = br#"{"errors": []}"#.to_vec();
+                Ok(())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_before_request_adds_header() {
+        let _m = mock("GET", "/v1/middleware_header")
+            .match_header("x-correlation-id", "job-42")
+            .with_status(200)
+            .with_body(r#"{"data": {"id": "123", "type": "test"}}"#)
+            .create();
+
+        let mut client = create_test_client();
+        client.options.middleware.push(CorrelationId);
+        let response: Response<serde_json::Value> =
+            client.get("middleware_header", None::<&()>).await.unwrap();
+
+        assert_eq!(response.body["data"]["id"], "123");
+    }
+
+    #[tokio::test]
+    async fn test_middleware_after_response_rewrites_response() {
+        let _m = mock("GET", "/v1/middleware_fault")
+            .with_status(200)
+            .with_body(r#"{"data": {"id": "123", "type": "test"}}"#)
+            .create();
+
+        let mut client = create_test_client();
+        client.options.middleware.push(ServerError);
+        let result: Result<Response<serde_json::Value>, Error> =
+            client.get("middleware_fault", None::<&()>).await;
+
+        assert!(matches!(result, Err(Error::UnexpectedError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_strict_signature_mode_requires_public_key() {
+        let _m = mock("GET", "/v1/strict_no_key")
+            .with_status(200)
+            .with_body(r#"{"data": {"id": "123", "type": "test"}}"#)
+            .create();
+
+        let mut client = create_test_client();
+        client.options.signature_mode = SignatureMode::Strict;
+        let result: Result<Response<serde_json::Value>, Error> =
+            client.get("strict_no_key", None::<&()>).await;
+
+        assert!(matches!(result, Err(Error::PublicKeyMissing)));
+    }
+
+    #[cfg(feature = "license-key")]
+    #[test]
+    fn test_strict_signature_mode_wins_over_legacy_flag() {
+        let options = ClientOptions::from(crate::config::KeygenConfig {
+            verify_keygen_signature: Some(false),
+            signature_mode: SignatureMode::Strict,
+            ..Default::default()
+        });
+        assert_eq!(options.effective_signature_mode(), SignatureMode::Strict);
+
+        let options = ClientOptions::from(crate::config::KeygenConfig {
+            verify_keygen_signature: Some(false),
+            ..Default::default()
+        });
+        assert_eq!(options.effective_signature_mode(), SignatureMode::Insecure);
+    }
+
+    #[tokio::test]
+    async fn test_strict_signature_mode_rejects_unsigned_error() {
+        let _m = mock("GET", "/v1/strict_unsigned_error")
+            .with_status(403)
+            .with_body(r#"{"errors": [{"code": "LICENSE_EXPIRED", "detail": "expired"}]}"#)
+            .create();
+
+        let mut client = create_test_client();
+        client.options.public_key = Some("0".repeat(64));
+        let result: Result<Response<serde_json::Value>, Error> =
+            client.get("strict_unsigned_error", None::<&()>).await;
+        assert!(matches!(result, Err(Error::LicenseExpired { .. })));
+
+        client.options.signature_mode = SignatureMode::Strict;
+        let result: Result<Response<serde_json::Value>, Error> =
+            client.get("strict_unsigned_error", None::<&()>).await;
+        assert!(matches!(result, Err(Error::KeygenSignatureMissing)));
+    }
+
+    #[test]
+    fn test_build_url_follows_deployment() {
+        let mut client = create_test_client();
+        let base = server_url();
+
+        let url = client.build_url("licenses/actions/validate-key").unwrap();
+        assert_eq!(
+            url.as_str(),
+            format!("{base}/v1/licenses/actions/validate-key")
+        );
+
+        client.options.deployment = Some(Deployment::SelfHosted);
+        let url = client.build_url("licenses/actions/validate-key").unwrap();
+        assert_eq!(
+            url.as_str(),
+            format!("{base}/v1/accounts/test_account/licenses/actions/validate-key")
+        );
+
+        client.options.deployment = Some(Deployment::CustomDomain);
+        let url = client.build_url("me").unwrap();
+        assert_eq!(url.as_str(), format!("{base}/v1/me"));
+
+        client.options.deployment = Some(Deployment::SelfHostedSingleTenant);
+        client.options.api_url = format!("{base}/keygen/");
+        let url = client.build_url("me").unwrap();
+        assert_eq!(url.as_str(), format!("{base}/keygen/v1/me"));
+
+        client.options.deployment = None;
+        client.options.api_url = "https://api.eu.keygen.sh/".to_string();
+        let url = client.build_url("me").unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://api.eu.keygen.sh/v1/accounts/test_account/me"
+        );
+    }
+
+    #[test]
+    fn test_relative_path_strips_api_prefixes() {
+        let mut client = create_test_client();
+        assert_eq!(
+            client.relative_path("/v1/accounts/test_account/licenses/KEY/actions/validate"),
+            "licenses/KEY/actions/validate"
+        );
+
+        // Single-tenant deployments have no account segment to anchor on
+        client.options.api_url = format!("{}/keygen/", server_url());
+        client.options.deployment = Some(Deployment::SelfHostedSingleTenant);
+        let relative = client.relative_path("/keygen/v1/licenses/KEY/actions/validate");
+        assert_eq!(relative, "licenses/KEY/actions/validate");
+        assert_eq!(
+            crate::metrics::endpoint(&relative),
+            "licenses/:id/actions/validate"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_signature_host_behind_reverse_proxy() {
+        use base64::{engine::general_purpose, Engine};
+        use ed25519_dalek::{Signer, SigningKey};
+        use rand::rngs::OsRng;
+        use sha2::{Digest, Sha256};
+
+        let keypair = SigningKey::generate(&mut OsRng);
+        let body = r#"{"data": {"id": "123", "type": "test"}}"#;
+        let date = "Wed, 09 Jun 2021 16:08:15 GMT";
+        let digest = format!(
+            "sha-256={}",
+            general_purpose::STANDARD.encode(Sha256::digest(body))
+        );
+        let signing_data = format!(
+            "(request-target): get /v1/behind_proxy\nhost: licensing.internal\ndate: {date}\ndigest: {digest}"
+        );
+        let signature =
+            general_purpose::STANDARD.encode(keypair.sign(signing_data.as_bytes()).to_bytes());
+        let _m = mock("GET", "/v1/behind_proxy")
+            .with_status(200)
+            .with_header("keygen-date", date)
+            .with_header("keygen-digest", &digest)
+            .with_header(
+                "keygen-signature",
+                &format!(
+                    r#"keyid="test_account", algorithm="ed25519", signature="{signature}", headers="(request-target) host date digest""#
+                ),
+            )
+            .with_body(body)
+            .create();
+
+        let mut client = create_test_client();
+        client.options.public_key = Some(hex::encode(keypair.verifying_key().as_bytes()));
+        let result: Result<Response<serde_json::Value>, Error> =
+            client.get("behind_proxy", None::<&()>).await;
+        assert!(matches!(result, Err(Error::KeygenSignatureInvalid { .. })));
+
+        client.options.signature_host = Some("licensing.internal".to_string());
+        let response: Response<serde_json::Value> =
+            client.get("behind_proxy", None::<&()>).await.unwrap();
+        assert_eq!(response.body["data"]["id"], "123");
+
+        // Keygen signs the path it saw, without the proxy's mount point
+        let _m = mock("GET", "/keygen/v1/behind_proxy")
+            .with_status(200)
+            .with_header("keygen-date", date)
+            .with_header("keygen-digest", &digest)
+            .with_header(
+                "keygen-signature",
+                &format!(
+                    r#"keyid="test_account", algorithm="ed25519", signature="{signature}", headers="(request-target) host date digest""#
+                ),
+            )
+            .with_body(body)
+            .create();
+        client.options.api_url = format!("{}/keygen/", server_url());
+        let response: Response<serde_json::Value> =
+            client.get("behind_proxy", None::<&()>).await.unwrap();
+        assert_eq!(response.body["data"]["id"], "123");
+    }
 }
@@ -15,10 +15,115 @@
 //!     ..Default::default()
 //! }).expect("Failed to set config");
 //! ```
+//!
+//! Configuration can also be loaded from `KEYGEN_*` environment variables and
+//! TOML or JSON files, see [`KeygenConfig::load`].
 
 use crate::errors::Error;
+use crate::keyring::Keyring;
+#[cfg(feature = "license-key")]
+use crate::keyring::PublicKey;
+use crate::middleware::{Middleware, MiddlewareChain};
 use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::str::FromStr;
 use std::sync::RwLock;
+use std::{env, fs};
+
+/// How strictly `Keygen-Signature` headers on API responses are checked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureMode {
+    /// Verify successful responses when a public key is configured, and let
+    /// everything else through unverified
+    #[default]
+    Lenient,
+    /// Verify every response, error responses included. A missing public key
+    /// or an unsigned response is an error.
+    Strict,
+    /// Never verify response signatures
+    Insecure,
+}
+
+/// Where the Keygen API is hosted, which decides how request URLs are laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Deployment {
+    /// Keygen Cloud. Requests are scoped to `/v1/accounts/<account>`.
+    Cloud,
+    /// A Keygen Cloud custom domain, which already identifies the account, so
+    /// requests go to `/v1/...`
+    CustomDomain,
+    /// A self-hosted Keygen CE or EE instance. Requests are scoped to
+    /// `/v1/accounts/<account>`, which both multi- and single-tenant servers
+    /// accept.
+    SelfHosted,
+    /// A single-tenant self-hosted instance (`KEYGEN_MODE=singleplayer`)
+    /// serving the unscoped `/v1/...` routes only, e.g. behind a proxy that
+    /// doesn't expose the account paths
+    SelfHostedSingleTenant,
+}
+
+impl Deployment {
+    /// The deployment implied by `api_url` when none is configured: any
+    /// `keygen.sh` host is Keygen Cloud, and any other host is assumed to need
+    /// unscoped paths, like a custom domain.
+    pub fn infer(api_url: &str) -> Self {
+        let keygen_cloud = url::Url::parse(api_url).is_ok_and(|url| {
+            url.host_str()
+                .is_some_and(|host| host == "keygen.sh" || host.ends_with(".keygen.sh"))
+        });
+        if keygen_cloud {
+            Deployment::Cloud
+        } else {
+            Deployment::CustomDomain
+        }
+    }
+
+    /// Whether request paths include `/accounts/<account>`
+    pub fn is_account_scoped(&self) -> bool {
+        matches!(self, Deployment::Cloud | Deployment::SelfHosted)
+    }
+
+    fn is_self_hosted(&self) -> bool {
+        matches!(
+            self,
+            Deployment::SelfHosted | Deployment::SelfHostedSingleTenant
+        )
+    }
+}
+
+impl FromStr for Deployment {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "cloud" => Ok(Deployment::Cloud),
+            "custom-domain" => Ok(Deployment::CustomDomain),
+            "self-hosted" => Ok(Deployment::SelfHosted),
+            "self-hosted-single-tenant" => Ok(Deployment::SelfHostedSingleTenant),
+            _ => Err(Error::InvalidConfiguration(vec![format!(
+                "unknown deployment {s:?}, expected cloud, custom-domain, self-hosted or self-hosted-single-tenant"
+            )])),
+        }
+    }
+}
+
+impl FromStr for SignatureMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "lenient" => Ok(SignatureMode::Lenient),
+            "strict" => Ok(SignatureMode::Strict),
+            "insecure" => Ok(SignatureMode::Insecure),
+            _ => Err(Error::InvalidConfiguration(vec![format!(
+                "unknown signature mode {s:?}, expected strict, lenient or insecure"
+            )])),
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct KeygenConfig {
@@ -29,6 +134,15 @@ pub struct KeygenConfig {
     pub account: String,
     pub environment: Option<String>,
     pub user_agent: Option<String>,
+    /// How the API is hosted. When unset, it is inferred from `api_url`, see
+    /// [`Deployment::infer`].
+    pub deployment: Option<Deployment>,
+    /// Host the server signs responses for, when it differs from the host in
+    /// `api_url`. Self-hosted servers sign for their `KEYGEN_HOST`, which a
+    /// reverse proxy in front of them may not match.
+    pub signature_host: Option<String>,
+    /// Hooks run around every API request, see [`crate::middleware`]
+    pub middleware: MiddlewareChain,
 
     // License Key Authentication configuration
     #[cfg(feature = "license-key")]
@@ -39,12 +153,19 @@ pub struct KeygenConfig {
     pub license_key: Option<String>,
     #[cfg(feature = "license-key")]
     pub public_key: Option<String>,
+    /// Additional trusted public keys, see [`crate::keyring`]
+    #[cfg(feature = "license-key")]
+    pub keyring: Keyring,
     #[cfg(feature = "license-key")]
     pub platform: Option<String>,
     #[cfg(feature = "license-key")]
     pub max_clock_drift: Option<i64>,
+    /// `Some(false)` is the same as [`SignatureMode::Insecure`], unless
+    /// `signature_mode` is [`SignatureMode::Strict`]
     #[cfg(feature = "license-key")]
     pub verify_keygen_signature: Option<bool>,
+    #[cfg(feature = "license-key")]
+    pub signature_mode: SignatureMode,
 
     // Token Authentication configuration
     #[cfg(feature = "token")]
@@ -61,6 +182,9 @@ impl Default for KeygenConfig {
             account: String::new(),
             environment: None,
             user_agent: None,
+            deployment: None,
+            signature_host: None,
+            middleware: MiddlewareChain::default(),
 
             // License Key Authentication defaults
             #[cfg(feature = "license-key")]
@@ -72,11 +196,15 @@ impl Default for KeygenConfig {
             #[cfg(feature = "license-key")]
             public_key: None,
             #[cfg(feature = "license-key")]
+            keyring: Keyring::default(),
+            #[cfg(feature = "license-key")]
             platform: None,
             #[cfg(feature = "license-key")]
             max_clock_drift: Some(5),
             #[cfg(feature = "license-key")]
             verify_keygen_signature: Some(true),
+            #[cfg(feature = "license-key")]
+            signature_mode: SignatureMode::default(),
 
             // Token Authentication defaults
             #[cfg(feature = "token")]
@@ -103,6 +231,12 @@ impl KeygenConfig {
         }
     }
 
+    /// The trusted public keys: `public_key`, when set, followed by the keyring
+    #[cfg(feature = "license-key")]
+    pub fn public_keys(&self) -> Keyring {
+        self.keyring.including(self.public_key.as_ref())
+    }
+
     /// Create a token authentication configuration
     #[cfg(feature = "token")]
     pub fn token(account: String, token: String) -> Self {
@@ -112,6 +246,589 @@ impl KeygenConfig {
             ..Default::default()
         }
     }
+
+    /// Load a validated configuration from the `KEYGEN_*` environment
+    /// variables listed on [`ConfigLayer::from_env`], over the defaults.
+    pub fn from_env() -> Result<Self, Error> {
+        ConfigLayer::from_env()?.into_config()
+    }
+
+    /// Load a validated configuration from a JSON file, or a TOML file with the
+    /// `toml` feature, over the defaults.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        ConfigLayer::from_file(path)?.into_config()
+    }
+
+    /// Load a validated configuration from the defaults, then the file at
+    /// `path` if given, then the environment, each overriding the last.
+    ///
+    /// To override the result explicitly, merge another layer on top:
+    /// ```no_run
+    /// use keygen_rs::config::ConfigLayer;
+    ///
+    /// let config = ConfigLayer::from_file("keygen.toml")?
+    ///     .merge(ConfigLayer::from_env()?)
+    ///     .merge(ConfigLayer {
+    ///         product: Some("PRODUCT_ID".to_string()),
+    ///         ..Default::default()
+    ///     })
+    ///     .into_config()?;
+    /// # Ok::<(), keygen_rs::errors::Error>(())
+    /// ```
+    pub fn load(path: Option<&Path>) -> Result<Self, Error> {
+        let file = match path {
+            Some(path) => ConfigLayer::from_file(path)?,
+            None => ConfigLayer::default(),
+        };
+        file.merge(ConfigLayer::from_env()?).into_config()
+    }
+
+    /// Check for missing or malformed fields, reporting all of them at once.
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut problems = Vec::new();
+
+        if self.account.is_empty() {
+            problems.push("account is missing".to_string());
+        } else if !is_account_id(&self.account) {
+            problems.push(format!(
+                "account {:?} must be an account ID (UUID) or a slug of lowercase letters, digits and hyphens",
+                self.account
+            ));
+        }
+        match url::Url::parse(&self.api_url) {
+            Ok(url) => self.validate_url_layout(&url, &mut problems),
+            Err(e) => problems.push(format!(
+                "api_url {:?} is not a valid URL: {e}",
+                self.api_url
+            )),
+        }
+        if !is_api_version(&self.api_version) {
+            problems.push(format!(
+                "api_version {:?} must look like \"1.7\"",
+                self.api_version
+            ));
+        }
+        if self.api_prefix.is_empty() || self.api_prefix.contains('/') {
+            problems.push(format!(
+                "api_prefix {:?} must be a single path segment such as \"v1\"",
+                self.api_prefix
+            ));
+        }
+        if self.environment.as_deref() == Some("") {
+            problems.push("environment is empty".to_string());
+        }
+
+        #[cfg(feature = "license-key")]
+        {
+            if self.license_key.as_deref() == Some("") {
+                problems.push("license_key is empty".to_string());
+            }
+            if let Some(public_key) = &self.public_key {
+                if !is_hex_public_key(public_key) {
+                    problems.push("public_key must be 64 hex characters".to_string());
+                }
+            }
+            for (i, key) in self.keyring.iter().enumerate() {
+                if !is_hex_public_key(&key.key) {
+                    problems.push(format!("keyring[{i}].key must be 64 hex characters"));
+                }
+                if let Some(account) = key.account.as_deref().filter(|a| !is_uuid(a)) {
+                    problems.push(format!(
+                        "keyring[{i}].account {account:?} must be an account ID (UUID), which Keygen signs with"
+                    ));
+                }
+            }
+            if self.max_clock_drift.is_some_and(|drift| drift < 0) {
+                problems.push("max_clock_drift must not be negative".to_string());
+            }
+        }
+
+        #[cfg(feature = "token")]
+        if self.token.as_deref() == Some("") {
+            problems.push("token is empty".to_string());
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidConfiguration(problems))
+        }
+    }
+}
+
+impl KeygenConfig {
+    /// Start building a configuration that is validated by
+    /// [`build`](KeygenConfigBuilder::build).
+    pub fn builder() -> KeygenConfigBuilder {
+        KeygenConfigBuilder::default()
+    }
+
+    /// The configured deployment, or the one inferred from `api_url`
+    pub fn effective_deployment(&self) -> Deployment {
+        self.deployment
+            .unwrap_or_else(|| Deployment::infer(&self.api_url))
+    }
+
+    /// The URL of an API `path` such as `engines/tauri/app`, laid out for the
+    /// deployment the way the client builds its own request URLs.
+    pub fn endpoint_url(&self, path: &str) -> Result<url::Url, Error> {
+        endpoint_url(
+            &self.api_url,
+            &self.api_prefix,
+            self.effective_deployment(),
+            &self.account,
+            path,
+        )
+    }
+
+    /// Check that `api_url` fits the deployment, so that request paths are
+    /// laid out the way the server expects.
+    fn validate_url_layout(&self, url: &url::Url, problems: &mut Vec<String>) {
+        let api_url = &self.api_url;
+        if !matches!(url.scheme(), "http" | "https") {
+            problems.push(format!("api_url {api_url:?} must be an http(s) URL"));
+            return;
+        }
+        if url.query().is_some() || url.fragment().is_some() {
+            problems.push(format!(
+                "api_url {api_url:?} must not have a query or fragment"
+            ));
+        }
+
+        let keygen_cloud = url
+            .host_str()
+            .is_some_and(|host| host == "keygen.sh" || host.ends_with(".keygen.sh"));
+        let deployment = self.effective_deployment();
+        match deployment {
+            Deployment::Cloud if !keygen_cloud => problems.push(format!(
+                "api_url {api_url:?} is not a Keygen Cloud URL; use a custom-domain or self-hosted deployment"
+            )),
+            Deployment::CustomDomain if keygen_cloud => problems.push(format!(
+                "api_url {api_url:?} is a Keygen Cloud URL; use a cloud deployment"
+            )),
+            _ if deployment.is_self_hosted() && keygen_cloud => problems.push(format!(
+                "api_url {api_url:?} is a Keygen Cloud URL; use a cloud deployment"
+            )),
+            Deployment::Cloud | Deployment::CustomDomain if url.scheme() != "https" => {
+                problems.push(format!("api_url {api_url:?} must use https for Keygen Cloud"))
+            }
+            _ => {}
+        }
+        if self.signature_host.as_deref() == Some("") {
+            problems.push("signature_host is empty".to_string());
+        }
+    }
+}
+
+pub(crate) fn is_uuid(value: &str) -> bool {
+    value.len() == 36
+        && value.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+/// Account IDs are UUIDs; slugs are lowercase letters, digits and hyphens.
+fn is_account_id(account: &str) -> bool {
+    is_uuid(account)
+        || account
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+fn is_api_version(version: &str) -> bool {
+    let mut parts = version.split('.');
+    let is_number = |part: Option<&str>| {
+        part.is_some_and(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+    };
+    is_number(parts.next()) && is_number(parts.next()) && parts.next().is_none()
+}
+
+/// Builder for a validated [`KeygenConfig`].
+///
+/// # Example
+/// ```no_run
+/// use keygen_rs::config::{self, KeygenConfig};
+///
+/// let config = KeygenConfig::builder()
+///     .self_hosted("https://keygen.example.com")
+///     .account("acme")
+///     .product("PRODUCT_ID")
+///     .license_key("LICENSE_KEY")
+///     .public_key("PUBLIC_KEY")
+///     .build()?;
+/// config::set_config(config)?;
+/// # Ok::<(), keygen_rs::errors::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct KeygenConfigBuilder {
+    config: KeygenConfig,
+}
+
+impl KeygenConfigBuilder {
+    pub fn account(mut self, account: impl Into<String>) -> Self {
+        self.config.account = account.into();
+        self
+    }
+
+    /// Use Keygen Cloud at `https://api.keygen.sh`
+    pub fn cloud(mut self) -> Self {
+        self.config.api_url = KeygenConfig::default().api_url;
+        self.config.deployment = Some(Deployment::Cloud);
+        self
+    }
+
+    /// Use a Keygen Cloud custom domain, e.g. `https://licensing.example.com`
+    pub fn custom_domain(mut self, api_url: impl Into<String>) -> Self {
+        self.config.api_url = api_url.into();
+        self.config.deployment = Some(Deployment::CustomDomain);
+        self
+    }
+
+    /// Use a self-hosted Keygen CE or EE instance
+    pub fn self_hosted(mut self, api_url: impl Into<String>) -> Self {
+        self.config.api_url = api_url.into();
+        self.config.deployment = Some(Deployment::SelfHosted);
+        self
+    }
+
+    /// Use a single-tenant self-hosted instance through its unscoped routes
+    pub fn self_hosted_single_tenant(mut self, api_url: impl Into<String>) -> Self {
+        self.config.api_url = api_url.into();
+        self.config.deployment = Some(Deployment::SelfHostedSingleTenant);
+        self
+    }
+
+    /// Verify response signatures against `host` instead of the `api_url`
+    /// host, e.g. the `KEYGEN_HOST` of a server behind a reverse proxy
+    pub fn signature_host(mut self, host: impl Into<String>) -> Self {
+        self.config.signature_host = Some(host.into());
+        self
+    }
+
+    pub fn api_version(mut self, api_version: impl Into<String>) -> Self {
+        self.config.api_version = api_version.into();
+        self
+    }
+
+    pub fn api_prefix(mut self, api_prefix: impl Into<String>) -> Self {
+        self.config.api_prefix = api_prefix.into();
+        self
+    }
+
+    pub fn environment(mut self, environment: impl Into<String>) -> Self {
+        self.config.environment = Some(environment.into());
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.config.user_agent = Some(user_agent.into());
+        self
+    }
+
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.config.middleware.push(middleware);
+        self
+    }
+
+    #[cfg(feature = "license-key")]
+    pub fn product(mut self, product: impl Into<String>) -> Self {
+        self.config.product = product.into();
+        self
+    }
+
+    #[cfg(feature = "license-key")]
+    pub fn package(mut self, package: impl Into<String>) -> Self {
+        self.config.package = package.into();
+        self
+    }
+
+    #[cfg(feature = "license-key")]
+    pub fn license_key(mut self, license_key: impl Into<String>) -> Self {
+        self.config.license_key = Some(license_key.into());
+        self
+    }
+
+    #[cfg(feature = "license-key")]
+    pub fn public_key(mut self, public_key: impl Into<String>) -> Self {
+        self.config.public_key = Some(public_key.into());
+        self
+    }
+
+    /// Trust an additional public key, see [`crate::keyring`]
+    #[cfg(feature = "license-key")]
+    pub fn add_public_key(mut self, public_key: PublicKey) -> Self {
+        self.config.keyring.push(public_key);
+        self
+    }
+
+    #[cfg(feature = "license-key")]
+    pub fn platform(mut self, platform: impl Into<String>) -> Self {
+        self.config.platform = Some(platform.into());
+        self
+    }
+
+    #[cfg(feature = "license-key")]
+    pub fn max_clock_drift(mut self, max_clock_drift: i64) -> Self {
+        self.config.max_clock_drift = Some(max_clock_drift);
+        self
+    }
+
+    #[cfg(feature = "license-key")]
+    pub fn signature_mode(mut self, signature_mode: SignatureMode) -> Self {
+        self.config.signature_mode = signature_mode;
+        self
+    }
+
+    #[cfg(feature = "token")]
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.config.token = Some(token.into());
+        self
+    }
+
+    /// Validate and return the configuration, reporting every problem found.
+    pub fn build(self) -> Result<KeygenConfig, Error> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+#[cfg(feature = "license-key")]
+fn is_hex_public_key(key: &str) -> bool {
+    key.len() == 64 && key.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Parse an environment variable, recording a problem if it is malformed.
+fn parse_var<T: FromStr>(
+    var: &impl Fn(&str) -> Option<String>,
+    name: &str,
+    expected: &str,
+    problems: &mut Vec<String>,
+) -> Option<T> {
+    let value = var(name)?;
+    match value.parse() {
+        Ok(parsed) => Some(parsed),
+        Err(_) => {
+            problems.push(format!("{name} must be {expected}, got {value:?}"));
+            None
+        }
+    }
+}
+
+/// One layer of configuration, such as a config file or the environment.
+/// Fields left unset keep the value from the layer below. Fields for a feature
+/// that isn't enabled are ignored.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigLayer {
+    pub api_url: Option<String>,
+    pub api_version: Option<String>,
+    pub api_prefix: Option<String>,
+    pub account: Option<String>,
+    pub environment: Option<String>,
+    pub user_agent: Option<String>,
+    pub deployment: Option<Deployment>,
+    pub signature_host: Option<String>,
+    pub product: Option<String>,
+    pub package: Option<String>,
+    pub license_key: Option<String>,
+    pub public_key: Option<String>,
+    pub keyring: Option<Keyring>,
+    pub platform: Option<String>,
+    pub max_clock_drift: Option<i64>,
+    pub verify_keygen_signature: Option<bool>,
+    pub signature_mode: Option<SignatureMode>,
+    pub token: Option<String>,
+}
+
+impl ConfigLayer {
+    /// Read the layer from the environment. Empty variables count as unset.
+    ///
+    /// | Variable | Field |
+    /// |---|---|
+    /// | `KEYGEN_API_URL` | `api_url` |
+    /// | `KEYGEN_API_VERSION` | `api_version` |
+    /// | `KEYGEN_API_PREFIX` | `api_prefix` |
+    /// | `KEYGEN_ACCOUNT` | `account` |
+    /// | `KEYGEN_ENVIRONMENT` | `environment` |
+    /// | `KEYGEN_USER_AGENT` | `user_agent` |
+    /// | `KEYGEN_DEPLOYMENT` | `deployment`, `cloud`, `custom-domain`, `self-hosted` or `self-hosted-single-tenant` |
+    /// | `KEYGEN_SIGNATURE_HOST` | `signature_host` |
+    /// | `KEYGEN_PRODUCT` | `product` |
+    /// | `KEYGEN_PACKAGE` | `package` |
+    /// | `KEYGEN_LICENSE_KEY` | `license_key` |
+    /// | `KEYGEN_PUBLIC_KEY` | `public_key` |
+    /// | `KEYGEN_PLATFORM` | `platform` |
+    /// | `KEYGEN_MAX_CLOCK_DRIFT` | `max_clock_drift`, in minutes |
+    /// | `KEYGEN_VERIFY_SIGNATURE` | `verify_keygen_signature`, `true` or `false` |
+    /// | `KEYGEN_SIGNATURE_MODE` | `signature_mode`, `strict`, `lenient` or `insecure` |
+    /// | `KEYGEN_TOKEN` | `token` |
+    pub fn from_env() -> Result<Self, Error> {
+        Self::from_vars(|name| env::var(name).ok().filter(|value| !value.is_empty()))
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, Error> {
+        let mut problems = Vec::new();
+        let max_clock_drift =
+            parse_var(&var, "KEYGEN_MAX_CLOCK_DRIFT", "an integer", &mut problems);
+        let verify_keygen_signature = parse_var(
+            &var,
+            "KEYGEN_VERIFY_SIGNATURE",
+            "true or false",
+            &mut problems,
+        );
+        let signature_mode = parse_var(
+            &var,
+            "KEYGEN_SIGNATURE_MODE",
+            "strict, lenient or insecure",
+            &mut problems,
+        );
+        let deployment = parse_var(
+            &var,
+            "KEYGEN_DEPLOYMENT",
+            "cloud, custom-domain, self-hosted or self-hosted-single-tenant",
+            &mut problems,
+        );
+        if !problems.is_empty() {
+            return Err(Error::InvalidConfiguration(problems));
+        }
+
+        Ok(ConfigLayer {
+            api_url: var("KEYGEN_API_URL"),
+            api_version: var("KEYGEN_API_VERSION"),
+            api_prefix: var("KEYGEN_API_PREFIX"),
+            account: var("KEYGEN_ACCOUNT"),
+            environment: var("KEYGEN_ENVIRONMENT"),
+            user_agent: var("KEYGEN_USER_AGENT"),
+            deployment,
+            signature_host: var("KEYGEN_SIGNATURE_HOST"),
+            product: var("KEYGEN_PRODUCT"),
+            package: var("KEYGEN_PACKAGE"),
+            license_key: var("KEYGEN_LICENSE_KEY"),
+            public_key: var("KEYGEN_PUBLIC_KEY"),
+            keyring: None,
+            platform: var("KEYGEN_PLATFORM"),
+            max_clock_drift,
+            verify_keygen_signature,
+            signature_mode,
+            token: var("KEYGEN_TOKEN"),
+        })
+    }
+
+    /// Read the layer from a file with the same keys as [`ConfigLayer`]. Files
+    /// ending in `.json` are parsed as JSON, anything else as TOML, which
+    /// needs the `toml` feature.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let invalid = |e: &dyn std::fmt::Display| {
+            Error::InvalidConfiguration(vec![format!("{}: {e}", path.display())])
+        };
+        let contents = fs::read_to_string(path).map_err(|e| invalid(&e))?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            return serde_json::from_str(&contents).map_err(|e| invalid(&e));
+        }
+        #[cfg(feature = "toml")]
+        {
+            toml::from_str(&contents).map_err(|e| invalid(&e))
+        }
+        #[cfg(not(feature = "toml"))]
+        {
+            Err(invalid(
+                &"TOML config files need the `toml` feature; use a .json file instead",
+            ))
+        }
+    }
+
+    /// Stack `other` on top of this layer, its set fields taking precedence.
+    pub fn merge(self, other: ConfigLayer) -> Self {
+        ConfigLayer {
+            api_url: other.api_url.or(self.api_url),
+            api_version: other.api_version.or(self.api_version),
+            api_prefix: other.api_prefix.or(self.api_prefix),
+            account: other.account.or(self.account),
+            environment: other.environment.or(self.environment),
+            user_agent: other.user_agent.or(self.user_agent),
+            deployment: other.deployment.or(self.deployment),
+            signature_host: other.signature_host.or(self.signature_host),
+            product: other.product.or(self.product),
+            package: other.package.or(self.package),
+            license_key: other.license_key.or(self.license_key),
+            public_key: other.public_key.or(self.public_key),
+            keyring: other.keyring.or(self.keyring),
+            platform: other.platform.or(self.platform),
+            max_clock_drift: other.max_clock_drift.or(self.max_clock_drift),
+            verify_keygen_signature: other
+                .verify_keygen_signature
+                .or(self.verify_keygen_signature),
+            signature_mode: other.signature_mode.or(self.signature_mode),
+            token: other.token.or(self.token),
+        }
+    }
+
+    /// Apply the set fields to `config`.
+    pub fn apply(self, mut config: KeygenConfig) -> KeygenConfig {
+        macro_rules! set {
+            ($($field:ident),*) => {
+                $(if let Some(value) = self.$field {
+                    config.$field = value;
+                })*
+            };
+        }
+        macro_rules! set_some {
+            ($($field:ident),*) => {
+                $(if let Some(value) = self.$field {
+                    config.$field = Some(value);
+                })*
+            };
+        }
+
+        set!(api_url, api_version, api_prefix, account);
+        set_some!(environment, user_agent, deployment, signature_host);
+        #[cfg(feature = "license-key")]
+        {
+            set!(product, package, keyring, signature_mode);
+            set_some!(
+                license_key,
+                public_key,
+                platform,
+                max_clock_drift,
+                verify_keygen_signature
+            );
+        }
+        #[cfg(feature = "token")]
+        set_some!(token);
+        config
+    }
+
+    /// Apply this layer over the defaults and validate the result.
+    pub fn into_config(self) -> Result<KeygenConfig, Error> {
+        let config = self.apply(KeygenConfig::default());
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// Join `api_url`, the API prefix, the account scope when the deployment has
+/// one, and `path`.
+pub(crate) fn endpoint_url(
+    api_url: &str,
+    api_prefix: &str,
+    deployment: Deployment,
+    account: &str,
+    path: &str,
+) -> Result<url::Url, Error> {
+    let mut url = url::Url::parse(api_url)?;
+    {
+        let mut segments = url.path_segments_mut().map_err(|_| Error::InvalidUrl)?;
+        // Keep any path prefix of the API URL, minus a trailing slash
+        segments.pop_if_empty();
+        segments.push(api_prefix);
+        if deployment.is_account_scoped() {
+            segments.push("accounts").push(account);
+        }
+        segments.extend(path.split('/'));
+    }
+    Ok(url)
 }
 
 lazy_static! {
@@ -192,6 +909,18 @@ pub fn set_public_key(public_key: &str) -> Result<(), Error> {
     update_config(|cfg| cfg.public_key = Some(public_key.to_string()))
 }
 
+/// Replace the additional trusted public keys.
+#[cfg(feature = "license-key")]
+pub fn set_keyring(keyring: Keyring) -> Result<(), Error> {
+    update_config(|cfg| cfg.keyring = keyring)
+}
+
+/// Trust another public key, e.g. the next one during a key rotation.
+#[cfg(feature = "license-key")]
+pub fn add_public_key(public_key: PublicKey) -> Result<(), Error> {
+    update_config(|cfg| cfg.keyring.push(public_key))
+}
+
 #[cfg(feature = "license-key")]
 pub fn set_platform(platform: &str) -> Result<(), Error> {
     update_config(|cfg| cfg.platform = Some(platform.to_string()))
@@ -206,6 +935,199 @@ pub fn set_max_clock_drift(max_clock_drift: i64) -> Result<(), Error> {
     update_config(|cfg| cfg.max_clock_drift = Some(max_clock_drift))
 }
 
+#[cfg(feature = "license-key")]
+pub fn set_signature_mode(signature_mode: SignatureMode) -> Result<(), Error> {
+    update_config(|cfg| cfg.signature_mode = signature_mode)
+}
+
+/// Append a middleware to the global configuration's chain.
+pub fn add_middleware(middleware: impl Middleware + 'static) -> Result<(), Error> {
+    update_config(|cfg| cfg.middleware.push(middleware))
+}
+
 pub fn reset_config() -> Result<(), Error> {
     update_config(|cfg| *cfg = KeygenConfig::default())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBLIC_KEY: &str = "e8601e48b69383ba520245fd07971e983d06d22c4257cfd82304601479cee788";
+
+    fn vars(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let pairs: Vec<(String, String)> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| {
+            pairs
+                .iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.clone())
+        }
+    }
+
+    #[test]
+    fn test_layers_override_in_order() {
+        let file: ConfigLayer = serde_json::from_value(serde_json::json!({
+            "account": "file-account",
+            "product": "file-product",
+            "environment": "staging",
+            "public_key": PUBLIC_KEY,
+            "signature_mode": "strict",
+        }))
+        .unwrap();
+        let env = ConfigLayer::from_vars(vars(&[
+            ("KEYGEN_ACCOUNT", "env-account"),
+            ("KEYGEN_PRODUCT", "env-product"),
+            ("KEYGEN_MAX_CLOCK_DRIFT", "10"),
+        ]))
+        .unwrap();
+        let explicit = ConfigLayer {
+            product: Some("explicit-product".to_string()),
+            ..Default::default()
+        };
+
+        let config = file.merge(env).merge(explicit).into_config().unwrap();
+
+        assert_eq!(config.account, "env-account");
+        assert_eq!(config.product, "explicit-product");
+        assert_eq!(config.environment.as_deref(), Some("staging"));
+        assert_eq!(config.public_key.as_deref(), Some(PUBLIC_KEY));
+        assert_eq!(config.max_clock_drift, Some(10));
+        assert_eq!(config.signature_mode, SignatureMode::Strict);
+        assert_eq!(config.api_url, KeygenConfig::default().api_url);
+    }
+
+    #[test]
+    fn test_from_vars_reports_malformed_values() {
+        let result = ConfigLayer::from_vars(vars(&[
+            ("KEYGEN_MAX_CLOCK_DRIFT", "soon"),
+            ("KEYGEN_SIGNATURE_MODE", "paranoid"),
+        ]));
+
+        let Err(Error::InvalidConfiguration(problems)) = result else {
+            panic!("expected invalid configuration, got {result:?}");
+        };
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].starts_with("KEYGEN_MAX_CLOCK_DRIFT"));
+        assert!(problems[1].starts_with("KEYGEN_SIGNATURE_MODE"));
+    }
+
+    #[test]
+    fn test_validate_reports_all_problems() {
+        let config = KeygenConfig {
+            api_url: "api.keygen.sh".to_string(),
+            public_key: Some("not-a-key".to_string()),
+            ..Default::default()
+        };
+
+        let Err(Error::InvalidConfiguration(problems)) = config.validate() else {
+            panic!("expected invalid configuration");
+        };
+        assert_eq!(problems.len(), 3);
+        assert!(problems.contains(&"account is missing".to_string()));
+        assert!(problems.contains(&"public_key must be 64 hex characters".to_string()));
+    }
+
+    #[test]
+    fn test_from_file_reads_json() {
+        let path = std::env::temp_dir().join(format!("keygen-config-{}.json", std::process::id()));
+        fs::write(
+            &path,
+            format!(r#"{{"account": "acme", "keyring": [{{"key": "{PUBLIC_KEY}", "account": "7f2c6e2a-1d2b-4a51-9c43-2f0e4b6f1a9d"}}]}}"#),
+        )
+        .unwrap();
+
+        let config = KeygenConfig::from_file(&path);
+        fs::remove_file(&path).unwrap();
+
+        let config = config.unwrap();
+        assert_eq!(config.account, "acme");
+        assert_eq!(config.keyring.len(), 1);
+    }
+
+    #[test]
+    fn test_builder_reports_descriptive_errors() {
+        let result = KeygenConfig::builder()
+            .account("Acme Inc")
+            .api_version("latest")
+            .public_key("abc")
+            .build();
+
+        let Err(Error::InvalidConfiguration(problems)) = result else {
+            panic!("expected invalid configuration, got {result:?}");
+        };
+        assert_eq!(problems.len(), 3, "{problems:?}");
+        assert!(problems[0].starts_with("account \"Acme Inc\""));
+        assert!(problems[1].starts_with("api_version \"latest\""));
+        assert_eq!(problems[2], "public_key must be 64 hex characters");
+    }
+
+    #[test]
+    fn test_keyring_account_must_be_an_id() {
+        let result = KeygenConfig::builder()
+            .account("acme")
+            .add_public_key(PublicKey::ed25519(PUBLIC_KEY).account("acme"))
+            .build();
+        let Err(Error::InvalidConfiguration(problems)) = result else {
+            panic!("expected InvalidConfiguration");
+        };
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("keyring[0].account \"acme\""));
+
+        assert!(KeygenConfig::builder()
+            .account("acme")
+            .add_public_key(
+                PublicKey::ed25519(PUBLIC_KEY).account("7f2c6e2a-1d2b-4a51-9c43-2f0e4b6f1a9d")
+            )
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_builder_checks_url_layout() {
+        let config = KeygenConfig::builder()
+            .self_hosted("http://keygen.internal:3000")
+            .account("7f2c6e2a-1d2b-4a51-9c43-2f0e4b6f1a9d")
+            .public_key(PUBLIC_KEY)
+            .build()
+            .unwrap();
+        assert_eq!(config.deployment, Some(Deployment::SelfHosted));
+
+        assert!(KeygenConfig::builder()
+            .custom_domain("https://api.keygen.sh")
+            .account("acme")
+            .build()
+            .is_err());
+        // Without a deployment, any Keygen Cloud host is inferred as cloud
+        assert!(KeygenConfig::builder().account("acme").build().is_ok());
+        let config = KeygenConfig {
+            api_url: "https://api.keygen.sh/".to_string(),
+            account: "acme".to_string(),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(config.effective_deployment(), Deployment::Cloud);
+        assert_eq!(
+            config.endpoint_url("engines/tauri/app").unwrap().as_str(),
+            "https://api.keygen.sh/v1/accounts/acme/engines/tauri/app"
+        );
+        assert_eq!(
+            Deployment::infer("https://licensing.example.com"),
+            Deployment::CustomDomain
+        );
+        assert!(KeygenConfig::builder()
+            .account("acme")
+            .self_hosted_single_tenant("https://api.keygen.sh")
+            .build()
+            .is_err());
+        assert!(KeygenConfig::builder()
+            .account("acme")
+            .self_hosted_single_tenant("http://localhost:3000")
+            .signature_host("keygen.internal")
+            .build()
+            .is_ok());
+    }
+}
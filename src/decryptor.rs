@@ -13,6 +13,7 @@ use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use crate::certificate::Certificate;
 use crate::errors::Error;
+use crate::trace::trace_event;
 
 #[derive(Zeroize, ZeroizeOnDrop)]
 pub struct Decryptor {
@@ -55,7 +56,10 @@ impl Decryptor {
 
         let plaintext = cipher
             .decrypt(nonce, encrypted_data.as_ref())
-            .map_err(|_| Error::DecryptionError("Decryption failed".into()))?;
+            .map_err(|_| {
+                trace_event!(warn, "certificate decryption failed");
+                Error::DecryptionError("Decryption failed".into())
+            })?;
 
         Ok(plaintext)
     }
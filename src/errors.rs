@@ -205,6 +205,12 @@ pub enum Error {
     #[error("Not found")]
     NotFound { code: String, detail: String },
 
+    #[error("Password reset token invalid")]
+    PasswordResetTokenInvalid { code: String, detail: String },
+
+    #[error("Password reset token expired")]
+    PasswordResetTokenExpired { code: String, detail: String },
+
     #[error("Keygen signature validation failed: {reason}")]
     KeygenSignatureInvalid { reason: String },
 
@@ -213,6 +219,16 @@ pub enum Error {
 
     #[error("Configuration missing")]
     MissingConfiguration,
+
+    #[error("Invalid configuration: {}", .0.join("; "))]
+    InvalidConfiguration(Vec<String>),
+
+    #[error("{feature} require API version {required} or newer, but {api_version} is configured")]
+    FeatureNotSupported {
+        feature: String,
+        required: String,
+        api_version: String,
+    },
 }
 
 pub trait ErrorMeta {
@@ -250,7 +266,9 @@ impl ErrorMeta for Error {
             | Error::ValidationFingerprintMissing { code, .. }
             | Error::ValidationComponentsMissing { code, .. }
             | Error::ValidationProductMissing { code, .. }
-            | Error::NotFound { code, .. } => code.to_string(),
+            | Error::NotFound { code, .. }
+            | Error::PasswordResetTokenInvalid { code, .. }
+            | Error::PasswordResetTokenExpired { code, .. } => code.to_string(),
             _ => "ERROR".to_string(),
         }
     }
@@ -284,8 +302,200 @@ impl ErrorMeta for Error {
             | Error::ValidationFingerprintMissing { detail, .. }
             | Error::ValidationComponentsMissing { detail, .. }
             | Error::ValidationProductMissing { detail, .. }
-            | Error::NotFound { detail, .. } => detail.to_string(),
+            | Error::NotFound { detail, .. }
+            | Error::PasswordResetTokenInvalid { detail, .. }
+            | Error::PasswordResetTokenExpired { detail, .. } => detail.to_string(),
             _ => self.to_string(),
         }
     }
 }
+
+/// Every name [`Error::kind`] can return, for bindings that declare them as
+/// a union type.
+pub const ERROR_KINDS: &[&str] = &[
+    "UnexpectedError",
+    "InvalidUrl",
+    "SystemClockUnsynced",
+    "DecryptionError",
+    "HttpClient",
+    "UrlParse",
+    "JsonError",
+    "InvalidHeader",
+    "UrlEncode",
+    "RateLimitExceeded",
+    "LicenseKeyMissing",
+    "LicenseSchemeMissing",
+    "LicenseSchemeNotSupported",
+    "LicenseNotSigned",
+    "LicenseKeyNotGenuine",
+    "PublicKeyMissing",
+    "PublicKeyInvalid",
+    "LicenseSchemeUnsupported",
+    "CertificateFileInvalid",
+    "CertificateFileNotGenuine",
+    "CertificateFileNotSupported",
+    "CertificateFileExpired",
+    "LicenseFileInvalid",
+    "LicenseFileNotGenuine",
+    "LicenseFileNotSupported",
+    "LicenseFileNotEncrypted",
+    "LicenseFileExpired",
+    "MachineFileInvalid",
+    "MachineFileNotGenuine",
+    "MachineFileNotSupported",
+    "MachineFileExpired",
+    "KeygenApiError",
+    "TokenNotAllowed",
+    "TokenFormatInvalid",
+    "TokenInvalid",
+    "TokenExpired",
+    "LicenseSuspended",
+    "LicenseExpired",
+    "LicenseNotAllowed",
+    "LicenseNotActivated",
+    "LicenseKeyInvalid",
+    "LicenseTokenInvalid",
+    "LicenseTooManyMachines",
+    "LicenseTooManyCores",
+    "LicenseTooManyProcesses",
+    "MachineAlreadyActivated",
+    "MachineLimitExceeded",
+    "MachineNotFound",
+    "ProcessLimitExceeded",
+    "ProcessNotFound",
+    "ComponentConflict",
+    "ComponentAlreadyActivated",
+    "ComponentNotActivated",
+    "EnvironmentError",
+    "HeartbeatDead",
+    "HeartbeatPingFailed",
+    "HeartbeatRequired",
+    "ValidationFingerprintMissing",
+    "ValidationComponentsMissing",
+    "ValidationProductMissing",
+    "NotFound",
+    "PasswordResetTokenInvalid",
+    "PasswordResetTokenExpired",
+    "KeygenSignatureInvalid",
+    "KeygenSignatureMissing",
+    "MissingConfiguration",
+    "InvalidConfiguration",
+    "FeatureNotSupported",
+];
+
+impl Error {
+    /// The error's variant name, such as `LicenseNotActivated`, for bindings
+    /// that expose a stable discriminator to other languages.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::UnexpectedError(_) => "UnexpectedError",
+            Error::InvalidUrl => "InvalidUrl",
+            Error::SystemClockUnsynced => "SystemClockUnsynced",
+            Error::DecryptionError(_) => "DecryptionError",
+            Error::HttpClient(_) => "HttpClient",
+            Error::UrlParse(_) => "UrlParse",
+            Error::JsonError(_) => "JsonError",
+            Error::InvalidHeader(_) => "InvalidHeader",
+            Error::UrlEncode(_) => "UrlEncode",
+            Error::RateLimitExceeded { .. } => "RateLimitExceeded",
+            Error::LicenseKeyMissing => "LicenseKeyMissing",
+            Error::LicenseSchemeMissing => "LicenseSchemeMissing",
+            Error::LicenseSchemeNotSupported => "LicenseSchemeNotSupported",
+            Error::LicenseNotSigned => "LicenseNotSigned",
+            Error::LicenseKeyNotGenuine => "LicenseKeyNotGenuine",
+            Error::PublicKeyMissing => "PublicKeyMissing",
+            Error::PublicKeyInvalid => "PublicKeyInvalid",
+            Error::LicenseSchemeUnsupported => "LicenseSchemeUnsupported",
+            Error::CertificateFileInvalid(_) => "CertificateFileInvalid",
+            Error::CertificateFileNotGenuine(_) => "CertificateFileNotGenuine",
+            Error::CertificateFileNotSupported(_) => "CertificateFileNotSupported",
+            Error::CertificateFileExpired => "CertificateFileExpired",
+            Error::LicenseFileInvalid(_) => "LicenseFileInvalid",
+            Error::LicenseFileNotGenuine(_) => "LicenseFileNotGenuine",
+            Error::LicenseFileNotSupported(_) => "LicenseFileNotSupported",
+            Error::LicenseFileNotEncrypted => "LicenseFileNotEncrypted",
+            Error::LicenseFileExpired(_) => "LicenseFileExpired",
+            Error::MachineFileInvalid(_) => "MachineFileInvalid",
+            Error::MachineFileNotGenuine(_) => "MachineFileNotGenuine",
+            Error::MachineFileNotSupported(_) => "MachineFileNotSupported",
+            Error::MachineFileExpired(_) => "MachineFileExpired",
+            Error::KeygenApiError { .. } => "KeygenApiError",
+            Error::TokenNotAllowed { .. } => "TokenNotAllowed",
+            Error::TokenFormatInvalid { .. } => "TokenFormatInvalid",
+            Error::TokenInvalid { .. } => "TokenInvalid",
+            Error::TokenExpired { .. } => "TokenExpired",
+            Error::LicenseSuspended { .. } => "LicenseSuspended",
+            Error::LicenseExpired { .. } => "LicenseExpired",
+            Error::LicenseNotAllowed { .. } => "LicenseNotAllowed",
+            Error::LicenseNotActivated { .. } => "LicenseNotActivated",
+            Error::LicenseKeyInvalid { .. } => "LicenseKeyInvalid",
+            Error::LicenseTokenInvalid { .. } => "LicenseTokenInvalid",
+            Error::LicenseTooManyMachines { .. } => "LicenseTooManyMachines",
+            Error::LicenseTooManyCores { .. } => "LicenseTooManyCores",
+            Error::LicenseTooManyProcesses { .. } => "LicenseTooManyProcesses",
+            Error::MachineAlreadyActivated { .. } => "MachineAlreadyActivated",
+            Error::MachineLimitExceeded { .. } => "MachineLimitExceeded",
+            Error::MachineNotFound => "MachineNotFound",
+            Error::ProcessLimitExceeded { .. } => "ProcessLimitExceeded",
+            Error::ProcessNotFound => "ProcessNotFound",
+            Error::ComponentConflict { .. } => "ComponentConflict",
+            Error::ComponentAlreadyActivated { .. } => "ComponentAlreadyActivated",
+            Error::ComponentNotActivated { .. } => "ComponentNotActivated",
+            Error::EnvironmentError { .. } => "EnvironmentError",
+            Error::HeartbeatDead { .. } => "HeartbeatDead",
+            Error::HeartbeatPingFailed { .. } => "HeartbeatPingFailed",
+            Error::HeartbeatRequired { .. } => "HeartbeatRequired",
+            Error::ValidationFingerprintMissing { .. } => "ValidationFingerprintMissing",
+            Error::ValidationComponentsMissing { .. } => "ValidationComponentsMissing",
+            Error::ValidationProductMissing { .. } => "ValidationProductMissing",
+            Error::NotFound { .. } => "NotFound",
+            Error::PasswordResetTokenInvalid { .. } => "PasswordResetTokenInvalid",
+            Error::PasswordResetTokenExpired { .. } => "PasswordResetTokenExpired",
+            Error::KeygenSignatureInvalid { .. } => "KeygenSignatureInvalid",
+            Error::KeygenSignatureMissing => "KeygenSignatureMissing",
+            Error::MissingConfiguration => "MissingConfiguration",
+            Error::InvalidConfiguration(_) => "InvalidConfiguration",
+            Error::FeatureNotSupported { .. } => "FeatureNotSupported",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_kind_names_the_variant() {
+        let err = Error::RateLimitExceeded {
+            window: "30s".into(),
+            count: 61,
+            limit: 60,
+            remaining: 0,
+            reset: 1_700_000_000,
+            retry_after: 30,
+        };
+        assert_eq!(err.kind(), "RateLimitExceeded");
+        assert_eq!(Error::MissingConfiguration.kind(), "MissingConfiguration");
+        assert_eq!(
+            Error::InvalidConfiguration(vec!["account is empty".into()]).kind(),
+            "InvalidConfiguration"
+        );
+    }
+
+    #[test]
+    fn test_error_kinds_match_kind() {
+        // `kind` can't be enumerated, so read its arms from this file's source
+        let source = include_str!("errors.rs");
+        let start = source.find("pub fn kind(").unwrap();
+        let end = start + source[start..].find("\n    }\n").unwrap();
+        let kinds: BTreeSet<&str> = source[start..end]
+            .split("=> \"")
+            .skip(1)
+            .map(|rest| &rest[..rest.find('"').unwrap()])
+            .collect();
+
+        assert_eq!(kinds.len(), ERROR_KINDS.len());
+        assert_eq!(kinds, ERROR_KINDS.iter().copied().collect());
+    }
+}
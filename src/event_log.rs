@@ -0,0 +1,214 @@
+use chrono::NaiveDate;
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::client::Client;
+use crate::errors::Error;
+use crate::pagination::{page_size, paginate};
+use crate::webhook::event_types::WebhookEvent;
+use crate::KeygenResponseData;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventLogAttributes {
+    pub event: String,
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub created: String,
+    pub updated: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventLog {
+    pub id: String,
+    /// Event name, e.g. `license.validation.succeeded`
+    pub event: String,
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub created: String,
+    pub updated: String,
+    pub account_id: Option<String>,
+    pub environment_id: Option<String>,
+    /// Type of the bearer that caused the event, e.g. `users` or `licenses`
+    pub whodunnit_type: Option<String>,
+    pub whodunnit_id: Option<String>,
+    /// Type of the resource the event occurred on
+    pub resource_type: Option<String>,
+    pub resource_id: Option<String>,
+    /// ID of the request log for the request that caused the event
+    pub request_log_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct EventLogResponse {
+    pub data: KeygenResponseData<EventLogAttributes>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct EventLogsResponse {
+    pub data: Vec<KeygenResponseData<EventLogAttributes>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListEventLogsOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(rename = "page[size]", skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+    #[serde(rename = "page[number]", skip_serializing_if = "Option::is_none")]
+    pub page_number: Option<u32>,
+    /// Only include events on or after this date
+    #[serde(rename = "date[start]", skip_serializing_if = "Option::is_none")]
+    pub date_start: Option<NaiveDate>,
+    /// Only include events on or before this date
+    #[serde(rename = "date[end]", skip_serializing_if = "Option::is_none")]
+    pub date_end: Option<NaiveDate>,
+    #[serde(rename = "whodunnit[type]", skip_serializing_if = "Option::is_none")]
+    pub whodunnit_type: Option<String>,
+    #[serde(rename = "whodunnit[id]", skip_serializing_if = "Option::is_none")]
+    pub whodunnit_id: Option<String>,
+    #[serde(rename = "resource[type]", skip_serializing_if = "Option::is_none")]
+    pub resource_type: Option<String>,
+    #[serde(rename = "resource[id]", skip_serializing_if = "Option::is_none")]
+    pub resource_id: Option<String>,
+    /// Filter by request log ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<WebhookEvent>,
+}
+
+impl EventLog {
+    pub(crate) fn from(data: KeygenResponseData<EventLogAttributes>) -> EventLog {
+        let whodunnit = data.relationships.other_relationship("whodunnit");
+        let resource = data.relationships.other_relationship("resource");
+        EventLog {
+            id: data.id,
+            event: data.attributes.event,
+            metadata: data.attributes.metadata,
+            created: data.attributes.created,
+            updated: data.attributes.updated,
+            account_id: data.relationships.account_id(),
+            environment_id: data.relationships.environment_id(),
+            whodunnit_type: whodunnit.as_ref().map(|w| w.r#type.clone()),
+            whodunnit_id: whodunnit.map(|w| w.id),
+            resource_type: resource.as_ref().map(|r| r.r#type.clone()),
+            resource_id: resource.map(|r| r.id),
+            request_log_id: data
+                .relationships
+                .other_relationship("request")
+                .map(|r| r.id),
+        }
+    }
+
+    /// The event name as a typed event, if it is one this crate knows about
+    pub fn event_type(&self) -> Option<WebhookEvent> {
+        self.event.parse().ok()
+    }
+
+    /// List event logs with optional filtering and pagination
+    pub async fn list(options: Option<ListEventLogsOptions>) -> Result<Vec<EventLog>, Error> {
+        let client = Client::from_global_config()?;
+        let response = client.get("event-logs", options.as_ref()).await?;
+        let event_logs_response: EventLogsResponse = serde_json::from_value(response.body)?;
+        Ok(event_logs_response
+            .data
+            .into_iter()
+            .map(EventLog::from)
+            .collect())
+    }
+
+    /// Stream every event log matching the filters, one page at a time.
+    ///
+    /// `page_size` defaults to the maximum of 100 and is clamped to 1..=100;
+    /// `limit` and `page_number` are ignored.
+    pub fn list_all(options: ListEventLogsOptions) -> impl Stream<Item = Result<EventLog, Error>> {
+        let page_size = page_size(options.page_size);
+        paginate(page_size, move |page_number| {
+            let options = ListEventLogsOptions {
+                limit: None,
+                page_size: Some(page_size),
+                page_number: Some(page_number),
+                ..options.clone()
+            };
+            EventLog::list(Some(options))
+        })
+    }
+
+    /// Get an event log by ID
+    pub async fn get(id: &str) -> Result<EventLog, Error> {
+        let client = Client::from_global_config()?;
+        let endpoint = format!("event-logs/{id}");
+        let response = client.get(&endpoint, None::<&()>).await?;
+        let event_log_response: EventLogResponse = serde_json::from_value(response.body)?;
+        Ok(EventLog::from(event_log_response.data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{reset_config, set_config, KeygenConfig};
+    use mockito::{mock, server_url, Matcher};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_list_event_logs_with_filters() {
+        let _m = mock("GET", "/v1/event-logs")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("whodunnit[type]".into(), "users".into()),
+                Matcher::UrlEncoded("whodunnit[id]".into(), "user-1".into()),
+                Matcher::UrlEncoded("event".into(), "machine.created".into()),
+                Matcher::UrlEncoded("date[start]".into(), "2024-01-01".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(
+                json!({
+                    "data": [{
+                        "id": "event-log-1",
+                        "type": "event-logs",
+                        "attributes": {
+                            "event": "machine.created",
+                            "metadata": null,
+                            "created": "2024-01-02T00:00:00Z",
+                            "updated": "2024-01-02T00:00:00Z"
+                        },
+                        "relationships": {
+                            "account": {"data": {"type": "accounts", "id": "account-1"}},
+                            "whodunnit": {"data": {"type": "users", "id": "user-1"}},
+                            "resource": {"data": {"type": "machines", "id": "machine-1"}},
+                            "request": {"data": {"type": "request-logs", "id": "request-log-1"}}
+                        }
+                    }]
+                })
+                .to_string(),
+            )
+            .create();
+
+        let _ = set_config(KeygenConfig {
+            api_url: server_url(),
+            account: "test_account".to_string(),
+            token: Some("admin-token".to_string()),
+            ..Default::default()
+        });
+
+        let logs = EventLog::list(Some(ListEventLogsOptions {
+            whodunnit_type: Some("users".to_string()),
+            whodunnit_id: Some("user-1".to_string()),
+            event: Some(WebhookEvent::MachineCreated),
+            date_start: Some(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+
+        assert_eq!(logs.len(), 1);
+        let log = &logs[0];
+        assert_eq!(log.event_type(), Some(WebhookEvent::MachineCreated));
+        assert_eq!(log.whodunnit_id.as_deref(), Some("user-1"));
+        assert_eq!(log.resource_type.as_deref(), Some("machines"));
+        assert_eq!(log.resource_id.as_deref(), Some("machine-1"));
+        assert_eq!(log.request_log_id.as_deref(), Some("request-log-1"));
+
+        let _ = reset_config();
+    }
+}
@@ -0,0 +1,234 @@
+//! Trusted public keys for signature verification.
+//!
+//! A [`Keyring`] holds every Ed25519 public key the SDK should trust, so that
+//! an application keeps working while an account rotates its signing key, or
+//! can verify licenses issued by several accounts. Keys may be labeled with the
+//! ID of the account they belong to, which is the `keyid` Keygen puts in
+//! `Keygen-Signature` headers; response signatures are then only checked
+//! against the keys whose label matches. License keys, license files and machine files carry no
+//! `keyid` and are accepted when any key in the ring verifies them.
+//!
+//! # Example
+//! ```no_run
+//! use keygen_rs::config::set_keyring;
+//! use keygen_rs::keyring::{Keyring, PublicKey};
+//!
+//! let account_id = "7f2c6e2a-1d2b-4a51-9c43-2f0e4b6f1a9d";
+//! let keyring = Keyring::new()
+//!     .with(PublicKey::ed25519("current-public-key").account(account_id))
+//!     .with(PublicKey::ed25519("previous-public-key").account(account_id));
+//!
+//! set_keyring(keyring).expect("Failed to set keyring");
+//! ```
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use crate::errors::Error;
+
+/// Signature algorithm of a [`PublicKey`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyAlgorithm {
+    #[default]
+    Ed25519,
+}
+
+impl KeyAlgorithm {
+    /// Name used in the `algorithm` parameter of `Keygen-Signature`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyAlgorithm::Ed25519 => "ed25519",
+        }
+    }
+}
+
+/// A hex-encoded public key, optionally labeled with its account and keyid.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Zeroize)]
+pub struct PublicKey {
+    pub key: String,
+    #[serde(default)]
+    #[zeroize(skip)]
+    pub algorithm: KeyAlgorithm,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyid: Option<String>,
+}
+
+impl PublicKey {
+    pub fn ed25519(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            algorithm: KeyAlgorithm::Ed25519,
+            account: None,
+            keyid: None,
+        }
+    }
+
+    /// Label the key with the ID (UUID) of the account it belongs to. Keygen
+    /// signs under the account ID, so a slug would never match.
+    pub fn account(mut self, account: impl Into<String>) -> Self {
+        self.account = Some(account.into());
+        self
+    }
+
+    /// Label the key with the `keyid` its signatures are issued under
+    pub fn keyid(mut self, keyid: impl Into<String>) -> Self {
+        self.keyid = Some(keyid.into());
+        self
+    }
+
+    /// Whether a signature issued under `keyid` is meant for this key.
+    /// Keygen uses the account ID as the `keyid`, so either label matches.
+    pub fn matches(&self, keyid: &str) -> bool {
+        self.keyid.as_deref() == Some(keyid) || self.account.as_deref() == Some(keyid)
+    }
+
+    fn is_labeled(&self) -> bool {
+        self.keyid.is_some() || self.account.is_some()
+    }
+
+    pub(crate) fn ed25519_bytes(&self) -> Result<[u8; 32], Error> {
+        if self.key.is_empty() {
+            return Err(Error::PublicKeyMissing);
+        }
+
+        let key = hex::decode(&self.key).map_err(|_| Error::PublicKeyInvalid)?;
+        if key.len() != 32 {
+            return Err(Error::PublicKeyInvalid);
+        }
+
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&key);
+        Ok(bytes)
+    }
+}
+
+/// An ordered set of trusted [`PublicKey`]s.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Zeroize)]
+#[serde(transparent)]
+pub struct Keyring(Vec<PublicKey>);
+
+impl Keyring {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, key: PublicKey) {
+        self.0.push(key);
+    }
+
+    /// Builder-style variant of [`push`](Self::push).
+    pub fn with(mut self, key: PublicKey) -> Self {
+        self.push(key);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, PublicKey> {
+        self.0.iter()
+    }
+
+    /// Keys to check a signature issued under `keyid` against: those labeled
+    /// with it, or, when none are, the unlabeled keys.
+    pub fn select(&self, keyid: &str) -> Vec<&PublicKey> {
+        let labeled: Vec<&PublicKey> = self.iter().filter(|key| key.matches(keyid)).collect();
+        if !labeled.is_empty() {
+            return labeled;
+        }
+        self.iter().filter(|key| !key.is_labeled()).collect()
+    }
+
+    /// This keyring with `public_key`, when set, prepended as an unlabeled key.
+    pub(crate) fn including(&self, public_key: Option<&String>) -> Keyring {
+        public_key
+            .map(|key| PublicKey::ed25519(key.clone()))
+            .into_iter()
+            .chain(self.iter().cloned())
+            .collect()
+    }
+}
+
+impl From<String> for Keyring {
+    fn from(key: String) -> Self {
+        Keyring(vec![PublicKey::ed25519(key)])
+    }
+}
+
+impl From<&str> for Keyring {
+    fn from(key: &str) -> Self {
+        Keyring::from(key.to_string())
+    }
+}
+
+impl From<PublicKey> for Keyring {
+    fn from(key: PublicKey) -> Self {
+        Keyring(vec![key])
+    }
+}
+
+impl FromIterator<PublicKey> for Keyring {
+    fn from_iter<I: IntoIterator<Item = PublicKey>>(iter: I) -> Self {
+        Keyring(iter.into_iter().collect())
+    }
+}
+
+impl<'a> IntoIterator for &'a Keyring {
+    type Item = &'a PublicKey;
+    type IntoIter = std::slice::Iter<'a, PublicKey>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_prefers_labeled_keys() {
+        let keyring = Keyring::new()
+            .with(PublicKey::ed25519("aa"))
+            .with(PublicKey::ed25519("bb").account("acme"))
+            .with(PublicKey::ed25519("cc").keyid("acme"))
+            .with(PublicKey::ed25519("dd").account("other"));
+
+        let keys: Vec<&str> = keyring
+            .select("acme")
+            .iter()
+            .map(|k| k.key.as_str())
+            .collect();
+        assert_eq!(keys, vec!["bb", "cc"]);
+
+        let keys: Vec<&str> = keyring
+            .select("unknown")
+            .iter()
+            .map(|k| k.key.as_str())
+            .collect();
+        assert_eq!(keys, vec!["aa"]);
+    }
+
+    #[test]
+    fn test_deserialize_keyring() {
+        let keyring: Keyring = serde_json::from_str(
+            r#"[{"key": "aa"}, {"key": "bb", "algorithm": "ed25519", "account": "acme"}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            keyring,
+            Keyring::new()
+                .with(PublicKey::ed25519("aa"))
+                .with(PublicKey::ed25519("bb").account("acme"))
+        );
+    }
+}
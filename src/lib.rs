@@ -2,7 +2,7 @@ use client::{Client, ClientOptions};
 use config::get_config;
 use errors::Error;
 use license::{License, SchemeCode};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::config::KeygenConfig;
 
@@ -22,6 +22,10 @@ pub(crate) fn insert_optional<T: Serialize>(
 pub(crate) mod certificate;
 pub(crate) mod client;
 pub(crate) mod decryptor;
+pub(crate) mod metrics;
+#[cfg(feature = "token")]
+pub(crate) mod pagination;
+pub(crate) mod trace;
 pub(crate) mod verifier;
 
 pub mod component;
@@ -29,22 +33,35 @@ pub mod config;
 pub mod entitlement;
 pub mod errors;
 pub mod group;
+pub mod keyring;
 pub mod license;
 pub mod license_file;
 pub mod machine;
 pub mod machine_file;
+pub mod middleware;
 pub mod service;
+pub mod signed_key;
 
 // Management features only available with "token" feature flag
 #[cfg(feature = "token")]
 pub mod environment;
 #[cfg(feature = "token")]
+pub mod event_log;
+#[cfg(feature = "token")]
+pub mod password;
+#[cfg(feature = "token")]
 pub mod policy;
 #[cfg(feature = "token")]
 pub mod product;
 #[cfg(feature = "token")]
+pub mod profile;
+#[cfg(feature = "token")]
 pub mod release;
 #[cfg(feature = "token")]
+pub mod request_log;
+#[cfg(feature = "token")]
+pub mod second_factor;
+#[cfg(feature = "token")]
 pub mod token;
 #[cfg(feature = "token")]
 pub mod user;
@@ -63,6 +80,14 @@ pub mod package;
 #[cfg(feature = "token")]
 pub mod platform;
 
+// Record and replay of API traffic for tests (only available with "cassette" feature flag)
+#[cfg(all(feature = "cassette", not(target_arch = "wasm32")))]
+pub mod cassette;
+
+// Synchronous facade (only available with "blocking" feature flag)
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct KeygenRelationshipData {
     pub r#type: String,
@@ -138,6 +163,16 @@ impl KeygenRelationships {
     pub(crate) fn license_id(&self) -> Option<String> {
         Self::extract_id(&self.license)
     }
+
+    /// Looks up a relationship without a dedicated field, such as a
+    /// polymorphic `requestor` or `resource`
+    #[cfg(feature = "token")]
+    pub(crate) fn other_relationship(&self, name: &str) -> Option<KeygenRelationshipData> {
+        self.other
+            .get(name)
+            .and_then(|value| serde_json::from_value::<KeygenRelationship>(value.clone()).ok())
+            .and_then(|rel| rel.data)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -233,3 +268,36 @@ pub fn verify_with_config(
         .with_config(config.clone())
         .verify()
 }
+
+/// Verifies a signed key and decodes its dataset as JSON
+///
+/// # Example
+/// ```no_run
+/// use keygen_rs::license::SchemeCode;
+/// use keygen_rs::signed_key::SignedKeyPayload;
+///
+/// let payload: SignedKeyPayload = keygen_rs::verify_as(SchemeCode::Ed25519Sign, "key/...")?;
+/// println!("License {:?} expires {:?}", payload.license_id(), payload.expiry());
+/// # Ok::<(), keygen_rs::errors::Error>(())
+/// ```
+#[must_use = "verification result should be checked"]
+pub fn verify_as<T: DeserializeOwned>(scheme: SchemeCode, signed_key: &str) -> Result<T, Error> {
+    let config = get_config()?;
+    verify_as_with_config(&config, scheme, signed_key)
+}
+
+/// [`verify_as`] with an explicit config. Check a [`SignedKeyPayload`]'s
+/// expiry against the same config with
+/// [`is_expired_with`](signed_key::SignedKeyPayload::is_expired_with).
+///
+/// [`SignedKeyPayload`]: signed_key::SignedKeyPayload
+#[must_use = "verification result should be checked"]
+pub fn verify_as_with_config<T: DeserializeOwned>(
+    config: &config::KeygenConfig,
+    scheme: SchemeCode,
+    signed_key: &str,
+) -> Result<T, Error> {
+    License::from_signed_key(scheme, signed_key)
+        .with_config(config.clone())
+        .verify_as()
+}
@@ -7,7 +7,7 @@
 use std::env;
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 
@@ -20,8 +20,9 @@ use crate::errors::Error;
 use crate::insert_optional;
 use crate::license_file::LicenseFile;
 use crate::machine::{Machine, MachineResponse, MachinesResponse};
+use crate::metrics;
 #[cfg(feature = "token")]
-use crate::token::{token_request_attributes, CreateTokenRequest, Token, TokenResponse};
+use crate::token::{token_request_body, CreateTokenRequest, Token, TokenResponse};
 #[cfg(feature = "token")]
 use crate::user::{User, UserAttributes};
 use crate::verifier::Verifier;
@@ -770,7 +771,9 @@ impl License {
         let validation: LicenseResponse<ValidationMeta> = serde_json::from_value(response.body)?;
         let meta = validation.meta.clone().unwrap();
         if !meta.valid {
-            return Err(self.handle_validation_code(&meta));
+            let error = self.handle_validation_code(&meta);
+            metrics::record_error(&error);
+            return Err(error);
         };
         let license = License::from(validation.data);
         Ok(if let Some(cfg) = self.config {
@@ -805,7 +808,9 @@ impl License {
         let validation: LicenseResponse<ValidationMeta> = serde_json::from_value(response.body)?;
         let meta = validation.meta.clone().unwrap();
         if !meta.valid {
-            return Err(self.handle_validation_code(&meta));
+            let error = self.handle_validation_code(&meta);
+            metrics::record_error(&error);
+            return Err(error);
         };
         let license = License::from(validation.data);
         Ok(if let Some(cfg) = self.config {
@@ -825,12 +830,13 @@ impl License {
         } else {
             get_config()?
         };
-        if let Some(public_key) = &config.public_key {
-            let verifier = Verifier::new(public_key.clone());
-            verifier.verify_license(self)
-        } else {
-            Err(Error::PublicKeyMissing)
-        }
+        Verifier::new(config.public_keys()).verify_license(self)
+    }
+
+    /// Verify the signed key and decode its dataset as JSON, e.g. into a
+    /// [`SignedKeyPayload`](crate::signed_key::SignedKeyPayload)
+    pub fn verify_as<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        Ok(serde_json::from_slice(&self.verify()?)?)
     }
 
     pub async fn activate(
@@ -1384,13 +1390,7 @@ impl License {
     ) -> Result<Token, Error> {
         let client = self.get_client()?;
         let endpoint = format!("licenses/{}/tokens", self.id);
-        let attributes = token_request_attributes(request.as_ref())?;
-        let body = json!({
-            "data": {
-                "type": "tokens",
-                "attributes": attributes
-            }
-        });
+        let body = token_request_body(request.as_ref())?;
         let response = client.post(&endpoint, Some(&body), None::<&()>).await?;
         let token_response: TokenResponse = serde_json::from_value(response.body)?;
         Ok(Token::from(token_response.data))
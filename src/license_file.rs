@@ -13,6 +13,7 @@ use crate::{
     errors::Error,
     group::Group,
     license::{License, LicenseAttributes},
+    trace::trace_event,
     verifier::Verifier,
     KeygenResponseData,
 };
@@ -146,7 +147,7 @@ pub struct LicenseFileDataset {
     pub included: Option<IncludedResources>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LicenseFile {
     pub id: String,
     pub certificate: String,
@@ -182,9 +183,14 @@ impl LicenseFile {
             expiry: dataset.expiry,
             ttl: dataset.ttl,
         };
+        trace_event!(debug, license_id = %dataset.license.id, ttl = dataset.ttl, "license file decrypted");
+
         if let Err(err) = validate_certificate_meta(&meta) {
             match err {
-                Error::CertificateFileExpired => Err(Error::LicenseFileExpired(Box::new(dataset))),
+                Error::CertificateFileExpired => {
+                    trace_event!(warn, license_id = %dataset.license.id, expiry = %dataset.expiry, "license file expired");
+                    Err(Error::LicenseFileExpired(Box::new(dataset)))
+                }
                 _ => Err(err),
             }
         } else {
@@ -201,18 +207,18 @@ impl LicenseFile {
     pub fn verify(&self) -> Result<(), Error> {
         let config = crate::config::get_config()?;
 
-        if let Some(public_key) = &config.public_key {
-            let verifier = Verifier::new(public_key.clone());
-            verifier.verify_license_file(self)
-        } else {
-            Err(Error::PublicKeyMissing)
-        }
+        Verifier::new(config.public_keys()).verify_license_file(self)
     }
 
     pub fn decrypt(&self, key: &str) -> Result<LicenseFileDataset, Error> {
         Self::_decrypt(key, &self.certificate)
     }
 
+    /// Decode an unencrypted (`base64+ed25519`) license file, which needs no key.
+    pub fn decode(&self) -> Result<LicenseFileDataset, Error> {
+        Self::_decode(&self.certificate)
+    }
+
     pub fn certificate(&self) -> Result<Certificate, Error> {
         Self::_certificate(self.certificate.clone())
     }
@@ -250,8 +256,24 @@ impl LicenseFile {
 
         let decryptor = Decryptor::new(key.to_string());
         let data = decryptor.decrypt_certificate(&cert)?;
+        Self::_dataset(&data)
+    }
+
+    fn _decode(content: &str) -> Result<LicenseFileDataset, Error> {
+        let cert = Self::_certificate(content.to_string())?;
+        if cert.alg != "base64+ed25519" {
+            return Err(Error::LicenseFileNotSupported(cert.alg));
+        }
+
+        let data = general_purpose::STANDARD
+            .decode(&cert.enc)
+            .map_err(|e| Error::LicenseFileInvalid(e.to_string()))?;
+        Self::_dataset(&data)
+    }
+
+    fn _dataset(data: &[u8]) -> Result<LicenseFileDataset, Error> {
         let dataset: Value =
-            serde_json::from_slice(&data).map_err(|e| Error::LicenseFileInvalid(e.to_string()))?;
+            serde_json::from_slice(data).map_err(|e| Error::LicenseFileInvalid(e.to_string()))?;
 
         let meta: CertificateFileMeta = serde_json::from_value(dataset["meta"].clone())
             .map_err(|e| Error::LicenseFileInvalid(e.to_string()))?;
@@ -8,8 +8,11 @@ use crate::client::{Client, ClientOptions, Response};
 use crate::config::get_config;
 use crate::config::KeygenConfig;
 use crate::errors::Error;
+#[cfg(feature = "token")]
 use crate::insert_optional;
 use crate::machine_file::MachineFile;
+use crate::metrics;
+use crate::trace::trace_event;
 use crate::KeygenResponseData;
 use chrono::{DateTime, Utc};
 #[cfg(not(target_arch = "wasm32"))]
@@ -259,14 +262,12 @@ impl Machine {
                 None::<&()>,
             )
             .await?;
-        let machine = Machine::from(response.body.data).with_config(
-            self.config
-                .as_ref()
-                .ok_or(Error::MissingConfiguration)?
-                .as_ref()
-                .clone(),
-        );
-        Ok(machine)
+        let machine = Machine::from(response.body.data);
+        Ok(if let Some(cfg) = &self.config {
+            machine.with_config(cfg.as_ref().clone())
+        } else {
+            machine
+        })
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -286,15 +287,26 @@ impl Machine {
                 }
             }
 
+            async fn beat(machine: &Machine) -> Result<Machine, Error> {
+                trace_event!(debug, machine_id = %machine.id, "heartbeat tick");
+                let result = machine.ping().await;
+                metrics::record_heartbeat(result.is_ok());
+                #[cfg(feature = "tracing")]
+                if let Err(error) = &result {
+                    tracing::warn!(target: "keygen", machine_id = %machine.id, %error, "heartbeat ping failed");
+                }
+                result
+            }
+
             let mut interval = tokio::time::interval(heartbeat_interval);
             interval.tick().await;
 
-            send(&tx, self.ping().await).await;
+            send(&tx, beat(&self).await).await;
 
             loop {
                 tokio::select! {
                     _ = interval.tick() => {
-                        send(&tx, self.ping().await).await;
+                        send(&tx, beat(&self).await).await;
                     }
                     _ = async {
                         if let Some(ref mut rx) = cancel_rx {
@@ -303,6 +315,7 @@ impl Machine {
                             std::future::pending::<Option<()>>().await
                         }
                     } => {
+                        trace_event!(debug, machine_id = %self.id, "heartbeat monitor stopped");
                         break;
                     }
                 }
@@ -15,6 +15,7 @@ use crate::{
     license::License,
     license_file::IncludedResources,
     machine::{Machine, MachineAttributes},
+    trace::trace_event,
     verifier::Verifier,
     KeygenResponseData,
 };
@@ -30,7 +31,7 @@ pub struct MachineFileDataset {
     pub included: Option<IncludedResources>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MachineFile {
     pub id: String,
     pub certificate: String,
@@ -72,15 +73,14 @@ impl MachineFile {
 
     pub fn verify(&self) -> Result<(), Error> {
         self.validate_ttl()?;
+        self.verify_signature()
+    }
 
+    /// Verify the machine file's signature without checking its TTL
+    pub fn verify_signature(&self) -> Result<(), Error> {
         let config = crate::config::get_config()?;
 
-        if let Some(public_key) = &config.public_key {
-            let verifier = Verifier::new(public_key.clone());
-            verifier.verify_machine_file(self)
-        } else {
-            Err(Error::PublicKeyMissing)
-        }
+        Verifier::new(config.public_keys()).verify_machine_file(self)
     }
 
     pub fn validate_ttl(&self) -> Result<(), Error> {
@@ -145,6 +145,11 @@ impl MachineFile {
         Self::_decrypt(key, &self.certificate)
     }
 
+    /// Decode an unencrypted (`base64+ed25519`) machine file, which needs no key.
+    pub fn decode(&self) -> Result<MachineFileDataset, Error> {
+        Self::_decode(&self.certificate)
+    }
+
     pub fn certificate(&self) -> Result<Certificate, Error> {
         Self::_certificate(self.certificate.clone())
     }
@@ -162,8 +167,24 @@ impl MachineFile {
 
         let decryptor = Decryptor::new(key.to_string());
         let data = decryptor.decrypt_certificate(&cert)?;
+        Self::_dataset(&data)
+    }
+
+    fn _decode(content: &str) -> Result<MachineFileDataset, Error> {
+        let cert = Self::_certificate(content.to_string())?;
+        if cert.alg != "base64+ed25519" {
+            return Err(Error::LicenseFileNotSupported(cert.alg));
+        }
+
+        let data = general_purpose::STANDARD
+            .decode(&cert.enc)
+            .map_err(|e| Error::MachineFileInvalid(e.to_string()))?;
+        Self::_dataset(&data)
+    }
+
+    fn _dataset(data: &[u8]) -> Result<MachineFileDataset, Error> {
         let dataset: Value =
-            serde_json::from_slice(&data).map_err(|e| Error::MachineFileInvalid(e.to_string()))?;
+            serde_json::from_slice(data).map_err(|e| Error::MachineFileInvalid(e.to_string()))?;
 
         let meta: CertificateFileMeta = serde_json::from_value(dataset["meta"].clone())
             .map_err(|e| Error::LicenseFileInvalid(e.to_string()))?;
@@ -207,9 +228,14 @@ impl MachineFile {
             included,
         };
 
+        trace_event!(debug, machine_id = %dataset.machine.id, ttl = dataset.ttl, "machine file decrypted");
+
         if let Err(err) = validate_certificate_meta(&meta) {
             match err {
-                Error::CertificateFileExpired => Err(Error::MachineFileExpired(Box::new(dataset))),
+                Error::CertificateFileExpired => {
+                    trace_event!(warn, machine_id = %dataset.machine.id, expiry = %dataset.expiry, "machine file expired");
+                    Err(Error::MachineFileExpired(Box::new(dataset)))
+                }
                 _ => Err(err),
             }
         } else {
@@ -0,0 +1,334 @@
+//! Optional metrics through the [`metrics`](https://docs.rs/metrics) facade.
+//!
+//! With the `metrics` feature enabled, the SDK reports to whichever recorder
+//! the application installs (Prometheus, StatsD, ...). Without it, everything
+//! in this module compiles to nothing.
+//!
+//! | Metric                            | Type      | Labels                        |
+//! |-----------------------------------|-----------|-------------------------------|
+//! | `keygen_requests_total`           | counter   | `method`, `endpoint`, `status` |
+//! | `keygen_request_duration_seconds` | histogram | `method`, `endpoint`          |
+//! | `keygen_errors_total`             | counter   | `code`                        |
+//! | `keygen_rate_limit_remaining`     | gauge     | `window`                      |
+//! | `keygen_heartbeats_total`         | counter   | `outcome`                     |
+//!
+//! `endpoint` is the path below the API prefix and account, with resource IDs
+//! and license keys collapsed to `:id`, so the label stays low-cardinality.
+//! Requests that fail before a response arrives are counted with the status
+//! `error`.
+
+#[cfg(feature = "metrics")]
+use reqwest::header::HeaderMap;
+#[cfg(feature = "metrics")]
+use reqwest::{Method, StatusCode};
+#[cfg(feature = "metrics")]
+use std::time::Duration;
+
+use crate::errors::Error;
+
+/// Collapse a path relative to the API prefix and account into a
+/// low-cardinality endpoint label, e.g. `licenses/KEY/actions/validate`
+/// becomes `licenses/:id/actions/validate`.
+#[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+pub(crate) fn endpoint(relative: &str) -> String {
+    let segments: Vec<&str> = relative.split('/').filter(|s| !s.is_empty()).collect();
+
+    let mut label = Vec::with_capacity(segments.len());
+    let mut expect_id = false;
+    let mut expect_name = false;
+    for segment in segments {
+        if expect_name {
+            // Action names and relationship names are part of the endpoint
+            label.push(segment);
+            expect_name = false;
+        } else if segment == "actions" || segment == "relationships" {
+            label.push(segment);
+            expect_name = true;
+            expect_id = false;
+        } else if expect_id {
+            label.push(":id");
+            expect_id = false;
+        } else {
+            label.push(segment);
+            expect_id = true;
+        }
+    }
+    label.join("/")
+}
+
+/// Record a completed request and the rate limit headers of its response.
+#[cfg(feature = "metrics")]
+pub(crate) fn record_request(
+    method: &Method,
+    endpoint: &str,
+    status: StatusCode,
+    elapsed: Option<Duration>,
+    headers: &HeaderMap,
+) {
+    ::metrics::counter!(
+        "keygen_requests_total",
+        "method" => method.to_string(),
+        "endpoint" => endpoint.to_string(),
+        "status" => status.as_u16().to_string(),
+    )
+    .increment(1);
+    if let Some(elapsed) = elapsed {
+        ::metrics::histogram!(
+            "keygen_request_duration_seconds",
+            "method" => method.to_string(),
+            "endpoint" => endpoint.to_string(),
+        )
+        .record(elapsed.as_secs_f64());
+    }
+
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    if let Some(remaining) = header("X-RateLimit-Remaining").and_then(|v| v.parse::<f64>().ok()) {
+        let window = header("X-RateLimit-Window").unwrap_or_default().to_string();
+        ::metrics::gauge!("keygen_rate_limit_remaining", "window" => window).set(remaining);
+    }
+}
+
+/// Record a request that failed before any response arrived.
+#[cfg(feature = "metrics")]
+pub(crate) fn record_failure(method: &Method, endpoint: &str, elapsed: Option<Duration>) {
+    ::metrics::counter!(
+        "keygen_requests_total",
+        "method" => method.to_string(),
+        "endpoint" => endpoint.to_string(),
+        "status" => "error",
+    )
+    .increment(1);
+    if let Some(elapsed) = elapsed {
+        ::metrics::histogram!(
+            "keygen_request_duration_seconds",
+            "method" => method.to_string(),
+            "endpoint" => endpoint.to_string(),
+        )
+        .record(elapsed.as_secs_f64());
+    }
+}
+
+/// Count an error by its [`ErrorMeta::code`](crate::errors::ErrorMeta::code).
+#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+pub(crate) fn record_error(error: &Error) {
+    #[cfg(feature = "metrics")]
+    {
+        use crate::errors::ErrorMeta;
+        ::metrics::counter!("keygen_errors_total", "code" => error.code()).increment(1);
+    }
+}
+
+/// Count a heartbeat ping sent by [`Machine::monitor`](crate::machine::Machine::monitor).
+#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+pub(crate) fn record_heartbeat(success: bool) {
+    #[cfg(feature = "metrics")]
+    {
+        let outcome = if success { "success" } else { "failure" };
+        ::metrics::counter!("keygen_heartbeats_total", "outcome" => outcome).increment(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_collapses_ids() {
+        assert_eq!(
+            endpoint("licenses/KEY-123/actions/validate"),
+            "licenses/:id/actions/validate"
+        );
+        assert_eq!(
+            endpoint("licenses/actions/validate-key"),
+            "licenses/actions/validate-key"
+        );
+        assert_eq!(
+            endpoint("releases/rel1/artifacts/app.zip"),
+            "releases/:id/artifacts/:id"
+        );
+        assert_eq!(
+            endpoint("licenses/lic1/relationships/policy"),
+            "licenses/:id/relationships/policy"
+        );
+        assert_eq!(endpoint("me"), "me");
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_requests_and_heartbeats_are_recorded() {
+        use crate::client::{Client, ClientOptions, Response};
+        use crate::config::KeygenConfig;
+        use crate::machine::Machine;
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+        use metrics_util::MetricKind;
+        use mockito::{mock, server_url};
+        use std::sync::Arc;
+        use tokio::sync::mpsc;
+
+        let _license = mock("GET", "/v1/licenses/METRICS-KEY")
+            .with_status(200)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_header("X-RateLimit-Window", "30s")
+            .with_header("X-RateLimit-Remaining", "59")
+            .with_body(r#"{"meta": {}}"#)
+            .create();
+        let _ping = mock("POST", "/v1/machines/metrics-machine/actions/ping")
+            .with_status(429)
+            .with_header("X-RateLimit-Window", "60s")
+            .with_header("X-RateLimit-Remaining", "0")
+            .with_header("Retry-After", "30")
+            .with_body(r#"{"errors": [{"code": "TOO_MANY_REQUESTS"}]}"#)
+            .create();
+
+        let config = KeygenConfig {
+            api_url: server_url(),
+            account: "test_account".to_string(),
+            license_key: Some("METRICS-KEY".to_string()),
+            ..KeygenConfig::default()
+        };
+        let machine = Arc::new(
+            Machine {
+                id: "metrics-machine".to_string(),
+                fingerprint: "metrics-fingerprint".to_string(),
+                name: None,
+                platform: None,
+                hostname: None,
+                ip: None,
+                cores: None,
+                metadata: None,
+                require_heartbeat: true,
+                heartbeat_status: "ALIVE".to_string(),
+                heartbeat_duration: Some(600),
+                created: chrono::Utc::now(),
+                updated: chrono::Utc::now(),
+                account_id: None,
+                environment_id: None,
+                product_id: None,
+                license_id: None,
+                owner_id: None,
+                group_id: None,
+                config: None,
+            }
+            .with_config(config.clone()),
+        );
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        ::metrics::with_local_recorder(&recorder, || {
+            runtime.block_on(async {
+                let client = Client::new(ClientOptions::from(config)).unwrap();
+                let _: Response<serde_json::Value> = client
+                    .get("licenses/METRICS-KEY", None::<&()>)
+                    .await
+                    .unwrap();
+
+                // A single beat, then stop the monitor
+                let (tx, mut rx) = mpsc::channel(1);
+                let (cancel_tx, cancel_rx) = mpsc::channel(1);
+                let monitor = tokio::spawn(machine.monitor(
+                    Duration::from_secs(3600),
+                    Some(tx),
+                    Some(cancel_rx),
+                ));
+                let beat = rx.recv().await.unwrap();
+                assert!(matches!(beat, Err(Error::RateLimitExceeded { .. })));
+                cancel_tx.send(()).await.unwrap();
+                monitor.await.unwrap();
+            })
+        });
+
+        let metrics: Vec<_> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| {
+                let (kind, key) = key.into_parts();
+                let labels: Vec<(String, String)> = key
+                    .labels()
+                    .map(|label| (label.key().to_string(), label.value().to_string()))
+                    .collect();
+                (kind, key.name().to_string(), labels, value)
+            })
+            .collect();
+        let find = |kind: MetricKind, name: &str, labels: &[(&str, &str)]| {
+            metrics
+                .iter()
+                .find(|(k, n, l, _)| {
+                    *k == kind
+                        && n == name
+                        && l.len() == labels.len()
+                        && labels
+                            .iter()
+                            .all(|(key, value)| l.iter().any(|(k, v)| k == key && v == value))
+                })
+                .map(|(_, _, _, value)| value)
+                .unwrap_or_else(|| panic!("missing {name} {labels:?} in {metrics:?}"))
+        };
+
+        let get = [("method", "GET"), ("endpoint", "licenses/:id")];
+        let ping = [
+            ("method", "POST"),
+            ("endpoint", "machines/:id/actions/ping"),
+        ];
+        assert_eq!(
+            find(
+                MetricKind::Counter,
+                "keygen_requests_total",
+                &[get[0], get[1], ("status", "200")]
+            ),
+            &DebugValue::Counter(1)
+        );
+        assert_eq!(
+            find(
+                MetricKind::Counter,
+                "keygen_requests_total",
+                &[ping[0], ping[1], ("status", "429")]
+            ),
+            &DebugValue::Counter(1)
+        );
+        assert!(matches!(
+            find(MetricKind::Histogram, "keygen_request_duration_seconds", &get),
+            DebugValue::Histogram(values) if values.len() == 1
+        ));
+        assert!(matches!(
+            find(MetricKind::Histogram, "keygen_request_duration_seconds", &ping),
+            DebugValue::Histogram(values) if values.len() == 1
+        ));
+        assert_eq!(
+            find(
+                MetricKind::Gauge,
+                "keygen_rate_limit_remaining",
+                &[("window", "30s")]
+            ),
+            &DebugValue::Gauge(59.0.into())
+        );
+        assert_eq!(
+            find(
+                MetricKind::Gauge,
+                "keygen_rate_limit_remaining",
+                &[("window", "60s")]
+            ),
+            &DebugValue::Gauge(0.0.into())
+        );
+        assert_eq!(
+            find(
+                MetricKind::Counter,
+                "keygen_errors_total",
+                &[("code", "ERROR")]
+            ),
+            &DebugValue::Counter(1)
+        );
+        assert_eq!(
+            find(
+                MetricKind::Counter,
+                "keygen_heartbeats_total",
+                &[("outcome", "failure")]
+            ),
+            &DebugValue::Counter(1)
+        );
+    }
+}
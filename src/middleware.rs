@@ -0,0 +1,163 @@
+//! Request and response hooks for the API client.
+//!
+//! Every API call made by the SDK runs through the [`Middleware`] registered in
+//! [`KeygenConfig::middleware`](crate::config::KeygenConfig::middleware), in
+//! registration order. `before_request` sees the outgoing [`Request`] and can
+//! add headers or abort the call; `intercept` can answer the request itself
+//! without touching the network; `after_response` sees the raw response before
+//! the SDK checks its status, verifies its signature and decodes it, and may
+//! rewrite it (useful for fault injection in tests).
+//!
+//! # Example
+//! ```no_run
+//! use keygen_rs::config::add_middleware;
+//! use keygen_rs::middleware::{HeaderValue, Middleware, MiddlewareFuture, Request};
+//!
+//! struct CorrelationId(String);
+//!
+//! impl Middleware for CorrelationId {
+//!     fn before_request<'a>(&'a self, request: &'a mut Request) -> MiddlewareFuture<'a> {
+//!         Box::pin(async move {
+//!             let value = HeaderValue::from_str(&self.0)?;
+//!             request.headers_mut().insert("X-Correlation-Id", value);
+//!             Ok(())
+//!         })
+//!     }
+//! }
+//!
+//! add_middleware(CorrelationId("job-42".to_string())).expect("Failed to add middleware");
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::errors::Error;
+
+pub use reqwest::header::{HeaderMap, HeaderValue};
+pub use reqwest::{Method, Request, StatusCode, Url};
+
+/// Future returned by [`Middleware`] hooks.
+#[cfg(not(target_arch = "wasm32"))]
+pub type MiddlewareFuture<'a, T = ()> = futures::future::BoxFuture<'a, Result<T, Error>>;
+/// Future returned by [`Middleware`] hooks.
+#[cfg(target_arch = "wasm32")]
+pub type MiddlewareFuture<'a, T = ()> = futures::future::LocalBoxFuture<'a, Result<T, Error>>;
+
+/// A response as received from the API, before the SDK processes it.
+#[derive(Debug, Clone)]
+pub struct ResponseParts {
+    pub method: Method,
+    pub url: Url,
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+    /// Body of the request that produced this response, if it had one
+    pub request_body: Option<Vec<u8>>,
+}
+
+/// Hooks around each API request. Every hook defaults to doing nothing, and
+/// returning an error from any of them aborts the call with that error.
+pub trait Middleware: Send + Sync {
+    /// Called before the request is sent.
+    fn before_request<'a>(&'a self, request: &'a mut Request) -> MiddlewareFuture<'a> {
+        let _ = request;
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Called once all `before_request` hooks have run. Returning a response
+    /// skips the network call; the first middleware to do so wins.
+    fn intercept<'a>(
+        &'a self,
+        request: &'a Request,
+    ) -> MiddlewareFuture<'a, Option<ResponseParts>> {
+        let _ = request;
+        Box::pin(async { Ok(None) })
+    }
+
+    /// Called with the response before its status is checked and its
+    /// signature verified.
+    fn after_response<'a>(&'a self, response: &'a mut ResponseParts) -> MiddlewareFuture<'a> {
+        let _ = response;
+        Box::pin(async { Ok(()) })
+    }
+}
+
+impl<M: Middleware + ?Sized> Middleware for Arc<M> {
+    fn before_request<'a>(&'a self, request: &'a mut Request) -> MiddlewareFuture<'a> {
+        (**self).before_request(request)
+    }
+
+    fn intercept<'a>(
+        &'a self,
+        request: &'a Request,
+    ) -> MiddlewareFuture<'a, Option<ResponseParts>> {
+        (**self).intercept(request)
+    }
+
+    fn after_response<'a>(&'a self, response: &'a mut ResponseParts) -> MiddlewareFuture<'a> {
+        (**self).after_response(response)
+    }
+}
+
+/// An ordered list of [`Middleware`].
+#[derive(Clone, Default)]
+pub struct MiddlewareChain(Vec<Arc<dyn Middleware>>);
+
+impl MiddlewareChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a middleware; it runs after those already in the chain.
+    pub fn push(&mut self, middleware: impl Middleware + 'static) {
+        self.0.push(Arc::new(middleware));
+    }
+
+    /// Builder-style variant of [`push`](Self::push).
+    pub fn with(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.push(middleware);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) async fn before_request(&self, request: &mut Request) -> Result<(), Error> {
+        for middleware in &self.0 {
+            middleware.before_request(request).await?;
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn intercept(
+        &self,
+        request: &Request,
+    ) -> Result<Option<ResponseParts>, Error> {
+        for middleware in &self.0 {
+            if let Some(response) = middleware.intercept(request).await? {
+                return Ok(Some(response));
+            }
+        }
+        Ok(None)
+    }
+
+    pub(crate) async fn after_response(&self, response: &mut ResponseParts) -> Result<(), Error> {
+        for middleware in &self.0 {
+            middleware.after_response(response).await?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for MiddlewareChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MiddlewareChain")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
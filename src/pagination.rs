@@ -0,0 +1,73 @@
+use futures::stream::{self, Stream, TryStreamExt};
+use std::future::Future;
+
+use crate::errors::Error;
+
+/// Keygen's maximum `page[size]`
+pub(crate) const MAX_PAGE_SIZE: u32 = 100;
+
+/// The `page[size]` to stream with: `requested`, clamped to the sizes Keygen
+/// accepts, or the maximum when unset. A size of 0 would make every page look
+/// full and the stream would never end.
+pub(crate) fn page_size(requested: Option<u32>) -> u32 {
+    requested.unwrap_or(MAX_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+}
+
+/// Stream every item across pages, fetching the next page only when the
+/// current one has been consumed. Stops after the first short page.
+pub(crate) fn paginate<T, F, Fut>(
+    page_size: u32,
+    fetch_page: F,
+) -> impl Stream<Item = Result<T, Error>>
+where
+    F: Fn(u32) -> Fut,
+    Fut: Future<Output = Result<Vec<T>, Error>>,
+{
+    stream::try_unfold(Some(1u32), move |page_number| {
+        let next = page_number.map(&fetch_page);
+        async move {
+            let (Some(page_number), Some(next)) = (page_number, next) else {
+                return Ok::<_, Error>(None);
+            };
+            let items = next.await?;
+            let next_page = (items.len() as u32 >= page_size).then_some(page_number + 1);
+            Ok(Some((stream::iter(items.into_iter().map(Ok)), next_page)))
+        }
+    })
+    .try_flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_page_size_is_clamped() {
+        assert_eq!(page_size(None), MAX_PAGE_SIZE);
+        assert_eq!(page_size(Some(0)), 1);
+        assert_eq!(page_size(Some(25)), 25);
+        assert_eq!(page_size(Some(MAX_PAGE_SIZE + 1)), MAX_PAGE_SIZE);
+    }
+
+    #[tokio::test]
+    async fn test_paginate_stops_after_short_page() {
+        let requested = Mutex::new(Vec::new());
+        let items: Vec<u32> = paginate(2, |page| {
+            requested.lock().unwrap().push(page);
+            async move {
+                Ok(match page {
+                    1 => vec![1, 2],
+                    2 => vec![3, 4],
+                    _ => vec![5],
+                })
+            }
+        })
+        .try_collect()
+        .await
+        .unwrap();
+
+        assert_eq!(items, vec![1, 2, 3, 4, 5]);
+        assert_eq!(*requested.lock().unwrap(), vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,146 @@
+use serde_json::json;
+
+use crate::client::Client;
+use crate::errors::Error;
+use crate::user::{ResetPasswordRequest, User, UserResponse};
+
+/// Start the forgot-password flow for a user by email.
+///
+/// With `deliver` set, Keygen emails the reset token to the user. Without it, no
+/// email is sent and the token is delivered through the `user.password-reset`
+/// webhook event, so you can send your own email. Keygen responds the same way
+/// whether or not the email belongs to a user.
+pub async fn request_reset(email: &str, deliver: bool) -> Result<(), Error> {
+    let client = Client::from_global_config()?;
+    let body = json!({
+        "meta": {
+            "email": email,
+            "deliver": deliver
+        }
+    });
+    client
+        .post::<_, (), _>("passwords", Some(&body), None::<&()>)
+        .await?;
+    Ok(())
+}
+
+/// Complete a password reset with the token from the reset email.
+///
+/// `user` is the user's ID or email. Fails with [`Error::PasswordResetTokenInvalid`]
+/// or [`Error::PasswordResetTokenExpired`] when the token can't be used.
+pub async fn reset(user: &str, request: ResetPasswordRequest) -> Result<User, Error> {
+    let client = Client::from_global_config()?;
+    let endpoint = format!("users/{user}/actions/reset-password");
+    let body = json!({
+        "meta": {
+            "passwordResetToken": request.password_reset_token,
+            "newPassword": request.new_password
+        }
+    });
+    let response = client.post(&endpoint, Some(&body), None::<&()>).await?;
+    let user_response: UserResponse = serde_json::from_value(response.body)?;
+    Ok(User::from(user_response.data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{reset_config, set_config, KeygenConfig};
+    use mockito::{mock, server_url, Matcher};
+
+    fn setup() {
+        let _ = set_config(KeygenConfig {
+            api_url: server_url(),
+            account: "test_account".to_string(),
+            ..Default::default()
+        });
+    }
+
+    #[tokio::test]
+    async fn test_request_reset_without_delivery() {
+        let _m = mock("POST", "/v1/passwords")
+            .match_body(Matcher::Json(json!({
+                "meta": {"email": "user@example.com", "deliver": false}
+            })))
+            .with_status(204)
+            .create();
+        setup();
+
+        request_reset("user@example.com", false).await.unwrap();
+
+        let _ = reset_config();
+    }
+
+    #[tokio::test]
+    async fn test_reset_with_expired_token() {
+        let _m = mock("POST", "/v1/users/user@example.com/actions/reset-password")
+            .with_status(401)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(
+                json!({
+                    "errors": [{
+                        "title": "Unauthorized",
+                        "detail": "is expired",
+                        "code": "PASSWORD_RESET_TOKEN_EXPIRED",
+                        "source": {"pointer": "/meta/passwordResetToken"}
+                    }]
+                })
+                .to_string(),
+            )
+            .create();
+        setup();
+
+        let result = reset(
+            "user@example.com",
+            ResetPasswordRequest {
+                password_reset_token: "stale-token".to_string(),
+                new_password: "new-password".to_string(),
+            },
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(Error::PasswordResetTokenExpired { .. })
+        ));
+
+        let _ = reset_config();
+    }
+
+    #[tokio::test]
+    async fn test_reset_with_expired_token_without_code() {
+        let _m = mock(
+            "POST",
+            "/v1/users/nocode@example.com/actions/reset-password",
+        )
+        .with_status(401)
+        .with_header("content-type", "application/vnd.api+json")
+        .with_body(
+            json!({
+                "errors": [{
+                    "title": "Unauthorized",
+                    "detail": "is expired",
+                    "source": {"pointer": "/meta/passwordResetToken"}
+                }]
+            })
+            .to_string(),
+        )
+        .create();
+        setup();
+
+        let result = reset(
+            "nocode@example.com",
+            ResetPasswordRequest {
+                password_reset_token: "stale-token".to_string(),
+                new_password: "new-password".to_string(),
+            },
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(Error::PasswordResetTokenExpired { code, .. })
+                if code == "PASSWORD_RESET_TOKEN_EXPIRED"
+        ));
+
+        let _ = reset_config();
+    }
+}
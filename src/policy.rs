@@ -300,7 +300,7 @@ pub struct UpdatePolicyRequest {
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Policy {
     pub id: String,
     pub name: String,
@@ -1,7 +1,8 @@
 use crate::client::Client;
 use crate::errors::Error;
 use crate::insert_optional;
-use crate::token::{token_request_attributes, CreateTokenRequest, Token, TokenResponse};
+use crate::service::Capability;
+use crate::token::{token_request_body, CreateTokenRequest, Token, TokenResponse};
 use crate::KeygenResponseData;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -123,7 +124,7 @@ pub struct UpdateProductRequest {
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Product {
     pub id: String,
     pub name: String,
@@ -167,6 +168,7 @@ impl Product {
         attributes.insert("name".to_string(), serde_json::json!(request.name));
         if let Some(code) = &request.code {
             if !code.is_empty() {
+                client.require(Capability::ProductCode)?;
                 attributes.insert("code".to_string(), serde_json::json!(code));
             }
         }
@@ -221,6 +223,9 @@ impl Product {
 
         let mut attributes = serde_json::Map::new();
         insert_optional(&mut attributes, "name", request.name)?;
+        if request.code.is_some() {
+            client.require(Capability::ProductCode)?;
+        }
         insert_optional(&mut attributes, "code", request.code)?;
         insert_optional(
             &mut attributes,
@@ -269,13 +274,7 @@ impl Product {
     ) -> Result<Token, Error> {
         let client = Client::from_global_config()?;
         let endpoint = format!("products/{}/tokens", self.id);
-        let attributes = token_request_attributes(request.as_ref())?;
-        let body = serde_json::json!({
-            "data": {
-                "type": "tokens",
-                "attributes": attributes
-            }
-        });
+        let body = token_request_body(request.as_ref())?;
 
         let response = client.post(&endpoint, Some(&body), None::<&()>).await?;
         let token_response: TokenResponse = serde_json::from_value(response.body)?;
@@ -286,9 +285,12 @@ impl Product {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{reset_config, set_config, KeygenConfig};
     use crate::{
         KeygenRelationship, KeygenRelationshipData, KeygenRelationships, KeygenResponseData,
     };
+    use mockito::{mock, server_url, Matcher};
+    use serde_json::json;
 
     #[test]
     fn test_product_account_relationship() {
@@ -354,6 +356,68 @@ mod tests {
         assert_eq!(product.account_id, None);
     }
 
+    #[tokio::test]
+    async fn test_create_product_with_code_requires_api_version() {
+        let _m = mock("POST", "/v1/products")
+            .match_body(Matcher::PartialJson(json!({
+                "data": { "attributes": { "code": "coded-product" } }
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "data": {
+                        "id": "coded-product-id",
+                        "type": "products",
+                        "attributes": {
+                            "name": "Coded Product",
+                            "code": "coded-product",
+                            "created": "2024-01-01T00:00:00Z",
+                            "updated": "2024-01-01T00:00:00Z"
+                        },
+                        "relationships": {}
+                    }
+                })
+                .to_string(),
+            )
+            .create();
+
+        let request = || CreateProductRequest {
+            name: "Coded Product".to_string(),
+            code: Some("coded-product".to_string()),
+            distribution_strategy: None,
+            url: None,
+            platforms: None,
+            permissions: None,
+            metadata: None,
+        };
+
+        // The default API version predates product codes
+        let _ = set_config(KeygenConfig {
+            api_url: server_url(),
+            account: "test_account".to_string(),
+            token: Some("admin-token".to_string()),
+            ..Default::default()
+        });
+        let result = Product::create(request()).await;
+        assert!(matches!(
+            result,
+            Err(Error::FeatureNotSupported { feature, .. }) if feature == "product codes"
+        ));
+
+        let _ = set_config(KeygenConfig {
+            api_url: server_url(),
+            account: "test_account".to_string(),
+            token: Some("admin-token".to_string()),
+            api_version: "1.8".to_string(),
+            ..Default::default()
+        });
+        let product = Product::create(request()).await.unwrap();
+        assert_eq!(product.code, Some("coded-product".to_string()));
+
+        let _ = reset_config();
+    }
+
     #[test]
     fn test_platform_serialization() {
         assert_eq!(
@@ -0,0 +1,191 @@
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::client::Client;
+use crate::environment::Environment;
+use crate::errors::Error;
+use crate::license::License;
+use crate::product::Product;
+use crate::user::{User, UserRole};
+use crate::KeygenResponseData;
+
+/// The resource the current credentials authenticate as.
+#[derive(Debug, Clone)]
+pub enum Bearer {
+    /// A user with the admin role
+    Admin(Box<User>),
+    /// A user with another account-level role (developer, read-only, sales or
+    /// support agent); the role itself is on [`User::role`]
+    Staff(Box<User>),
+    User(Box<User>),
+    Product(Box<Product>),
+    License(Box<License>),
+    Environment(Box<Environment>),
+}
+
+/// Identity of the current credentials, as returned by `GET /me`.
+#[derive(Debug, Clone)]
+pub struct Profile {
+    pub bearer: Bearer,
+    /// Permissions granted to the bearer, not to the token itself: `GET /me`
+    /// doesn't report a token's own scope, and a token can be scoped to a
+    /// subset of these, so a management call may still be rejected with a
+    /// 403. `None` when the bearer has no permission list, such as an
+    /// environment or any bearer on older API versions.
+    pub bearer_permissions: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileResponse {
+    data: serde_json::Value,
+}
+
+impl Profile {
+    /// Whether the bearer has the given permission, e.g. `license.create`,
+    /// counting the `*` wildcard. `None` when the bearer's permissions are
+    /// unknown, so callers decide for themselves rather than assume access.
+    pub fn can(&self, permission: &str) -> Option<bool> {
+        self.bearer_permissions
+            .as_ref()
+            .map(|permissions| permissions.iter().any(|p| p == "*" || p == permission))
+    }
+
+    /// Whether the bearer is an account-level admin user
+    pub fn is_admin(&self) -> bool {
+        matches!(self.bearer, Bearer::Admin(_))
+    }
+}
+
+fn decode<T: DeserializeOwned>(data: serde_json::Value) -> Result<KeygenResponseData<T>, Error> {
+    Ok(serde_json::from_value(data)?)
+}
+
+/// Retrieve the bearer for the configured token or license key.
+pub async fn me() -> Result<Profile, Error> {
+    let client = Client::from_global_config()?;
+    let response = client.get("me", None::<&()>).await?;
+    let profile: ProfileResponse = serde_json::from_value(response.body)?;
+
+    let resource_type = profile.data["type"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    let (bearer, bearer_permissions) = match resource_type.as_str() {
+        "users" => {
+            let user = User::from(decode(profile.data)?);
+            let permissions = user.permissions.clone();
+            let bearer = match user.role {
+                UserRole::Admin => Bearer::Admin(Box::new(user)),
+                UserRole::User => Bearer::User(Box::new(user)),
+                _ => Bearer::Staff(Box::new(user)),
+            };
+            (bearer, permissions)
+        }
+        "products" => {
+            let product = Product::from(decode(profile.data)?);
+            let permissions = product.permissions.clone();
+            (Bearer::Product(Box::new(product)), permissions)
+        }
+        "licenses" => {
+            let license = License::from(decode(profile.data)?);
+            let permissions = license.permissions.clone();
+            (Bearer::License(Box::new(license)), permissions)
+        }
+        "environments" => {
+            let environment = Environment::from(decode(profile.data)?);
+            (Bearer::Environment(Box::new(environment)), None)
+        }
+        other => {
+            return Err(Error::UnexpectedError(format!(
+                "Unsupported profile bearer type: {other}"
+            )))
+        }
+    };
+
+    Ok(Profile {
+        bearer,
+        bearer_permissions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{reset_config, set_config, KeygenConfig};
+    use mockito::{mock, server_url};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_me_returns_staff_bearer() {
+        let _m = mock("GET", "/v1/me")
+            .match_header("authorization", "Bearer admin-token")
+            .with_status(200)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(
+                json!({
+                    "data": {
+                        "id": "user-1",
+                        "type": "users",
+                        "attributes": {
+                            "email": "admin@example.com",
+                            "firstName": null,
+                            "lastName": null,
+                            "fullName": null,
+                            "status": "ACTIVE",
+                            "role": "read-only",
+                            "permissions": ["license.read", "machine.read"],
+                            "metadata": {},
+                            "lastSeenAt": null,
+                            "banReason": null,
+                            "created": "2024-01-01T00:00:00Z",
+                            "updated": "2024-01-01T00:00:00Z"
+                        },
+                        "relationships": {}
+                    }
+                })
+                .to_string(),
+            )
+            .create();
+
+        let _ = set_config(KeygenConfig {
+            api_url: server_url(),
+            account: "test_account".to_string(),
+            token: Some("admin-token".to_string()),
+            ..Default::default()
+        });
+
+        let profile = me().await.unwrap();
+        assert!(!profile.is_admin());
+        assert!(matches!(
+            &profile.bearer,
+            Bearer::Staff(user) if user.id == "user-1" && user.role == UserRole::ReadOnly
+        ));
+        assert_eq!(profile.can("license.read"), Some(true));
+        assert_eq!(profile.can("license.create"), Some(false));
+
+        let _ = reset_config();
+    }
+
+    #[test]
+    fn test_can_is_unknown_without_bearer_permissions() {
+        let profile = Profile {
+            bearer: Bearer::Environment(Box::new(Environment {
+                id: "env-1".to_string(),
+                name: "Staging".to_string(),
+                code: "staging".to_string(),
+                isolation_strategy: Default::default(),
+                created: "2024-01-01T00:00:00Z".to_string(),
+                updated: "2024-01-01T00:00:00Z".to_string(),
+                account_id: None,
+            })),
+            bearer_permissions: None,
+        };
+        assert_eq!(profile.can("license.create"), None);
+
+        let profile = Profile {
+            bearer_permissions: Some(vec!["*".to_string()]),
+            ..profile
+        };
+        assert_eq!(profile.can("license.create"), Some(true));
+    }
+}
@@ -7,8 +7,6 @@ use crate::KeygenRelationship;
 use crate::KeygenResponseData;
 #[cfg(not(target_arch = "wasm32"))]
 use reqwest::header::HeaderMap;
-#[cfg(not(target_arch = "wasm32"))]
-use reqwest::{redirect::Policy, Client as ReqwestClient};
 use serde::{Deserialize, Serialize, Serializer};
 use std::collections::HashMap;
 
@@ -187,7 +185,7 @@ struct ConstraintsResponse {
 }
 
 /// A release represents a specific version of your software
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Release {
     pub id: String,
     pub name: Option<String>,
@@ -389,15 +387,15 @@ impl Release {
         let path = format!("releases/{}/artifacts/{}", self.id, artifact);
         let request = client.build_request(reqwest::Method::GET, &path, None::<&()>, true)?;
 
-        let no_redirect = ReqwestClient::builder()
-            .redirect(Policy::none())
-            .build()
-            .map_err(|e| Error::UnexpectedError(format!("Failed to build HTTP client: {e}")))?;
+        let no_redirect = client.without_redirects()?;
 
-        let response = no_redirect.execute(request).await?;
+        let trace = client.trace(&request);
+        let response = trace
+            .instrument(client.execute_verified(&no_redirect, request, &trace))
+            .await?;
 
-        if response.status().is_client_error() || response.status().is_server_error() {
-            let body = response.json().await?;
+        if response.status.is_client_error() || response.status.is_server_error() {
+            let body = serde_json::from_slice(&response.body)?;
             return Err(Error::KeygenApiError {
                 code: "DOWNLOAD_FAILED".to_string(),
                 detail: "Failed to download release artifact".to_string(),
@@ -405,7 +403,7 @@ impl Release {
             });
         }
 
-        let headers = response.headers().clone();
+        let headers = response.headers;
         let location = headers
             .get(reqwest::header::LOCATION)
             .and_then(|value| value.to_str().ok())
@@ -686,4 +684,48 @@ mod tests {
         assert!(!query.contains("page"));
         assert!(!query.contains("status"));
     }
+
+    #[cfg(all(feature = "license-key", not(target_arch = "wasm32")))]
+    #[tokio::test]
+    async fn test_download_artifact_rejects_unsigned_redirect_in_strict_mode() {
+        use crate::config::{reset_config, set_config, KeygenConfig, SignatureMode};
+        use mockito::{mock, server_url};
+
+        let _download = mock("GET", "/v1/releases/test-release-id/artifacts/app.dmg")
+            .with_status(303)
+            .with_header("location", "https://attacker.example/app.dmg")
+            .create();
+
+        let _ = set_config(KeygenConfig {
+            api_url: server_url(),
+            account: "test_account".to_string(),
+            token: Some("admin-token".to_string()),
+            public_key: Some("0".repeat(64)),
+            signature_mode: SignatureMode::Strict,
+            ..Default::default()
+        });
+
+        let release = Release::from(KeygenResponseData {
+            id: "test-release-id".to_string(),
+            r#type: "releases".to_string(),
+            attributes: ReleaseAttributes {
+                name: None,
+                description: None,
+                version: "1.0.0".to_string(),
+                semver: None,
+                channel: ReleaseChannel::Stable,
+                status: ReleaseStatus::Published,
+                tag: None,
+                metadata: None,
+                created: "2023-01-01T00:00:00Z".to_string(),
+                updated: "2023-01-01T00:00:00Z".to_string(),
+                yanked_at: None,
+            },
+            relationships: KeygenRelationships::default(),
+        });
+        let result = release.download_artifact("app.dmg").await;
+        assert!(matches!(result, Err(Error::KeygenSignatureMissing)));
+
+        let _ = reset_config();
+    }
 }
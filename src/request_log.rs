@@ -0,0 +1,331 @@
+use chrono::NaiveDate;
+use futures::Stream;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+
+use crate::client::Client;
+use crate::errors::Error;
+use crate::pagination::{page_size, paginate};
+use crate::KeygenResponseData;
+
+/// Keygen reports the response status as a string, e.g. `"200"`
+fn deserialize_status<'de, D>(deserializer: D) -> Result<Option<u16>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Status {
+        Number(u16),
+        Text(String),
+    }
+
+    match Option::<Status>::deserialize(deserializer)? {
+        Some(Status::Number(status)) => Ok(Some(status)),
+        Some(Status::Text(status)) => status.parse().map(Some).map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestLogAttributes {
+    pub url: Option<String>,
+    pub method: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_status")]
+    pub status: Option<u16>,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+    pub request_headers: Option<HashMap<String, serde_json::Value>>,
+    pub request_body: Option<String>,
+    pub response_headers: Option<HashMap<String, serde_json::Value>>,
+    pub response_body: Option<String>,
+    pub response_signature: Option<String>,
+    pub created: String,
+    pub updated: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestLog {
+    pub id: String,
+    pub url: Option<String>,
+    pub method: Option<String>,
+    pub status: Option<u16>,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+    pub request_headers: Option<HashMap<String, serde_json::Value>>,
+    /// Request body, if Keygen recorded one
+    pub request_body: Option<String>,
+    pub response_headers: Option<HashMap<String, serde_json::Value>>,
+    /// Response body, if Keygen recorded one
+    pub response_body: Option<String>,
+    pub response_signature: Option<String>,
+    pub created: String,
+    pub updated: String,
+    pub account_id: Option<String>,
+    pub environment_id: Option<String>,
+    /// Type of the bearer that made the request, e.g. `users` or `licenses`
+    pub requestor_type: Option<String>,
+    pub requestor_id: Option<String>,
+    /// Type of the resource the request acted on
+    pub resource_type: Option<String>,
+    pub resource_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RequestLogResponse {
+    pub data: KeygenResponseData<RequestLogAttributes>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RequestLogsResponse {
+    pub data: Vec<KeygenResponseData<RequestLogAttributes>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListRequestLogsOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(rename = "page[size]", skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+    #[serde(rename = "page[number]", skip_serializing_if = "Option::is_none")]
+    pub page_number: Option<u32>,
+    /// Only include requests on or after this date
+    #[serde(rename = "date[start]", skip_serializing_if = "Option::is_none")]
+    pub date_start: Option<NaiveDate>,
+    /// Only include requests on or before this date
+    #[serde(rename = "date[end]", skip_serializing_if = "Option::is_none")]
+    pub date_end: Option<NaiveDate>,
+    #[serde(rename = "requestor[type]", skip_serializing_if = "Option::is_none")]
+    pub requestor_type: Option<String>,
+    #[serde(rename = "requestor[id]", skip_serializing_if = "Option::is_none")]
+    pub requestor_id: Option<String>,
+    #[serde(rename = "resource[type]", skip_serializing_if = "Option::is_none")]
+    pub resource_type: Option<String>,
+    #[serde(rename = "resource[id]", skip_serializing_if = "Option::is_none")]
+    pub resource_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+}
+
+impl RequestLog {
+    pub(crate) fn from(data: KeygenResponseData<RequestLogAttributes>) -> RequestLog {
+        let requestor = data.relationships.other_relationship("requestor");
+        let resource = data.relationships.other_relationship("resource");
+        RequestLog {
+            id: data.id,
+            url: data.attributes.url,
+            method: data.attributes.method,
+            status: data.attributes.status,
+            user_agent: data.attributes.user_agent,
+            ip: data.attributes.ip,
+            request_headers: data.attributes.request_headers,
+            request_body: data.attributes.request_body,
+            response_headers: data.attributes.response_headers,
+            response_body: data.attributes.response_body,
+            response_signature: data.attributes.response_signature,
+            created: data.attributes.created,
+            updated: data.attributes.updated,
+            account_id: data.relationships.account_id(),
+            environment_id: data.relationships.environment_id(),
+            requestor_type: requestor.as_ref().map(|r| r.r#type.clone()),
+            requestor_id: requestor.map(|r| r.id),
+            resource_type: resource.as_ref().map(|r| r.r#type.clone()),
+            resource_id: resource.map(|r| r.id),
+        }
+    }
+
+    /// List request logs with optional filtering and pagination
+    pub async fn list(options: Option<ListRequestLogsOptions>) -> Result<Vec<RequestLog>, Error> {
+        let client = Client::from_global_config()?;
+        let response = client.get("request-logs", options.as_ref()).await?;
+        let request_logs_response: RequestLogsResponse = serde_json::from_value(response.body)?;
+        Ok(request_logs_response
+            .data
+            .into_iter()
+            .map(RequestLog::from)
+            .collect())
+    }
+
+    /// Stream every request log matching the filters, one page at a time.
+    ///
+    /// `page_size` defaults to the maximum of 100 and is clamped to 1..=100;
+    /// `limit` and `page_number` are ignored.
+    pub fn list_all(
+        options: ListRequestLogsOptions,
+    ) -> impl Stream<Item = Result<RequestLog, Error>> {
+        let page_size = page_size(options.page_size);
+        paginate(page_size, move |page_number| {
+            let options = ListRequestLogsOptions {
+                limit: None,
+                page_size: Some(page_size),
+                page_number: Some(page_number),
+                ..options.clone()
+            };
+            RequestLog::list(Some(options))
+        })
+    }
+
+    /// Get a request log by ID
+    pub async fn get(id: &str) -> Result<RequestLog, Error> {
+        let client = Client::from_global_config()?;
+        let endpoint = format!("request-logs/{id}");
+        let response = client.get(&endpoint, None::<&()>).await?;
+        let request_log_response: RequestLogResponse = serde_json::from_value(response.body)?;
+        Ok(RequestLog::from(request_log_response.data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{reset_config, set_config, KeygenConfig};
+    use futures::TryStreamExt;
+    use mockito::{mock, server_url, Matcher};
+    use serde_json::json;
+
+    fn request_log_json(id: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "type": "request-logs",
+            "attributes": {
+                "url": "/v1/accounts/test/licenses/actions/validate-key",
+                "method": "POST",
+                "status": "200",
+                "userAgent": "keygen-rs",
+                "ip": "203.0.113.7",
+                "requestHeaders": null,
+                "requestBody": "{\"meta\":{\"key\":\"KEY\"}}",
+                "responseHeaders": null,
+                "responseBody": null,
+                "responseSignature": null,
+                "created": "2024-01-01T00:00:00Z",
+                "updated": "2024-01-01T00:00:00Z"
+            },
+            "relationships": {
+                "account": {"data": {"type": "accounts", "id": "account-1"}},
+                "requestor": {"data": {"type": "licenses", "id": "license-1"}},
+                "resource": {"data": {"type": "licenses", "id": "license-1"}}
+            }
+        })
+    }
+
+    #[test]
+    fn test_list_request_logs_options_serialization() {
+        let options = ListRequestLogsOptions {
+            date_start: Some(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
+            date_end: Some(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap()),
+            requestor_type: Some("users".to_string()),
+            requestor_id: Some("user-1".to_string()),
+            status: Some(422),
+            ..Default::default()
+        };
+        let query = serde_urlencoded::to_string(&options).unwrap();
+        assert_eq!(
+            query,
+            "date%5Bstart%5D=2024-01-01&date%5Bend%5D=2024-01-31&requestor%5Btype%5D=users&requestor%5Bid%5D=user-1&status=422"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_all_request_logs_streams_pages() {
+        let _page_1 = mock("GET", "/v1/request-logs")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("page[size]".into(), "2".into()),
+                Matcher::UrlEncoded("page[number]".into(), "1".into()),
+                Matcher::UrlEncoded("method".into(), "POST".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(
+                json!({"data": [request_log_json("log-1"), request_log_json("log-2")]}).to_string(),
+            )
+            .create();
+        let _page_2 = mock("GET", "/v1/request-logs")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("page[size]".into(), "2".into()),
+                Matcher::UrlEncoded("page[number]".into(), "2".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(json!({"data": [request_log_json("log-3")]}).to_string())
+            .create();
+
+        let _ = set_config(KeygenConfig {
+            api_url: server_url(),
+            account: "test_account".to_string(),
+            token: Some("admin-token".to_string()),
+            ..Default::default()
+        });
+
+        let logs: Vec<RequestLog> = RequestLog::list_all(ListRequestLogsOptions {
+            page_size: Some(2),
+            method: Some("POST".to_string()),
+            ..Default::default()
+        })
+        .try_collect()
+        .await
+        .unwrap();
+
+        let ids: Vec<&str> = logs.iter().map(|log| log.id.as_str()).collect();
+        assert_eq!(ids, vec!["log-1", "log-2", "log-3"]);
+        assert_eq!(logs[0].status, Some(200));
+        assert_eq!(logs[0].requestor_type.as_deref(), Some("licenses"));
+        assert_eq!(logs[0].resource_id.as_deref(), Some("license-1"));
+        assert_eq!(logs[0].account_id.as_deref(), Some("account-1"));
+
+        let _ = reset_config();
+    }
+
+    #[tokio::test]
+    async fn test_list_all_request_logs_clamps_zero_page_size() {
+        let _page_1 = mock("GET", "/v1/request-logs")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("page[size]".into(), "1".into()),
+                Matcher::UrlEncoded("page[number]".into(), "1".into()),
+                Matcher::UrlEncoded("method".into(), "DELETE".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(json!({"data": [request_log_json("log-1")]}).to_string())
+            .create();
+        let _page_2 = mock("GET", "/v1/request-logs")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("page[size]".into(), "1".into()),
+                Matcher::UrlEncoded("page[number]".into(), "2".into()),
+                Matcher::UrlEncoded("method".into(), "DELETE".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(json!({"data": []}).to_string())
+            .expect(1)
+            .create();
+
+        let _ = set_config(KeygenConfig {
+            api_url: server_url(),
+            account: "test_account".to_string(),
+            token: Some("admin-token".to_string()),
+            ..Default::default()
+        });
+
+        let logs: Vec<RequestLog> = RequestLog::list_all(ListRequestLogsOptions {
+            page_size: Some(0),
+            method: Some("DELETE".to_string()),
+            ..Default::default()
+        })
+        .try_collect()
+        .await
+        .unwrap();
+
+        assert_eq!(logs.len(), 1);
+        _page_2.assert();
+
+        let _ = reset_config();
+    }
+}
@@ -0,0 +1,281 @@
+use crate::client::Client;
+use crate::errors::Error;
+use crate::KeygenResponseData;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecondFactorAttributes {
+    /// TOTP provisioning URI, only present on creation
+    pub uri: Option<String>,
+    pub enabled: bool,
+    pub created: String,
+    pub updated: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecondFactor {
+    pub id: String,
+    /// TOTP provisioning URI (`otpauth://...`), only present on creation.
+    /// Render it as a QR code for an authenticator app.
+    pub uri: Option<String>,
+    pub enabled: bool,
+    pub created: String,
+    pub updated: String,
+    pub user_id: Option<String>,
+    pub account_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SecondFactorResponse {
+    pub data: KeygenResponseData<SecondFactorAttributes>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SecondFactorsResponse {
+    pub data: Vec<KeygenResponseData<SecondFactorAttributes>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateSecondFactorRequest {
+    pub enabled: bool,
+    /// Current one-time password from the authenticator app
+    pub otp: String,
+}
+
+impl SecondFactor {
+    pub(crate) fn from(data: KeygenResponseData<SecondFactorAttributes>) -> SecondFactor {
+        SecondFactor {
+            id: data.id,
+            uri: data.attributes.uri,
+            enabled: data.attributes.enabled,
+            created: data.attributes.created,
+            updated: data.attributes.updated,
+            user_id: data.relationships.other_relationship("user").map(|d| d.id),
+            account_id: data.relationships.account_id(),
+        }
+    }
+
+    fn user_id(&self) -> Result<&str, Error> {
+        self.user_id
+            .as_deref()
+            .ok_or_else(|| Error::UnexpectedError("Second factor has no user".to_string()))
+    }
+
+    /// Create a second factor for a user. The user's password is required.
+    ///
+    /// The second factor is disabled until it is enabled with a valid OTP.
+    pub async fn create(user_id: &str, password: &str) -> Result<SecondFactor, Error> {
+        let client = Client::from_global_config()?;
+        let endpoint = format!("users/{user_id}/second-factors");
+        let body = json!({
+            "meta": {
+                "password": password
+            }
+        });
+        let response = client.post(&endpoint, Some(&body), None::<&()>).await?;
+        let second_factor_response: SecondFactorResponse = serde_json::from_value(response.body)?;
+        Ok(SecondFactor::from(second_factor_response.data))
+    }
+
+    /// List a user's second factors
+    pub async fn list(user_id: &str) -> Result<Vec<SecondFactor>, Error> {
+        let client = Client::from_global_config()?;
+        let endpoint = format!("users/{user_id}/second-factors");
+        let response = client.get(&endpoint, None::<&()>).await?;
+        let second_factors_response: SecondFactorsResponse = serde_json::from_value(response.body)?;
+        Ok(second_factors_response
+            .data
+            .into_iter()
+            .map(SecondFactor::from)
+            .collect())
+    }
+
+    /// Get a user's second factor by ID
+    pub async fn get(user_id: &str, id: &str) -> Result<SecondFactor, Error> {
+        let client = Client::from_global_config()?;
+        let endpoint = format!("users/{user_id}/second-factors/{id}");
+        let response = client.get(&endpoint, None::<&()>).await?;
+        let second_factor_response: SecondFactorResponse = serde_json::from_value(response.body)?;
+        Ok(SecondFactor::from(second_factor_response.data))
+    }
+
+    /// Enable or disable this second factor
+    pub async fn update(&self, request: UpdateSecondFactorRequest) -> Result<SecondFactor, Error> {
+        let client = Client::from_global_config()?;
+        let endpoint = format!("users/{}/second-factors/{}", self.user_id()?, self.id);
+        let body = json!({
+            "data": {
+                "type": "second-factors",
+                "attributes": {
+                    "enabled": request.enabled
+                }
+            },
+            "meta": {
+                "otp": request.otp
+            }
+        });
+        let response = client.patch(&endpoint, Some(&body), None::<&()>).await?;
+        let second_factor_response: SecondFactorResponse = serde_json::from_value(response.body)?;
+        Ok(SecondFactor::from(second_factor_response.data))
+    }
+
+    /// Enable this second factor using an OTP from the authenticator app
+    pub async fn enable(&self, otp: &str) -> Result<SecondFactor, Error> {
+        self.update(UpdateSecondFactorRequest {
+            enabled: true,
+            otp: otp.to_string(),
+        })
+        .await
+    }
+
+    /// Delete this second factor. A current OTP is required.
+    pub async fn delete(&self, otp: &str) -> Result<(), Error> {
+        let client = Client::from_global_config()?;
+        let endpoint = format!("users/{}/second-factors/{}", self.user_id()?, self.id);
+        let body = json!({
+            "meta": {
+                "otp": otp
+            }
+        });
+        client.delete::<_, ()>(&endpoint, Some(&body)).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{reset_config, set_config, KeygenConfig};
+    use mockito::{mock, server_url, Matcher};
+
+    fn second_factor_data(user_id: &str, uri: Option<&str>, enabled: bool) -> serde_json::Value {
+        json!({
+            "id": "second-factor-1",
+            "type": "second-factors",
+            "attributes": {
+                "uri": uri,
+                "enabled": enabled,
+                "created": "2024-01-01T00:00:00Z",
+                "updated": "2024-01-01T00:00:00Z"
+            },
+            "relationships": {
+                "account": {"data": {"type": "accounts", "id": "account-1"}},
+                "user": {"data": {"type": "users", "id": user_id}}
+            }
+        })
+    }
+
+    fn second_factor_json(uri: Option<&str>, enabled: bool) -> serde_json::Value {
+        json!({"data": second_factor_data("user-1", uri, enabled)})
+    }
+
+    fn set_test_config() {
+        let _ = set_config(KeygenConfig {
+            api_url: server_url(),
+            account: "test_account".to_string(),
+            token: Some("admin-token".to_string()),
+            ..Default::default()
+        });
+    }
+
+    #[tokio::test]
+    async fn test_create_and_enable_second_factor() {
+        let uri = "otpauth://totp/Keygen:admin@example.com?secret=ABC&issuer=Keygen";
+        let _create = mock("POST", "/v1/users/user-1/second-factors")
+            .match_body(Matcher::Json(json!({"meta": {"password": "secret"}})))
+            .with_status(201)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(second_factor_json(Some(uri), false).to_string())
+            .create();
+        let _enable = mock("PATCH", "/v1/users/user-1/second-factors/second-factor-1")
+            .match_body(Matcher::Json(json!({
+                "data": {"type": "second-factors", "attributes": {"enabled": true}},
+                "meta": {"otp": "123456"}
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(second_factor_json(None, true).to_string())
+            .create();
+
+        set_test_config();
+
+        let second_factor = SecondFactor::create("user-1", "secret").await.unwrap();
+        assert_eq!(second_factor.uri.as_deref(), Some(uri));
+        assert!(!second_factor.enabled);
+        assert_eq!(second_factor.user_id.as_deref(), Some("user-1"));
+        assert_eq!(second_factor.account_id.as_deref(), Some("account-1"));
+
+        let enabled = second_factor.enable("123456").await.unwrap();
+        assert!(enabled.enabled);
+        assert!(enabled.uri.is_none());
+
+        let _ = reset_config();
+    }
+
+    #[tokio::test]
+    async fn test_list_and_get_second_factors() {
+        let _list = mock("GET", "/v1/users/user-2/second-factors")
+            .with_status(200)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(json!({"data": [second_factor_data("user-2", None, true)]}).to_string())
+            .create();
+        let _get = mock("GET", "/v1/users/user-2/second-factors/second-factor-1")
+            .with_status(200)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(json!({"data": second_factor_data("user-2", None, true)}).to_string())
+            .create();
+
+        set_test_config();
+
+        let second_factors = SecondFactor::list("user-2").await.unwrap();
+        assert_eq!(second_factors.len(), 1);
+        assert_eq!(second_factors[0].id, "second-factor-1");
+        assert!(second_factors[0].enabled);
+        assert!(second_factors[0].uri.is_none());
+
+        let second_factor = SecondFactor::get("user-2", "second-factor-1")
+            .await
+            .unwrap();
+        assert!(second_factor.enabled);
+        assert_eq!(second_factor.user_id.as_deref(), Some("user-2"));
+
+        let _ = reset_config();
+    }
+
+    #[tokio::test]
+    async fn test_disable_and_delete_second_factor() {
+        let _disable = mock("PATCH", "/v1/users/user-3/second-factors/second-factor-1")
+            .match_body(Matcher::Json(json!({
+                "data": {"type": "second-factors", "attributes": {"enabled": false}},
+                "meta": {"otp": "654321"}
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(json!({"data": second_factor_data("user-3", None, false)}).to_string())
+            .create();
+        let _delete = mock("DELETE", "/v1/users/user-3/second-factors/second-factor-1")
+            .match_body(Matcher::Json(json!({"meta": {"otp": "987654"}})))
+            .with_status(204)
+            .create();
+
+        set_test_config();
+
+        let second_factor = SecondFactor::from(
+            serde_json::from_value(second_factor_data("user-3", None, true)).unwrap(),
+        );
+        let disabled = second_factor
+            .update(UpdateSecondFactorRequest {
+                enabled: false,
+                otp: "654321".to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(!disabled.enabled);
+
+        disabled.delete("987654").await.unwrap();
+        _delete.assert();
+
+        let _ = reset_config();
+    }
+}
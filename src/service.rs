@@ -1,7 +1,150 @@
 use crate::client::Client;
+use crate::config::{get_config, set_api_version, Deployment, KeygenConfig};
 use crate::errors::Error;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Newest API version this SDK knows how to speak
+pub const LATEST_API_VERSION: ApiVersion = ApiVersion::new(1, 8);
+
+/// A Keygen API version, such as the `1.7` sent in `Keygen-Version` headers.
+/// Versions compare numerically, so `1.10` is newer than `1.8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApiVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl ApiVersion {
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch: 0,
+        }
+    }
+}
+
+impl FromStr for ApiVersion {
+    type Err = Error;
+
+    /// Parse `major[.minor[.patch]]`, with an optional leading `v`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidConfiguration(vec![format!("invalid API version {s:?}")]);
+        let trimmed = s.trim();
+        let trimmed = trimmed.strip_prefix(['v', 'V']).unwrap_or(trimmed);
+        let mut parts = trimmed.split('.').map(|part| {
+            if !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()) {
+                part.parse::<u32>().map_err(|_| invalid())
+            } else {
+                Err(invalid())
+            }
+        });
+
+        let major = parts.next().ok_or_else(invalid)??;
+        let minor = parts.next().transpose()?.unwrap_or(0);
+        let patch = parts.next().transpose()?.unwrap_or(0);
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(ApiVersion {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)?;
+        if self.patch != 0 {
+            write!(f, ".{}", self.patch)?;
+        }
+        Ok(())
+    }
+}
+
+/// API features that only exist from a given API version on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Capability {
+    /// The `code` attribute of products
+    ProductCode,
+    /// Machine processes, `/processes`
+    Processes,
+    /// Machine components, `/components`
+    Components,
+    /// Environments, `/environments`
+    Environments,
+    /// Package engines such as PyPI and Tauri, `/engines`
+    Engines,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 5] = [
+        Capability::ProductCode,
+        Capability::Processes,
+        Capability::Components,
+        Capability::Environments,
+        Capability::Engines,
+    ];
+
+    /// Oldest API version that supports the feature
+    pub fn min_api_version(&self) -> ApiVersion {
+        match self {
+            Capability::Processes => ApiVersion::new(1, 1),
+            Capability::Environments => ApiVersion::new(1, 3),
+            Capability::Components => ApiVersion::new(1, 4),
+            Capability::Engines => ApiVersion::new(1, 5),
+            Capability::ProductCode => ApiVersion::new(1, 8),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Capability::ProductCode => "product codes",
+            Capability::Processes => "processes",
+            Capability::Components => "components",
+            Capability::Environments => "environments",
+            Capability::Engines => "engines",
+        }
+    }
+
+    /// The feature an API path belongs to, judged by its resource segments
+    /// anywhere in the path, so nested routes such as
+    /// `machines/<id>/components` count too
+    pub fn for_path(path: &str) -> Option<Capability> {
+        path.split('/').find_map(|segment| match segment {
+            "processes" => Some(Capability::Processes),
+            "components" => Some(Capability::Components),
+            "environments" => Some(Capability::Environments),
+            "engines" => Some(Capability::Engines),
+            _ => None,
+        })
+    }
+
+    /// Whether `api_version` supports the feature
+    pub fn is_supported_by(&self, api_version: ApiVersion) -> bool {
+        api_version >= self.min_api_version()
+    }
+
+    /// Fail with [`Error::FeatureNotSupported`] unless `api_version` supports
+    /// the feature. An unparsable version is let through, the server decides.
+    pub fn require(&self, api_version: &str) -> Result<(), Error> {
+        match api_version.parse::<ApiVersion>() {
+            Ok(version) if !self.is_supported_by(version) => Err(Error::FeatureNotSupported {
+                feature: self.as_str().to_string(),
+                required: self.min_api_version().to_string(),
+                api_version: version.to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PingResponse {
@@ -22,6 +165,20 @@ pub struct ServiceInfo {
     pub headers: HashMap<String, String>,
 }
 
+impl ServiceInfo {
+    /// The server's API version, when it reported a parsable one
+    pub fn version(&self) -> Option<ApiVersion> {
+        self.api_version.as_deref()?.parse().ok()
+    }
+
+    /// Whether the server supports `capability`. A server that doesn't report
+    /// its version is assumed to be up to date.
+    pub fn supports(&self, capability: Capability) -> bool {
+        self.version()
+            .is_none_or(|version| capability.is_supported_by(version))
+    }
+}
+
 /// Get service information using the /v1/ping endpoint
 /// This can help determine the Keygen.sh service version and capabilities
 pub async fn get_service_info() -> Result<ServiceInfo, Error> {
@@ -61,15 +218,100 @@ pub async fn get_service_info() -> Result<ServiceInfo, Error> {
     })
 }
 
+/// Result of probing the configured API with [`check_compatibility`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Compatibility {
+    pub service: ServiceInfo,
+    /// Deployment the SDK builds request paths for
+    pub deployment: Deployment,
+    /// Problems found, empty when the server matches the configuration
+    pub warnings: Vec<String>,
+}
+
+impl Compatibility {
+    pub fn is_compatible(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+/// Ping the configured API and compare what it reports with the configuration.
+/// Useful at startup to catch a self-hosted server running an older API
+/// version than the SDK requests; each warning is also traced.
+pub async fn check_compatibility() -> Result<Compatibility, Error> {
+    let config = get_config()?;
+    let service = get_service_info().await?;
+    Ok(compatibility(&config, service))
+}
+
+fn compatibility(config: &KeygenConfig, service: ServiceInfo) -> Compatibility {
+    let deployment = config.effective_deployment();
+    let mut warnings = Vec::new();
+
+    match service.api_version.as_deref() {
+        None => warnings.push(format!(
+            "{} did not report its API version; expected {}",
+            config.api_url, config.api_version
+        )),
+        Some(version) if !serves_at_least(version, &config.api_version) => warnings.push(format!(
+            "{} serves API version {version}, older than the {} the SDK requests",
+            config.api_url, config.api_version
+        )),
+        Some(_) => {}
+    }
+
+    #[cfg(feature = "tracing")]
+    for warning in &warnings {
+        crate::trace::trace_event!(warn, deployment = ?deployment, "{warning}");
+    }
+
+    Compatibility {
+        service,
+        deployment,
+        warnings,
+    }
+}
+
+/// Whether the server's version is at least the requested one. A newer
+/// server still answers older versions, so only an older one is a problem.
+fn serves_at_least(server: &str, requested: &str) -> bool {
+    match (
+        server.parse::<ApiVersion>(),
+        requested.parse::<ApiVersion>(),
+    ) {
+        (Ok(server), Ok(requested)) => server >= requested,
+        _ => false,
+    }
+}
+
+/// Ask the server for its API version and lower the global configuration's
+/// `api_version` to it when the server is older. A pinned version is never
+/// raised, and when the server doesn't report a version the configured one is
+/// kept.
+pub async fn negotiate_api_version() -> Result<ApiVersion, Error> {
+    let config = get_config()?;
+    let service = get_service_info().await?;
+    let version = negotiate(service.version(), &config.api_version)?;
+    set_api_version(&version.to_string())?;
+    Ok(version)
+}
+
+/// The newest version the server, the configuration and this SDK all support
+fn negotiate(server: Option<ApiVersion>, configured: &str) -> Result<ApiVersion, Error> {
+    let configured = configured.parse::<ApiVersion>()?;
+    Ok(server
+        .map_or(configured, |server| server.min(configured))
+        .min(LATEST_API_VERSION))
+}
+
 /// Check if the service supports a specific feature by version
 pub fn supports_feature(service_info: &ServiceInfo, required_version: &str) -> bool {
-    if let Some(version) = &service_info.api_version {
-        // Simple version comparison - can be enhanced with semver crate
-        version.as_str() >= required_version
-    } else {
-        // If we can't determine version, assume latest
-        true
-    }
+    let Ok(required) = required_version.parse::<ApiVersion>() else {
+        return false;
+    };
+    // If we can't determine version, assume latest
+    service_info
+        .version()
+        .is_none_or(|version| version >= required)
 }
 
 /// Ping the Keygen service and get basic information
@@ -103,7 +345,7 @@ pub async fn ping() -> Result<PingResponse, Error> {
 /// Check if product code field is supported (requires API v1.8+)
 pub async fn supports_product_code() -> Result<bool, Error> {
     let service_info = get_service_info().await?;
-    Ok(supports_feature(&service_info, "1.8"))
+    Ok(service_info.supports(Capability::ProductCode))
 }
 
 #[cfg(test)]
@@ -122,5 +364,130 @@ mod tests {
         assert!(supports_feature(&service_info, "1.7"));
         assert!(supports_feature(&service_info, "1.8"));
         assert!(!supports_feature(&service_info, "1.9"));
+
+        let service_info = ServiceInfo {
+            api_version: Some("1.10".to_string()),
+            ..service_info
+        };
+        assert!(supports_feature(&service_info, "1.8"));
+        assert!(!supports_feature(&service_info, "2.0"));
+        assert!(service_info.supports(Capability::ProductCode));
+    }
+
+    #[test]
+    fn test_api_version_parsing() {
+        let version: ApiVersion = "1.10".parse().unwrap();
+        assert!(version > "1.8".parse().unwrap());
+        assert_eq!(version, ApiVersion::new(1, 10));
+        assert_eq!("v1.7.2".parse::<ApiVersion>().unwrap().to_string(), "1.7.2");
+        assert_eq!("2".parse::<ApiVersion>().unwrap(), ApiVersion::new(2, 0));
+        for invalid in ["", "1.", "1.x", "1.2.3.4", "-1.0"] {
+            assert!(invalid.parse::<ApiVersion>().is_err(), "{invalid:?}");
+        }
+
+        assert_eq!(
+            negotiate(Some(ApiVersion::new(1, 5)), "1.7").unwrap(),
+            ApiVersion::new(1, 5)
+        );
+        assert_eq!(
+            negotiate(Some(ApiVersion::new(2, 0)), "1.7").unwrap(),
+            ApiVersion::new(1, 7)
+        );
+        assert_eq!(
+            negotiate(Some(ApiVersion::new(2, 0)), "1.9").unwrap(),
+            LATEST_API_VERSION
+        );
+        assert_eq!(negotiate(None, "1.7").unwrap(), ApiVersion::new(1, 7));
+    }
+
+    #[test]
+    fn test_capability_requires_api_version() {
+        assert_eq!(
+            Capability::for_path("environments/abc/tokens"),
+            Some(Capability::Environments)
+        );
+        assert_eq!(
+            Capability::for_path("machines/abc/components"),
+            Some(Capability::Components)
+        );
+        assert_eq!(
+            Capability::for_path("machines/abc/processes"),
+            Some(Capability::Processes)
+        );
+        assert_eq!(Capability::for_path("licenses"), None);
+        assert!(Capability::Components.require("1.7").is_ok());
+        assert!(Capability::ProductCode.require("not a version").is_ok());
+        assert!(matches!(
+            Capability::ProductCode.require("1.7"),
+            Err(Error::FeatureNotSupported { required, .. }) if required == "1.8"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_api_version_with_server() {
+        use crate::config::{reset_config, set_config};
+        use mockito::{mock, server_url};
+
+        async fn negotiate_with(server: Option<&str>, configured: &str) -> ApiVersion {
+            set_config(KeygenConfig {
+                api_url: server_url(),
+                account: "test_account".to_string(),
+                api_version: configured.to_string(),
+                ..KeygenConfig::default()
+            })
+            .unwrap();
+            let mut ping = mock("GET", "/v1/ping").with_status(200).with_body("pong");
+            if let Some(version) = server {
+                ping = ping.with_header("keygen-version", version);
+            }
+            let _m = ping.create();
+
+            let version = negotiate_api_version().await.unwrap();
+            assert_eq!(get_config().unwrap().api_version, version.to_string());
+            version
+        }
+
+        assert_eq!(
+            negotiate_with(Some("1.9"), "1.7").await,
+            ApiVersion::new(1, 7)
+        );
+        assert_eq!(negotiate_with(Some("1.9"), "1.8").await, LATEST_API_VERSION);
+        assert_eq!(
+            negotiate_with(Some("1.5"), "1.7").await,
+            ApiVersion::new(1, 5)
+        );
+        assert_eq!(negotiate_with(None, "1.6").await, ApiVersion::new(1, 6));
+
+        let _ = reset_config();
+    }
+
+    #[test]
+    fn test_compatibility_warns_on_older_server() {
+        let config = KeygenConfig {
+            api_url: "https://licensing.example.com".to_string(),
+            deployment: Some(Deployment::SelfHosted),
+            api_version: "1.7".to_string(),
+            ..KeygenConfig::default()
+        };
+        let service = |api_version: Option<&str>| ServiceInfo {
+            timestamp: None,
+            api_version: api_version.map(str::to_string),
+            message: None,
+            headers: HashMap::new(),
+        };
+
+        let result = compatibility(&config, service(Some("1.7")));
+        assert!(result.is_compatible());
+        assert_eq!(result.deployment, Deployment::SelfHosted);
+
+        let result = compatibility(&config, service(Some("1.8")));
+        assert!(result.is_compatible());
+
+        let result = compatibility(&config, service(Some("1.6")));
+        assert!(!result.is_compatible());
+        assert!(result.warnings[0].contains("serves API version 1.6, older than the 1.7"));
+
+        let result = compatibility(&config, service(None));
+        assert!(result.warnings[0].contains("did not report its API version"));
     }
 }
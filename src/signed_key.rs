@@ -0,0 +1,283 @@
+//! Typed payloads of signed license keys.
+//!
+//! Keys issued under an `ED25519_SIGN` policy embed a dataset rendered from the
+//! policy's key template. [`verify_as`](crate::verify_as) checks the signature
+//! and decodes that dataset into any `DeserializeOwned` type; [`SignedKeyPayload`]
+//! covers Keygen's default template, plus an optional top-level `entitlements`
+//! list, so key-only licensing can be enforced fully offline.
+//!
+//! # Example
+//! ```no_run
+//! use keygen_rs::license::SchemeCode;
+//! use keygen_rs::signed_key::SignedKeyPayload;
+//!
+//! let payload: SignedKeyPayload =
+//!     keygen_rs::verify_as(SchemeCode::Ed25519Sign, "key/...").expect("Key is not genuine");
+//! if payload.is_expired() || !payload.has_entitlements(&["PRO"]) {
+//!     println!("Upgrade required");
+//! }
+//! ```
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+
+/// Dataset of a signed key rendered from Keygen's default key template.
+/// Fields a custom template adds are kept in `extra`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SignedKeyPayload {
+    #[serde(default)]
+    pub account: Option<SignedKeyResource>,
+    #[serde(default)]
+    pub product: Option<SignedKeyResource>,
+    #[serde(default)]
+    pub policy: Option<SignedKeyResource>,
+    #[serde(default)]
+    pub user: Option<SignedKeyResource>,
+    #[serde(default)]
+    pub license: Option<SignedKeyLicense>,
+    /// Entitlement codes, written by the template either as strings or as
+    /// objects with a `code`
+    #[serde(default, deserialize_with = "deserialize_entitlements")]
+    pub entitlements: Vec<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A resource referenced by the payload, such as `product` or `user`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SignedKeyResource {
+    #[serde(default, deserialize_with = "deserialize_blank_as_none")]
+    pub id: Option<String>,
+    #[serde(flatten)]
+    pub attributes: Map<String, Value>,
+}
+
+/// The `license` section of the payload.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SignedKeyLicense {
+    #[serde(default, deserialize_with = "deserialize_blank_as_none")]
+    pub id: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_blank_as_none")]
+    pub created: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "deserialize_blank_as_none")]
+    pub expiry: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub attributes: Map<String, Value>,
+}
+
+impl SignedKeyPayload {
+    /// The ID of the license the key was issued for
+    pub fn license_id(&self) -> Option<&str> {
+        self.license.as_ref()?.id.as_deref()
+    }
+
+    /// The ID of the product the license belongs to
+    pub fn product_id(&self) -> Option<&str> {
+        self.product.as_ref()?.id.as_deref()
+    }
+
+    /// When the license expires, or `None` if it never does
+    pub fn expiry(&self) -> Option<DateTime<Utc>> {
+        self.license.as_ref()?.expiry
+    }
+
+    /// Whether the license has expired, allowing for the global config's
+    /// `max_clock_drift`. For a payload verified with another config, use
+    /// [`is_expired_with`](Self::is_expired_with).
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_with_drift(max_clock_drift())
+    }
+
+    /// Whether the license has expired, allowing for `config`'s `max_clock_drift`
+    #[cfg(feature = "license-key")]
+    pub fn is_expired_with(&self, config: &crate::config::KeygenConfig) -> bool {
+        self.is_expired_with_drift(clock_drift(config))
+    }
+
+    fn is_expired_with_drift(&self, drift: Duration) -> bool {
+        self.expiry()
+            .is_some_and(|expiry| expiry + drift < Utc::now())
+    }
+
+    /// The codes of the entitlements embedded in the key
+    pub fn entitlement_codes(&self) -> &[String] {
+        &self.entitlements
+    }
+
+    /// Whether the key grants every one of `codes`
+    pub fn has_entitlements<S: AsRef<str>>(&self, codes: &[S]) -> bool {
+        codes
+            .iter()
+            .all(|code| self.entitlements.iter().any(|e| e == code.as_ref()))
+    }
+
+    /// Whether the key was issued for `product`
+    pub fn matches_product(&self, product: &str) -> bool {
+        self.product_id() == Some(product)
+    }
+}
+
+/// The clock drift tolerated by expiry checks, from the global config.
+#[cfg(feature = "license-key")]
+fn max_clock_drift() -> Duration {
+    crate::config::get_config()
+        .ok()
+        .map_or_else(Duration::zero, |config| clock_drift(&config))
+}
+
+#[cfg(feature = "license-key")]
+fn clock_drift(config: &crate::config::KeygenConfig) -> Duration {
+    config
+        .max_clock_drift
+        .map_or_else(Duration::zero, Duration::minutes)
+}
+
+#[cfg(not(feature = "license-key"))]
+fn max_clock_drift() -> Duration {
+    Duration::zero()
+}
+
+/// Key templates render unset values as `""`; treat those like `null`.
+fn deserialize_blank_as_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) if s.is_empty() => Ok(None),
+        Some(value) => serde_json::from_value(value)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+fn deserialize_entitlements<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Entitlement {
+        Code(String),
+        Object { code: String },
+    }
+
+    let entitlements = Option::<Vec<Entitlement>>::deserialize(deserializer)?;
+    Ok(entitlements
+        .unwrap_or_default()
+        .into_iter()
+        .map(|entitlement| match entitlement {
+            Entitlement::Code(code) | Entitlement::Object { code } => code,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::KeygenConfig;
+    use crate::errors::Error;
+    use crate::license::{License, SchemeCode};
+    use base64::{engine::general_purpose, Engine};
+    use ed25519_dalek::{Signer, SigningKey};
+    use rand::rngs::OsRng;
+    use serde_json::json;
+
+    fn sign(keypair: &SigningKey, payload: &Value) -> String {
+        let encoded = general_purpose::URL_SAFE.encode(payload.to_string());
+        let signing_data = format!("key/{encoded}");
+        let signature = keypair.sign(signing_data.as_bytes());
+        format!(
+            "{signing_data}.{}",
+            general_purpose::URL_SAFE.encode(signature.to_bytes())
+        )
+    }
+
+    #[test]
+    fn test_verify_as_signed_key_payload() {
+        let keypair = SigningKey::generate(&mut OsRng);
+        let key = sign(
+            &keypair,
+            &json!({
+                "account": {"id": "acme"},
+                "product": {"id": "product-1"},
+                "policy": {"id": "policy-1", "duration": 31536000},
+                "user": {"id": "", "email": ""},
+                "license": {
+                    "id": "license-1",
+                    "created": "2024-01-01T00:00:00Z",
+                    "expiry": "2000-01-01T00:00:00Z"
+                },
+                "entitlements": ["PRO", {"code": "SSO"}],
+                "seats": 5
+            }),
+        );
+        let config = KeygenConfig {
+            public_key: Some(hex::encode(keypair.verifying_key().as_bytes())),
+            ..KeygenConfig::default()
+        };
+
+        let payload: SignedKeyPayload = License::from_signed_key(SchemeCode::Ed25519Sign, &key)
+            .with_config(config)
+            .verify_as()
+            .unwrap();
+
+        assert_eq!(payload.license_id(), Some("license-1"));
+        assert!(payload.user.as_ref().unwrap().id.is_none());
+        assert!(payload.is_expired());
+        assert!(payload.matches_product("product-1"));
+        assert!(!payload.matches_product("product-2"));
+        assert_eq!(payload.entitlement_codes(), ["PRO", "SSO"]);
+        assert!(payload.has_entitlements(&["SSO", "PRO"]));
+        assert!(!payload.has_entitlements(&["PRO", "AUDIT"]));
+        assert_eq!(payload.extra["seats"], 5);
+    }
+
+    #[test]
+    fn test_is_expired_allows_clock_drift() {
+        let payload = SignedKeyPayload {
+            license: Some(SignedKeyLicense {
+                expiry: Some(Utc::now() - Duration::minutes(2)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert!(!payload.is_expired_with_drift(Duration::minutes(5)));
+        assert!(payload.is_expired_with_drift(Duration::minutes(1)));
+        assert!(payload.is_expired_with_drift(Duration::zero()));
+
+        let config = KeygenConfig {
+            max_clock_drift: Some(5),
+            ..Default::default()
+        };
+        assert!(!payload.is_expired_with(&config));
+        let config = KeygenConfig {
+            max_clock_drift: Some(1),
+            ..Default::default()
+        };
+        assert!(payload.is_expired_with(&config));
+    }
+
+    #[test]
+    fn test_verify_as_rejects_forged_key() {
+        let keypair = SigningKey::generate(&mut OsRng);
+        let other = SigningKey::generate(&mut OsRng);
+        let key = sign(
+            &other,
+            &json!({"license": {"id": "license-1", "expiry": null}}),
+        );
+        let config = KeygenConfig {
+            public_key: Some(hex::encode(keypair.verifying_key().as_bytes())),
+            ..KeygenConfig::default()
+        };
+
+        let result = License::from_signed_key(SchemeCode::Ed25519Sign, &key)
+            .with_config(config)
+            .verify_as::<SignedKeyPayload>();
+
+        assert!(matches!(result, Err(Error::LicenseKeyNotGenuine)));
+    }
+}
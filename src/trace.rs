@@ -0,0 +1,331 @@
+//! Optional `tracing` instrumentation.
+//!
+//! With the `tracing` feature enabled, API calls are wrapped in a
+//! `keygen.request` span and licensing flows emit events through the
+//! [`tracing`](https://docs.rs/tracing) crate. Without it, everything in this
+//! module compiles to nothing. License keys and tokens are redacted from
+//! recorded paths; certificates and decrypted file contents are never logged.
+//! The same request hooks feed [`crate::metrics`] when that feature is on.
+
+use crate::config::is_uuid;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+use std::future::Future;
+
+/// Emit a `tracing` event when the feature is enabled; a no-op otherwise.
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        ::tracing::$level!(target: "keygen", $($arg)+);
+    }};
+}
+pub(crate) use trace_event;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Replace every occurrence of the given secrets in `text`.
+pub(crate) fn redact(text: &str, secrets: &[Option<&str>]) -> String {
+    secrets
+        .iter()
+        .flatten()
+        .filter(|secret| !secret.is_empty())
+        .fold(text.to_string(), |text, secret| {
+            text.replace(secret, REDACTED)
+        })
+}
+
+/// Redact the segment after `licenses/` unless it is a license ID, since
+/// licenses can also be addressed by key, e.g. `licenses/<key>/actions/validate`.
+pub(crate) fn redact_license_keys(path: &str) -> String {
+    let mut after_licenses = false;
+    path.split('/')
+        .map(|segment| {
+            let key = after_licenses && !matches!(segment, "" | "actions") && !is_uuid(segment);
+            after_licenses = segment == "licenses";
+            if key {
+                REDACTED
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// The span, timing and metric labels for a single API request.
+pub(crate) struct RequestTrace {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "metrics")]
+    method: Method,
+    #[cfg(feature = "metrics")]
+    endpoint: String,
+    #[cfg(all(
+        any(feature = "tracing", feature = "metrics"),
+        not(target_arch = "wasm32")
+    ))]
+    started: std::time::Instant,
+}
+
+impl RequestTrace {
+    /// Open a span for a request. `path` must already be redacted, and
+    /// `relative` is the same path below the API prefix and account.
+    #[cfg_attr(
+        not(all(feature = "tracing", feature = "metrics")),
+        allow(unused_variables)
+    )]
+    pub(crate) fn start(method: &Method, path: &str, relative: &str) -> Self {
+        RequestTrace {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                target: "keygen",
+                "keygen.request",
+                method = %method,
+                path,
+                status = tracing::field::Empty,
+                duration_ms = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+            ),
+            #[cfg(feature = "metrics")]
+            method: method.clone(),
+            #[cfg(feature = "metrics")]
+            endpoint: crate::metrics::endpoint(relative),
+            #[cfg(all(
+                any(feature = "tracing", feature = "metrics"),
+                not(target_arch = "wasm32")
+            ))]
+            started: std::time::Instant::now(),
+        }
+    }
+
+    /// Run `future` inside the request span.
+    pub(crate) fn instrument<F: Future>(&self, future: F) -> impl Future<Output = F::Output> {
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Instrument::instrument(future, self.span.clone())
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            future
+        }
+    }
+
+    /// Record the response status, duration and `X-Request-Id` on the span,
+    /// and report the request to the metrics recorder.
+    #[cfg_attr(
+        not(any(feature = "tracing", feature = "metrics")),
+        allow(unused_variables)
+    )]
+    pub(crate) fn finish(&self, status: StatusCode, headers: &HeaderMap) {
+        #[cfg(any(feature = "tracing", feature = "metrics"))]
+        let elapsed = self.elapsed();
+
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_request(&self.method, &self.endpoint, status, elapsed, headers);
+
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("status", status.as_u16());
+            if let Some(elapsed) = elapsed {
+                self.span.record("duration_ms", elapsed.as_millis() as u64);
+            }
+            if let Some(request_id) = headers.get("x-request-id").and_then(|v| v.to_str().ok()) {
+                self.span.record("request_id", request_id);
+            }
+            let _entered = self.span.enter();
+            if status.is_client_error() || status.is_server_error() {
+                tracing::warn!(target: "keygen", status = status.as_u16(), "request failed");
+            } else {
+                tracing::debug!(target: "keygen", status = status.as_u16(), "request completed");
+            }
+        }
+    }
+
+    /// Record a request that never got a response, e.g. a connection failure
+    /// or timeout. The error itself is not logged since it may carry the URL.
+    #[cfg_attr(
+        not(any(feature = "tracing", feature = "metrics")),
+        allow(unused_variables)
+    )]
+    pub(crate) fn fail(&self, error: &crate::errors::Error) {
+        #[cfg(any(feature = "tracing", feature = "metrics"))]
+        let elapsed = self.elapsed();
+
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_failure(&self.method, &self.endpoint, elapsed);
+        crate::metrics::record_error(error);
+
+        #[cfg(feature = "tracing")]
+        {
+            use crate::errors::ErrorMeta;
+            if let Some(elapsed) = elapsed {
+                self.span.record("duration_ms", elapsed.as_millis() as u64);
+            }
+            let _entered = self.span.enter();
+            tracing::warn!(target: "keygen", code = error.code(), "request failed without a response");
+        }
+    }
+
+    #[cfg(any(feature = "tracing", feature = "metrics"))]
+    fn elapsed(&self) -> Option<std::time::Duration> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Some(self.started.elapsed())
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_replaces_secrets() {
+        let path = "/v1/accounts/acme/licenses/KEY-123/actions/validate";
+        assert_eq!(
+            redact(path, &[Some("KEY-123"), None, Some("")]),
+            "/v1/accounts/acme/licenses/[REDACTED]/actions/validate"
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn test_request_span_records_response_without_secrets() {
+        use crate::client::Client;
+        use crate::config::{reset_config, set_config, KeygenConfig};
+        use mockito::{mock, server_url};
+        use std::collections::HashMap;
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+        #[derive(Default)]
+        struct Fields(HashMap<String, String>);
+
+        impl Visit for Fields {
+            fn record_str(&mut self, field: &Field, value: &str) {
+                self.0.insert(field.name().to_string(), value.to_string());
+            }
+
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0
+                    .insert(field.name().to_string(), format!("{value:?}"));
+            }
+        }
+
+        /// Collects the fields of request spans and of every event
+        #[derive(Clone, Default)]
+        struct Capture {
+            spans: Arc<Mutex<HashMap<Id, HashMap<String, String>>>>,
+            events: Arc<Mutex<Vec<HashMap<String, String>>>>,
+        }
+
+        impl<S: tracing::Subscriber> Layer<S> for Capture {
+            fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _: Context<'_, S>) {
+                if attrs.metadata().name() == "keygen.request" {
+                    let mut fields = Fields::default();
+                    attrs.record(&mut fields);
+                    self.spans.lock().unwrap().insert(id.clone(), fields.0);
+                }
+            }
+
+            fn on_record(&self, id: &Id, values: &Record<'_>, _: Context<'_, S>) {
+                if let Some(span) = self.spans.lock().unwrap().get_mut(id) {
+                    let mut fields = Fields::default();
+                    values.record(&mut fields);
+                    span.extend(fields.0);
+                }
+            }
+
+            fn on_event(&self, event: &tracing::Event<'_>, _: Context<'_, S>) {
+                let mut fields = Fields::default();
+                event.record(&mut fields);
+                self.events.lock().unwrap().push(fields.0);
+            }
+        }
+
+        let _validate = mock("POST", "/v1/licenses/OTHER-TRACE-KEY/actions/validate")
+            .with_status(200)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_header("x-request-id", "req-trace-1")
+            .with_body(r#"{"meta": {"valid": true}}"#)
+            .create();
+        let _get = mock("GET", "/v1/licenses/SECRET-TRACE-KEY")
+            .with_status(404)
+            .with_header("content-type", "application/vnd.api+json")
+            .with_body(r#"{"errors": [{"title": "Not found", "code": "NOT_FOUND"}]}"#)
+            .create();
+
+        let _ = set_config(KeygenConfig {
+            api_url: server_url(),
+            account: "test_account".to_string(),
+            license_key: Some("SECRET-TRACE-KEY".to_string()),
+            token: Some("SECRET-TRACE-TOKEN".to_string()),
+            ..Default::default()
+        });
+
+        let capture = Capture::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+        let client = Client::from_global_config().unwrap();
+        let _: crate::client::Response<serde_json::Value> = client
+            .post(
+                "licenses/OTHER-TRACE-KEY/actions/validate",
+                None::<&()>,
+                None::<&()>,
+            )
+            .await
+            .unwrap();
+        let result: Result<crate::client::Response<serde_json::Value>, _> =
+            client.get("licenses/SECRET-TRACE-KEY", None::<&()>).await;
+        assert!(result.is_err());
+
+        let spans = capture.spans.lock().unwrap();
+        let validate = spans
+            .values()
+            .find(|span| span["method"] == "POST")
+            .expect("request span");
+        assert_eq!(validate["path"], "/v1/licenses/[REDACTED]/actions/validate");
+        assert_eq!(validate["status"], "200");
+        assert_eq!(validate["request_id"], "req-trace-1");
+        assert!(validate.contains_key("duration_ms"));
+
+        let get = spans
+            .values()
+            .find(|span| span["method"] == "GET")
+            .expect("request span");
+        assert_eq!(get["path"], "/v1/licenses/[REDACTED]");
+        assert_eq!(get["status"], "404");
+
+        let events = capture.events.lock().unwrap();
+        assert!(!events.is_empty());
+        for value in spans.values().chain(events.iter()).flat_map(|f| f.values()) {
+            for secret in ["OTHER-TRACE-KEY", "SECRET-TRACE-KEY", "SECRET-TRACE-TOKEN"] {
+                assert!(!value.contains(secret), "{secret} leaked into {value}");
+            }
+        }
+
+        let _ = reset_config();
+    }
+
+    #[test]
+    fn test_redact_license_keys_keeps_ids() {
+        assert_eq!(
+            redact_license_keys("/v1/accounts/acme/licenses/OTHER-KEY/actions/validate"),
+            "/v1/accounts/acme/licenses/[REDACTED]/actions/validate"
+        );
+        assert_eq!(
+            redact_license_keys("/v1/licenses/4f1e9a2c-7b3d-4c5e-9f8a-1b2c3d4e5f60/machines"),
+            "/v1/licenses/4f1e9a2c-7b3d-4c5e-9f8a-1b2c3d4e5f60/machines"
+        );
+        assert_eq!(
+            redact_license_keys("/v1/licenses/actions/validate-key"),
+            "/v1/licenses/actions/validate-key"
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use crate::client::Client;
 use crate::errors::Error;
 use crate::insert_optional;
-use crate::token::{token_request_attributes, CreateTokenRequest, Token, TokenResponse};
+use crate::token::{token_request_body, CreateTokenRequest, Token, TokenResponse};
 use crate::KeygenResponseData;
 use serde::{Deserialize, Serialize, Serializer};
 use std::collections::HashMap;
@@ -137,6 +137,8 @@ pub struct UpdatePasswordRequest {
     pub old_password: Option<String>,
     #[serde(rename = "newPassword")]
     pub new_password: String,
+    /// One-time password, required when the user has a second factor enabled
+    pub otp: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -299,13 +301,7 @@ impl User {
     ) -> Result<Token, Error> {
         let client = Client::from_global_config()?;
         let endpoint = format!("users/{}/tokens", self.id);
-        let attributes = token_request_attributes(request.as_ref())?;
-        let body = serde_json::json!({
-            "data": {
-                "type": "tokens",
-                "attributes": attributes
-            }
-        });
+        let body = token_request_body(request.as_ref())?;
         let response = client.post(&endpoint, Some(&body), None::<&()>).await?;
         let token_response: TokenResponse = serde_json::from_value(response.body)?;
         Ok(Token::from(token_response.data))
@@ -336,6 +332,7 @@ impl User {
             "newPassword".to_string(),
             serde_json::Value::String(request.new_password),
         );
+        insert_optional(&mut meta, "otp", request.otp)?;
         let body = serde_json::json!({ "meta": meta });
         let response = client.post(&endpoint, Some(&body), None::<&()>).await?;
         let user_response: UserResponse = serde_json::from_value(response.body)?;
@@ -343,17 +340,9 @@ impl User {
     }
 
     /// Reset this user's password using a reset token.
+    ///
+    /// See [`password::reset`](crate::password::reset) to reset by email.
     pub async fn reset_password(&self, request: ResetPasswordRequest) -> Result<User, Error> {
-        let client = Client::from_global_config()?;
-        let endpoint = format!("users/{}/actions/reset-password", self.id);
-        let body = serde_json::json!({
-            "meta": {
-                "passwordResetToken": request.password_reset_token,
-                "newPassword": request.new_password
-            }
-        });
-        let response = client.post(&endpoint, Some(&body), None::<&()>).await?;
-        let user_response: UserResponse = serde_json::from_value(response.body)?;
-        Ok(User::from(user_response.data))
+        crate::password::reset(&self.id, request).await
     }
 }
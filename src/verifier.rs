@@ -1,7 +1,8 @@
 //! Cryptographic verification for licenses and API responses.
 //!
 //! This module provides Ed25519 signature verification for license keys,
-//! license files, machine files, and Keygen API response signatures.
+//! license files, machine files, and Keygen API response signatures, against
+//! any of the public keys in a [`Keyring`].
 
 use base64::{engine::general_purpose, Engine};
 use ed25519_dalek::{Signature, Verifier as Ed25519Verifier, VerifyingKey};
@@ -12,11 +13,15 @@ use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use crate::certificate::Certificate;
 use crate::errors::Error;
+use crate::keyring::{Keyring, PublicKey};
 use crate::license::{License, SchemeCode};
 use crate::license_file::LicenseFile;
 use crate::machine_file::MachineFile;
 
-#[allow(dead_code)]
+/// Components a response signature must cover for the body and request to be
+/// authenticated
+const REQUIRED_SIGNED_HEADERS: [&str; 4] = ["(request-target)", "host", "date", "digest"];
+
 struct SignatureComponents {
     keyid: String,
     algorithm: String,
@@ -26,12 +31,15 @@ struct SignatureComponents {
 
 #[derive(Zeroize, ZeroizeOnDrop)]
 pub struct Verifier {
-    public_key: String,
+    keyring: Keyring,
 }
 
 impl Verifier {
-    pub fn new(public_key: String) -> Self {
-        Self { public_key }
+    /// Create a verifier for a single hex public key or a whole [`Keyring`]
+    pub fn new(public_keys: impl Into<Keyring>) -> Self {
+        Self {
+            keyring: public_keys.into(),
+        }
     }
 
     #[must_use = "verification result should be checked"]
@@ -92,12 +100,32 @@ impl Verifier {
         path: &str,
         host: &str,
     ) -> Result<(), Error> {
-        let signature_header = self.get_required_header(headers, "keygen-signature")?;
-        let date_header = self.get_required_header(headers, "keygen-date")?;
-        let digest_header = self.get_required_header(headers, "keygen-digest")?;
-
-        self.verify_digest(digest_header, body)?;
+        let result = self.check_keygen_signature(headers, body, method, path, host);
+        #[cfg(feature = "tracing")]
+        match &result {
+            Ok(()) => tracing::debug!(target: "keygen", "response signature verified"),
+            Err(error) => {
+                tracing::warn!(target: "keygen", %error, "response signature verification failed")
+            }
+        }
+        result
+    }
 
+    fn check_keygen_signature(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+        method: &str,
+        path: &str,
+        host: &str,
+    ) -> Result<(), Error> {
+        let signature_header = headers
+            .get("keygen-signature")
+            .ok_or(Error::KeygenSignatureMissing)?
+            .to_str()
+            .map_err(|_| Error::KeygenSignatureInvalid {
+                reason: "Invalid keygen-signature header".to_string(),
+            })?;
         let signature_components = self.parse_signature_header(signature_header)?;
         if signature_components.algorithm != "ed25519" {
             return Err(Error::KeygenSignatureInvalid {
@@ -105,14 +133,79 @@ impl Verifier {
             });
         }
 
-        let request_target = format!("{} {}", method.to_lowercase(), path);
-        let signing_data = format!(
-            "(request-target): {request_target}\nhost: {host}\ndate: {date_header}\ndigest: {digest_header}"
-        );
+        let signed_headers: Vec<String> = signature_components
+            .headers
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect();
+        if let Some(missing) = REQUIRED_SIGNED_HEADERS
+            .iter()
+            .find(|name| !signed_headers.iter().any(|signed| signed == *name))
+        {
+            return Err(Error::KeygenSignatureInvalid {
+                reason: format!("Signature does not cover {missing}"),
+            });
+        }
 
-        self.verify_ed25519_signature(&signing_data, &signature_components.signature)?;
+        let digest_header = self.get_signed_header(headers, "digest")?;
+        self.verify_digest(digest_header, body)?;
 
-        Ok(())
+        // Rebuild the signing string from exactly the components the signature
+        // declares, in the order it declares them
+        let mut lines = Vec::with_capacity(signed_headers.len());
+        for name in &signed_headers {
+            let value = match name.as_str() {
+                "(request-target)" => format!("{} {}", method.to_lowercase(), path),
+                "host" => host.to_string(),
+                _ => self.get_signed_header(headers, name)?.to_string(),
+            };
+            lines.push(format!("{name}: {value}"));
+        }
+        let signing_data = lines.join("\n");
+
+        if self.keyring.is_empty() {
+            return Err(Error::PublicKeyMissing);
+        }
+        let keys = self.keyring.select(&signature_components.keyid);
+        if keys.is_empty() {
+            return Err(Error::KeygenSignatureInvalid {
+                reason: format!("No public key for keyid: {}", signature_components.keyid),
+            });
+        }
+
+        self.try_keys(keys, |key| {
+            self.verify_ed25519_signature(key, &signing_data, &signature_components.signature)
+        })
+    }
+
+    /// Value of a header named in the signature's `headers=` list. Keygen
+    /// mirrors `Date` and `Digest` in `Keygen-Date` and `Keygen-Digest`, which
+    /// proxies leave alone, so those are preferred when present.
+    fn get_signed_header<'a>(&self, headers: &'a HeaderMap, name: &str) -> Result<&'a str, Error> {
+        let keygen_name = format!("keygen-{name}");
+        match name {
+            "date" | "digest" if headers.contains_key(keygen_name.as_str()) => {
+                self.get_required_header(headers, &keygen_name)
+            }
+            _ => self.get_required_header(headers, name),
+        }
+    }
+
+    /// Run `verify` with each key in turn, succeeding on the first key that
+    /// verifies and otherwise returning the last key's error.
+    fn try_keys<'a, T>(
+        &self,
+        keys: impl IntoIterator<Item = &'a PublicKey>,
+        mut verify: impl FnMut(&PublicKey) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let mut result = Err(Error::PublicKeyMissing);
+        for key in keys {
+            result = verify(key);
+            if result.is_ok() {
+                break;
+            }
+        }
+        result
     }
 
     fn get_required_header<'a>(
@@ -214,10 +307,11 @@ impl Verifier {
 
     fn verify_ed25519_signature(
         &self,
+        key: &PublicKey,
         signing_data: &str,
         signature_bytes: &[u8],
     ) -> Result<(), Error> {
-        let public_key_bytes = self.public_key_bytes()?;
+        let public_key_bytes = key.ed25519_bytes()?;
         let public_key = VerifyingKey::from_bytes(&public_key_bytes).map_err(|_| {
             Error::KeygenSignatureInvalid {
                 reason: "Invalid public key".to_string(),
@@ -239,9 +333,41 @@ impl Verifier {
     }
 
     fn verify_certificate(&self, cert: &Certificate, prefix: &str) -> Result<(), Error> {
+        let result = self.check_certificate(cert, prefix);
+        #[cfg(feature = "tracing")]
+        match &result {
+            Ok(()) => tracing::debug!(
+                target: "keygen",
+                file = prefix,
+                alg = %cert.alg,
+                "file signature verified"
+            ),
+            Err(error) => tracing::warn!(
+                target: "keygen",
+                file = prefix,
+                alg = %cert.alg,
+                %error,
+                "file signature verification failed"
+            ),
+        }
+        result
+    }
+
+    fn check_certificate(&self, cert: &Certificate, prefix: &str) -> Result<(), Error> {
+        self.try_keys(&self.keyring, |key| {
+            self.check_certificate_with(key, cert, prefix)
+        })
+    }
+
+    fn check_certificate_with(
+        &self,
+        key: &PublicKey,
+        cert: &Certificate,
+        prefix: &str,
+    ) -> Result<(), Error> {
         match cert.alg.as_str() {
             "aes-256-gcm+ed25519" | "base64+ed25519" => {
-                let public_key = self.public_key_bytes()?;
+                let public_key = key.ed25519_bytes()?;
 
                 let msg = format!("{}/{}", prefix, cert.enc).into_bytes();
                 let sig = general_purpose::STANDARD
@@ -263,7 +389,13 @@ impl Verifier {
     }
 
     fn verify_key(&self, key: &str) -> Result<Vec<u8>, Error> {
-        let public_key = self.public_key_bytes()?;
+        self.try_keys(&self.keyring, |public_key| {
+            self.verify_key_with(public_key, key)
+        })
+    }
+
+    fn verify_key_with(&self, public_key: &PublicKey, key: &str) -> Result<Vec<u8>, Error> {
+        let public_key = public_key.ed25519_bytes()?;
 
         let parts: Vec<&str> = key.split('.').collect();
         if parts.len() != 2 {
@@ -299,21 +431,6 @@ impl Verifier {
             Err(Error::LicenseKeyNotGenuine)
         }
     }
-
-    fn public_key_bytes(&self) -> Result<[u8; 32], Error> {
-        if self.public_key.is_empty() {
-            return Err(Error::PublicKeyMissing);
-        }
-
-        let key = hex::decode(&self.public_key).map_err(|_| Error::PublicKeyInvalid)?;
-        if key.len() != 32 {
-            return Err(Error::PublicKeyInvalid);
-        }
-
-        let mut bytes = [0u8; 32];
-        bytes.copy_from_slice(&key);
-        Ok(bytes)
-    }
 }
 
 #[cfg(test)]
@@ -523,6 +640,126 @@ mod tests {
             "/v1/accounts/keygen/licenses?limit=1",
             "api.keygen.sh",
         );
-        assert!(matches!(result, Err(Error::KeygenSignatureInvalid { .. })));
+        assert!(matches!(result, Err(Error::KeygenSignatureMissing)));
+    }
+
+    /// Sign a response to `GET /v1/licenses` the way Keygen does, covering the
+    /// components listed in `signed`
+    fn signed_response_headers(keypair: &SigningKey, keyid: &str, signed: &str) -> HeaderMap {
+        let body = b"test body";
+        let digest = format!(
+            "sha-256={}",
+            general_purpose::STANDARD.encode(Sha256::digest(body))
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "keygen-date",
+            HeaderValue::from_static("Wed, 09 Jun 2021 16:08:15 GMT"),
+        );
+        headers.insert("keygen-digest", HeaderValue::from_str(&digest).unwrap());
+        headers.insert("keygen-environment", HeaderValue::from_static("staging"));
+
+        let signing_data = signed
+            .split(' ')
+            .map(|name| match name {
+                "(request-target)" => format!("{name}: get /v1/licenses"),
+                "host" => format!("{name}: api.keygen.sh"),
+                _ => format!(
+                    "{name}: {}",
+                    headers
+                        .get(format!("keygen-{name}"))
+                        .or_else(|| headers.get(name))
+                        .unwrap()
+                        .to_str()
+                        .unwrap()
+                ),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let signature =
+            general_purpose::STANDARD.encode(keypair.sign(signing_data.as_bytes()).to_bytes());
+        headers.insert(
+            "keygen-signature",
+            HeaderValue::from_str(&format!(
+                r#"keyid="{keyid}", algorithm="ed25519", signature="{signature}", headers="{signed}""#
+            ))
+            .unwrap(),
+        );
+        headers
+    }
+
+    fn verify_response(verifier: &Verifier, headers: &HeaderMap) -> Result<(), Error> {
+        verifier.verify_keygen_signature(
+            headers,
+            b"test body",
+            "GET",
+            "/v1/licenses",
+            "api.keygen.sh",
+        )
+    }
+
+    #[test]
+    fn test_verify_keygen_signature_with_rotated_keys() {
+        let previous = SigningKey::generate(&mut OsRng);
+        let current = SigningKey::generate(&mut OsRng);
+        let other = SigningKey::generate(&mut OsRng);
+        let verifier = Verifier::new(
+            Keyring::new()
+                .with(
+                    PublicKey::ed25519(hex::encode(previous.verifying_key().as_bytes()))
+                        .account("acme"),
+                )
+                .with(
+                    PublicKey::ed25519(hex::encode(current.verifying_key().as_bytes()))
+                        .account("acme"),
+                )
+                .with(
+                    PublicKey::ed25519(hex::encode(other.verifying_key().as_bytes()))
+                        .account("other"),
+                ),
+        );
+        let signed = "(request-target) host date digest";
+
+        assert!(verify_response(
+            &verifier,
+            &signed_response_headers(&previous, "acme", signed)
+        )
+        .is_ok());
+        assert!(verify_response(
+            &verifier,
+            &signed_response_headers(&current, "acme", signed)
+        )
+        .is_ok());
+        assert!(
+            verify_response(&verifier, &signed_response_headers(&other, "other", signed)).is_ok()
+        );
+        // A key is only trusted for the keyid it is labeled with
+        assert!(matches!(
+            verify_response(&verifier, &signed_response_headers(&other, "acme", signed)),
+            Err(Error::KeygenSignatureInvalid { .. })
+        ));
+        assert!(matches!(
+            verify_response(&verifier, &signed_response_headers(&current, "unknown", signed)),
+            Err(Error::KeygenSignatureInvalid { reason }) if reason.contains("No public key")
+        ));
+    }
+
+    #[test]
+    fn test_verify_keygen_signature_uses_declared_headers() {
+        let keypair = SigningKey::generate(&mut OsRng);
+        let verifier = Verifier::new(hex::encode(keypair.verifying_key().as_bytes()));
+
+        let headers = signed_response_headers(
+            &keypair,
+            "acme",
+            "digest date keygen-environment host (request-target)",
+        );
+        assert!(verify_response(&verifier, &headers).is_ok());
+
+        let headers = signed_response_headers(&keypair, "acme", "(request-target) host date");
+        assert!(matches!(
+            verify_response(&verifier, &headers),
+            Err(Error::KeygenSignatureInvalid { reason }) if reason.contains("digest")
+        ));
     }
 }
@@ -4,6 +4,7 @@ use serde_json::{json, Value};
 
 use crate::client::Client;
 use crate::errors::Error;
+use crate::trace::trace_event;
 use crate::KeygenResponseData;
 
 use super::event::{WebhookEventRecord, WebhookEventResponse, WebhookEventsResponse};
@@ -116,7 +117,7 @@ impl WebhookEndpointCreateRequest {
                 .into_iter()
                 .map(|event| {
                     // Convert enum to string representation
-                    serde_json::to_value(&event)
+                    serde_json::to_value(event)
                         .ok()
                         .and_then(|v| v.as_str().map(String::from))
                         .unwrap_or_else(|| "*".to_string())
@@ -190,7 +191,7 @@ impl WebhookEndpointUpdateRequest {
             let subscription_strings: Vec<String> = subscriptions
                 .into_iter()
                 .map(|event| {
-                    serde_json::to_value(&event)
+                    serde_json::to_value(event)
                         .ok()
                         .and_then(|v| v.as_str().map(String::from))
                         .unwrap_or_else(|| "*".to_string())
@@ -350,6 +351,7 @@ impl WebhookEndpoint {
     #[cfg(feature = "token")]
     pub async fn retry_event(&self, event_id: &str) -> Result<WebhookEventRecord, Error> {
         let client = Client::from_global_config()?;
+        trace_event!(info, event_id, endpoint_id = %self.id, "retrying webhook event");
         let endpoint = format!("webhook-events/{}/actions/retry", event_id);
         let response = client.post(&endpoint, None::<&()>, None::<&()>).await?;
 
@@ -566,15 +568,15 @@ mod tests {
     #[test]
     fn test_webhook_event_serialization() {
         let event = WebhookEvent::LicenseCreated;
-        let json = serde_json::to_value(&event).unwrap();
+        let json = serde_json::to_value(event).unwrap();
         assert_eq!(json, "license.created");
 
         let event = WebhookEvent::MachineHeartbeatPing;
-        let json = serde_json::to_value(&event).unwrap();
+        let json = serde_json::to_value(event).unwrap();
         assert_eq!(json, "machine.heartbeat.ping");
 
         let event = WebhookEvent::All;
-        let json = serde_json::to_value(&event).unwrap();
+        let json = serde_json::to_value(event).unwrap();
         assert_eq!(json, "*");
     }
 
@@ -4,6 +4,9 @@ use serde_json::Value;
 
 use crate::client::Client;
 use crate::errors::Error;
+use crate::trace::trace_event;
+use crate::webhook::event_types::WebhookEvent;
+use crate::webhook::payload::WebhookPayload;
 use crate::KeygenResponseData;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +59,25 @@ pub struct WebhookEventRecord {
     pub account_id: Option<String>,
 }
 
+/// A webhook event together with its decoded payload
+#[derive(Debug, Clone)]
+pub struct WebhookDelivery {
+    pub record: WebhookEventRecord,
+    pub event: Option<WebhookEvent>,
+    pub payload: WebhookPayload,
+}
+
+impl WebhookDelivery {
+    pub fn from_record(record: WebhookEventRecord) -> Result<WebhookDelivery, Error> {
+        let payload = record.decode_payload()?;
+        Ok(WebhookDelivery {
+            event: record.event_type(),
+            record,
+            payload,
+        })
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct WebhookEventListOptions {
     pub limit: Option<i32>,
@@ -85,6 +107,22 @@ impl WebhookEventRecord {
         }
     }
 
+    /// Parse an incoming webhook delivery body into an event record
+    pub fn from_delivery(body: &[u8]) -> Result<WebhookEventRecord, Error> {
+        let event_response: WebhookEventResponse = serde_json::from_slice(body)?;
+        Ok(WebhookEventRecord::from(event_response.data))
+    }
+
+    /// The typed event, or `None` for event names this crate doesn't know about
+    pub fn event_type(&self) -> Option<WebhookEvent> {
+        self.event.parse().ok()
+    }
+
+    /// Decode the payload into the resource type implied by the event name
+    pub fn decode_payload(&self) -> Result<WebhookPayload, Error> {
+        WebhookPayload::decode(&self.event, &self.payload)
+    }
+
     /// List all webhook events
     #[cfg(feature = "token")]
     pub async fn list(
@@ -134,6 +172,7 @@ impl WebhookEventRecord {
     #[cfg(feature = "token")]
     pub async fn retry(&self) -> Result<WebhookEventRecord, Error> {
         let client = Client::from_global_config()?;
+        trace_event!(info, event_id = %self.id, event = %self.event, "retrying webhook event");
         let endpoint = format!("webhook-events/{}/actions/retry", self.id);
         let response = client.post(&endpoint, None::<&()>, None::<&()>).await?;
         let event_response: WebhookEventResponse = serde_json::from_value(response.body)?;
@@ -377,4 +416,92 @@ mod tests {
         assert!(!delivering_event.is_failed());
         assert!(delivering_event.is_pending());
     }
+
+    #[test]
+    fn test_from_delivery_decodes_typed_payload() {
+        let license = json!({
+            "data": {
+                "id": "license-456",
+                "type": "licenses",
+                "attributes": {
+                    "key": "TEST-KEY",
+                    "name": null,
+                    "expiry": "2025-01-01T00:00:00Z",
+                    "status": "EXPIRED",
+                    "metadata": {}
+                },
+                "relationships": {
+                    "policy": {"data": {"type": "policies", "id": "policy-1"}}
+                }
+            }
+        });
+        let body = json!({
+            "data": {
+                "id": "event-789",
+                "type": "webhook-events",
+                "attributes": {
+                    "endpoint": "https://example.com/webhook",
+                    "payload": license.to_string(),
+                    "event": "license.expired",
+                    "status": "DELIVERING",
+                    "lastResponseCode": null,
+                    "lastResponseBody": null,
+                    "created": "2025-01-01T00:00:00Z",
+                    "updated": "2025-01-01T00:00:00Z"
+                },
+                "relationships": {}
+            }
+        });
+
+        let event = WebhookEventRecord::from_delivery(body.to_string().as_bytes()).unwrap();
+        assert_eq!(event.event_type(), Some(WebhookEvent::LicenseExpired));
+
+        match event.decode_payload().unwrap() {
+            WebhookPayload::License(license) => {
+                assert_eq!(license.id, "license-456");
+                assert_eq!(license.status.as_deref(), Some("EXPIRED"));
+                assert_eq!(license.policy.as_deref(), Some("policy-1"));
+            }
+            other => panic!("Expected license payload, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_payload_falls_back_for_untyped_events() {
+        let event = WebhookEventRecord {
+            id: "1".to_string(),
+            endpoint: "https://example.com".to_string(),
+            payload: json!({"data": {"id": "account-1", "type": "accounts"}}),
+            event: "account.updated".to_string(),
+            status: WebhookEventStatus::Delivered,
+            last_response_code: Some(200),
+            last_response_body: None,
+            created: Utc::now(),
+            updated: Utc::now(),
+            account_id: None,
+        };
+
+        let payload = event.decode_payload().unwrap();
+        assert!(matches!(payload, WebhookPayload::Other(_)));
+        assert_eq!(payload.id(), Some("account-1"));
+
+        let unknown = WebhookEventRecord {
+            event: "license.something-new".to_string(),
+            payload: json!({"data": {"id": "key-1", "type": "keys"}}),
+            ..event
+        };
+        assert_eq!(unknown.event_type(), None);
+        assert!(matches!(
+            unknown.decode_payload().unwrap(),
+            WebhookPayload::Other(_)
+        ));
+    }
+
+    #[test]
+    fn test_webhook_event_names() {
+        let event: WebhookEvent = "machine.heartbeat.dead".parse().unwrap();
+        assert_eq!(event, WebhookEvent::MachineHeartbeatDead);
+        assert_eq!(event.to_string(), "machine.heartbeat.dead");
+        assert!("not.an.event".parse::<WebhookEvent>().is_err());
+    }
 }
@@ -1,3 +1,11 @@
 pub mod endpoint;
 pub mod event;
 pub mod event_types;
+pub mod payload;
+pub mod reconcile;
+#[cfg(feature = "webhook-server")]
+pub mod server;
+pub mod store;
+
+/// Boxed error returned by user-supplied webhook handlers
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
@@ -0,0 +1,142 @@
+//! Typed decoding of webhook event payloads.
+//!
+//! Keygen delivers the affected resource as a JSON:API document in the `payload`
+//! attribute of a webhook event. The resource type follows from the event name
+//! (`license.*` carries a license, `machine.*` a machine, and so on), so payloads
+//! can be decoded into the crate's resource types without hand-parsing JSON.
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::artifact::Artifact;
+use crate::component::Component;
+use crate::entitlement::Entitlement;
+use crate::errors::Error;
+use crate::group::Group;
+use crate::license::License;
+use crate::machine::Machine;
+use crate::package::Package;
+use crate::policy::Policy;
+use crate::product::Product;
+use crate::release::Release;
+use crate::second_factor::SecondFactor;
+use crate::token::Token;
+use crate::user::User;
+use crate::KeygenResponseData;
+
+#[derive(Debug, Deserialize)]
+struct PayloadDocument<T> {
+    data: KeygenResponseData<T>,
+}
+
+/// A webhook event payload decoded into the resource it describes.
+#[derive(Debug, Clone)]
+pub enum WebhookPayload {
+    Artifact(Box<Artifact>),
+    Component(Box<Component>),
+    Entitlement(Box<Entitlement>),
+    Group(Box<Group>),
+    License(Box<License>),
+    Machine(Box<Machine>),
+    Package(Box<Package>),
+    Policy(Box<Policy>),
+    Product(Box<Product>),
+    Release(Box<Release>),
+    SecondFactor(Box<SecondFactor>),
+    Token(Box<Token>),
+    User(Box<User>),
+    /// Events without a typed resource (e.g. `account.*`), or payloads whose
+    /// resource type doesn't match the event name.
+    Other(Value),
+}
+
+impl WebhookPayload {
+    /// Decode a payload for the given event name.
+    ///
+    /// The payload may be either the JSON:API document itself or the document
+    /// serialized as a string, which is how Keygen sends it in deliveries.
+    pub fn decode(event: &str, payload: &Value) -> Result<WebhookPayload, Error> {
+        let document = match payload {
+            Value::String(raw) => serde_json::from_str(raw)?,
+            other => other.clone(),
+        };
+
+        let resource = event.split('.').next().unwrap_or_default();
+        let payload = match resource {
+            "artifact" if is_type(&document, "artifacts") => {
+                WebhookPayload::Artifact(decode(document, Artifact::from)?)
+            }
+            "component" if is_type(&document, "components") => {
+                WebhookPayload::Component(decode(document, Component::from)?)
+            }
+            "entitlement" if is_type(&document, "entitlements") => {
+                WebhookPayload::Entitlement(decode(document, Entitlement::from)?)
+            }
+            "group" if is_type(&document, "groups") => {
+                WebhookPayload::Group(decode(document, Group::from)?)
+            }
+            "license" if is_type(&document, "licenses") => {
+                WebhookPayload::License(decode(document, License::from)?)
+            }
+            "machine" if is_type(&document, "machines") => {
+                WebhookPayload::Machine(decode(document, Machine::from)?)
+            }
+            "package" if is_type(&document, "packages") => {
+                WebhookPayload::Package(decode(document, Package::from)?)
+            }
+            "policy" if is_type(&document, "policies") => {
+                WebhookPayload::Policy(decode(document, Policy::from)?)
+            }
+            "product" if is_type(&document, "products") => {
+                WebhookPayload::Product(decode(document, Product::from)?)
+            }
+            "release" if is_type(&document, "releases") => {
+                WebhookPayload::Release(decode(document, Release::from)?)
+            }
+            "second-factor" if is_type(&document, "second-factors") => {
+                WebhookPayload::SecondFactor(decode(document, SecondFactor::from)?)
+            }
+            "token" if is_type(&document, "tokens") => {
+                WebhookPayload::Token(decode(document, Token::from)?)
+            }
+            "user" if is_type(&document, "users") => {
+                WebhookPayload::User(decode(document, User::from)?)
+            }
+            _ => WebhookPayload::Other(document),
+        };
+        Ok(payload)
+    }
+
+    /// The ID of the decoded resource, if any
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            WebhookPayload::Artifact(r) => Some(&r.id),
+            WebhookPayload::Component(r) => Some(&r.id),
+            WebhookPayload::Entitlement(r) => Some(&r.id),
+            WebhookPayload::Group(r) => Some(&r.id),
+            WebhookPayload::License(r) => Some(&r.id),
+            WebhookPayload::Machine(r) => Some(&r.id),
+            WebhookPayload::Package(r) => Some(&r.id),
+            WebhookPayload::Policy(r) => Some(&r.id),
+            WebhookPayload::Product(r) => Some(&r.id),
+            WebhookPayload::Release(r) => Some(&r.id),
+            WebhookPayload::SecondFactor(r) => Some(&r.id),
+            WebhookPayload::Token(r) => Some(&r.id),
+            WebhookPayload::User(r) => Some(&r.id),
+            WebhookPayload::Other(value) => value["data"]["id"].as_str(),
+        }
+    }
+}
+
+fn is_type(document: &Value, resource_type: &str) -> bool {
+    document["data"]["type"] == resource_type
+}
+
+fn decode<T, R>(document: Value, from: fn(KeygenResponseData<T>) -> R) -> Result<Box<R>, Error>
+where
+    T: DeserializeOwned,
+{
+    let document: PayloadDocument<T> = serde_json::from_value(document)?;
+    Ok(Box::new(from(document.data)))
+}
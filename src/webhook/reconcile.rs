@@ -0,0 +1,344 @@
+//! Recovery of webhook deliveries after an outage.
+//!
+//! [`list_failed_events`] collects `FAILING`/`FAILED` events over a time window,
+//! [`retry_events`] asks Keygen to redeliver them with bounded concurrency, and
+//! [`replay_events`] feeds them oldest-first into a local handler, skipping events
+//! a [`WebhookEventStore`] has already recorded as processed.
+//!
+//! # Example
+//! ```no_run
+//! use chrono::{Duration, Utc};
+//! use keygen_rs::webhook::reconcile::{self, ReconcileOptions};
+//! use keygen_rs::webhook::store::MemoryEventStore;
+//!
+//! # async fn run() -> Result<(), keygen_rs::errors::Error> {
+//! let events = reconcile::list_failed_events(&ReconcileOptions {
+//!     since: Some(Utc::now() - Duration::hours(6)),
+//!     ..Default::default()
+//! })
+//! .await?;
+//!
+//! let store = MemoryEventStore::default();
+//! let report = reconcile::replay_events(&events, &store, |delivery| async move {
+//!     println!("Replaying {}", delivery.record.event);
+//!     Ok::<_, keygen_rs::errors::Error>(())
+//! })
+//! .await?;
+//! println!("Replayed {} events", report.replayed.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use std::future::Future;
+
+use crate::errors::Error;
+use crate::pagination::page_size;
+use crate::webhook::event::{
+    WebhookDelivery, WebhookEventListOptions, WebhookEventRecord, WebhookEventStatus,
+};
+use crate::webhook::store::WebhookEventStore;
+use crate::webhook::BoxError;
+
+/// Options for collecting undelivered webhook events
+#[derive(Debug, Clone)]
+pub struct ReconcileOptions {
+    /// Only include events delivered to this webhook endpoint URL
+    pub endpoint: Option<String>,
+    /// Only include events created at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only include events created before this time
+    pub until: Option<DateTime<Utc>>,
+    /// Page size used while listing events, clamped to 1..=100
+    pub page_size: i32,
+}
+
+impl Default for ReconcileOptions {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            since: None,
+            until: None,
+            page_size: 100,
+        }
+    }
+}
+
+/// Outcome of replaying events into a local handler
+#[derive(Debug, Default)]
+pub struct ReplayReport {
+    /// IDs of events passed to the handler successfully
+    pub replayed: Vec<String>,
+    /// IDs of events skipped because the store had already processed them
+    pub skipped: Vec<String>,
+    /// The event that stopped the replay and the handler's error.
+    /// Later events are not replayed so ordering is preserved.
+    pub failed: Option<(String, BoxError)>,
+}
+
+/// List `FAILING` and `FAILED` webhook events in the time window, oldest first
+pub async fn list_failed_events(
+    options: &ReconcileOptions,
+) -> Result<Vec<WebhookEventRecord>, Error> {
+    let mut events = Vec::new();
+    for status in [WebhookEventStatus::Failing, WebhookEventStatus::Failed] {
+        events.extend(list_events_with_status(options, status).await?);
+    }
+    events.sort_by_key(|record| record.created);
+    Ok(events)
+}
+
+async fn list_events_with_status(
+    options: &ReconcileOptions,
+    status: WebhookEventStatus,
+) -> Result<Vec<WebhookEventRecord>, Error> {
+    let page_size = page_size(Some(options.page_size.max(1) as u32)) as i32;
+    let mut events = Vec::new();
+    let mut page_number = 1;
+    loop {
+        let page = WebhookEventRecord::list(Some(&WebhookEventListOptions {
+            page_number: Some(page_number),
+            page_size: Some(page_size),
+            status: Some(status.clone()),
+            ..Default::default()
+        }))
+        .await?;
+        let page_len = page.len();
+
+        let mut reached_window_start = false;
+        for record in page {
+            if options.since.is_some_and(|since| record.created < since) {
+                // Events are listed newest first, so nothing older can match
+                reached_window_start = true;
+                continue;
+            }
+            if options.until.is_some_and(|until| record.created >= until) {
+                continue;
+            }
+            if options
+                .endpoint
+                .as_ref()
+                .is_some_and(|endpoint| record.endpoint != *endpoint)
+            {
+                continue;
+            }
+            events.push(record);
+        }
+
+        if reached_window_start || page_len < page_size as usize {
+            break;
+        }
+        page_number += 1;
+    }
+    Ok(events)
+}
+
+/// Ask Keygen to redeliver events, running at most `concurrency` retries at once.
+///
+/// Returns the retry result for each event ID.
+pub async fn retry_events(
+    events: &[WebhookEventRecord],
+    concurrency: usize,
+) -> Vec<(String, Result<WebhookEventRecord, Error>)> {
+    stream::iter(events)
+        .map(|record| async move { (record.id.clone(), record.retry().await) })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+/// Feed events into a local handler in chronological order.
+///
+/// Events already in `store` are skipped, and each successfully handled event
+/// is recorded there. Replay stops at the first handler error.
+pub async fn replay_events<F, Fut, E>(
+    events: &[WebhookEventRecord],
+    store: &dyn WebhookEventStore,
+    handler: F,
+) -> Result<ReplayReport, Error>
+where
+    F: Fn(WebhookDelivery) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+    E: Into<BoxError>,
+{
+    let mut events: Vec<&WebhookEventRecord> = events.iter().collect();
+    events.sort_by_key(|record| record.created);
+
+    let mut report = ReplayReport::default();
+    for record in events {
+        if store.contains(&record.id).await? {
+            report.skipped.push(record.id.clone());
+            continue;
+        }
+
+        let delivery = WebhookDelivery::from_record(record.clone())?;
+        if let Err(err) = handler(delivery).await {
+            report.failed = Some((record.id.clone(), err.into()));
+            break;
+        }
+
+        store.insert(&record.id).await?;
+        report.replayed.push(record.id.clone());
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{reset_config, set_config, KeygenConfig};
+    use crate::webhook::store::MemoryEventStore;
+    use mockito::{mock, server_url, Matcher};
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    fn event_json(id: &str, status: &str, created: &str) -> serde_json::Value {
+        endpoint_event_json(id, status, created, "https://example.com/webhook")
+    }
+
+    fn endpoint_event_json(
+        id: &str,
+        status: &str,
+        created: &str,
+        endpoint: &str,
+    ) -> serde_json::Value {
+        json!({
+            "id": id,
+            "type": "webhook-events",
+            "attributes": {
+                "endpoint": endpoint,
+                "payload": {"data": {"id": "account-1", "type": "accounts"}},
+                "event": "account.updated",
+                "status": status,
+                "lastResponseCode": 500,
+                "lastResponseBody": null,
+                "created": created,
+                "updated": created
+            },
+            "relationships": {}
+        })
+    }
+
+    fn record(id: &str, created: &str) -> WebhookEventRecord {
+        WebhookEventRecord::from(serde_json::from_value(event_json(id, "FAILED", created)).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_list_failed_events_in_window() {
+        let page = |status: &str, data: Vec<serde_json::Value>| {
+            mock("GET", "/v1/webhook-events")
+                .match_header("authorization", "Bearer admin-token")
+                .match_query(Matcher::UrlEncoded("status".into(), status.into()))
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(json!({ "data": data }).to_string())
+                .create()
+        };
+        let _failed = page(
+            "FAILED",
+            vec![
+                event_json("event-4", "FAILED", "2024-01-03T00:00:00Z"),
+                endpoint_event_json(
+                    "event-3",
+                    "FAILED",
+                    "2024-01-02T12:00:00Z",
+                    "https://example.com/other",
+                ),
+                event_json("event-1", "FAILED", "2023-12-31T00:00:00Z"),
+            ],
+        );
+        let _failing = page(
+            "FAILING",
+            vec![event_json("event-2", "FAILING", "2024-01-02T00:00:00Z")],
+        );
+
+        let _ = set_config(KeygenConfig {
+            api_url: server_url(),
+            account: "test_account".to_string(),
+            token: Some("admin-token".to_string()),
+            ..Default::default()
+        });
+
+        let events = list_failed_events(&ReconcileOptions {
+            endpoint: Some("https://example.com/webhook".to_string()),
+            since: Some("2024-01-01T00:00:00Z".parse().unwrap()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+        let ids: Vec<&str> = events.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["event-2", "event-4"]);
+        drop((_failed, _failing));
+
+        // An oversized page size is clamped, so a full page of 100 still
+        // leads on to the next one
+        let full_page = (0..100)
+            .map(|i| event_json(&format!("full-{i}"), "FAILED", "2024-01-03T00:00:00Z"))
+            .collect();
+        let page_of = |number: &str, data: Vec<serde_json::Value>| {
+            mock("GET", "/v1/webhook-events")
+                .match_query(Matcher::AllOf(vec![
+                    Matcher::UrlEncoded("status".into(), "FAILED".into()),
+                    Matcher::UrlEncoded("page[size]".into(), "100".into()),
+                    Matcher::UrlEncoded("page[number]".into(), number.into()),
+                ]))
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(json!({ "data": data }).to_string())
+                .create()
+        };
+        let _first = page_of("1", full_page);
+        let _second = page_of(
+            "2",
+            vec![event_json("event-5", "FAILED", "2024-01-02T00:00:00Z")],
+        );
+        let _failing = page("FAILING", vec![]);
+
+        let events = list_failed_events(&ReconcileOptions {
+            page_size: 500,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(events.len(), 101);
+        assert_eq!(events[0].id, "event-5");
+
+        let _ = reset_config();
+    }
+
+    #[tokio::test]
+    async fn test_replay_events_skips_processed_and_stops_on_error() {
+        let events = vec![
+            record("event-3", "2024-01-03T00:00:00Z"),
+            record("event-1", "2024-01-01T00:00:00Z"),
+            record("event-2", "2024-01-02T00:00:00Z"),
+            record("event-4", "2024-01-04T00:00:00Z"),
+        ];
+        let store = MemoryEventStore::default();
+        store.insert("event-1").await.unwrap();
+
+        let seen = Mutex::new(Vec::new());
+        let report = replay_events(&events, &store, |delivery| {
+            seen.lock().unwrap().push(delivery.record.id.clone());
+            let id = delivery.record.id;
+            async move {
+                if id == "event-3" {
+                    return Err(Error::UnexpectedError("handler failed".to_string()));
+                }
+                Ok(())
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(*seen.lock().unwrap(), vec!["event-2", "event-3"]);
+        assert_eq!(report.skipped, vec!["event-1"]);
+        assert_eq!(report.replayed, vec!["event-2"]);
+        assert_eq!(report.failed.map(|(id, _)| id), Some("event-3".to_string()));
+        assert!(store.contains("event-2").await.unwrap());
+        assert!(!store.contains("event-3").await.unwrap());
+    }
+}
@@ -0,0 +1,679 @@
+//! Embeddable receiver for Keygen webhook deliveries.
+//!
+//! [`WebhookReceiver`] is a [`tower_service::Service`] that can be mounted in axum
+//! (`post_service(receiver)`) or hyper. Each delivery is verified against the
+//! `Keygen-Signature` header, rejected if its signed date is older than the
+//! receiver's max age, deduplicated by event ID, decoded into a typed
+//! payload and dispatched to the handlers registered for its [`WebhookEvent`].
+//!
+//! # Example
+//! ```no_run
+//! use keygen_rs::webhook::event_types::WebhookEvent;
+//! use keygen_rs::webhook::payload::WebhookPayload;
+//! use keygen_rs::webhook::server::WebhookReceiver;
+//!
+//! let receiver = WebhookReceiver::builder("your-public-key")
+//!     .on(WebhookEvent::LicenseExpired, |delivery| async move {
+//!         if let WebhookPayload::License(license) = delivery.payload {
+//!             println!("License expired: {}", license.id);
+//!         }
+//!         Ok::<_, keygen_rs::errors::Error>(())
+//!     })
+//!     .build();
+//! ```
+
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use http::{HeaderMap, Method, Request, Response, StatusCode, Uri};
+use http_body::Body;
+use http_body_util::BodyExt;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tower_service::Service;
+
+use crate::errors::Error;
+use crate::keyring::Keyring;
+use crate::verifier::Verifier;
+use crate::webhook::event::{WebhookDelivery, WebhookEventRecord};
+use crate::webhook::event_types::WebhookEvent;
+use crate::webhook::store::{MemoryEventStore, WebhookEventStore};
+use crate::webhook::BoxError;
+
+type Handler =
+    Arc<dyn Fn(WebhookDelivery) -> BoxFuture<'static, Result<(), BoxError>> + Send + Sync>;
+type StatusMapper = Arc<dyn Fn(&WebhookError) -> StatusCode + Send + Sync>;
+
+/// Failures while receiving a webhook delivery
+#[derive(Debug)]
+pub enum WebhookError {
+    /// The `Keygen-Signature` or digest didn't verify
+    SignatureInvalid(Error),
+    /// The signed date is missing or outside the receiver's max age, e.g. a
+    /// captured delivery being replayed
+    Stale(Error),
+    /// The body couldn't be read or isn't a webhook event document
+    Malformed(Error),
+    /// The processed event store failed
+    Store(Error),
+    /// A registered handler returned an error
+    Handler(BoxError),
+}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebhookError::SignatureInvalid(e) => write!(f, "Webhook signature invalid: {e}"),
+            WebhookError::Stale(e) => write!(f, "Webhook delivery stale: {e}"),
+            WebhookError::Malformed(e) => write!(f, "Webhook delivery malformed: {e}"),
+            WebhookError::Store(e) => write!(f, "Webhook event store error: {e}"),
+            WebhookError::Handler(e) => write!(f, "Webhook handler failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for WebhookError {}
+
+/// Status codes returned for deliveries that didn't fail
+#[derive(Debug, Clone, Copy)]
+pub struct WebhookResponses {
+    /// Every handler for the event succeeded
+    pub success: StatusCode,
+    /// The event ID was already processed
+    pub duplicate: StatusCode,
+    /// No handler is registered for the event
+    pub unhandled: StatusCode,
+}
+
+impl Default for WebhookResponses {
+    fn default() -> Self {
+        Self {
+            success: StatusCode::OK,
+            duplicate: StatusCode::OK,
+            unhandled: StatusCode::OK,
+        }
+    }
+}
+
+/// Default error mapping. Handler and store failures return 5xx so Keygen retries
+/// the delivery; deliveries that can never succeed return 4xx.
+pub fn default_error_status(error: &WebhookError) -> StatusCode {
+    match error {
+        WebhookError::SignatureInvalid(_) => StatusCode::UNAUTHORIZED,
+        WebhookError::Malformed(_) | WebhookError::Stale(_) => StatusCode::BAD_REQUEST,
+        WebhookError::Store(_) | WebhookError::Handler(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// How far a delivery's signed date may be from the current time, in either
+/// direction to allow for clock drift, unless overridden with
+/// [`WebhookReceiverBuilder::max_age`]
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(5 * 60);
+
+pub struct WebhookReceiverBuilder {
+    keyring: Keyring,
+    host: Option<String>,
+    max_age: Duration,
+    handlers: HashMap<WebhookEvent, Vec<Handler>>,
+    store: Arc<dyn WebhookEventStore>,
+    responses: WebhookResponses,
+    error_status: StatusMapper,
+}
+
+impl WebhookReceiverBuilder {
+    /// Register a handler for an event. Handlers registered for
+    /// [`WebhookEvent::All`] run for every event.
+    pub fn on<F, Fut, E>(mut self, event: WebhookEvent, handler: F) -> Self
+    where
+        F: Fn(WebhookDelivery) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: Into<BoxError>,
+    {
+        let handler: Handler = Arc::new(move |delivery| {
+            let fut = handler(delivery);
+            Box::pin(async move { fut.await.map_err(Into::into) })
+        });
+        self.handlers.entry(event).or_default().push(handler);
+        self
+    }
+
+    /// Override the host used for signature verification, e.g. when running
+    /// behind a reverse proxy that rewrites the `Host` header
+    pub fn host(mut self, host: impl Into<String>) -> Self {
+        self.host = Some(host.into());
+        self
+    }
+
+    /// How far a delivery's signed date may be from the current time before it
+    /// is rejected as stale. The event store only remembers recent event IDs,
+    /// so this is what stops an older captured delivery from being replayed.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Use a custom store for deduplicating events
+    pub fn store(mut self, store: impl WebhookEventStore + 'static) -> Self {
+        self.store = Arc::new(store);
+        self
+    }
+
+    pub fn responses(mut self, responses: WebhookResponses) -> Self {
+        self.responses = responses;
+        self
+    }
+
+    /// Map receiver errors to response status codes
+    pub fn error_status<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&WebhookError) -> StatusCode + Send + Sync + 'static,
+    {
+        self.error_status = Arc::new(f);
+        self
+    }
+
+    pub fn build(self) -> WebhookReceiver {
+        WebhookReceiver {
+            inner: Arc::new(ReceiverInner {
+                keyring: self.keyring,
+                host: self.host,
+                max_age: self.max_age,
+                handlers: self.handlers,
+                store: self.store,
+                responses: self.responses,
+                error_status: self.error_status,
+            }),
+        }
+    }
+}
+
+struct ReceiverInner {
+    keyring: Keyring,
+    host: Option<String>,
+    max_age: Duration,
+    handlers: HashMap<WebhookEvent, Vec<Handler>>,
+    store: Arc<dyn WebhookEventStore>,
+    responses: WebhookResponses,
+    error_status: StatusMapper,
+}
+
+#[derive(Clone)]
+pub struct WebhookReceiver {
+    inner: Arc<ReceiverInner>,
+}
+
+impl WebhookReceiver {
+    /// Create a receiver builder for the account's Ed25519 public key, or a
+    /// [`Keyring`] of keys to accept signatures from
+    pub fn builder(public_keys: impl Into<Keyring>) -> WebhookReceiverBuilder {
+        WebhookReceiverBuilder {
+            keyring: public_keys.into(),
+            host: None,
+            max_age: DEFAULT_MAX_AGE,
+            handlers: HashMap::new(),
+            store: Arc::new(MemoryEventStore::default()),
+            responses: WebhookResponses::default(),
+            error_status: Arc::new(default_error_status),
+        }
+    }
+
+    /// Process a delivery from its raw parts, for frameworks that don't use tower.
+    pub async fn receive(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Response<String> {
+        match self.process(method, uri, headers, body).await {
+            Ok(status) => response(status, String::new()),
+            Err(err) => response((self.inner.error_status)(&err), err.to_string()),
+        }
+    }
+
+    async fn process(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<StatusCode, WebhookError> {
+        let host = self
+            .inner
+            .host
+            .clone()
+            .or_else(|| {
+                headers
+                    .get(http::header::HOST)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string)
+            })
+            .or_else(|| uri.authority().map(|a| a.to_string()))
+            .unwrap_or_default();
+        let path = uri
+            .path_and_query()
+            .map(|p| p.as_str())
+            .unwrap_or_else(|| uri.path());
+
+        Verifier::new(self.inner.keyring.clone())
+            .verify_keygen_signature(headers, body, method.as_str(), path, &host)
+            .map_err(WebhookError::SignatureInvalid)?;
+        self.check_date(headers)?;
+
+        let record = WebhookEventRecord::from_delivery(body).map_err(WebhookError::Malformed)?;
+        let event = record.event_type();
+        let handlers: Vec<&Handler> = event
+            .iter()
+            .chain(std::iter::once(&WebhookEvent::All))
+            .filter_map(|e| self.inner.handlers.get(e))
+            .flatten()
+            .collect();
+        if handlers.is_empty() {
+            return Ok(self.inner.responses.unhandled);
+        }
+
+        // Claim the event before dispatching so a concurrent duplicate is
+        // turned away, and release it if a handler fails so Keygen's retry
+        // gets through
+        let store = &self.inner.store;
+        if !store
+            .insert(&record.id)
+            .await
+            .map_err(WebhookError::Store)?
+        {
+            return Ok(self.inner.responses.duplicate);
+        }
+        let id = record.id.clone();
+        if let Err(err) = self.dispatch(record, &handlers).await {
+            store.remove(&id).await.map_err(WebhookError::Store)?;
+            return Err(err);
+        }
+        Ok(self.inner.responses.success)
+    }
+
+    /// Reject a delivery whose signed date is further than the max age from
+    /// now. Like the signature, this prefers `Keygen-Date` over `Date`.
+    fn check_date(&self, headers: &HeaderMap) -> Result<(), WebhookError> {
+        let date = headers
+            .get("keygen-date")
+            .or_else(|| headers.get(http::header::DATE))
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| {
+                WebhookError::Stale(Error::UnexpectedError("Missing date header".to_string()))
+            })?;
+        let date = DateTime::parse_from_rfc2822(date).map_err(|e| {
+            WebhookError::Stale(Error::UnexpectedError(format!(
+                "Invalid date header {date:?}: {e}"
+            )))
+        })?;
+
+        let age = Utc::now().signed_duration_since(date).abs();
+        if age.to_std().unwrap_or(Duration::MAX) > self.inner.max_age {
+            return Err(WebhookError::Stale(Error::UnexpectedError(format!(
+                "Delivery dated {date} is more than {}s from the current time",
+                self.inner.max_age.as_secs()
+            ))));
+        }
+        Ok(())
+    }
+
+    /// Run every handler for the event. Each one that succeeds is recorded
+    /// under `<event id>/<handler index>`, so a redelivery after a failure
+    /// only runs the handlers that haven't completed yet.
+    async fn dispatch(
+        &self,
+        record: WebhookEventRecord,
+        handlers: &[&Handler],
+    ) -> Result<(), WebhookError> {
+        let store = &self.inner.store;
+        let delivery = WebhookDelivery::from_record(record).map_err(WebhookError::Malformed)?;
+        let keys: Vec<String> = (0..handlers.len())
+            .map(|index| format!("{}/{index}", delivery.record.id))
+            .collect();
+        for (handler, key) in handlers.iter().zip(&keys) {
+            if store.contains(key).await.map_err(WebhookError::Store)? {
+                continue;
+            }
+            handler(delivery.clone())
+                .await
+                .map_err(WebhookError::Handler)?;
+            store.insert(key).await.map_err(WebhookError::Store)?;
+        }
+        for key in &keys {
+            store.remove(key).await.map_err(WebhookError::Store)?;
+        }
+        Ok(())
+    }
+}
+
+fn response(status: StatusCode, body: String) -> Response<String> {
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+    response
+}
+
+impl<B> Service<Request<B>> for WebhookReceiver
+where
+    B: Body + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Response = Response<String>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response<String>, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let receiver = self.clone();
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let body = match body.collect().await {
+                Ok(collected) => collected.to_bytes(),
+                Err(err) => {
+                    let err = WebhookError::Malformed(Error::UnexpectedError(format!(
+                        "Failed to read body: {}",
+                        err.into()
+                    )));
+                    return Ok(response(
+                        (receiver.inner.error_status)(&err),
+                        err.to_string(),
+                    ));
+                }
+            };
+            Ok(receiver
+                .receive(&parts.method, &parts.uri, &parts.headers, &body)
+                .await)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhook::payload::WebhookPayload;
+    use base64::{engine::general_purpose, Engine};
+    use ed25519_dalek::{Signer, SigningKey};
+    use rand::rngs::OsRng;
+    use serde_json::json;
+    use sha2::{Digest, Sha256};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn http_date(date: DateTime<Utc>) -> String {
+        date.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+    }
+
+    fn delivery_body(id: &str, event: &str) -> String {
+        let payload = json!({
+            "data": {
+                "id": "machine-1",
+                "type": "machines",
+                "attributes": {
+                    "fingerprint": "fp-1",
+                    "name": null,
+                    "platform": null,
+                    "hostname": null,
+                    "ip": null,
+                    "cores": null,
+                    "metadata": null,
+                    "requireHeartbeat": true,
+                    "heartbeatStatus": "DEAD",
+                    "heartbeatDuration": 600,
+                    "created": "2024-01-01T00:00:00Z",
+                    "updated": "2024-01-01T00:00:00Z"
+                },
+                "relationships": {}
+            }
+        });
+        json!({
+            "data": {
+                "id": id,
+                "type": "webhook-events",
+                "attributes": {
+                    "endpoint": "https://example.com/webhooks",
+                    "payload": payload.to_string(),
+                    "event": event,
+                    "status": "DELIVERING",
+                    "lastResponseCode": null,
+                    "lastResponseBody": null,
+                    "created": "2024-01-01T00:00:00Z",
+                    "updated": "2024-01-01T00:00:00Z"
+                },
+                "relationships": {}
+            }
+        })
+        .to_string()
+    }
+
+    fn signed_request(keypair: &SigningKey, body: &str) -> Request<String> {
+        signed_request_at(keypair, body, &http_date(Utc::now()))
+    }
+
+    fn signed_request_at(keypair: &SigningKey, body: &str, date: &str) -> Request<String> {
+        let digest = format!(
+            "sha-256={}",
+            general_purpose::STANDARD.encode(Sha256::digest(body.as_bytes()))
+        );
+        let signing_data = format!(
+            "(request-target): post /webhooks\nhost: example.com\ndate: {date}\ndigest: {digest}"
+        );
+        let signature =
+            general_purpose::STANDARD.encode(keypair.sign(signing_data.as_bytes()).to_bytes());
+
+        Request::builder()
+            .method(Method::POST)
+            .uri("/webhooks")
+            .header("host", "example.com")
+            .header("keygen-date", date)
+            .header("keygen-digest", digest)
+            .header(
+                "keygen-signature",
+                format!(
+                    r#"keyid="account", algorithm="ed25519", signature="{signature}", headers="(request-target) host date digest""#
+                ),
+            )
+            .body(body.to_string())
+            .unwrap()
+    }
+
+    fn keypair() -> (SigningKey, String) {
+        let keypair = SigningKey::generate(&mut OsRng);
+        let public_key = hex::encode(keypair.verifying_key().as_bytes());
+        (keypair, public_key)
+    }
+
+    #[tokio::test]
+    async fn test_dispatches_typed_event_once() {
+        let (keypair, public_key) = keypair();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let mut receiver = WebhookReceiver::builder(public_key)
+            .on(WebhookEvent::MachineHeartbeatDead, move |delivery| {
+                let counter = counter.clone();
+                async move {
+                    assert!(matches!(delivery.payload, WebhookPayload::Machine(_)));
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Ok::<_, Error>(())
+                }
+            })
+            .build();
+
+        let body = delivery_body("event-1", "machine.heartbeat.dead");
+        let response = receiver
+            .call(signed_request(&keypair, &body))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = receiver
+            .call(signed_request(&keypair, &body))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_signature() {
+        let (_, public_key) = keypair();
+        let (other, _) = keypair();
+        let mut receiver = WebhookReceiver::builder(public_key).build();
+
+        let body = delivery_body("event-1", "license.expired");
+        let response = receiver.call(signed_request(&other, &body)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_stale_delivery() {
+        let (keypair, public_key) = keypair();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let mut receiver = WebhookReceiver::builder(public_key)
+            .on(WebhookEvent::All, move |_| {
+                let counter = counter.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Ok::<_, Error>(())
+                }
+            })
+            .max_age(Duration::from_secs(60))
+            .build();
+
+        let body = delivery_body("event-5", "license.expired");
+        for date in [
+            "Wed, 09 Jun 2021 16:08:15 GMT".to_string(),
+            http_date(Utc::now() - chrono::Duration::minutes(2)),
+            http_date(Utc::now() + chrono::Duration::minutes(2)),
+        ] {
+            let response = receiver
+                .call(signed_request_at(&keypair, &body, &date))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let date = http_date(Utc::now() - chrono::Duration::seconds(30));
+        let response = receiver
+            .call(signed_request_at(&keypair, &body, &date))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_handler_is_retried() {
+        let (keypair, public_key) = keypair();
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = attempts.clone();
+        let mut receiver = WebhookReceiver::builder(public_key)
+            .on(WebhookEvent::All, move |_| {
+                let counter = counter.clone();
+                async move {
+                    if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                        return Err(Error::UnexpectedError("database down".to_string()));
+                    }
+                    Ok(())
+                }
+            })
+            .error_status(|err| match err {
+                WebhookError::Handler(_) => StatusCode::SERVICE_UNAVAILABLE,
+                other => default_error_status(other),
+            })
+            .build();
+
+        let body = delivery_body("event-2", "machine.heartbeat.dead");
+        let response = receiver
+            .call(signed_request(&keypair, &body))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let response = receiver
+            .call(signed_request(&keypair, &body))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_duplicates_are_handled_once() {
+        let (keypair, public_key) = keypair();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let receiver = WebhookReceiver::builder(public_key)
+            .on(WebhookEvent::All, move |_| {
+                let counter = counter.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                    Ok::<_, Error>(())
+                }
+            })
+            .responses(WebhookResponses {
+                duplicate: StatusCode::ACCEPTED,
+                ..WebhookResponses::default()
+            })
+            .build();
+
+        let body = delivery_body("event-3", "machine.heartbeat.dead");
+        let (first, second) = tokio::join!(
+            receiver.clone().call(signed_request(&keypair, &body)),
+            receiver.clone().call(signed_request(&keypair, &body)),
+        );
+        let mut statuses = [first.unwrap().status(), second.unwrap().status()];
+        statuses.sort();
+        assert_eq!(statuses, [StatusCode::OK, StatusCode::ACCEPTED]);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_redelivery_skips_completed_handlers() {
+        let (keypair, public_key) = keypair();
+        let first_calls = Arc::new(AtomicUsize::new(0));
+        let second_calls = Arc::new(AtomicUsize::new(0));
+        let (first, second) = (first_calls.clone(), second_calls.clone());
+        let mut receiver = WebhookReceiver::builder(public_key)
+            .on(WebhookEvent::MachineHeartbeatDead, move |_| {
+                let first = first.clone();
+                async move {
+                    first.fetch_add(1, Ordering::SeqCst);
+                    Ok::<_, Error>(())
+                }
+            })
+            .on(WebhookEvent::All, move |_| {
+                let second = second.clone();
+                async move {
+                    if second.fetch_add(1, Ordering::SeqCst) == 0 {
+                        return Err(Error::UnexpectedError("database down".to_string()));
+                    }
+                    Ok(())
+                }
+            })
+            .build();
+
+        let body = delivery_body("event-4", "machine.heartbeat.dead");
+        let response = receiver
+            .call(signed_request(&keypair, &body))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let response = receiver
+            .call(signed_request(&keypair, &body))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(first_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(second_calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,123 @@
+//! Storage for webhook event IDs that have already been processed.
+//!
+//! Keygen retries deliveries until it receives a 2xx response, so the same event
+//! can arrive more than once. A [`WebhookEventStore`] lets receivers and replays
+//! skip events that were already handled.
+
+use futures::future::BoxFuture;
+use std::collections::{HashSet, VecDeque};
+use std::sync::Mutex;
+
+use crate::errors::Error;
+
+/// Pluggable store of processed webhook event IDs.
+///
+/// Implement this over a database or cache to share state between processes;
+/// [`MemoryEventStore`] is suitable for a single process.
+pub trait WebhookEventStore: Send + Sync {
+    /// Whether the event has already been processed
+    fn contains<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<bool, Error>>;
+
+    /// Record the event as processed, returning `false` when it already was.
+    ///
+    /// Must check and insert atomically: receivers use it to claim an event,
+    /// so two concurrent deliveries of the same ID can't both be handled.
+    fn insert<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<bool, Error>>;
+
+    /// Forget the event, releasing a claim whose processing failed
+    fn remove<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<(), Error>>;
+}
+
+/// In-memory store that remembers the most recent `capacity` event IDs.
+pub struct MemoryEventStore {
+    capacity: usize,
+    inner: Mutex<MemoryEventStoreInner>,
+}
+
+#[derive(Default)]
+struct MemoryEventStoreInner {
+    ids: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl MemoryEventStore {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::new(MemoryEventStoreInner::default()),
+        }
+    }
+}
+
+impl Default for MemoryEventStore {
+    fn default() -> Self {
+        Self::new(10_000)
+    }
+}
+
+impl WebhookEventStore for MemoryEventStore {
+    fn contains<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<bool, Error>> {
+        Box::pin(async move {
+            let inner = self
+                .inner
+                .lock()
+                .map_err(|_| Error::UnexpectedError("Event store lock poisoned".to_string()))?;
+            Ok(inner.ids.contains(id))
+        })
+    }
+
+    fn insert<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<bool, Error>> {
+        Box::pin(async move {
+            let mut inner = self
+                .inner
+                .lock()
+                .map_err(|_| Error::UnexpectedError("Event store lock poisoned".to_string()))?;
+            if !inner.ids.insert(id.to_string()) {
+                return Ok(false);
+            }
+            inner.order.push_back(id.to_string());
+            while inner.order.len() > self.capacity {
+                if let Some(oldest) = inner.order.pop_front() {
+                    inner.ids.remove(&oldest);
+                }
+            }
+            Ok(true)
+        })
+    }
+
+    fn remove<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let mut inner = self
+                .inner
+                .lock()
+                .map_err(|_| Error::UnexpectedError("Event store lock poisoned".to_string()))?;
+            if inner.ids.remove(id) {
+                inner.order.retain(|other| other != id);
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_event_store_evicts_oldest() {
+        let store = MemoryEventStore::new(2);
+        assert!(store.insert("event-1").await.unwrap());
+        assert!(store.insert("event-2").await.unwrap());
+        assert!(!store.insert("event-2").await.unwrap());
+        assert!(store.contains("event-1").await.unwrap());
+
+        store.insert("event-3").await.unwrap();
+        assert!(!store.contains("event-1").await.unwrap());
+        assert!(store.contains("event-2").await.unwrap());
+        assert!(store.contains("event-3").await.unwrap());
+
+        store.remove("event-2").await.unwrap();
+        assert!(!store.contains("event-2").await.unwrap());
+        assert!(store.insert("event-2").await.unwrap());
+    }
+}